@@ -208,10 +208,15 @@ pub async fn adm(
             .await?;
         }
         Command::Reskin(id) => {
-            let mut character = parse_character!(id).context("character not found")?;
-            character.appearance = generate_random_character_appearance();
+            let character = parse_character!(id).context("character not found")?;
+            let appearance = generate_random_character_appearance();
 
-            ctx.db().characters().save(character.clone()).await?;
+            ctx.db()
+                .characters()
+                .update(character.id, |character| {
+                    character.appearance = appearance.clone();
+                })
+                .await?;
 
             ctx.reply(Response::new_user_reply(
                 &author,
@@ -223,10 +228,12 @@ pub async fn adm(
             .await?;
         }
         Command::AddOrbs(id, quantity) => {
-            let mut character = parse_character!(id).context("character not found")?;
+            let character = parse_character!(id).context("character not found")?;
 
-            character.add_orbs(quantity.clamp(1, i32::MAX) as i64);
-            ctx.db().characters().save(character.clone()).await?;
+            ctx.db()
+                .characters()
+                .add_orbs(character.id, quantity.clamp(1, i32::MAX) as i64)
+                .await?;
 
             ctx.reply(Response::new_user_reply(
                 &author,
@@ -238,10 +245,14 @@ pub async fn adm(
             .await?;
         }
         Command::RemoveOrbs(id, quantity) => {
-            let mut character = parse_character!(id).context("character not found")?;
+            let character = parse_character!(id).context("character not found")?;
 
-            character.remove_orbs(quantity.clamp(1, i32::MAX) as i64);
-            ctx.db().characters().save(character.clone()).await?;
+            ctx.db()
+                .characters()
+                .update(character.id, |character| {
+                    character.remove_orbs(quantity.clamp(1, i32::MAX) as i64);
+                })
+                .await?;
 
             ctx.reply(Response::new_user_reply(
                 &author,
@@ -253,14 +264,21 @@ pub async fn adm(
             .await?;
         }
         Command::AddItem(id, item, quantity) => {
-            let mut character = parse_character!(id).context("character not found")?;
+            let character = parse_character!(id).context("character not found")?;
             let item = match get_item(&item) {
                 Some(item) => item,
                 None => get_item_by_name(&item).context("item not found")?,
             };
 
-            character.add_item(item, quantity.clamp(1, i32::MAX) as usize, None);
-            ctx.db().characters().save(character.clone()).await?;
+            ctx.db()
+                .characters()
+                .add_item(
+                    character.id,
+                    item,
+                    quantity.clamp(1, i32::MAX) as usize,
+                    None,
+                )
+                .await?;
 
             ctx.reply(Response::new_user_reply(
                 &author,
@@ -272,14 +290,18 @@ pub async fn adm(
             .await?;
         }
         Command::RemoveItem(id, item, quantity) => {
-            let mut character = parse_character!(id).context("character not found")?;
+            let character = parse_character!(id).context("character not found")?;
             let item = match get_item(&item) {
                 Some(item) => item,
                 None => get_item_by_name(&item).context("item not found")?,
             };
 
-            character.remove_item(item, quantity.clamp(1, i32::MAX) as usize);
-            ctx.db().characters().save(character.clone()).await?;
+            ctx.db()
+                .characters()
+                .update(character.id, |character| {
+                    character.remove_item(item, quantity.clamp(1, i32::MAX) as usize);
+                })
+                .await?;
 
             ctx.reply(Response::new_user_reply(
                 &author,
@@ -19,7 +19,7 @@ pub async fn allocate(
     quantity: Option<i64>,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     let quantity = quantity.unwrap_or(1).clamp(1, i32::MAX as i64) as usize;
 
     if character.battle_inventory.len() >= MAX_ALLOCATIONS {
@@ -98,10 +98,26 @@ pub async fn allocate(
         }
     }
 
-    character.remove_item(item, quantity);
-    character.add_battle_item(item, quantity, Some(inventory_item.values.clone()));
+    let mut allocated = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            allocated = character.has_item_with_values(&item, &inventory_item.values, quantity);
+            if allocated {
+                character.remove_item_with_values(item, &inventory_item.values, quantity);
+                character.add_battle_item(item, quantity, Some(inventory_item.values.clone()));
+            }
+        })
+        .await?;
 
-    ctx.db().characters().save(character).await?;
+    if !allocated {
+        ctx.reply(
+            Response::new_user_reply(&author, "esse item não está mais no seu inventário!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
 
     ctx.send(
         Response::new_user_reply(
@@ -1,3 +1,4 @@
+use etheris_database::ranked::{is_ranked_pl_gap_allowed, MAX_RANKED_PL_RATIO};
 use etheris_rpg::{Battle, BattleController, BattleSettings, FighterData};
 
 use crate::prelude::*;
@@ -16,9 +17,20 @@ pub async fn battle(
     #[rename("intrusos")]
     #[description("Número máximo de intrusos permitidos na luta")]
     intruders: Option<i64>,
+    #[rename("ranqueada")]
+    #[description(
+        "Se a batalha vale pontos no rank PvP. Batalhas ranqueadas nunca são até a morte"
+    )]
+    ranked: Option<bool>,
 ) -> anyhow::Result<()> {
-    let serious_battle = serious_battle.unwrap_or(false);
-    let intruders = intruders.unwrap_or(0).clamp(0, 5) as u8;
+    let ranked = ranked.unwrap_or(false);
+    // Ranked duels are always one against one and can't kill anyone
+    let serious_battle = serious_battle.unwrap_or(false) && !ranked;
+    let intruders = if ranked {
+        0
+    } else {
+        intruders.unwrap_or(0).clamp(0, 5) as u8
+    };
     let author = ctx.author().await?;
 
     if opponent.id == author.id {
@@ -41,7 +53,9 @@ pub async fn battle(
                 format!(
                     "**{}** chamou você para um duelo {}. Você aceita?{}",
                     author.display_name(),
-                    if serious_battle {
+                    if ranked {
+                        "**ranqueado**! A batalha vale pontos no rank PvP"
+                    } else if serious_battle {
                         "**sério!** A batalha poderá ou não ser até a morte"
                     } else {
                         "casual"
@@ -95,10 +109,29 @@ pub async fn battle(
     }
 
     let character_fighter =
-        FighterData::new_from_character(0, &author_character, author, Default::default());
+        FighterData::new_from_character(0, &author_character, author.clone(), Default::default());
     let opponent_fighter =
         FighterData::new_from_character(1, &opponent_character, opponent, Default::default());
 
+    if ranked
+        && !is_ranked_pl_gap_allowed(
+            character_fighter.power_level(),
+            opponent_fighter.power_level(),
+        )
+    {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "a diferença de poder entre vocês é grande demais para uma batalha ranqueada! O mais forte pode ter no máximo `{MAX_RANKED_PL_RATIO}x` o PL do mais fraco."
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
     let battle = Battle::new(
         author_character.region,
         BattleSettings {
@@ -106,6 +139,8 @@ pub async fn battle(
             has_consequences: serious_battle,
             casual: !serious_battle,
             max_intruders: intruders,
+            seed: None,
+            ranked,
         },
         vec![character_fighter, opponent_fighter],
     )?;
@@ -0,0 +1,40 @@
+use etheris_data::{items::recipes::BlacksmithWork, weapon::WeaponEnchantment};
+
+use crate::prelude::*;
+
+#[command("Peça para o ferreiro encantar a sua arma equipada com um elemento!")]
+#[name("ferreiro encantar")]
+#[character_required(true)]
+pub async fn blacksmith_enchant(
+    mut ctx: CommandContext,
+    #[rename("encantamento")]
+    #[description("Flamejante, Congelante, Elétrico ou Venenoso")]
+    #[min_max_length(1, 32)]
+    enchantment: String,
+) -> anyhow::Result<()> {
+    let name = unidecode::unidecode(&enchantment).to_lowercase();
+    let Some(enchantment) = WeaponEnchantment::LIST
+        .into_iter()
+        .find(|e| unidecode::unidecode(e.name()).to_lowercase() == name)
+    else {
+        let author = ctx.author().await?;
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "esse encantamento não existe! Os encantamentos são: {}.",
+                    WeaponEnchantment::LIST
+                        .iter()
+                        .map(|e| format!("**{}**", e.name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    super::forge(&mut ctx, BlacksmithWork::Enchant(enchantment)).await
+}
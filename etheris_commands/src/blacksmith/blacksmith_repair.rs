@@ -0,0 +1,10 @@
+use etheris_data::items::recipes::BlacksmithWork;
+
+use crate::prelude::*;
+
+#[command("Peça para o ferreiro consertar a sua arma equipada!")]
+#[name("ferreiro consertar")]
+#[character_required(true)]
+pub async fn blacksmith_repair(mut ctx: CommandContext) -> anyhow::Result<()> {
+    super::forge(&mut ctx, BlacksmithWork::Repair).await
+}
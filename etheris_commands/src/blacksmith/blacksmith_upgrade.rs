@@ -0,0 +1,10 @@
+use etheris_data::items::recipes::BlacksmithWork;
+
+use crate::prelude::*;
+
+#[command("Peça para o ferreiro melhorar a sua arma equipada!")]
+#[name("ferreiro melhorar")]
+#[character_required(true)]
+pub async fn blacksmith_upgrade(mut ctx: CommandContext) -> anyhow::Result<()> {
+    super::forge(&mut ctx, BlacksmithWork::Upgrade).await
+}
@@ -0,0 +1,205 @@
+use etheris_data::{
+    items::{
+        get_item_by_weapon,
+        recipes::{get_blacksmith_recipe, BlacksmithWork},
+    },
+    weapon::MAX_WEAPON_UPGRADE_LEVEL,
+    ItemValue,
+};
+
+use crate::{prelude::*, CommandMap};
+
+mod blacksmith_enchant;
+mod blacksmith_repair;
+mod blacksmith_upgrade;
+
+pub fn register_commands(map: &mut CommandMap) {
+    register_command!(map, blacksmith_upgrade::Blacksmith_upgradeCommand);
+    register_command!(map, blacksmith_repair::Blacksmith_repairCommand);
+    register_command!(map, blacksmith_enchant::Blacksmith_enchantCommand);
+}
+
+/// Asks the blacksmith of the city to work on the equipped weapon, paying with ores and orbs.
+async fn forge(ctx: &mut CommandContext, work: BlacksmithWork) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    if character.region.city().is_none() {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "não há nenhum ferreiro por aqui! Viaje para uma cidade para usar o ferreiro.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(weapon) = character.weapon else {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você precisa equipar a arma que quer levar ao ferreiro com **/equipar**!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let weapon_item = get_item_by_weapon(weapon);
+    let values = &character.weapon_values;
+    let upgrade_level = values.upgrade_level();
+    let durability = values.durability().unwrap_or(weapon.max_durability());
+
+    let refusal = match work {
+        BlacksmithWork::Upgrade if upgrade_level >= MAX_WEAPON_UPGRADE_LEVEL => {
+            Some("essa arma já está no nível máximo de melhoria!")
+        }
+        BlacksmithWork::Repair if durability >= weapon.max_durability() => {
+            Some("essa arma não precisa de conserto!")
+        }
+        BlacksmithWork::Enchant(enchantment) if values.enchantment() == Some(enchantment) => {
+            Some("essa arma já tem esse encantamento!")
+        }
+        _ => None,
+    };
+
+    if let Some(refusal) = refusal {
+        ctx.reply(Response::new_user_reply(&author, refusal).add_emoji_prefix(emojis::ERROR))
+            .await?;
+        return Ok(());
+    }
+
+    let recipe = get_blacksmith_recipe(work, upgrade_level);
+    let ingredients_string = recipe
+        .ingredients
+        .iter()
+        .filter_map(|i| items::get_item(i.item).map(|item| (i.quantity, item)))
+        .map(|(quantity, item)| format!("{quantity}x {}", item.display_name))
+        .collect::<Vec<_>>();
+
+    let work_description = match work {
+        BlacksmithWork::Upgrade => format!("melhorar para o nível **+{}**", upgrade_level + 1),
+        BlacksmithWork::Repair => String::from("consertar"),
+        BlacksmithWork::Enchant(enchantment) => {
+            format!("encantar como **{}**", enchantment.name())
+        }
+    };
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "o ferreiro vai {work_description} a sua arma **{}**. Ele pede **{} ◎** e os materiais: `{}`. Você aceita?",
+                    weapon_item.display_name,
+                    recipe.orbs,
+                    ingredients_string.join("`, `")
+                ),
+            )
+            .add_emoji_prefix("⚒️"),
+        )
+        .await?;
+
+    if !confirmation {
+        return Ok(());
+    }
+
+    let character = parse_user_character!(ctx, author);
+    if character.weapon != Some(weapon) {
+        return Ok(());
+    }
+
+    if character.orbs < recipe.orbs {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você precisa de **{} ◎** para pagar o ferreiro!",
+                    recipe.orbs
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut ingredients = Vec::with_capacity(recipe.ingredients.len());
+    for ingredient in recipe.ingredients.iter() {
+        let item = items::get_item(ingredient.item).context("expected a valid ore item")?;
+        ingredients.push((item, ingredient.quantity));
+        if !character.has_item(&item, ingredient.quantity) {
+            ctx.reply(
+                Response::new_user_reply(
+                    &author,
+                    format!(
+                        "você não tem **{}x {}**!",
+                        ingredient.quantity, item.display_name
+                    ),
+                )
+                .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        }
+    }
+
+    let mut forged = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            forged = character.weapon == Some(weapon)
+                && character.weapon_values.upgrade_level() == upgrade_level
+                && character.orbs >= recipe.orbs
+                && ingredients
+                    .iter()
+                    .all(|(item, quantity)| character.has_item(item, *quantity));
+            if !forged {
+                return;
+            }
+
+            for (item, quantity) in ingredients.iter() {
+                character.remove_item(*item, *quantity);
+            }
+
+            character.remove_orbs(recipe.orbs);
+            character.weapon_values.set(match work {
+                BlacksmithWork::Upgrade => ItemValue::UpgradeLevel(upgrade_level + 1),
+                BlacksmithWork::Repair => ItemValue::Durability(weapon.max_durability()),
+                BlacksmithWork::Enchant(enchantment) => ItemValue::Enchantment(enchantment),
+            });
+        })
+        .await?;
+
+    if !forged {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "sua arma ou seus materiais mudaram enquanto o ferreiro trabalhava, tente novamente!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "o ferreiro terminou o trabalho! Sua arma **{}** está pronta.",
+                weapon_item.display_name
+            ),
+        )
+        .add_emoji_prefix(format!("{}⚒️", weapon_item.emoji)),
+    )
+    .await?;
+
+    Ok(())
+}
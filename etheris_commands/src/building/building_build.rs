@@ -0,0 +1,194 @@
+use etheris_data::building::BuildingKind;
+use etheris_database::building::BuildingModel;
+
+use crate::prelude::*;
+
+#[command("Compre um terreno na cidade e construa uma loja, agência de empregos ou hospital!")]
+#[name("construção construir")]
+#[character_required(true)]
+pub async fn building_build(
+    mut ctx: CommandContext,
+    #[rename("tipo")]
+    #[description("Loja, Agência de Empregos ou Hospital")]
+    #[min_max_length(1, 32)]
+    kind: String,
+    #[rename("nome")]
+    #[description("O nome da construção")]
+    #[min_max_length(3, 32)]
+    name: String,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(city) = character.region.city() else {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você precisa estar em uma cidade para comprar um terreno! Use **/viajar** para trocar de região e buscar uma região do tipo cidade.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let kind_name = clear_string(&kind);
+    let Some(kind) = BuildingKind::LIST
+        .into_iter()
+        .find(|k| clear_string(k.display_name()) == kind_name)
+    else {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "esse tipo de construção não existe! Os tipos são: {}.",
+                    BuildingKind::LIST
+                        .iter()
+                        .map(|k| format!("**{}**", k.display_name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let buildings = ctx.db().buildings();
+    if buildings
+        .get_by_owner_in_region(character.id, character.region)
+        .await?
+        .is_some()
+    {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("você já tem uma construção em **{}**!", character.region),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if buildings
+        .get_by_region_and_name(character.region, &name)
+        .await?
+        .is_some()
+    {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "já existe uma construção com esse nome nessa cidade!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let price = city.land_price + kind.build_price();
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você quer comprar um terreno em **{}** e construir a {} **{}**? O terreno custa **{} ◎** e a construção **{} ◎**, um total de **{price} ◎**.",
+                    character.region,
+                    kind.display_name(),
+                    name,
+                    city.land_price,
+                    kind.build_price()
+                ),
+            )
+            .add_emoji_prefix(kind.emoji()),
+        )
+        .await?;
+    if !confirmation {
+        return Ok(());
+    }
+
+    let character = parse_user_character!(ctx, author);
+    if character.orbs < price {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!("você precisa de **{price} ◎** para construir!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if buildings
+        .get_by_region_and_name(character.region, &name)
+        .await?
+        .is_some()
+    {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "alguém construiu com esse nome nessa cidade antes de você! Escolha outro nome.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !ctx.db().characters().take_orbs(character.id, price).await? {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!("você precisa de **{price} ◎** para construir!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let building = BuildingModel::new(
+        character.region,
+        character.id,
+        0,
+        name.clone(),
+        kind.initial_data(),
+    );
+
+    // The indexes refuse a second building of the owner or a taken name, even if they raced the checks above
+    if !buildings.create_building(building).await? {
+        ctx.db().characters().add_orbs(character.id, price).await?;
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você já tem uma construção em **{}** ou esse nome já foi usado! Seus **{price} ◎** foram devolvidos.",
+                    character.region
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "a {} **{name}** foi construída! Use **/construção ver** para cuidar dela.",
+                kind.display_name()
+            ),
+        )
+        .add_emoji_prefix(kind.emoji()),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::prelude::*;
+
+#[command("Recolha os orbs que a sua construção juntou no cofre!")]
+#[name("construção coletar")]
+#[character_required(true)]
+pub async fn building_collect(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let Some(building) = super::get_own_building(&mut ctx, &author, &character).await? else {
+        return Ok(());
+    };
+
+    // The vault is emptied in place so nothing credited to it in the meantime is lost
+    let orbs = ctx.db().buildings().take_all_orbs(building.id).await?;
+    if orbs <= 0 {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("o cofre de **{}** está vazio!", building.name),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.db().characters().add_orbs(character.id, orbs).await?;
+
+    ctx.reply(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você recolheu **{orbs} ◎** do cofre de **{}**!",
+                building.name
+            ),
+        )
+        .add_emoji_prefix(emojis::ORB),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use etheris_data::building::BuildingData;
+
+use crate::prelude::*;
+
+#[command("Veja como vai a sua construção na cidade em que você está!")]
+#[name("construção ver")]
+#[character_required(true)]
+pub async fn building_info(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let Some(building) = super::get_own_building(&mut ctx, &author, &character).await? else {
+        return Ok(());
+    };
+
+    let mut embed = EmbedBuilder::new_common()
+        .set_color(Color::CYAN_GREEN)
+        .set_author(EmbedAuthor {
+            name: format!("{} {}", building.kind.emoji(), building.name),
+            icon_url: Some(author.avatar_url()),
+        })
+        .set_description(format!(
+            "**{}** em **{}**\n**Cofre**: {} ◎",
+            building.kind.display_name(),
+            character.region,
+            building.orbs
+        ));
+
+    match &building.data {
+        BuildingData::JobAgency(data) => {
+            embed = embed.add_inlined_field(
+                "🏤 Estatísticas",
+                format!(
+                    "**Empregos**: {}\n**Comissões**: {} ◎",
+                    data.stats_total_employments, data.stats_total_payouts
+                ),
+            );
+        }
+        BuildingData::Hospital => {}
+        BuildingData::Shop(data) => {
+            embed = embed
+                .add_inlined_field(
+                    "🏪 Estatísticas",
+                    format!(
+                        "**Itens vendidos**: {}\n**Lucro**: {} ◎",
+                        data.stats_total_sells, data.stats_total_profit
+                    ),
+                )
+                .add_inlined_field(
+                    "📦 Estoque",
+                    if data.items.is_empty() {
+                        String::from("Vazio. Use **/construção estocar** para vender itens.")
+                    } else {
+                        data.items
+                            .iter()
+                            .filter_map(|i| items::get_item(&i.identifier).map(|item| (i, item)))
+                            .map(|(i, item)| {
+                                format!(
+                                    "{} **{}x {}**: {} ◎",
+                                    item.emoji, i.quantity, item.display_name, i.price
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    },
+                );
+        }
+    }
+
+    ctx.send(embed).await?;
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::prelude::*;
+
+/// How many different items a player shop can sell at once.
+const MAX_SHOP_ITEMS: usize = 24;
+
+#[command("Coloque itens do seu inventário à venda na sua loja!")]
+#[name("construção estocar")]
+#[character_required(true)]
+pub async fn building_stock(
+    mut ctx: CommandContext,
+    #[rename("item")]
+    #[description("O item do seu inventário que você quer vender")]
+    item_name: String,
+    #[rename("quantia")]
+    #[description("Quantos itens colocar no estoque")]
+    amount: i64,
+    #[rename("preço")]
+    #[description("O preço de cada item")]
+    price: i64,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let amount = amount.clamp(1, i32::MAX as i64);
+    let price = price.max(1);
+
+    let Some(building) = super::get_own_building(&mut ctx, &author, &character).await? else {
+        return Ok(());
+    };
+
+    let Some(shop) = building.clone().get_shop_data() else {
+        ctx.reply(
+            Response::new_user_reply(&author, "apenas lojas podem ter itens em estoque!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let Some((item, values)) = character
+        .get_inventory_item_by_name(&item_name)
+        .and_then(|i| items::get_item(&i.identifier).map(|item| (item, i.values.clone())))
+    else {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "esse item não existe, não está no seu inventário ou teve o nome escrito errado!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    // The stock only keeps identifiers, so an upgrade would be lost when someone buys the weapon
+    if values.upgrade_level() > 0 || values.enchantment().is_some() {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "armas melhoradas ou encantadas não podem ser vendidas em lojas! Use o **/mercado vender**.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !character.has_item_with_values(&item, &values, amount as usize) {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você não tem essa quantia desse item no **/inventário**!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let full = shop.items.len() >= MAX_SHOP_ITEMS;
+    if full && !shop.items.iter().any(|i| i.identifier == item.identifier) {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("uma loja só pode vender {MAX_SHOP_ITEMS} itens diferentes!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut removed = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            removed = character.has_item_with_values(&item, &values, amount as usize);
+            if removed {
+                character.remove_item_with_values(item, &values, amount as usize);
+            }
+        })
+        .await?;
+
+    if !removed {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você não tem essa quantia desse item no **/inventário**!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // The stock is changed in place so it doesn't overwrite what was bought in the meantime
+    let stocked = ctx
+        .db()
+        .buildings()
+        .stock_shop_item(
+            building.id,
+            item.identifier,
+            amount,
+            Some(price),
+            Some(MAX_SHOP_ITEMS),
+        )
+        .await?;
+    if !stocked {
+        ctx.db()
+            .characters()
+            .add_item(character.id, item, amount as usize, Some(values))
+            .await?;
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("uma loja só pode vender {MAX_SHOP_ITEMS} itens diferentes!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.reply(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você colocou **{amount}x {}** à venda em **{}** por **{price} ◎** cada!",
+                item.display_name, building.name
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
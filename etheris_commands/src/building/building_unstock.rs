@@ -0,0 +1,72 @@
+use etheris_data::building::BuildingKind;
+
+use crate::prelude::*;
+
+#[command("Tire um item do estoque da sua loja e devolva ao seu inventário!")]
+#[name("construção retirar")]
+#[character_required(true)]
+pub async fn building_unstock(
+    mut ctx: CommandContext,
+    #[rename("item")]
+    #[description("O item que você quer tirar do estoque")]
+    item_name: String,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let Some(building) = super::get_own_building(&mut ctx, &author, &character).await? else {
+        return Ok(());
+    };
+
+    let item = items::get_item_by_name(&item_name);
+    let stock = match item {
+        Some(item) if building.kind == BuildingKind::Shop => {
+            ctx.db()
+                .buildings()
+                .unstock_shop_item(building.id, item.identifier)
+                .await?
+        }
+        _ => None,
+    };
+
+    let (Some(item), Some(stock)) = (item, stock) else {
+        ctx.reply(
+            Response::new_user_reply(&author, "esse item não está no estoque da sua loja!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if let Err(e) = ctx
+        .db()
+        .characters()
+        .add_item(character.id, item, stock.quantity.max(0) as usize, None)
+        .await
+    {
+        ctx.db()
+            .buildings()
+            .stock_shop_item(
+                building.id,
+                item.identifier,
+                stock.quantity as i64,
+                Some(stock.price),
+                None,
+            )
+            .await?;
+        return Err(e);
+    }
+
+    ctx.reply(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você tirou **{}x {}** do estoque de **{}**!",
+                stock.quantity, item.display_name, building.name
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
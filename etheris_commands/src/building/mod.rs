@@ -0,0 +1,58 @@
+use etheris_database::building::BuildingModel;
+
+use crate::{prelude::*, CommandMap};
+
+mod building_build;
+mod building_collect;
+mod building_info;
+mod building_stock;
+mod building_unstock;
+
+pub fn register_commands(map: &mut CommandMap) {
+    register_command!(map, building_build::Building_buildCommand);
+    register_command!(map, building_info::Building_infoCommand);
+    register_command!(map, building_stock::Building_stockCommand);
+    register_command!(map, building_unstock::Building_unstockCommand);
+    register_command!(map, building_collect::Building_collectCommand);
+}
+
+/// Gets the building the character owns in the city they are, or replies why it couldn't.
+async fn get_own_building(
+    ctx: &mut CommandContext,
+    author: &User,
+    character: &CharacterModel,
+) -> anyhow::Result<Option<BuildingModel>> {
+    if character.region.city().is_none() {
+        ctx.reply(
+            Response::new_user_reply(
+                author,
+                "você precisa estar em uma cidade para cuidar da sua construção!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(None);
+    }
+
+    let building = ctx
+        .db()
+        .buildings()
+        .get_by_owner_in_region(character.id, character.region)
+        .await?;
+
+    if building.is_none() {
+        ctx.reply(
+            Response::new_user_reply(
+                author,
+                format!(
+                    "você não tem nenhuma construção em **{}**! Construa uma com **/construção construir**.",
+                    character.region
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+    }
+
+    Ok(building)
+}
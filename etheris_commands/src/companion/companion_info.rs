@@ -0,0 +1,67 @@
+use etheris_database::companion::MAX_COMPANIONS;
+use etheris_rpg::FighterData;
+
+use crate::prelude::*;
+
+#[command("Veja os companheiros que um personagem domou!")]
+#[name("companheiro ver")]
+#[character_required(true)]
+pub async fn companion_info(
+    mut ctx: CommandContext,
+    #[rename("usuário")]
+    #[description("O usuário que você quer ver os companheiros")]
+    user: Option<User>,
+) -> anyhow::Result<()> {
+    let user = user.unwrap_or(ctx.author().await?);
+    let character = parse_user_character!(ctx, user);
+
+    if character.companions.is_empty() {
+        ctx.send(
+            Response::new_user_reply(
+                &ctx.author().await?,
+                format!(
+                    "**{}** não tem nenhum companheiro! Alguns animais derrotados em batalha podem querer te seguir.",
+                    character.name
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut embed = EmbedBuilder::new_common()
+        .set_color(Color::BLURPLE)
+        .set_author(EmbedAuthor {
+            name: format!("Companheiros de {}", character.name),
+            icon_url: Some(user.avatar_url()),
+        })
+        .set_description(format!(
+            "`{}/{MAX_COMPANIONS}` companheiros. Apenas o companheiro ativo luta nas batalhas.",
+            character.companions.len()
+        ));
+
+    for companion in character.companions.iter() {
+        let pl = FighterData::new_from_companion(0, companion)
+            .map(|data| data.power_level())
+            .unwrap_or_default();
+
+        embed = embed.add_inlined_field(
+            format!(
+                "{}{}",
+                if companion.active { "⭐ " } else { "🐾 " },
+                companion.name
+            ),
+            format!(
+                "**Nível**: {}\n**XP**: {}/{}\n**PL**: `{pl}`",
+                companion.level,
+                companion.xp,
+                companion.required_xp()
+            ),
+        );
+    }
+
+    ctx.send(embed).await?;
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use crate::prelude::*;
+
+#[command("Liberte um dos seus companheiros!")]
+#[name("companheiro libertar")]
+#[character_required(true)]
+pub async fn companion_release(
+    mut ctx: CommandContext,
+    #[rename("companheiro")]
+    #[description("O nome do companheiro que você quer libertar")]
+    #[min_max_length(1, 128)]
+    name: String,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let name = unidecode::unidecode(&name).to_lowercase();
+    let Some(index) = character
+        .companions
+        .iter()
+        .position(|c| unidecode::unidecode(&c.name).to_lowercase() == name)
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não tem nenhum companheiro com esse nome! Use **/companheiro ver** para ver seus companheiros.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você tem certeza que quer libertar **{}**? Ele não vai voltar.",
+                    character.companions[index].name
+                ),
+            )
+            .add_emoji_prefix("🐾"),
+        )
+        .await?;
+
+    if !confirmation {
+        return Ok(());
+    }
+
+    let companion = character.companions[index].clone();
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character
+                .companions
+                .retain(|c| c.enemy != companion.enemy || c.tamed_at != companion.tamed_at);
+        })
+        .await?;
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você libertou **{}**. Ele voltou para a natureza.",
+                companion.name
+            ),
+        )
+        .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use etheris_database::companion::Companion;
+
+use crate::prelude::*;
+
+#[command("Escolha o companheiro que vai lutar ao seu lado!")]
+#[name("companheiro escolher")]
+#[character_required(true)]
+pub async fn companion_select(
+    mut ctx: CommandContext,
+    #[rename("companheiro")]
+    #[description("O nome do companheiro que você quer escolher")]
+    #[min_max_length(1, 128)]
+    name: String,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let name = unidecode::unidecode(&name).to_lowercase();
+    let Some(selected) = character
+        .companions
+        .iter()
+        .position(|c| unidecode::unidecode(&c.name).to_lowercase() == name)
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não tem nenhum companheiro com esse nome! Use **/companheiro ver** para ver seus companheiros.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let selected = character.companions[selected].clone();
+    let companion_name = selected.name.clone();
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            let is_selected =
+                |c: &Companion| c.enemy == selected.enemy && c.tamed_at == selected.tamed_at;
+            if !character.companions.iter().any(is_selected) {
+                return;
+            }
+
+            for companion in character.companions.iter_mut() {
+                companion.active = is_selected(companion);
+            }
+        })
+        .await?;
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!("**{companion_name}** vai lutar ao seu lado a partir de agora!"),
+        )
+        .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
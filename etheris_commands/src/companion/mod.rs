@@ -0,0 +1,11 @@
+use crate::CommandMap;
+
+mod companion_info;
+mod companion_release;
+mod companion_select;
+
+pub fn register_commands(map: &mut CommandMap) {
+    register_command!(map, companion_info::Companion_infoCommand);
+    register_command!(map, companion_select::Companion_selectCommand);
+    register_command!(map, companion_release::Companion_releaseCommand);
+}
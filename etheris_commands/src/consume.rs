@@ -16,7 +16,7 @@ pub async fn consume(
     quantity: Option<i64>,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     let quantity = quantity.unwrap_or(1).clamp(1, i32::MAX as i64) as i32;
 
     let Some(inventory_item) = character.get_inventory_item_by_name(&item_name).cloned() else {
@@ -81,11 +81,32 @@ pub async fn consume(
         outputs.push(format!("{} `{}`", emojis::ETHER, ether_regeneration));
     }
 
-    character.heal(health_regeneration);
-    character.add_ether(ether_regeneration);
-    character.remove_item(item, quantity as usize);
+    let mut consumed = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            consumed = character.has_item(&item, quantity as usize);
+            if !consumed {
+                return;
+            }
+
+            character.heal(health_regeneration);
+            character.add_ether(ether_regeneration);
+            character.remove_item(item, quantity as usize);
+        })
+        .await?;
 
-    ctx.db().characters().save(character).await?;
+    if !consumed {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("você não possui **{quantity}x {}**!", item.display_name),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
 
     ctx.send(
         Response::new_user_reply(
@@ -106,36 +127,14 @@ pub async fn consume(
 
 async fn special_consumption_function(
     mut ctx: CommandContext,
-    mut character: CharacterModel,
+    character: CharacterModel,
     item: Item,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
 
-    match item.identifier {
-        "intelligence_crystal" => {
-            character.intelligence_xp += 500;
-            ctx.reply(
-                Response::new_user_reply(&author, "você consumiu um cristal da inteligência e ganhou **500 XP**! Estude uma vez e sinta o conhecimento expandir.")
-                .add_emoji_prefix(item.emoji),
-            ).await?;
-        }
-        "invigorating_crystal" => {
-            if character.action_points < character.max_action_points {
-                character.action_points = character.max_action_points;
-            }
-
-            character.stats.resistance.value = character.stats.resistance.max;
-            character.stats.vitality.value = character.stats.vitality.max;
-            character.stats.ether.value = character.stats.ether.max;
-            ctx.reply(
-                Response::new_user_reply(
-                    &author,
-                    "você consumiu um cristal revigorante e entrou no seu potencial máximo!",
-                )
-                .add_emoji_prefix(item.emoji),
-            )
-            .await?;
-        }
+    let message = match item.identifier {
+        "intelligence_crystal" => "você consumiu um cristal da inteligência e ganhou **500 XP**! Estude uma vez e sinta o conhecimento expandir.",
+        "invigorating_crystal" => "você consumiu um cristal revigorante e entrou no seu potencial máximo!",
         _ => {
             ctx.reply(
                 Response::new_user_reply(&author, "esse item não pode ser utilizado!.")
@@ -144,10 +143,48 @@ async fn special_consumption_function(
             .await?;
             return Ok(());
         }
+    };
+
+    let mut consumed = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            consumed = character.has_item(&item, 1);
+            if !consumed {
+                return;
+            }
+
+            match item.identifier {
+                "intelligence_crystal" => {
+                    character.intelligence_xp += 500;
+                }
+                "invigorating_crystal" => {
+                    if character.action_points < character.max_action_points {
+                        character.action_points = character.max_action_points;
+                    }
+
+                    character.stats.resistance.value = character.stats.resistance.max;
+                    character.stats.vitality.value = character.stats.vitality.max;
+                    character.stats.ether.value = character.stats.ether.max;
+                }
+                _ => {}
+            }
+
+            character.remove_item(item, 1);
+        })
+        .await?;
+
+    if !consumed {
+        ctx.reply(
+            Response::new_user_reply(&author, "esse item não está mais no seu inventário!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
     }
 
-    character.remove_item(item, 1);
-    ctx.db().characters().save(character).await?;
+    ctx.reply(Response::new_user_reply(&author, message).add_emoji_prefix(item.emoji))
+        .await?;
 
     Ok(())
 }
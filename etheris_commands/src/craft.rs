@@ -104,31 +104,46 @@ pub async fn craft(
         return Ok(());
     }
 
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
 
-    for ingredient in output.2 {
+    let mut ingredient_items = vec![];
+    for ingredient in output.2.iter() {
         let item = items::get_item(ingredient.item).context("expected a valid ingredient item")?;
-        if !character.has_item(&item, ingredient.quantity) {
-            ctx.reply(
-                Response::new_user_reply(
-                    &author,
-                    format!(
-                        "você não tem **{}x {}**!",
-                        ingredient.quantity, item.display_name
-                    ),
-                )
-                .add_emoji_prefix(emojis::ERROR),
-            )
-            .await?;
-            return Ok(());
-        }
-
-        character.remove_item(item, ingredient.quantity);
+        ingredient_items.push((item, ingredient.quantity));
     }
 
-    character.add_recipe(output.0.identifier.to_owned());
-    character.add_item(output.0, output.1, None);
-    ctx.db().characters().save(character).await?;
+    let mut missing = None;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            missing = ingredient_items
+                .iter()
+                .find(|(item, quantity)| !character.has_item(item, *quantity))
+                .copied();
+            if missing.is_some() {
+                return;
+            }
+
+            for (item, quantity) in ingredient_items.iter() {
+                character.remove_item(*item, *quantity);
+            }
+
+            character.add_recipe(output.0.identifier.to_owned());
+            character.add_item(output.0, output.1, None);
+        })
+        .await?;
+
+    if let Some((item, quantity)) = missing {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("você não tem **{}x {}**!", quantity, item.display_name),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
 
     ctx.send(
         Response::new_user_reply(
@@ -11,13 +11,16 @@ pub async fn daily(mut ctx: CommandContext) -> anyhow::Result<()> {
         .create_cooldown(author.id, "DAILY", chrono::Duration::try_hours(12).unwrap())
         .await?;
 
-    let mut character = parse_user_character!(ctx, author);
-    character.action_points = character.max_action_points;
-    character.stats.resistance.value = character.stats.resistance.max;
-    character.stats.vitality.value = character.stats.vitality.max;
-    character.stats.ether.value = character.stats.ether.max;
-
-    ctx.db().characters().save(character).await?;
+    let character = parse_user_character!(ctx, author);
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character.action_points = character.max_action_points;
+            character.stats.resistance.value = character.stats.resistance.max;
+            character.stats.vitality.value = character.stats.vitality.max;
+            character.stats.ether.value = character.stats.ether.max;
+        })
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
@@ -12,7 +12,7 @@ pub async fn deallocate(
     item_name: String,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
 
     let Some(inventory_item) = character
         .get_battle_inventory_item_by_name(&item_name)
@@ -33,14 +33,21 @@ pub async fn deallocate(
         return Ok(());
     };
 
-    character.remove_battle_item(item, inventory_item.quantity);
-    character.add_item(
-        item,
-        inventory_item.quantity,
-        Some(inventory_item.values.clone()),
-    );
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            // Only what is still in the battle inventory is moved
+            let Some(quantity) = character
+                .get_battle_inventory_item(&item)
+                .map(|i| i.quantity.min(inventory_item.quantity))
+            else {
+                return;
+            };
 
-    ctx.db().characters().save(character).await?;
+            character.remove_battle_item(item, quantity);
+            character.add_item(item, quantity, Some(inventory_item.values.clone()));
+        })
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
@@ -2,42 +2,53 @@ use etheris_data::{
     appearance::{FACE_COSMETICS, HEAD_COSMETICS},
     items::{CosmeticKind, Item},
     weapon::WeaponKind,
+    ItemValues,
 };
 
 use crate::prelude::*;
 
-#[command("Equipe no seu personagem um item cosmético!")]
+#[command("Equipe no seu personagem um item cosmético, uma arma ou um equipamento!")]
 #[name("equipar")]
 #[character_required(true)]
 pub async fn equip(
     mut ctx: CommandContext,
     #[rename("item")]
     #[description("Nome do item que você quer vestir")]
-    item: String,
+    item_name: String,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let Some(item) = items::get_item_by_name(&item) else {
-        ctx.reply(Response::new_user_reply(
-            &author,
-            format!("nenhum item chamado **{}** foi encontrado! Utilize **/inventário** para ver os seus itens.", item)
-        ).add_emoji_prefix(emojis::ERROR)
-        ).await?;
+    let character = parse_user_character!(ctx, author);
+
+    // Upgraded weapons are picked by their full name, like `Katana +2`
+    let Some(inventory_item) = character.get_inventory_item_by_name(&item_name).cloned() else {
+        if items::get_item_by_name(&item_name).is_some() {
+            ctx.reply(
+                Response::new_user_reply(&author, "você não possui esse item!")
+                    .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+        } else {
+            ctx.reply(Response::new_user_reply(
+                &author,
+                format!("nenhum item chamado **{}** foi encontrado! Utilize **/inventário** para ver os seus itens.", item_name)
+            ).add_emoji_prefix(emojis::ERROR)
+            ).await?;
+        }
 
         return Ok(());
     };
 
-    let mut character = parse_user_character!(ctx, author);
-    if !character.has_item(&item, 1) {
-        ctx.reply(
-            Response::new_user_reply(&author, "você não possui esse item!")
-                .add_emoji_prefix(emojis::ERROR),
-        )
-        .await?;
+    let Some(item) = items::get_item(&inventory_item.identifier) else {
         return Ok(());
-    }
+    };
 
     if let Some(weapon) = item.weapon {
-        equip_weapon(&mut ctx, weapon, item).await?;
+        equip_weapon(&mut ctx, weapon, item, inventory_item.values).await?;
+        return Ok(());
+    }
+
+    if item.equipment_properties.is_some() {
+        equip_equipment(&mut ctx, item).await?;
         return Ok(());
     }
 
@@ -74,13 +85,31 @@ pub async fn equip(
         return Ok(());
     };
 
-    match cosmetic_properties.kind {
-        CosmeticKind::Face => character.appearance.face_cosmetic = (*cosmetic).into(),
-        CosmeticKind::Head => character.appearance.head_cosmetic = (*cosmetic).into(),
-    };
+    let mut equipped = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            equipped = character.has_item(&item, 1);
+            if !equipped {
+                return;
+            }
+
+            match cosmetic_properties.kind {
+                CosmeticKind::Face => character.appearance.face_cosmetic = (*cosmetic).into(),
+                CosmeticKind::Head => character.appearance.head_cosmetic = (*cosmetic).into(),
+            };
+            character.remove_item(item, 1);
+        })
+        .await?;
 
-    character.remove_item(item, 1);
-    ctx.db().characters().save(character).await?;
+    if !equipped {
+        ctx.reply(
+            Response::new_user_reply(&author, "você não possui esse item!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
 
     ctx.reply(
         Response::new_user_reply(
@@ -98,9 +127,10 @@ pub async fn equip_weapon(
     ctx: &mut CommandContext,
     weapon: WeaponKind,
     item: Item,
+    values: ItemValues,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     if character.weapon.is_some() {
         ctx.reply(
             Response::new_user_reply(
@@ -113,14 +143,112 @@ pub async fn equip_weapon(
         return Ok(());
     }
 
-    character.remove_item(item, 1);
-    character.weapon = Some(weapon);
-    ctx.db().characters().save(character).await?;
+    if !character.has_item_with_values(&item, &values, 1) {
+        ctx.reply(
+            Response::new_user_reply(&author, "você não possui esse item!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut equipped = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            equipped =
+                character.weapon.is_none() && character.has_item_with_values(&item, &values, 1);
+            if equipped {
+                character.remove_item_with_values(item, &values, 1);
+                character.weapon = Some(weapon);
+                character.weapon_values = values.clone();
+            }
+        })
+        .await?;
+
+    if !equipped {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você não possui esse item ou já está equipando uma arma!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.reply(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você equipou com sucesso a arma **{}**!",
+                values.name_with_upgrades(item.display_name)
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn equip_equipment(ctx: &mut CommandContext, item: Item) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let mut equipped = false;
+    let mut previous = None;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            equipped = character.has_item(&item, 1);
+            if !equipped {
+                return;
+            }
+
+            character.remove_item(item, 1);
+            previous = character.equipment.equip(item);
+            if let Some(previous) = previous {
+                character.add_item(previous, 1, Some(previous.default_values.into()));
+            }
+        })
+        .await?;
+
+    if !equipped {
+        ctx.reply(
+            Response::new_user_reply(&author, "você não possui esse item!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let stats = item
+        .equipment_properties
+        .map(|properties| {
+            properties
+                .stats
+                .iter()
+                .map(|stat| format!("- {}", stat.description()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
 
     ctx.reply(
         Response::new_user_reply(
             &author,
-            format!("você equipou com sucesso a arma **{}**!", item.display_name),
+            format!(
+                "você equipou com sucesso o equipamento **{}**!{}\n{stats}",
+                item.display_name,
+                previous
+                    .map(|previous| format!(
+                        " **{}** voltou para o seu inventário.",
+                        previous.display_name
+                    ))
+                    .unwrap_or_default()
+            ),
         )
         .add_emoji_prefix(item.emoji),
     )
@@ -63,9 +63,12 @@ pub async fn explore(mut ctx: CommandContext) -> anyhow::Result<()> {
                 USERS_EXPLORING.remove(&author_id);
             }
 
-            let mut character = parse_user_character!(ctx, author);
-            character.action_points = character.action_points.saturating_sub(1);
-            ctx.db().characters().save(character).await?;
+            ctx.db()
+                .characters()
+                .update(character.id, |character| {
+                    character.action_points = character.action_points.saturating_sub(1);
+                })
+                .await?;
         }
         Err(e) => {
             unsafe {
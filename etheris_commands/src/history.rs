@@ -0,0 +1,101 @@
+use etheris_database::{battle_model::BattleModel, bson::oid::ObjectId};
+
+use crate::prelude::*;
+
+const BATTLES_LIMIT: i64 = 50;
+const BATTLES_PER_PAGE: usize = 5;
+
+#[command("Veja as lutas mais recentes de um personagem!")]
+#[name("historico")]
+#[character_required(true)]
+pub async fn history(
+    mut ctx: CommandContext,
+    #[rename("usuário")]
+    #[description("O usuário que você quer ver o histórico de lutas")]
+    user: Option<User>,
+) -> anyhow::Result<()> {
+    let user = user.unwrap_or(ctx.author().await?);
+    let character = parse_user_character!(ctx, user);
+
+    let battles = ctx
+        .db()
+        .battles()
+        .get_character_battles(character.id, BATTLES_LIMIT)
+        .await?;
+
+    if battles.is_empty() {
+        ctx.reply(
+            Response::new_user_reply(
+                &ctx.author().await?,
+                format!("**{}** ainda não lutou nenhuma vez!", character.name),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut pages = vec![];
+    for chunk in battles.chunks(BATTLES_PER_PAGE) {
+        let mut embed = EmbedBuilder::new_common()
+            .set_color(Color::LIGHT_YELLOW)
+            .set_author(EmbedAuthor {
+                name: format!("Histórico de lutas de {}", character.name),
+                icon_url: Some(user.avatar_url()),
+            });
+
+        for battle in chunk {
+            let (name, value) = render_battle(battle, character.id);
+            embed = embed.add_not_inlined_field(name, value);
+        }
+
+        pages.push(embed);
+    }
+
+    EmbedPagination::new(ctx, pages).send().await?;
+
+    Ok(())
+}
+
+fn render_battle(battle: &BattleModel, character_id: ObjectId) -> (String, String) {
+    let Some(participant) = battle.get_participant(character_id) else {
+        return ("Luta desconhecida".to_string(), "-".to_string());
+    };
+
+    let result = if battle.is_winner(character_id) {
+        "🌟 Vitória"
+    } else if participant.died {
+        "💀 Morte"
+    } else {
+        "❌ Derrota"
+    };
+
+    let opponents = battle
+        .participants
+        .iter()
+        .filter(|p| p.team != participant.team)
+        .map(|p| format!("{} (`{} PL`)", p.name, p.pl))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let kind = if battle.pvp { "PvP" } else { "PvE" };
+
+    let duration = battle.duration();
+    let mut value = format!(
+        "**Região**: {}\n**Turnos**: `{}` | **Duração**: `{}m {}s`\n**Data**: <t:{}:R>",
+        battle.region,
+        battle.turns,
+        duration.num_minutes(),
+        duration.num_seconds() % 60,
+        battle.ended_at.timestamp()
+    );
+
+    if battle.is_winner(character_id) && (battle.rewards.orbs > 0 || battle.rewards.xp > 0) {
+        value.push_str(&format!(
+            "\n**Recompensas**: `{}` orbs, `{}` XP",
+            battle.rewards.orbs, battle.rewards.xp
+        ));
+    }
+
+    (format!("{result} [{kind}] contra {opponents}"), value)
+}
@@ -0,0 +1,107 @@
+use etheris_data::building::{BuildingKind, HOSPITAL_TREATMENT_PRICE};
+use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+
+use crate::prelude::*;
+
+#[command("Em uma cidade, pague um hospital de jogador para se recuperar rapidamente!")]
+#[name("hospital")]
+#[character_required(true)]
+pub async fn hospital(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let region = character.region;
+
+    if region.city().is_none() {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você precisa estar em uma cidade para ir a um hospital!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(hospital) = ctx
+        .db()
+        .buildings()
+        .get_all_by_region_as_vec(region)
+        .await?
+        .into_iter()
+        .filter(|b| b.kind == BuildingKind::Hospital)
+        .choose(&mut StdRng::from_entropy())
+    else {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "não há nenhum hospital em **{region}**! Use **/descansar** para se recuperar."
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if character.orbs < HOSPITAL_TREATMENT_PRICE {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("o tratamento custa **{HOSPITAL_TREATMENT_PRICE} ◎**!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    verify_user_cooldown!(ctx, author, "HOSPITAL");
+    ctx.db()
+        .cooldowns()
+        .create_cooldown(
+            author.id,
+            "HOSPITAL",
+            chrono::Duration::try_minutes(3).unwrap(),
+        )
+        .await?;
+
+    let mut treated = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            treated = character.orbs >= HOSPITAL_TREATMENT_PRICE;
+            if !treated {
+                return;
+            }
+
+            character.remove_orbs(HOSPITAL_TREATMENT_PRICE);
+            character.stats.resistance.value = character.stats.resistance.max;
+            character.stats.vitality.value = character.stats.vitality.max;
+            character.stats.ether.value = character.stats.ether.max;
+        })
+        .await?;
+
+    if !treated {
+        return Ok(());
+    }
+    ctx.db()
+        .buildings()
+        .add_orbs(hospital.id, HOSPITAL_TREATMENT_PRICE)
+        .await?;
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você foi tratado no hospital **{}** por **{HOSPITAL_TREATMENT_PRICE} ◎** e retornou ao seu potencial máximo!",
+                hospital.name
+            ),
+        )
+        .add_emoji_prefix(BuildingKind::Hospital.emoji()),
+    )
+    .await?;
+
+    Ok(())
+}
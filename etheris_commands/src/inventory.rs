@@ -159,7 +159,9 @@ fn generate_inventory_pages(
 
             description.push_str(&format!(
                 "{} **{}** ({}x)",
-                item.emoji, item.display_name, inventory_item.quantity
+                item.emoji,
+                inventory_item.values.name_with_upgrades(item.display_name),
+                inventory_item.quantity
             ));
 
             let alt = inventory_item.values.alternative_names();
@@ -117,27 +117,32 @@ pub async fn learn(mut ctx: CommandContext) -> anyhow::Result<()> {
 
     let cost = skill.kind().knowledge_cost();
 
-    let mut character = parse_user_character!(ctx, author);
-    if character.action_points < 1 {
-        return Ok(());
-    }
+    let mut learned = false;
+    let mut auto_equipped = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            learned = character.action_points >= 1 && character.knowledge_points >= cost;
+            if !learned {
+                return;
+            }
+
+            character.action_points -= 1;
+            character.knowledge_points -= cost;
+
+            character.learn_skill(skill.kind());
+
+            auto_equipped = character.learned_skills.len() <= 7;
+            if auto_equipped {
+                character.equip_skill(skill.kind());
+            }
+        })
+        .await?;
 
-    if character.knowledge_points < cost {
+    if !learned {
         return Ok(());
     }
 
-    character.action_points -= 1;
-    character.knowledge_points -= cost;
-
-    character.learn_skill(skill.kind());
-
-    let auto_equipped = character.learned_skills.len() <= 7;
-    if auto_equipped {
-        character.equip_skill(skill.kind());
-    }
-
-    ctx.db().characters().save(character).await?;
-
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(component));
 
     let buttons = buttons
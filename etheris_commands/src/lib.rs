@@ -31,6 +31,8 @@ mod daily;
 mod deallocate;
 mod equip;
 mod explore;
+mod history;
+mod hospital;
 mod hunt;
 mod infos;
 mod inventory;
@@ -45,18 +47,26 @@ mod shop;
 mod skills;
 mod stats;
 mod study;
+mod trade;
 mod train;
 mod travel;
 mod tutorial;
 mod unequip;
 mod usecmd;
+mod watch;
 mod work;
 
 mod test;
 
+mod blacksmith;
+mod building;
+mod companion;
+pub mod market;
+mod party;
 mod rank;
 mod send;
 mod skill;
+pub mod tournament;
 
 pub type CommandMap = HashMap<String, BoxedCommand>;
 
@@ -79,6 +89,7 @@ pub static COMMANDS: Lazy<CommandMap> = Lazy::new(|| {
     register_command!(map, battle::BattleCommand);
     register_command!(map, travel::TravelCommand);
     register_command!(map, rest::RestCommand);
+    register_command!(map, hospital::HospitalCommand);
     register_command!(map, learn::LearnCommand);
     register_command!(map, work::WorkCommand);
     register_command!(map, stats::StatsCommand);
@@ -96,10 +107,19 @@ pub static COMMANDS: Lazy<CommandMap> = Lazy::new(|| {
     register_command!(map, adm::AdmCommand);
     register_command!(map, sell::SellCommand);
     register_command!(map, meditate::MeditateCommand);
+    register_command!(map, history::HistoryCommand);
+    register_command!(map, watch::WatchCommand);
+    register_command!(map, trade::TradeCommand);
 
     send::register_commands(&mut map);
     skill::register_commands(&mut map);
     rank::register_commands(&mut map);
+    party::register_commands(&mut map);
+    companion::register_commands(&mut map);
+    blacksmith::register_commands(&mut map);
+    building::register_commands(&mut map);
+    market::register_commands(&mut map);
+    tournament::register_commands(&mut map);
 
     map
 });
@@ -0,0 +1,33 @@
+use etheris_database::market_model::ListingKind;
+
+use crate::prelude::*;
+
+#[command("Leiloe um item do seu inventário no mercado! Quem der o maior lance leva.")]
+#[name("mercado leiloar")]
+#[character_required(true)]
+pub async fn market_auction(
+    mut ctx: CommandContext,
+    #[rename("item")]
+    #[description("O item do seu inventário que você quer leiloar")]
+    item_name: String,
+    #[rename("quantia")]
+    #[description("Quantos itens leiloar juntos")]
+    amount: i64,
+    #[rename("lance")]
+    #[description("O lance inicial do leilão")]
+    price: i64,
+    #[rename("horas")]
+    #[description("Por quantas horas o leilão fica aberto (padrão: 24)")]
+    hours: Option<i64>,
+) -> anyhow::Result<()> {
+    let hours = hours.unwrap_or(24).clamp(1, 72);
+    super::list_item(
+        &mut ctx,
+        item_name,
+        amount,
+        price,
+        ListingKind::Auction,
+        chrono::Duration::try_hours(hours).unwrap_or_default(),
+    )
+    .await
+}
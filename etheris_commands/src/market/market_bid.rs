@@ -0,0 +1,172 @@
+use etheris_database::{
+    bson::oid::ObjectId,
+    market_model::{ListingKind, MarketBid},
+};
+
+use crate::prelude::*;
+
+#[command(
+    "Dê um lance em um leilão do mercado! Os orbs ficam guardados até alguém cobrir o lance."
+)]
+#[name("mercado lance")]
+#[character_required(true)]
+pub async fn market_bid(
+    mut ctx: CommandContext,
+    #[rename("código")]
+    #[description("O código do leilão")]
+    #[min_max_length(1, 16)]
+    code: String,
+    #[rename("valor")]
+    #[description("Quantos orbs você oferece")]
+    amount: i64,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let Some(listing) = super::get_listing(&mut ctx, &author, &code).await? else {
+        return Ok(());
+    };
+
+    let Some(item) = items::get_item(&listing.item.identifier) else {
+        return Ok(());
+    };
+    let item_name = listing.item.values.name_with_upgrades(item.display_name);
+
+    if listing.kind != ListingKind::Auction {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "essa oferta não é um leilão! Compre com **/mercado comprar**.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if listing.seller_id == character.id {
+        ctx.reply(
+            Response::new_user_reply(&author, "você não pode dar lances no seu próprio leilão!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let min_bid = listing.min_next_bid();
+    if amount < min_bid {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!("o lance precisa ser de pelo menos **{min_bid} ◎**!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você quer dar um lance de **{amount} ◎** por **{}x {}**? Os orbs ficam guardados e voltam para você se alguém cobrir o lance.",
+                    listing.item.quantity, item_name
+                ),
+            )
+            .add_emoji_prefix(item.emoji),
+        )
+        .await?;
+    if !confirmation {
+        return Ok(());
+    }
+
+    let character = parse_user_character!(ctx, author);
+
+    // Take the orbs first so they can't be spent while the bid is open
+    if !ctx
+        .db()
+        .characters()
+        .take_orbs(character.id, amount)
+        .await?
+    {
+        ctx.send(
+            Response::new_user_reply(&author, "você não tem orbs suficientes!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let previous = listing.highest_bid.clone();
+    let bid = MarketBid {
+        character_id: character.id,
+        user_id: author.id.to_string(),
+        amount,
+    };
+
+    let placed = if listing.is_expired() {
+        Ok(false)
+    } else {
+        ctx.db()
+            .market()
+            .place_bid(listing.id, previous.as_ref(), bid.clone())
+            .await
+    };
+
+    // The orbs of the bid were already taken, so they go back on every way out
+    let placed = match placed {
+        Ok(placed) => placed,
+        Err(e) => {
+            refund_bid(&ctx, listing.id, &bid).await;
+            return Err(e);
+        }
+    };
+
+    if !placed {
+        refund_bid(&ctx, listing.id, &bid).await;
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "alguém deu um lance antes de você ou o leilão acabou! Seus orbs foram devolvidos.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // The refund of the previous bid was recorded with the new one, it's retried by the market job if this fails
+    if let Err(e) = ctx.db().market().pay(listing.id).await {
+        eprintln!(
+            "Failed to refund the bid of the listing {}: {e:?}",
+            listing.id
+        );
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você deu um lance de **{amount} ◎** por **{}x {}**!",
+                listing.item.quantity, item_name
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn refund_bid(ctx: &CommandContext, listing_id: ObjectId, bid: &MarketBid) {
+    if let Err(e) = ctx.db().market().refund_bid(listing_id, bid).await {
+        eprintln!(
+            "Failed to refund {} orbs to {} for the listing {listing_id}: {e:?}",
+            bid.amount, bid.character_id
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use etheris_database::market_model::ListingKind;
+
+use crate::prelude::*;
+
+#[command("Compre um item à venda no mercado!")]
+#[name("mercado comprar")]
+#[character_required(true)]
+pub async fn market_buy(
+    mut ctx: CommandContext,
+    #[rename("código")]
+    #[description("O código da oferta")]
+    #[min_max_length(1, 16)]
+    code: String,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let Some(listing) = super::get_listing(&mut ctx, &author, &code).await? else {
+        return Ok(());
+    };
+
+    let Some(item) = items::get_item(&listing.item.identifier) else {
+        return Ok(());
+    };
+    let item_name = listing.item.values.name_with_upgrades(item.display_name);
+
+    if listing.kind == ListingKind::Auction {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "essa oferta é um leilão! Dê um lance com **/mercado lance**.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if listing.seller_id == character.id {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você não pode comprar a sua própria oferta! Use **/mercado cancelar** para tirar ela do mercado.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você quer comprar **{}x {}** de **{}** por **{} ◎**?",
+                    listing.item.quantity, item_name, listing.seller_name, listing.price
+                ),
+            )
+            .add_emoji_prefix(item.emoji),
+        )
+        .await?;
+    if !confirmation {
+        return Ok(());
+    }
+
+    let price = listing.price;
+    if !ctx.db().characters().take_orbs(character.id, price).await? {
+        ctx.send(
+            Response::new_user_reply(&author, "você não tem orbs suficientes!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // The orbs were already taken, so they go back if the listing can't be claimed
+    let listing = match ctx.db().market().claim(listing.id).await {
+        Ok(Some(listing)) => listing,
+        result => {
+            ctx.db().characters().add_orbs(character.id, price).await?;
+            result?;
+
+            ctx.send(
+                Response::new_user_reply(
+                    &author,
+                    "essa oferta acabou de ser vendida ou cancelada!",
+                )
+                .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if let Err(e) = ctx
+        .db()
+        .characters()
+        .add_item(
+            character.id,
+            item,
+            listing.item.quantity,
+            Some(listing.item.values.clone()),
+        )
+        .await
+    {
+        ctx.db().market().unclaim(listing.id).await?;
+        ctx.db().characters().add_orbs(character.id, price).await?;
+        return Err(e);
+    }
+
+    ctx.db()
+        .characters()
+        .add_orbs(listing.seller_id, listing.price)
+        .await?;
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você comprou **{}x {}** de **{}** por **{} ◎**!",
+                listing.item.quantity, item_name, listing.seller_name, listing.price
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::prelude::*;
+
+#[command("Tire uma oferta sua do mercado e receba o item de volta!")]
+#[name("mercado cancelar")]
+#[character_required(true)]
+pub async fn market_cancel(
+    mut ctx: CommandContext,
+    #[rename("código")]
+    #[description("O código da oferta")]
+    #[min_max_length(1, 16)]
+    code: String,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let Some(listing) = super::get_listing(&mut ctx, &author, &code).await? else {
+        return Ok(());
+    };
+
+    if listing.seller_id != character.id {
+        ctx.reply(
+            Response::new_user_reply(&author, "essa oferta não é sua!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if listing.highest_bid.is_some() {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "você não pode cancelar um leilão que já recebeu lances!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(item) = items::get_item(&listing.item.identifier) else {
+        return Ok(());
+    };
+    let item_name = listing.item.values.name_with_upgrades(item.display_name);
+
+    // A bid could have been placed since the listing was fetched
+    let Some(listing) = ctx.db().market().claim(listing.id).await? else {
+        return Ok(());
+    };
+
+    if let Err(e) = ctx
+        .db()
+        .characters()
+        .add_item(
+            character.id,
+            item,
+            listing.item.quantity,
+            Some(listing.item.values.clone()),
+        )
+        .await
+    {
+        ctx.db().market().unclaim(listing.id).await?;
+        return Err(e);
+    }
+
+    if let Some(bid) = &listing.highest_bid {
+        ctx.db()
+            .characters()
+            .add_orbs(bid.character_id, bid.amount)
+            .await?;
+    }
+
+    ctx.reply(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você tirou **{}x {}** do mercado e recebeu o item de volta! A taxa do mercado não é devolvida.",
+                listing.item.quantity, item_name
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use etheris_data::items;
+use etheris_database::{market_model::MarketListingModel, EtherisDatabase};
+use etheris_framework::EtherisClient;
+
+use crate::util::send_direct_message;
+
+/// Gives the items of finished auctions to the highest bidders, and returns unsold items to their sellers.
+pub async fn settle_expired_listings(
+    db: &EtherisDatabase,
+    client: &EtherisClient,
+) -> anyhow::Result<()> {
+    for listing in db.market().get_expired_listings().await? {
+        let id = listing.id;
+        if let Err(e) = settle_expired_listing(db, client, listing).await {
+            eprintln!("Failed to settle the listing {id}: {e:?}");
+        }
+    }
+
+    // Payouts that failed before are given again
+    for listing in db.market().get_listings_with_unpaid_payouts().await? {
+        if let Err(e) = db.market().pay(listing.id).await {
+            eprintln!("Failed to pay the listing {}: {e:?}", listing.id);
+        }
+    }
+
+    Ok(())
+}
+
+async fn settle_expired_listing(
+    db: &EtherisDatabase,
+    client: &EtherisClient,
+    listing: MarketListingModel,
+) -> anyhow::Result<()> {
+    let item = items::get_item(&listing.item.identifier);
+    if item.is_none() {
+        eprintln!(
+            "The listing {} has an unknown item: {}",
+            listing.id, listing.item.identifier
+        );
+    }
+
+    // The payouts are recorded in the same write that closes the listing, so each one is given once
+    if !db
+        .market()
+        .settle(&listing, listing.settlement_payouts(item.is_some()))
+        .await?
+    {
+        return Ok(());
+    }
+
+    // If it fails, the payouts stay in the listing and are given in the next settlement
+    if let Err(e) = db.market().pay(listing.id).await {
+        eprintln!("Failed to pay the listing {}: {e:?}", listing.id);
+    }
+
+    let Some(item) = item else {
+        return Ok(());
+    };
+    let item_name = listing.item.values.name_with_upgrades(item.display_name);
+
+    match &listing.highest_bid {
+        Some(bid) => {
+            send_direct_message(
+                client,
+                &bid.user_id,
+                format!(
+                    "🔨 Você venceu o leilão `{}` e recebeu **{}x {}** por **{} ◎**!",
+                    listing.code, listing.item.quantity, item_name, bid.amount
+                ),
+            )
+            .await
+            .ok();
+            send_direct_message(
+                client,
+                &listing.seller_user_id,
+                format!(
+                    "🔨 O seu leilão `{}` de **{}x {}** terminou e você recebeu **{} ◎**!",
+                    listing.code, listing.item.quantity, item_name, bid.amount
+                ),
+            )
+            .await
+            .ok();
+        }
+        None => {
+            send_direct_message(
+                client,
+                &listing.seller_user_id,
+                format!(
+                    "📦 Ninguém comprou a sua oferta `{}` no mercado e **{}x {}** voltou para o seu inventário.",
+                    listing.code, listing.item.quantity, item_name
+                ),
+            )
+            .await
+            .ok();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use etheris_data::items::ItemTag;
+use etheris_database::market_model::ListingKind;
+
+use crate::{prelude::*, util::format_duration};
+
+const LISTINGS_PER_PAGE: i64 = 15;
+
+#[command("Procure itens à venda e em leilão no mercado!")]
+#[name("mercado buscar")]
+#[character_required(true)]
+pub async fn market_search(
+    mut ctx: CommandContext,
+    #[rename("item")]
+    #[description("O nome do item que você procura")]
+    item_name: Option<String>,
+    #[rename("categoria")]
+    #[description("Consumível, Ferramenta, Material, Minério, Cosmético, Equipamento...")]
+    category: Option<String>,
+    #[rename("página")]
+    #[description("A página dos resultados que você quer ver")]
+    page: Option<i64>,
+) -> anyhow::Result<()> {
+    let page = page.unwrap_or(1).max(1);
+    let author = ctx.author().await?;
+
+    let tag = match category {
+        Some(category) => {
+            let category = clear_string(&category);
+            let Some(tag) = ItemTag::LIST
+                .into_iter()
+                .find(|t| clear_string(t.display_name()) == category)
+            else {
+                ctx.reply(
+                    Response::new_user_reply(
+                        &author,
+                        format!(
+                            "essa categoria não existe! As categorias são: {}.",
+                            ItemTag::LIST
+                                .iter()
+                                .map(|t| format!("**{}**", t.display_name()))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    )
+                    .add_emoji_prefix(emojis::ERROR),
+                )
+                .await?;
+                return Ok(());
+            };
+
+            Some(tag)
+        }
+        None => None,
+    };
+
+    // Listings only know the identifiers of their items, so the items are searched first
+    let name = item_name.map(|n| clear_string(&n));
+    let identifiers = (name.is_some() || tag.is_some()).then(|| {
+        items::ALL_ITEMS
+            .iter()
+            .filter(|item| {
+                name.as_ref()
+                    .is_none_or(|n| clear_string(item.display_name).contains(n))
+            })
+            .filter(|item| tag.is_none_or(|t| item.tags.contains(&t)))
+            .map(|item| item.identifier)
+            .collect::<Vec<_>>()
+    });
+
+    let (listings, total) = ctx
+        .db()
+        .market()
+        .search_open_listings(identifiers, page as u64 - 1, LISTINGS_PER_PAGE)
+        .await?;
+    let listings = listings
+        .into_iter()
+        .filter_map(|l| items::get_item(&l.item.identifier).map(|item| (l, item)))
+        .collect::<Vec<_>>();
+
+    if listings.is_empty() {
+        ctx.reply(
+            Response::new_user_reply(&author, "nenhuma oferta foi encontrada no mercado!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+    let mut embed = EmbedBuilder::new_common()
+        .set_color(Color::CYAN_GREEN)
+        .set_author(EmbedAuthor {
+            name: "Mercado".to_string(),
+            icon_url: Some(author.avatar_url()),
+        })
+        .set_description("Compre com **/mercado comprar** ou dê lances com **/mercado lance** usando o código da oferta.")
+        .set_footer(EmbedFooter {
+            text: format!(
+                "Página {page} de {}",
+                total.div_ceil(LISTINGS_PER_PAGE as u64).max(1)
+            ),
+            icon_url: None,
+        });
+
+    for (listing, item) in listings {
+        let price = match listing.kind {
+            ListingKind::FixedPrice => format!("**Preço**: {} ◎", listing.price),
+            ListingKind::Auction => format!(
+                "**Leilão**: {} ◎{}",
+                listing
+                    .highest_bid
+                    .as_ref()
+                    .map(|b| b.amount)
+                    .unwrap_or(listing.price),
+                if listing.highest_bid.is_some() {
+                    ""
+                } else {
+                    " (sem lances)"
+                }
+            ),
+        };
+
+        embed = embed.add_inlined_field(
+            format!(
+                "{} {}x {} `{}`",
+                item.emoji,
+                listing.item.quantity,
+                listing.item.values.name_with_upgrades(item.display_name),
+                listing.code
+            ),
+            format!(
+                "{price}\n**Vendedor**: {}\n**Termina em**: {}",
+                listing.seller_name,
+                format_duration(listing.ends_at.0 - now)
+            ),
+        );
+    }
+
+    ctx.reply(embed).await?;
+
+    Ok(())
+}
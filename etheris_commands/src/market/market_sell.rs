@@ -0,0 +1,29 @@
+use etheris_database::market_model::{ListingKind, MARKET_LISTING_DAYS};
+
+use crate::prelude::*;
+
+#[command("Coloque um item do seu inventário à venda no mercado por um preço fixo!")]
+#[name("mercado vender")]
+#[character_required(true)]
+pub async fn market_sell(
+    mut ctx: CommandContext,
+    #[rename("item")]
+    #[description("O item do seu inventário que você quer vender")]
+    item_name: String,
+    #[rename("quantia")]
+    #[description("Quantos itens vender juntos")]
+    amount: i64,
+    #[rename("preço")]
+    #[description("O preço de todos os itens juntos")]
+    price: i64,
+) -> anyhow::Result<()> {
+    super::list_item(
+        &mut ctx,
+        item_name,
+        amount,
+        price,
+        ListingKind::FixedPrice,
+        chrono::Duration::try_days(MARKET_LISTING_DAYS).unwrap_or_default(),
+    )
+    .await
+}
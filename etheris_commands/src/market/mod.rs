@@ -0,0 +1,177 @@
+use etheris_database::{
+    common::InventoryItem,
+    market_model::{ListingKind, MarketListingModel},
+};
+
+use crate::{prelude::*, CommandMap};
+
+pub use market_jobs::settle_expired_listings;
+
+mod market_auction;
+mod market_bid;
+mod market_buy;
+mod market_cancel;
+mod market_jobs;
+mod market_search;
+mod market_sell;
+
+pub fn register_commands(map: &mut CommandMap) {
+    register_command!(map, market_sell::Market_sellCommand);
+    register_command!(map, market_auction::Market_auctionCommand);
+    register_command!(map, market_search::Market_searchCommand);
+    register_command!(map, market_buy::Market_buyCommand);
+    register_command!(map, market_bid::Market_bidCommand);
+    register_command!(map, market_cancel::Market_cancelCommand);
+}
+
+/// Puts an item of the author's inventory in the market, keeping it in escrow until the listing is over.
+async fn list_item(
+    ctx: &mut CommandContext,
+    item_name: String,
+    amount: i64,
+    price: i64,
+    kind: ListingKind,
+    duration: chrono::Duration,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let amount = amount.clamp(1, i32::MAX as i64) as usize;
+    let price = price.max(1);
+    let fee = MarketListingModel::fee(price);
+
+    let Some(inventory_item) = character.get_inventory_item_by_name(&item_name) else {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                "esse item não existe, não está no seu inventário ou teve o nome escrito errado!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let Some(item) = items::get_item(&inventory_item.identifier) else {
+        return Ok(());
+    };
+    let values = inventory_item.values.clone();
+    let item_name = values.name_with_upgrades(item.display_name);
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você quer {} **{amount}x {}** no mercado {} **{price} ◎**? O mercado cobra uma taxa de **{fee} ◎** que não é devolvida, e o item fica guardado até a venda acabar.",
+                    if kind == ListingKind::Auction { "leiloar" } else { "vender" },
+                    item_name,
+                    if kind == ListingKind::Auction { "com lance inicial de" } else { "por" },
+                ),
+            )
+            .add_emoji_prefix(item.emoji),
+        )
+        .await?;
+    if !confirmation {
+        return Ok(());
+    }
+
+    let character = parse_user_character!(ctx, author);
+    if !character.has_item_with_values(&item, &values, amount) {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não tem essa quantia desse item no **/inventário**!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if character.orbs < fee {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!("você precisa de **{fee} ◎** para pagar a taxa do mercado!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut listing = MarketListingModel::new(
+        character.id,
+        character.user_id.clone(),
+        character.name.clone(),
+        InventoryItem {
+            identifier: item.identifier.to_string(),
+            quantity: amount,
+            values,
+        },
+        kind,
+        price,
+        duration,
+    );
+    if !ctx.db().market().create_listing(&mut listing, fee).await? {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "seu inventário mudou enquanto o item era colocado no mercado! Tente de novo.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "**{amount}x {}** {} no mercado com o código `{}`!",
+                item_name,
+                if kind == ListingKind::Auction {
+                    "foi a leilão"
+                } else {
+                    "está à venda"
+                },
+                listing.code
+            ),
+        )
+        .add_emoji_prefix(item.emoji),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Gets the open listing with the code, or replies that it doesn't exist.
+async fn get_listing(
+    ctx: &mut CommandContext,
+    author: &User,
+    code: &str,
+) -> anyhow::Result<Option<MarketListingModel>> {
+    let listing = ctx
+        .db()
+        .market()
+        .get_open_by_code(code)
+        .await?
+        .filter(|l| !l.is_expired());
+
+    if listing.is_none() {
+        ctx.reply(
+            Response::new_user_reply(
+                author,
+                "não existe nenhuma oferta aberta com esse código! Procure ofertas com **/mercado buscar**.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+    }
+
+    Ok(listing)
+}
@@ -54,19 +54,21 @@ pub async fn meditate(mut ctx: CommandContext) -> anyhow::Result<()> {
             has_consequences: false,
             casual: true,
             max_intruders: 0,
+            seed: None,
+            ranked: false,
         },
         vec![fighter, inner_shadow],
     )?;
 
     let mut controller = BattleController::new(battle, ctx);
     let result = controller.run().await?;
-    let mut ctx = controller.ctx.clone();
+    let mut ctx = controller.ctx().await;
 
     let all_fighters = result.losers.iter().chain(result.winners.iter()).cloned().collect::<Vec<_>>();
 
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
 
     let (_, inner_shadow_fighter) = {
         let author_fighter = all_fighters.iter().find(|f| f.team == 0 && f.user.is_some());
@@ -105,20 +107,26 @@ pub async fn meditate(mut ctx: CommandContext) -> anyhow::Result<()> {
         
         let mut extras = vec![];
 
-        character.knowledge_xp += knowledge_xp;
-        character.intelligence_xp += intelligence_xp;
-        if let Some(next_level) = next_mental_level {
+        if next_mental_level.is_some() {
             extras.push("**Você liberou 10% do seu potencial oculto!**".to_string());
-            character.potential += 0.1;
-            character.mental_level = next_level;
         }
 
-        if MentalLevel::Accustomed == character.mental_level {
-            character.actions.insert(BattleAction::ControlPower);
+        if MentalLevel::Accustomed == next_mental_level.unwrap_or(character.mental_level) {
             extras.push("**Agora você é capaz de controlar o seu poder no meio de batalhas!**".to_string());
         }
 
-        ctx.db().characters().save(character).await?;
+        ctx.db().characters().update(character.id, |character| {
+            character.knowledge_xp += knowledge_xp;
+            character.intelligence_xp += intelligence_xp;
+            if let Some(next_level) = next_mental_level {
+                character.potential += 0.1;
+                character.mental_level = next_level;
+            }
+
+            if MentalLevel::Accustomed == character.mental_level {
+                character.actions.insert(BattleAction::ControlPower);
+            }
+        }).await?;
 
         ctx.send(
             Response::new_user_reply(
@@ -144,7 +152,10 @@ pub async fn meditate(mut ctx: CommandContext) -> anyhow::Result<()> {
         let knowledge_xp = 1 + (total_dmg_dealt_ratio * 10.0).round() as u32;
         let intelligence_xp = 3 + (total_dmg_dealt_ratio * 30.0).round() as u32;
 
-        ctx.db().characters().save(character).await?;
+        ctx.db().characters().update(character.id, |character| {
+            character.knowledge_xp += knowledge_xp;
+            character.intelligence_xp += intelligence_xp;
+        }).await?;
 
         ctx.send(
             Response::new_user_reply(
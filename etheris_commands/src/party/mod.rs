@@ -0,0 +1,17 @@
+use crate::CommandMap;
+
+mod party_create;
+mod party_disband;
+mod party_info;
+mod party_invite;
+mod party_join;
+mod party_leave;
+
+pub fn register_commands(map: &mut CommandMap) {
+    register_command!(map, party_create::Party_createCommand);
+    register_command!(map, party_invite::Party_inviteCommand);
+    register_command!(map, party_join::Party_joinCommand);
+    register_command!(map, party_leave::Party_leaveCommand);
+    register_command!(map, party_disband::Party_disbandCommand);
+    register_command!(map, party_info::Party_infoCommand);
+}
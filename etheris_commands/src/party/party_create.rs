@@ -0,0 +1,57 @@
+use etheris_database::party_model::PartyModel;
+
+use crate::prelude::*;
+
+#[command("Crie um grupo para explorar e caçar com outros personagens!")]
+#[name("grupo criar")]
+#[character_required(true)]
+pub async fn party_create(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    if ctx
+        .db()
+        .parties()
+        .get_by_member(character.id)
+        .await?
+        .is_some()
+    {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você já está em um grupo! Saia dele com **/grupo sair** antes de criar outro.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !ctx
+        .db()
+        .parties()
+        .create(PartyModel::new(character.id))
+        .await?
+    {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você já está em um grupo! Saia dele com **/grupo sair** antes de criar outro.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            "você criou um grupo! Convide outros personagens com **/grupo convidar**.",
+        )
+        .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
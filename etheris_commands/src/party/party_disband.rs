@@ -0,0 +1,48 @@
+use crate::prelude::*;
+
+#[command("Desfaça o grupo que você lidera!")]
+#[name("grupo dissolver")]
+#[character_required(true)]
+pub async fn party_disband(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(party) = ctx.db().parties().get_by_leader(character.id).await? else {
+        ctx.send(
+            Response::new_user_reply(&author, "você não é líder de nenhum grupo!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você tem certeza que quer dissolver o seu grupo de `{}` membros?",
+                    party.members.len()
+                ),
+            )
+            .add_emoji_prefix("❓"),
+        )
+        .await?;
+
+    if !confirmation {
+        return Ok(());
+    }
+
+    ctx.db().parties().delete(&party).await?;
+
+    ctx.send(
+        Response::new_user_reply(&author, "o seu grupo foi dissolvido!")
+            .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
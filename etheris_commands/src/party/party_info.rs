@@ -0,0 +1,62 @@
+use crate::prelude::*;
+
+#[command("Veja os membros do grupo de um personagem!")]
+#[name("grupo ver")]
+#[character_required(true)]
+pub async fn party_info(
+    mut ctx: CommandContext,
+    #[rename("usuário")]
+    #[description("O usuário que você quer ver o grupo")]
+    user: Option<User>,
+) -> anyhow::Result<()> {
+    let user = user.unwrap_or(ctx.author().await?);
+    let character = parse_user_character!(ctx, user);
+
+    let Some(party) = ctx.db().parties().get_by_member(character.id).await? else {
+        ctx.send(
+            Response::new_user_reply(
+                &ctx.author().await?,
+                format!("**{}** não está em nenhum grupo!", character.name),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let mut description = String::new();
+    let mut total_pl = 0;
+    for member_id in party.members.iter() {
+        let Some(member) = ctx.db().characters().get_by_id(*member_id).await? else {
+            continue;
+        };
+
+        total_pl += member.pl;
+        description.push_str(&format!(
+            "{} **{}** - `{} PL` - {}{}\n",
+            if party.is_leader(member.id) {
+                "👑"
+            } else {
+                "👤"
+            },
+            member.name,
+            member.pl,
+            member.region,
+            if member.alive { "" } else { " 💀" }
+        ));
+    }
+
+    let embed = EmbedBuilder::new_common()
+        .set_color(Color::BLURPLE)
+        .set_author(EmbedAuthor {
+            name: format!("Grupo de {}", character.name),
+            icon_url: Some(user.avatar_url()),
+        })
+        .set_description(description)
+        .add_inlined_field("Poder total", format!("`{total_pl} PL`"))
+        .add_inlined_field("Convites pendentes", format!("`{}`", party.invites.len()));
+
+    ctx.send(embed).await?;
+
+    Ok(())
+}
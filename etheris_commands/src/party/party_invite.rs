@@ -0,0 +1,78 @@
+use etheris_database::party_model::MAX_PARTY_MEMBERS;
+
+use crate::prelude::*;
+
+#[command("Convide alguém para o seu grupo!")]
+#[name("grupo convidar")]
+#[character_required(true)]
+pub async fn party_invite(
+    mut ctx: CommandContext,
+    #[rename("usuário")]
+    #[description("O usuário que você quer convidar")]
+    user: User,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let invited_character = parse_user_character!(ctx, user);
+
+    let Some(party) = ctx.db().parties().get_by_leader(character.id).await? else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "só o líder de um grupo pode convidar! Crie um grupo com **/grupo criar**.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if party.is_full() {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!("o seu grupo já tem o máximo de `{MAX_PARTY_MEMBERS}` membros!"),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if ctx
+        .db()
+        .parties()
+        .get_by_member(invited_character.id)
+        .await?
+        .is_some()
+    {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!("**{}** já está em um grupo!", invited_character.name),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.db()
+        .parties()
+        .invite(party.id, invited_character.id)
+        .await?;
+
+    ctx.send(
+        Response::new_user_reply(
+            &user,
+            format!(
+                "**{}** convidou você para o grupo dele! Use **/grupo entrar** para aceitar o convite.",
+                character.name
+            ),
+        )
+        .add_emoji_prefix("👥"),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::prelude::*;
+
+#[command("Entre no grupo de alguém que convidou você!")]
+#[name("grupo entrar")]
+#[character_required(true)]
+pub async fn party_join(
+    mut ctx: CommandContext,
+    #[rename("líder")]
+    #[description("O líder do grupo que convidou você")]
+    leader: User,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+    let leader_character = parse_user_character!(ctx, leader);
+
+    if ctx
+        .db()
+        .parties()
+        .get_by_member(character.id)
+        .await?
+        .is_some()
+    {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você já está em um grupo! Saia dele com **/grupo sair** antes de entrar em outro.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(party) = ctx
+        .db()
+        .parties()
+        .get_by_leader(leader_character.id)
+        .await?
+        .filter(|p| p.is_invited(character.id))
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "**{}** não convidou você para um grupo!",
+                    leader_character.name
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if party.is_full() || !ctx.db().parties().join(party.id, character.id).await? {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "esse grupo já está cheio, foi dissolvido ou você já entrou em outro grupo!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você entrou no grupo de **{}**! Membros do grupo na mesma região exploram e caçam juntos.",
+                leader_character.name
+            ),
+        )
+        .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::prelude::*;
+
+#[command("Saia do seu grupo!")]
+#[name("grupo sair")]
+#[character_required(true)]
+pub async fn party_leave(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(party) = ctx.db().parties().get_by_member(character.id).await? else {
+        ctx.send(
+            Response::new_user_reply(&author, "você não está em nenhum grupo!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if !ctx.db().parties().leave(party.id, character.id).await? {
+        ctx.send(
+            Response::new_user_reply(&author, "você não está mais nesse grupo!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(&author, "você saiu do seu grupo!")
+            .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
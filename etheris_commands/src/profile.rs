@@ -1,4 +1,4 @@
-use etheris_data::items::get_item_by_weapon;
+use etheris_data::{equipment::EquipmentSlot, items::get_item_by_weapon};
 use etheris_rpg::{list::get_boxed_skill_from_kind, Fighter, FighterData};
 
 use crate::prelude::*;
@@ -41,9 +41,20 @@ pub async fn profile(
                 .collect::<Vec<_>>()
                 .join(", "),
             if let Some(weapon) = character.weapon {
+                let values = &character.weapon_values;
                 format!(
-                    "**Arma equipada**: {}",
-                    get_item_by_weapon(weapon).display_name
+                    "**Arma equipada**: {}{}{} (`{}/{}` de durabilidade)",
+                    get_item_by_weapon(weapon).display_name,
+                    match values.upgrade_level() {
+                        0 => String::new(),
+                        level => format!(" **+{level}**"),
+                    },
+                    values
+                        .enchantment()
+                        .map(|e| format!(" **{}**", e.name()))
+                        .unwrap_or_default(),
+                    values.durability().unwrap_or(weapon.max_durability()),
+                    weapon.max_durability()
                 )
             } else {
                 String::from("**Nenhuma arma equipada**")
@@ -129,6 +140,26 @@ pub async fn profile(
             value: format!("{} **Resistência**: **{}**/{}\n{} **Vitalidade**: {}/{}\n{} **Ether**: {}/{}", emojis::RESISTANCE, character.stats.resistance.value, character.stats.resistance.max, emojis::VITALITY, character.stats.vitality.value, character.stats.vitality.max, emojis::ETHER, character.stats.ether.value, character.stats.ether.max),
             inline: true,
         })
+        .add_field_with_emoji(Emoji::from_unicode("🛡️"), EmbedField {
+            name: "Equipamentos".into(),
+            value: {
+                let equipment = EquipmentSlot::LIST
+                    .iter()
+                    .filter_map(|slot| {
+                        character.equipment.get(*slot).map(|item| {
+                            format!("**{}**: {} {}", slot.name(), item.emoji, item.display_name)
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                if equipment.is_empty() {
+                    String::from("Nenhum equipamento")
+                } else {
+                    equipment.join("\n")
+                }
+            },
+            inline: true,
+        })
         .set_image(if let Some(attachment) = &attachment {
             format!("attachment://{}", attachment.filename)
         } else {
@@ -0,0 +1,69 @@
+use etheris_database::ranked::current_season;
+
+use crate::prelude::*;
+
+use super::rank_pl::{DEFAULT_MEDAL, MEDAL_EMOJIS};
+
+#[command("Lista dos melhores duelistas da temporada!")]
+#[name("rank pvp")]
+#[character_required(true)]
+pub async fn rank_pvp(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let mut character = parse_user_character!(ctx, author);
+    ctx.reply("Carregando rank...").await?;
+
+    let rank = ctx.db().characters().get_pvp_ranking().await?;
+
+    let embed = EmbedBuilder::new_common()
+        .set_color(Color::YELLOW)
+        .set_title(format!(
+            "{} Ranking PvP da temporada {}",
+            "⚔️",
+            current_season()
+        ));
+
+    let mut description = String::new();
+    for (index, ranked_character) in rank.into_iter().enumerate() {
+        let user_id = Id::new(ranked_character.user_id.parse::<u64>()?);
+        let user = ctx.client.get_user(user_id).await?;
+
+        let medal = *MEDAL_EMOJIS.get(index).unwrap_or(&DEFAULT_MEDAL);
+        let rating = ranked_character.pvp_rating;
+
+        description.push_str(&format!(
+            "{medal} **{}**: {} `{}` ({}V/{}D) ({})",
+            ranked_character.name,
+            rating.tier().emoji(),
+            rating.rating,
+            rating.wins,
+            rating.losses,
+            user.display_name()
+        ));
+
+        if user_id == author.id {
+            description.push_str(" 🙋");
+        }
+
+        description.push('\n');
+        if index == 2 {
+            description.push('\n');
+        }
+    }
+
+    character.pvp_rating.update_season();
+    let rating = character.pvp_rating;
+    description.push_str(&format!(
+        "\n**Sua classificação**: {} {} `{}` (pico: `{}`)",
+        rating.tier().emoji(),
+        rating.tier(),
+        rating.rating,
+        rating.peak
+    ));
+
+    ctx.update_interaction_reply(
+        Response::from(author.mention()).add_embed(embed.set_description(description)),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -16,7 +16,7 @@ pub async fn read(
     item: String,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     let Some(inventory_item) = character.get_inventory_item_by_name(&item) else {
         ctx.reply(
             Response::new_user_reply(&author, "você não possui esse item ou esse item não existe! Utilize **/inventário** para ver os seus itens.")
@@ -101,11 +101,17 @@ pub async fn read(
             Response::new_user_reply(&author, "você possui o item **Tradutor** e pode usar para ler. Você quer usar? (Você perderá o tradutor depois de usar!)")
             .add_emoji_prefix("❓")
         ).await?;
-        used_translator = confirmation;
 
         if confirmation {
-            character.remove_item(items::tool::TRANSLATOR, 1);
-            ctx.db().characters().save(character).await?;
+            ctx.db()
+                .characters()
+                .update(character.id, |character| {
+                    used_translator = character.has_item(&items::tool::TRANSLATOR, 1);
+                    if used_translator {
+                        character.remove_item(items::tool::TRANSLATOR, 1);
+                    }
+                })
+                .await?;
         }
     }
 
@@ -36,13 +36,15 @@ pub async fn rest(mut ctx: CommandContext) -> anyhow::Result<()> {
         )
         .await?;
 
-    let mut character = parse_user_character!(ctx, author);
-    character.action_points = character.action_points.saturating_sub(ap_price);
-    character.stats.resistance.value = character.stats.resistance.max;
-    character.stats.vitality.value = character.stats.vitality.max;
-    character.stats.ether.value = character.stats.ether.max;
-
-    ctx.db().characters().save(character).await?;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character.action_points = character.action_points.saturating_sub(ap_price);
+            character.stats.resistance.value = character.stats.resistance.max;
+            character.stats.vitality.value = character.stats.vitality.max;
+            character.stats.ether.value = character.stats.ether.max;
+        })
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
@@ -1,4 +1,5 @@
 use etheris_data::items::get_item;
+use etheris_database::city_price_model::{price_with_demand, PriceTrend};
 
 use crate::prelude::*;
 
@@ -45,6 +46,7 @@ pub async fn sell(
     let Some(item) = get_item(&inventory_item.identifier) else {
         return Ok(());
     };
+    let item_name = inventory_item.values.name_with_upgrades(item.display_name);
 
     if !item.purchase_properties.is_sellable || item.purchase_properties.base_sell_price < 1 {
         ctx.reply(
@@ -58,9 +60,15 @@ pub async fn sell(
         return Ok(());
     }
 
-    let price = ((item.purchase_properties.base_sell_price as f32 * city.sell_multiplier).round()
-        as i32)
-        .max(1);
+    let region = character.region;
+    let demand = ctx
+        .db()
+        .city_prices()
+        .get_demand(region, item.identifier)
+        .await?;
+    let base_price =
+        (item.purchase_properties.base_sell_price as f32 * city.sell_multiplier).round();
+    let price = price_with_demand(base_price as i64, demand).clamp(1, i32::MAX as i64) as i32;
     let profit = amount * price;
 
     let confirmation = ctx
@@ -71,8 +79,8 @@ pub async fn sell(
             Response::new_user_reply(
                 &author,
                 format!(
-                    "você tem certeza que quer vender **{}x {}** por **{} ◎** {}? (Valor por unidade: {} ◎)",
-                    amount, item.display_name, profit, emojis::ORB, price
+                    "você tem certeza que quer vender **{}x {}** por **{} ◎** {}? (Valor por unidade: {} ◎ | Tendência em **{region}**: {})",
+                    amount, item_name, profit, emojis::ORB, price, PriceTrend::from_demand(demand)
                 ),
             )
             .add_emoji_prefix(item.emoji),
@@ -82,9 +90,21 @@ pub async fn sell(
         return Ok(());
     }
 
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
+
+    let mut sold = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            sold = character.has_item_with_values(&item, &inventory_item.values, amount as usize);
+            if sold {
+                character.remove_item_with_values(item, &inventory_item.values, amount as usize);
+                character.add_orbs(profit as i64);
+            }
+        })
+        .await?;
 
-    if !character.has_item_by_name(&item_name, amount as usize) {
+    if !sold {
         ctx.send(
             Response::new_user_reply(
                 &author,
@@ -96,17 +116,17 @@ pub async fn sell(
         return Ok(());
     }
 
-    character.remove_item(item, amount as usize);
-    character.add_orbs(profit as i64);
-
-    ctx.db().characters().save(character).await?;
+    ctx.db()
+        .city_prices()
+        .record_trade(region, item.identifier, -(amount as i64))
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
             &author,
             format!(
                 "você vendeu **{}x {}** e ganhou **{profit} ◎**!",
-                amount, item.display_name
+                amount, item_name
             ),
         )
         .add_emoji_prefix(item.emoji),
@@ -49,6 +49,7 @@ pub async fn send_item(
     let Some(item) = get_item(&inventory_item.identifier) else {
         return Ok(());
     };
+    let item_name = inventory_item.values.name_with_upgrades(item.display_name);
 
     let confirmation = ctx
         .helper()
@@ -59,7 +60,7 @@ pub async fn send_item(
                 &author,
                 format!(
                     "você tem certeza que quer enviar **{}x {}** para o personagem **{}**?",
-                    amount, item.display_name, user_character.name
+                    amount, item_name, user_character.name
                 ),
             )
             .add_emoji_prefix(item.emoji),
@@ -69,10 +70,21 @@ pub async fn send_item(
         return Ok(());
     }
 
-    let mut author_character = parse_user_character!(ctx, author);
-    let mut user_character = parse_user_character!(ctx, user);
+    let author_character = parse_user_character!(ctx, author);
+    let user_character = parse_user_character!(ctx, user);
+
+    let mut sent = false;
+    ctx.db()
+        .characters()
+        .update(author_character.id, |character| {
+            sent = character.has_item_with_values(&item, &inventory_item.values, amount as usize);
+            if sent {
+                character.remove_item_with_values(item, &inventory_item.values, amount as usize);
+            }
+        })
+        .await?;
 
-    if !author_character.has_item_by_name(&item_name, amount as usize) {
+    if !sent {
         ctx.send(
             Response::new_user_reply(
                 &author,
@@ -84,23 +96,27 @@ pub async fn send_item(
         return Ok(());
     }
 
-    author_character.remove_item(item, amount as usize);
-    user_character.add_item(item, amount as usize, Some(inventory_item.values.clone()));
+    ctx.db()
+        .characters()
+        .add_item(
+            user_character.id,
+            item,
+            amount as usize,
+            Some(inventory_item.values.clone()),
+        )
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
             &author,
             format!(
                 "você enviou **{}x {}** para **{}**!",
-                amount, item.display_name, user_character.name
+                amount, item_name, user_character.name
             ),
         )
         .add_emoji_prefix(item.emoji),
     )
     .await?;
 
-    ctx.db().characters().save(author_character).await?;
-    ctx.db().characters().save(user_character).await?;
-
     Ok(())
 }
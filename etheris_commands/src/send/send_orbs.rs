@@ -48,10 +48,12 @@ pub async fn send_orbs(
         return Ok(());
     }
 
-    let mut author_character = parse_user_character!(ctx, author);
-    let mut user_character = parse_user_character!(ctx, user);
-
-    if author_character.orbs < orbs {
+    if !ctx
+        .db()
+        .characters()
+        .take_orbs(author_character.id, orbs)
+        .await?
+    {
         ctx.send(
             Response::new_user_reply(&author, "você não tem essa quantia de orbs!")
                 .add_emoji_prefix(emojis::ERROR),
@@ -60,8 +62,10 @@ pub async fn send_orbs(
         return Ok(());
     }
 
-    author_character.remove_orbs(orbs);
-    user_character.add_orbs(orbs);
+    ctx.db()
+        .characters()
+        .add_orbs(user_character.id, orbs)
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
@@ -72,8 +76,5 @@ pub async fn send_orbs(
     )
     .await?;
 
-    ctx.db().characters().save(author_character).await?;
-    ctx.db().characters().save(user_character).await?;
-
     Ok(())
 }
@@ -5,7 +5,12 @@ use crate::prelude::*;
 #[command("Em uma cidade, visite uma loja para comprar itens!")]
 #[name("loja")]
 #[character_required(true)]
-pub async fn shop(mut ctx: CommandContext) -> anyhow::Result<()> {
+pub async fn shop(
+    mut ctx: CommandContext,
+    #[rename("construção")]
+    #[description("O nome de uma loja de jogador na cidade")]
+    building_name: Option<String>,
+) -> anyhow::Result<()> {
     let author = ctx.author().await?;
     let character = parse_user_character!(ctx, author);
     let region = character.region;
@@ -22,8 +27,65 @@ pub async fn shop(mut ctx: CommandContext) -> anyhow::Result<()> {
         return Ok(());
     };
 
+    if let Some(building_name) = building_name {
+        let Some(building) = ctx
+            .db()
+            .buildings()
+            .get_by_region_and_name(region, &building_name)
+            .await?
+        else {
+            ctx.reply(
+                Response::new_user_reply(
+                    &author,
+                    format!("não existe nenhuma construção com esse nome em **{region}**!"),
+                )
+                .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        };
+
+        let (id, name) = (building.id, building.name.clone());
+        let Some(shop_data) = building.get_shop_data() else {
+            ctx.reply(
+                Response::new_user_reply(&author, format!("**{name}** não é uma loja!"))
+                    .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        };
+
+        // What is sold goes to the vault of the building, which the owner collects
+        let shop = Shop::new(name, None, shop_data.items).with_building(id);
+        shop.prompt(author, &mut ctx).await?;
+        return Ok(());
+    }
+
+    let buildings = ctx
+        .db()
+        .buildings()
+        .get_all_by_region_as_vec(region)
+        .await?;
+    if !buildings.is_empty() {
+        ctx.reply(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "construções de jogadores em **{region}**: {}. Visite uma loja com **/loja construção:<nome>**.",
+                    buildings
+                        .iter()
+                        .map(|b| format!("{} **{}**", b.kind.emoji(), b.name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )
+            .add_emoji_prefix("🏙️"),
+        )
+        .await?;
+    }
+
     let items: Vec<ShopItem> = city.shop_items;
-    let shop = Shop::new(format!("{} Shop", region), None, items);
+    let shop = Shop::new(format!("{} Shop", region), None, items).with_city(region);
 
     shop.prompt(author, &mut ctx).await?;
 
@@ -1,4 +1,4 @@
-use etheris_rpg::{list::get_boxed_skill_from_kind, Fighter, FighterData};
+use etheris_rpg::{list::get_boxed_skill_from_kind, skill_combos_with, Fighter, FighterData};
 
 use crate::prelude::*;
 
@@ -40,7 +40,40 @@ pub async fn skill_analyze(
         return Ok(());
     };
 
-    let embed = EmbedBuilder::new_common()
+    let mastery = character.get_skill_mastery(&skill.kind());
+    let mastery_progress = match mastery.xp_to_next_level() {
+        Some(xp) => format!("Faltam **{xp}** usos para o próximo nível."),
+        None => "Dominada completamente!".to_string(),
+    };
+
+    let (discovered_combos, undiscovered_combos): (Vec<_>, Vec<_>) =
+        skill_combos_with(&skill.kind()).partition(|combo| {
+            character
+                .discovered_combos
+                .iter()
+                .any(|c| c == combo.identifier)
+        });
+
+    let mut combos = discovered_combos
+        .into_iter()
+        .map(|combo| {
+            let sequence = combo
+                .sequence
+                .iter()
+                .map(|s| get_boxed_skill_from_kind(s.clone()).data(&fighter).name)
+                .collect::<Vec<_>>()
+                .join(" → ");
+            format!("**{}** ({sequence})\n*{}*", combo.name, combo.description)
+        })
+        .collect::<Vec<_>>();
+    if !undiscovered_combos.is_empty() {
+        combos.push(format!(
+            "**{}** combo(s) com esta habilidade ainda não descoberto(s).",
+            undiscovered_combos.len()
+        ));
+    }
+
+    let mut embed = EmbedBuilder::new_common()
         .set_author_to_user(&author)
         .set_description(format!(
             "## {}\n### {}\n{}",
@@ -49,12 +82,23 @@ pub async fn skill_analyze(
             skill.display(&fighter).body
         ))
         .add_inlined_field("💡 Explicação:", skill.data(&fighter).explanation)
+        .add_not_inlined_field(
+            "🎯 Maestria:",
+            format!(
+                "Nível **{}**/{MAX_SKILL_MASTERY_LEVEL}. {mastery_progress}",
+                mastery.level()
+            ),
+        )
         .add_footer_text(format!(
             "Complexidade: {} | ID: {}",
             skill.data(&fighter).complexity,
             skill.data(&fighter).identifier
         ));
 
+    if !combos.is_empty() {
+        embed = embed.add_not_inlined_field("🔗 Combos:", combos.join("\n"));
+    }
+
     ctx.send(Response::from(embed)).await?;
 
     Ok(())
@@ -13,7 +13,7 @@ pub async fn skill_equip(
     skill: String,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character: CharacterModel = parse_user_character!(ctx, author);
+    let character: CharacterModel = parse_user_character!(ctx, author);
     let fighter = Fighter::new(
         0,
         Default::default(),
@@ -51,8 +51,14 @@ pub async fn skill_equip(
         return Ok(());
     }
 
-    character.equip_skill(skill.kind());
-    ctx.db().characters().save(character).await?;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            if character.skills.len() < 7 {
+                character.equip_skill(skill.kind());
+            }
+        })
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
@@ -13,7 +13,7 @@ pub async fn skill_unequip(
     skill: String,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character: CharacterModel = parse_user_character!(ctx, author);
+    let character: CharacterModel = parse_user_character!(ctx, author);
     let fighter = Fighter::new(
         0,
         Default::default(),
@@ -38,8 +38,12 @@ pub async fn skill_unequip(
         return Ok(());
     };
 
-    character.unequip_skill(skill.kind());
-    ctx.db().characters().save(character).await?;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character.unequip_skill(skill.kind());
+        })
+        .await?;
 
     ctx.send(
         Response::new_user_reply(
@@ -10,7 +10,9 @@ pub async fn stats(
     user: Option<User>,
 ) -> anyhow::Result<()> {
     let user = user.unwrap_or(ctx.author().await?);
-    let character = parse_user_character!(ctx, user);
+    let mut character = parse_user_character!(ctx, user);
+    character.pvp_rating.update_season();
+    let rating = character.pvp_rating;
 
     let image = character.create_image_bufer();
     let attachment =
@@ -29,6 +31,18 @@ pub async fn stats(
         })
         .add_inlined_field("PvE", render_stats(character.stats.pve))
         .add_inlined_field("PvP", render_stats(character.stats.pvp))
+        .add_inlined_field(
+            "Rank PvP",
+            format!(
+                "{} **{}**\n**Pontos**: `{}`\n**Pico**: `{}`\n**Partidas**: `{}V/{}D`",
+                rating.tier().emoji(),
+                rating.tier(),
+                rating.rating,
+                rating.peak,
+                rating.wins,
+                rating.losses
+            ),
+        )
         .set_current_timestamp();
 
     ctx.reply(
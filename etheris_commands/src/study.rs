@@ -1,4 +1,4 @@
-use std::{mem::discriminant, time::Duration};
+use std::time::Duration;
 
 use etheris_discord::twilight_model::channel::message::component::ButtonStyle;
 use etheris_framework::{util::make_multiple_rows, watcher::WatcherOptions};
@@ -18,7 +18,7 @@ const KNOWLEDGE_XP_REQUIRED_TO_LEVELUP: u32 = 200;
 #[character_required(true)]
 pub async fn study(mut ctx: CommandContext) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     if character.action_points < 2 {
         ctx.reply(
             Response::new_user_reply(
@@ -31,8 +31,6 @@ pub async fn study(mut ctx: CommandContext) -> anyhow::Result<()> {
         return Ok(());
     }
 
-    let initial_intelligence_level = character.stats.intelligence_level;
-
     verify_user_cooldown!(ctx, author, "STUDY");
     ctx.db()
         .cooldowns()
@@ -49,109 +47,125 @@ pub async fn study(mut ctx: CommandContext) -> anyhow::Result<()> {
         _ => StdRng::from_entropy().gen_range(10..=30),
     };
 
-    character.intelligence_xp += xp;
-    character.knowledge_xp += xp + StdRng::from_entropy().gen_range(5..=10);
+    let knowledge_xp = xp + StdRng::from_entropy().gen_range(5..=10);
+
+    let mut studied = false;
+    let mut messages: Vec<String> = Vec::with_capacity(3);
+    let character = ctx.db().characters().update(character.id, |character| {
+        messages.clear();
+        studied = character.action_points >= 2;
+        if !studied {
+            return;
+        }
 
-    let levels_upgraded = character.intelligence_xp / XP_REQUIRED_TO_LEVELUP;
-    let new_xp = character.intelligence_xp % XP_REQUIRED_TO_LEVELUP;
+        let initial_intelligence_level = character.stats.intelligence_level;
 
-    let knowledge_levels_upgraded = character.knowledge_xp / KNOWLEDGE_XP_REQUIRED_TO_LEVELUP;
-    let knowledge_new_xp = character.knowledge_xp % KNOWLEDGE_XP_REQUIRED_TO_LEVELUP;
-    character.knowledge_points += knowledge_levels_upgraded;
-    character.knowledge_xp = knowledge_new_xp;
+        character.intelligence_xp += xp;
+        character.knowledge_xp += knowledge_xp;
 
-    let ether_upgraded = levels_upgraded * 2;
+        let levels_upgraded = character.intelligence_xp / XP_REQUIRED_TO_LEVELUP;
+        let new_xp = character.intelligence_xp % XP_REQUIRED_TO_LEVELUP;
 
-    character.stats.ether.max += ether_upgraded as i32;
-    character.stats.ether.value += ether_upgraded as i32;
+        let knowledge_levels_upgraded = character.knowledge_xp / KNOWLEDGE_XP_REQUIRED_TO_LEVELUP;
+        let knowledge_new_xp = character.knowledge_xp % KNOWLEDGE_XP_REQUIRED_TO_LEVELUP;
+        character.knowledge_points += knowledge_levels_upgraded;
+        character.knowledge_xp = knowledge_new_xp;
 
-    character.intelligence_xp = new_xp;
-    character.stats.intelligence_level += levels_upgraded;
+        let ether_upgraded = levels_upgraded * 2;
 
-    let refill_minutes_upgrade = if character.stats.intelligence_level < 20 {
-        levels_upgraded * 3
-    } else {
-        0
-    };
+        character.stats.ether.max += ether_upgraded as i32;
+        character.stats.ether.value += ether_upgraded as i32;
 
-    let mut messages: Vec<String> = Vec::with_capacity(3);
-    let first_message = match character.stats.intelligence_level.saturating_sub(levels_upgraded) {
-        0..=2 => format!("você leu um livro simples e recebeu **{xp} XP** em inteligência!"),
-        3..=6 => format!("você leu alguns livros simples e recebeu **{xp} XP** em inteligência!"),
-        7..=10 => format!("você se dedicou à leitura de um livro mais complexo e recebeu **{xp} XP** em inteligência!"),
-        11..=15 => format!("você explorou diversos temas em livros complexos e recebeu **{xp} XP** em inteligência!"),
-        16..=20 => format!("você mergulhou em um livro ancestral escrito em uma língua perdida e recebeu **{xp} XP** em inteligência!"),
-        21..=30 => format!("você desvendou os segredos de idiomas antigos e perdidos e recebeu **{xp} XP** em inteligência!"),
-        31..=40 => format!("você absorveu conhecimento como uma esponja, expandindo seus horizontes mentais e recebendo **{xp} XP** em inteligência!"),
-        41..=50 => format!("seus insights transcendem as barreiras do intelecto comum, e você ganhou **{xp} XP** em inteligência!"),
-        51..=60 => format!("você se tornou um verdadeiro mestre do conhecimento, desvendando os mistérios do universo e ganhando **{xp} XP** em inteligência!"),
-        61..=70 => format!("sua mente é uma fonte inesgotável de sabedoria, e você ganhou **{xp} XP** em inteligência!"),
-        71..=80 => format!("você alcançou uma compreensão profunda e transcendental, e ganhou **{xp} XP** em inteligência!"),
-        _ => format!("você leu um livro milenar codificado em hieróglifos de olhos fechados enquanto meditava e recebeu **{xp} XP** em inteligência!"),
-    };
+        character.intelligence_xp = new_xp;
+        character.stats.intelligence_level += levels_upgraded;
 
-    messages.push(first_message);
-
-    if levels_upgraded > 0 {
-        let levelup_message = match character.stats.intelligence_level {
-            0..=2 => "Você sentiu que sua velocidade de raciocínio melhorou. Sua inteligência aumentou!",
-            3..=6 => "Você começa a compreender melhor os mistérios do ether. Sua inteligência aumentou!",
-            7..=10 => "O que antes era complexo agora parece mais simples para você. Sua inteligência aumentou!",
-            11..=15 => "Sua capacidade mental agora está muito além do que você podia prever no passado. Sua inteligência aumentou!",
-            16..=20 => "Você mergulhou mais fundo nos segredos do universo, expandindo sua compreensão. Sua inteligência aumentou!",
-            21..=30 => "Problemas físicos e metafísicos envolvendo o ether e o universo agora são desafios triviais para você. Sua inteligência aumentou!",
-            31..=40 => "Você desvenda os enigmas do cosmos com facilidade, expandindo seus horizontes intelectuais. Sua inteligência aumentou!",
-            41..=50 => "Seus insights transcendem os limites do conhecimento convencional. Sua inteligência aumentou!",
-            51..=60 => "Você se tornou um verdadeiro sábio, desvendando os segredos mais profundos da existência. Sua inteligência aumentou!",
-            61..=70 => "Sua mente é uma fonte de sabedoria incomparável, iluminando os mistérios do universo. Sua inteligência aumentou!",
-            71..=80 => "Você alcançou uma compreensão cósmica, vendo além das fronteiras do conhecimento humano. Sua inteligência aumentou!",
-            _ => "A sua inteligência é tão avassaladora que não há problema que você não seja capaz de resolver. Sua inteligência aumentou!"
+        let refill_minutes_upgrade = if character.stats.intelligence_level < 20 {
+            levels_upgraded * 3
+        } else {
+            0
         };
 
-        messages.push(levelup_message.to_string());
-    }
+        let first_message = match character.stats.intelligence_level.saturating_sub(levels_upgraded) {
+            0..=2 => format!("você leu um livro simples e recebeu **{xp} XP** em inteligência!"),
+            3..=6 => format!("você leu alguns livros simples e recebeu **{xp} XP** em inteligência!"),
+            7..=10 => format!("você se dedicou à leitura de um livro mais complexo e recebeu **{xp} XP** em inteligência!"),
+            11..=15 => format!("você explorou diversos temas em livros complexos e recebeu **{xp} XP** em inteligência!"),
+            16..=20 => format!("você mergulhou em um livro ancestral escrito em uma língua perdida e recebeu **{xp} XP** em inteligência!"),
+            21..=30 => format!("você desvendou os segredos de idiomas antigos e perdidos e recebeu **{xp} XP** em inteligência!"),
+            31..=40 => format!("você absorveu conhecimento como uma esponja, expandindo seus horizontes mentais e recebendo **{xp} XP** em inteligência!"),
+            41..=50 => format!("seus insights transcendem as barreiras do intelecto comum, e você ganhou **{xp} XP** em inteligência!"),
+            51..=60 => format!("você se tornou um verdadeiro mestre do conhecimento, desvendando os mistérios do universo e ganhando **{xp} XP** em inteligência!"),
+            61..=70 => format!("sua mente é uma fonte inesgotável de sabedoria, e você ganhou **{xp} XP** em inteligência!"),
+            71..=80 => format!("você alcançou uma compreensão profunda e transcendental, e ganhou **{xp} XP** em inteligência!"),
+            _ => format!("você leu um livro milenar codificado em hieróglifos de olhos fechados enquanto meditava e recebeu **{xp} XP** em inteligência!"),
+        };
 
-    if ether_upgraded > 0 {
-        messages.push(format!(
-            "Seu ether máximo aumentou em **{ether_upgraded}**!"
-        ));
-    }
+        messages.push(first_message);
+
+        if levels_upgraded > 0 {
+            let levelup_message = match character.stats.intelligence_level {
+                0..=2 => "Você sentiu que sua velocidade de raciocínio melhorou. Sua inteligência aumentou!",
+                3..=6 => "Você começa a compreender melhor os mistérios do ether. Sua inteligência aumentou!",
+                7..=10 => "O que antes era complexo agora parece mais simples para você. Sua inteligência aumentou!",
+                11..=15 => "Sua capacidade mental agora está muito além do que você podia prever no passado. Sua inteligência aumentou!",
+                16..=20 => "Você mergulhou mais fundo nos segredos do universo, expandindo sua compreensão. Sua inteligência aumentou!",
+                21..=30 => "Problemas físicos e metafísicos envolvendo o ether e o universo agora são desafios triviais para você. Sua inteligência aumentou!",
+                31..=40 => "Você desvenda os enigmas do cosmos com facilidade, expandindo seus horizontes intelectuais. Sua inteligência aumentou!",
+                41..=50 => "Seus insights transcendem os limites do conhecimento convencional. Sua inteligência aumentou!",
+                51..=60 => "Você se tornou um verdadeiro sábio, desvendando os segredos mais profundos da existência. Sua inteligência aumentou!",
+                61..=70 => "Sua mente é uma fonte de sabedoria incomparável, iluminando os mistérios do universo. Sua inteligência aumentou!",
+                71..=80 => "Você alcançou uma compreensão cósmica, vendo além das fronteiras do conhecimento humano. Sua inteligência aumentou!",
+                _ => "A sua inteligência é tão avassaladora que não há problema que você não seja capaz de resolver. Sua inteligência aumentou!"
+            };
+
+            messages.push(levelup_message.to_string());
+        }
 
-    if refill_minutes_upgrade > 0 {
-        character.refill_minutes -= refill_minutes_upgrade;
-        messages.push(format!("Agora seus pontos de ação recarregam **{refill_minutes_upgrade} minutos** mais rápido!"));
-    }
+        if ether_upgraded > 0 {
+            messages.push(format!(
+                "Seu ether máximo aumentou em **{ether_upgraded}**!"
+            ));
+        }
 
-    if knowledge_levels_upgraded > 0 {
-        character.insert_flag(CharacterFlag::CanAknowledgeSkill);
-        messages.push(format!("Você recebeu **{knowledge_levels_upgraded} pontos de conhecimento**! Use **/aprender** para gastar aprendendo habilidades novas."));
-    }
+        if refill_minutes_upgrade > 0 {
+            character.refill_minutes -= refill_minutes_upgrade;
+            messages.push(format!("Agora seus pontos de ação recarregam **{refill_minutes_upgrade} minutos** mais rápido!"));
+        }
 
-    macro_rules! upgrade_intelligence_level {
-        ($x:expr) => {
-            if initial_intelligence_level < $x && character.stats.intelligence_level >= $x {
-                character.max_action_points += 1;
-                messages.push("Seus pontos de ação máximos aumentaram em 1!".to_string());
-            }
-        };
-    }
+        if knowledge_levels_upgraded > 0 {
+            character.insert_flag(CharacterFlag::CanAknowledgeSkill);
+            messages.push(format!("Você recebeu **{knowledge_levels_upgraded} pontos de conhecimento**! Use **/aprender** para gastar aprendendo habilidades novas."));
+        }
 
-    upgrade_intelligence_level!(2); // 11 AP
-    upgrade_intelligence_level!(3); // 12 AP
-    upgrade_intelligence_level!(5); // 13 AP
-    upgrade_intelligence_level!(10); // 14 AP
-    upgrade_intelligence_level!(20); // 15 AP
-    upgrade_intelligence_level!(30); // 16 AP
-    upgrade_intelligence_level!(40); // 17 AP
-    upgrade_intelligence_level!(50); // 18 AP
-    upgrade_intelligence_level!(60); // 19 AP
-    upgrade_intelligence_level!(80); // 20 AP
+        macro_rules! upgrade_intelligence_level {
+            ($x:expr) => {
+                if initial_intelligence_level < $x && character.stats.intelligence_level >= $x {
+                    character.max_action_points += 1;
+                    messages.push("Seus pontos de ação máximos aumentaram em 1!".to_string());
+                }
+            };
+        }
 
-    character.action_points -= 2;
+        upgrade_intelligence_level!(2); // 11 AP
+        upgrade_intelligence_level!(3); // 12 AP
+        upgrade_intelligence_level!(5); // 13 AP
+        upgrade_intelligence_level!(10); // 14 AP
+        upgrade_intelligence_level!(20); // 15 AP
+        upgrade_intelligence_level!(30); // 16 AP
+        upgrade_intelligence_level!(40); // 17 AP
+        upgrade_intelligence_level!(50); // 18 AP
+        upgrade_intelligence_level!(60); // 19 AP
+        upgrade_intelligence_level!(80); // 20 AP
+
+        character.action_points -= 2;
+    }).await?;
+
+    let Some(character) = character.filter(|_| studied) else {
+        return Ok(());
+    };
 
     ctx.send(Response::new_user_reply(&author, messages.join("\n")))
         .await?;
-    ctx.db().characters().save(character.clone()).await?;
 
     if character.has_flag(CharacterFlag::CanAknowledgeSkill) {
         aknowledge_skill(&author, &mut ctx).await?;
@@ -177,7 +191,7 @@ pub async fn aknowledge_skill(author: &User, ctx: &mut CommandContext) -> anyhow
             !character
                 .study_skills_cache
                 .iter()
-                .any(|ss| discriminant(ss) == discriminant(&s.kind()))
+                .any(|ss| *ss == s.kind())
                 && !character.already_knows_skill(s.kind())
                 && s.kind().intelligence_requirement() <= character.stats.intelligence_level
         })
@@ -230,10 +244,14 @@ pub async fn aknowledge_skill(author: &User, ctx: &mut CommandContext) -> anyhow
         return Ok(());
     }
 
-    let mut character = parse_user_character!(ctx, author);
     let character_name = character.name.clone();
-    character.study_skills_cache = skills.iter().map(|s| s.kind()).collect();
-    ctx.db().characters().save(character).await?;
+    let study_skills_cache = skills.iter().map(|s| s.kind()).collect::<Vec<_>>();
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character.study_skills_cache = study_skills_cache.clone();
+        })
+        .await?;
 
     let embed = EmbedBuilder::new_common()
         .set_color(Color::VERY_LIGHT_YELLOW)
@@ -308,8 +326,6 @@ pub async fn aknowledge_skill(author: &User, ctx: &mut CommandContext) -> anyhow
 
     ctx.update_message(Response::default().set_components(make_multiple_rows(buttons)))
         .await?;
-    let mut character = parse_user_character!(ctx, author);
-
     let Some(skill) = skills
         .iter()
         .find(|s| s.data(&fighter).identifier == data.custom_id)
@@ -317,10 +333,14 @@ pub async fn aknowledge_skill(author: &User, ctx: &mut CommandContext) -> anyhow
         return Ok(());
     };
 
-    character.study_skills_cache.clear();
-    character.aknowledge_skill(skill.kind());
-    character.remove_flag(CharacterFlag::CanAknowledgeSkill);
-    ctx.db().characters().save(character).await?;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character.study_skills_cache.clear();
+            character.aknowledge_skill(skill.kind());
+            character.remove_flag(CharacterFlag::CanAknowledgeSkill);
+        })
+        .await?;
 
     ctx.send(
         Response::new_user_reply(author, format!("você descobriu a habilidade **{}**! Use **/aprender** para obtê-la para o seu personagem.", skill.data(&fighter).name))
@@ -0,0 +1,109 @@
+use etheris_data::items::get_item;
+use etheris_database::tournament_model::{BracketSide, TournamentKind, TournamentModel};
+use etheris_framework::CommandContext;
+use etheris_util::bracket_image::{
+    generate_bracket_image, BracketColumn, BracketMatchBox, BracketSection,
+};
+
+use crate::CommandMap;
+
+pub use tournament_jobs::{pay_unpaid_prizes, settle_overdue_matches, start_due_tournaments};
+
+mod tournament_cancel;
+mod tournament_create;
+mod tournament_fight;
+mod tournament_jobs;
+mod tournament_join;
+mod tournament_leave;
+mod tournament_start;
+mod tournament_view;
+
+pub fn register_commands(map: &mut CommandMap) {
+    register_command!(map, tournament_create::Tournament_createCommand);
+    register_command!(map, tournament_join::Tournament_joinCommand);
+    register_command!(map, tournament_leave::Tournament_leaveCommand);
+    register_command!(map, tournament_start::Tournament_startCommand);
+    register_command!(map, tournament_cancel::Tournament_cancelCommand);
+    register_command!(map, tournament_fight::Tournament_fightCommand);
+    register_command!(map, tournament_view::Tournament_viewCommand);
+}
+
+/// The tournament of the server the command was used in that isn't finished yet.
+pub async fn get_guild_tournament(ctx: &CommandContext) -> anyhow::Result<Option<TournamentModel>> {
+    let Some(guild_id) = ctx.interaction.guild_id else {
+        return Ok(None);
+    };
+
+    ctx.db()
+        .tournaments()
+        .get_active_by_guild(&guild_id.to_string())
+        .await
+}
+
+pub fn format_prizes(tournament: &TournamentModel) -> String {
+    tournament
+        .prizes
+        .iter()
+        .enumerate()
+        .map(|(index, prize)| {
+            format!(
+                "**{}º lugar**: {} ◎{}",
+                index + 1,
+                prize.orbs,
+                prize
+                    .items
+                    .iter()
+                    .filter_map(|i| get_item(&i.identifier).map(|item| (item, i.quantity)))
+                    .map(|(item, quantity)| format!(" + {}x {}", quantity, item.display_name))
+                    .collect::<String>()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn create_bracket_image(tournament: &TournamentModel) -> Vec<u8> {
+    let sides: &[(BracketSide, &str)] = match tournament.kind {
+        TournamentKind::SingleElimination => &[(BracketSide::Winners, "Chave")],
+        TournamentKind::DoubleElimination => &[
+            (BracketSide::Winners, "Chave dos vencedores"),
+            (BracketSide::Losers, "Chave dos perdedores"),
+            (BracketSide::GrandFinal, "Grande final"),
+        ],
+    };
+
+    let sections = sides
+        .iter()
+        .map(|(side, title)| {
+            let matches = tournament
+                .matches
+                .iter()
+                .filter(|m| m.side == *side)
+                .collect::<Vec<_>>();
+            let rounds = matches.iter().map(|m| m.round).max().unwrap_or(0);
+
+            let columns = (1..=rounds)
+                .map(|round| BracketColumn {
+                    matches: matches
+                        .iter()
+                        .filter(|m| m.round == round)
+                        .map(|m| BracketMatchBox {
+                            players: m.players.map(|p| tournament.participant_name(p)),
+                            winner: m
+                                .winner
+                                .filter(|_| m.resolved)
+                                .and_then(|w| m.players.iter().position(|p| *p == Some(w))),
+                        })
+                        .collect(),
+                })
+                .collect();
+
+            BracketSection {
+                title: title.to_string(),
+                columns,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    generate_bracket_image(&sections)
+}
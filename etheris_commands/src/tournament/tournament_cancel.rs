@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+use super::get_guild_tournament;
+
+#[command("Cancele o seu torneio e receba os prêmios de volta!")]
+#[name("torneio cancelar")]
+#[character_required(true)]
+pub async fn tournament_cancel(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(tournament) = get_guild_tournament(&ctx)
+        .await?
+        .filter(|t| t.host_id == author.id.to_string())
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não é o organizador do torneio deste servidor!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            author.id,
+            false,
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "você tem certeza que quer cancelar o torneio **{}**? Os prêmios voltam para você.",
+                    tournament.name
+                ),
+            )
+            .add_emoji_prefix("🏆"),
+        )
+        .await?;
+    if !confirmation {
+        return Ok(());
+    }
+
+    // The refund is claimed in the same write that finishes the tournament, so it's never paid twice
+    let cancelled = ctx
+        .db()
+        .tournaments()
+        .update(tournament.id, |t| {
+            t.host_character_id.get_or_insert(character.id);
+            !t.cancel().is_empty()
+        })
+        .await?;
+    if cancelled.is_none() {
+        ctx.send(
+            Response::new_user_reply(&author, "esse torneio já acabou!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    // If it fails, the refund stays in the tournament and is given again later
+    if let Err(e) = ctx.db().tournaments().pay_prizes(tournament.id).await {
+        eprintln!("Failed to refund the tournament {}: {e:?}", tournament.id);
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!(
+                "você cancelou o torneio **{}** e os prêmios voltaram para você.",
+                tournament.name
+            ),
+        )
+        .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
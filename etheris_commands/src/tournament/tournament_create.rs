@@ -0,0 +1,181 @@
+use etheris_data::items::get_item;
+use etheris_database::{
+    common::InventoryItem,
+    tournament_model::{TournamentKind, TournamentModel},
+};
+
+use crate::prelude::*;
+
+use super::{format_prizes, get_guild_tournament};
+
+#[command("Organize um torneio no servidor! Os prêmios saem do seu bolso.")]
+#[name("torneio criar")]
+#[character_required(true)]
+pub async fn tournament_create(
+    mut ctx: CommandContext,
+    #[rename("nome")]
+    #[description("O nome do torneio")]
+    #[min_max_length(2, 32)]
+    name: String,
+    #[rename("prêmio")]
+    #[description("Quantos orbs serão divididos entre os melhores colocados")]
+    prize_orbs: i64,
+    #[rename("inscrições")]
+    #[description("Por quantos minutos as inscrições ficam abertas (padrão: 30)")]
+    signup_minutes: Option<i64>,
+    #[rename("dupla")]
+    #[description(
+        "Se o torneio é de eliminação dupla, onde é preciso perder duas vezes para sair"
+    )]
+    double_elimination: Option<bool>,
+    #[rename("item")]
+    #[description("Um item do seu inventário para dar ao campeão")]
+    item_name: Option<String>,
+    #[rename("quantidade")]
+    #[description("Quantidade do item dado ao campeão")]
+    item_amount: Option<i64>,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let kind = if double_elimination.unwrap_or(false) {
+        TournamentKind::DoubleElimination
+    } else {
+        TournamentKind::SingleElimination
+    };
+    let signup_minutes = signup_minutes.unwrap_or(30).clamp(5, 24 * 60);
+    let prize_orbs = prize_orbs.max(0);
+    let item_amount = item_amount.unwrap_or(1).max(1) as usize;
+
+    let (Some(guild_id), Some(channel)) =
+        (ctx.interaction.guild_id, ctx.interaction.channel.clone())
+    else {
+        ctx.send(
+            Response::new_user_reply(&author, "torneios só podem ser criados em servidores!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if get_guild_tournament(&ctx).await?.is_some() {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "já existe um torneio acontecendo neste servidor! Veja ele com **/torneio ver**.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if character.orbs < prize_orbs {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não tem orbs suficientes para pagar o prêmio do torneio!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut prize_items = vec![];
+    if let Some(item_name) = item_name {
+        let Some(inventory_item) = character.get_inventory_item_by_name(&item_name).cloned() else {
+            ctx.send(
+                Response::new_user_reply(
+                    &author,
+                    "esse item não existe, não está no seu inventário ou teve o nome escrito errado!",
+                )
+                .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        };
+
+        let Some(item) = get_item(&inventory_item.identifier) else {
+            return Ok(());
+        };
+
+        if !character.has_item_with_values(&item, &inventory_item.values, item_amount) {
+            ctx.send(
+                Response::new_user_reply(
+                    &author,
+                    "você não tem essa quantia desse item no **/inventário**!",
+                )
+                .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        }
+
+        prize_items.push(InventoryItem {
+            identifier: inventory_item.identifier,
+            quantity: item_amount,
+            values: inventory_item.values,
+        });
+    }
+
+    let mut paid = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            paid = character.orbs >= prize_orbs
+                && prize_items.iter().all(|prize| {
+                    get_item(&prize.identifier).is_some_and(|item| {
+                        character.has_item_with_values(&item, &prize.values, prize.quantity)
+                    })
+                });
+            if !paid {
+                return;
+            }
+
+            for prize in &prize_items {
+                if let Some(item) = get_item(&prize.identifier) {
+                    character.remove_item_with_values(item, &prize.values, prize.quantity);
+                }
+            }
+            character.remove_orbs(prize_orbs);
+        })
+        .await?;
+
+    if !paid {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não tem mais os orbs ou itens do prêmio do torneio!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let tournament = TournamentModel::new(
+        guild_id.to_string(),
+        channel.id.to_string(),
+        author.id.to_string(),
+        character.id,
+        name,
+        kind,
+        TournamentModel::create_prizes(kind, prize_orbs, prize_items),
+        chrono::Duration::try_minutes(signup_minutes).unwrap_or_default(),
+    );
+
+    let prizes = format_prizes(&tournament);
+
+    let message = format!(
+        "você organizou o torneio **{}** ({})! As inscrições ficam abertas por **{} minutos** com **/torneio entrar**.\n{}",
+        tournament.name, tournament.kind, signup_minutes, prizes
+    );
+
+    ctx.db().tournaments().create(tournament).await?;
+
+    ctx.send(Response::new_user_reply(&author, message).add_emoji_prefix("🏆"))
+        .await?;
+
+    Ok(())
+}
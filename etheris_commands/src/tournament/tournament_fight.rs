@@ -0,0 +1,229 @@
+use etheris_database::{
+    bson::oid::ObjectId,
+    tournament_model::{TournamentModel, TournamentPrize, TournamentState},
+};
+use etheris_rpg::{Battle, BattleController, BattleSettings, FighterData};
+
+use crate::prelude::*;
+
+use super::get_guild_tournament;
+
+#[command("Dispute sua próxima partida no torneio do servidor!")]
+#[name("torneio lutar")]
+#[character_required(true)]
+pub async fn tournament_fight(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(tournament) = get_guild_tournament(&ctx)
+        .await?
+        .filter(|t| t.state == TournamentState::Running)
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "não há nenhum torneio em andamento neste servidor!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let Some(tournament_match) = tournament.ready_match_of(character.id).cloned() else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não tem nenhuma partida esperando por você nesse torneio!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let Some(opponent) = tournament_match
+        .opponent_of(character.id)
+        .and_then(|id| tournament.get_participant(id))
+        .cloned()
+    else {
+        return Ok(());
+    };
+
+    let opponent = ctx
+        .client
+        .get_user(Id::new(opponent.user_id.parse::<u64>()?))
+        .await?;
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            opponent.id,
+            false,
+            Response::new_user_reply(
+                &opponent,
+                format!(
+                    "**{}** está pronto para a partida de vocês no torneio **{}**. Vamos lutar?",
+                    author.display_name(),
+                    tournament.name
+                ),
+            )
+            .add_emoji_prefix("🏆"),
+        )
+        .await?;
+
+    if !confirmation {
+        // Whoever showed up wins the match if it isn't fought before the deadline
+        ctx.db()
+            .tournaments()
+            .update(tournament.id, |t| {
+                t.mark_showed_up(tournament_match.id, character.id)
+            })
+            .await?;
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "**{}** não aceitou a partida. Se ela não for disputada até o prazo, você vence por W.O.!",
+                    opponent.display_name()
+                ),
+            )
+            .add_emoji_prefix("🏆"),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    for user in [&author, &opponent] {
+        if ctx.client.is_user_fighting(user.id).await {
+            ctx.send(
+                Response::new_user_reply(user, "você já está no meio de uma batalha!")
+                    .add_emoji_prefix(emojis::ERROR),
+            )
+            .await?;
+            return Ok(());
+        }
+    }
+
+    let author_character = parse_user_character!(ctx, author);
+    let opponent_character = parse_user_character!(ctx, opponent);
+
+    let battle = Battle::new(
+        author_character.region,
+        BattleSettings {
+            is_risking_life_allowed: true,
+            has_consequences: false,
+            casual: true,
+            max_intruders: 0,
+            seed: None,
+            ranked: false,
+        },
+        vec![
+            FighterData::new_from_character(
+                0,
+                &author_character,
+                author.clone(),
+                Default::default(),
+            ),
+            FighterData::new_from_character(
+                1,
+                &opponent_character,
+                opponent.clone(),
+                Default::default(),
+            ),
+        ],
+    )?;
+
+    let mut controller = BattleController::new(battle, ctx.clone());
+    let result = controller.run().await?;
+
+    let won_by = |user: &User| {
+        result
+            .winners
+            .iter()
+            .any(|f| f.user.as_ref().is_some_and(|u| u.id == user.id))
+    };
+    let (winner, loser) = match (won_by(&author), won_by(&opponent)) {
+        (true, false) => (&author_character, &opponent_character),
+        (false, true) => (&opponent_character, &author_character),
+        _ => {
+            ctx.send(
+                Response::new_user_reply(
+                    &author,
+                    "a partida terminou empatada! Ela continua valendo e precisa ser disputada de novo com **/torneio lutar**.",
+                )
+                .add_emoji_prefix("🏆"),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // The tournament may have changed while the battle was running, so the result is only
+    // recorded if the match is still open, and the prizes are claimed in the same write
+    let mut payouts = vec![];
+    let Some(tournament) = ctx
+        .db()
+        .tournaments()
+        .update(tournament.id, |t| {
+            if t.report_result(tournament_match.id, winner.id).is_err() {
+                return false;
+            }
+
+            payouts = t.claim_prizes();
+            true
+        })
+        .await?
+    else {
+        ctx.send(
+            Response::new_user_reply(&author, "essa partida já tinha sido decidida!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let mut message = format!(
+        "**{}** venceu **{}** e avançou no torneio **{}**!",
+        winner.name, loser.name, tournament.name
+    );
+    message.push_str(&pay_prizes(&ctx, &tournament, payouts).await?);
+
+    ctx.send(Response::new_user_reply(&author, message).add_emoji_prefix("🏆"))
+        .await?;
+
+    Ok(())
+}
+
+/// Gives the prizes claimed when the tournament finished and returns the podium to be announced.
+async fn pay_prizes(
+    ctx: &CommandContext,
+    tournament: &TournamentModel,
+    payouts: Vec<(ObjectId, TournamentPrize)>,
+) -> anyhow::Result<String> {
+    if payouts.is_empty() {
+        return Ok(String::new());
+    }
+
+    // If it fails, the prizes stay in the tournament and are given again later
+    if let Err(e) = ctx.db().tournaments().pay_prizes(tournament.id).await {
+        eprintln!(
+            "Failed to pay the prizes of the tournament {}: {e:?}",
+            tournament.id
+        );
+    }
+
+    let mut podium = String::from("\n\n**O torneio acabou!**");
+    for (index, (character_id, prize)) in payouts.into_iter().enumerate() {
+        podium.push_str(&format!(
+            "\n**{}º lugar**: {} (+{} ◎)",
+            index + 1,
+            tournament
+                .participant_name(Some(character_id))
+                .unwrap_or_default(),
+            prize.orbs
+        ));
+    }
+
+    Ok(podium)
+}
@@ -0,0 +1,158 @@
+use etheris_database::{bson::oid::ObjectId, tournament_model::TournamentState, EtherisDatabase};
+use etheris_framework::EtherisClient;
+
+use crate::util::send_channel_message;
+
+/// Starts the tournaments whose sign-up time is over, or cancels them if not enough people joined.
+pub async fn start_due_tournaments(
+    db: &EtherisDatabase,
+    client: &EtherisClient,
+) -> anyhow::Result<()> {
+    for tournament in db.tournaments().get_due_tournaments().await? {
+        if let Err(e) = start_due_tournament(db, client, tournament.id).await {
+            eprintln!("Failed to start the tournament {}: {e:?}", tournament.id);
+        }
+    }
+
+    Ok(())
+}
+
+async fn start_due_tournament(
+    db: &EtherisDatabase,
+    client: &EtherisClient,
+    tournament_id: ObjectId,
+) -> anyhow::Result<()> {
+    // The refund is claimed in the same write that cancels the tournament, so it's only refunded once
+    let mut cancelled = false;
+    let Some(tournament) = db
+        .tournaments()
+        .update(tournament_id, |t| {
+            if t.state != TournamentState::SigningUp {
+                return false;
+            }
+
+            cancelled = t.start(&mut rand::thread_rng()).is_err();
+            if cancelled {
+                t.cancel();
+            }
+            true
+        })
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let message = if cancelled {
+        // If it fails, the refund stays in the tournament and is given by `pay_unpaid_prizes`
+        if let Err(e) = db.tournaments().pay_prizes(tournament.id).await {
+            eprintln!("Failed to refund the tournament {}: {e:?}", tournament.id);
+        }
+
+        format!("❌ O torneio **{}** foi cancelado por falta de participantes. Os prêmios voltaram para o organizador.", tournament.name)
+    } else {
+        format!("🏆 As inscrições do torneio **{}** acabaram e as chaves foram sorteadas! Veja sua partida com **/torneio ver** e dispute ela com **/torneio lutar**.", tournament.name)
+    };
+
+    send_channel_message(client, &tournament.channel_id, message).await
+}
+
+/// Settles the tournament matches that weren't fought before their deadline as walkovers.
+pub async fn settle_overdue_matches(
+    db: &EtherisDatabase,
+    client: &EtherisClient,
+) -> anyhow::Result<()> {
+    for tournament in db.tournaments().get_running_tournaments().await? {
+        if tournament.overdue_matches().is_empty() {
+            continue;
+        }
+
+        if let Err(e) = settle_tournament_overdue_matches(db, client, tournament.id).await {
+            eprintln!(
+                "Failed to settle the overdue matches of the tournament {}: {e:?}",
+                tournament.id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn settle_tournament_overdue_matches(
+    db: &EtherisDatabase,
+    client: &EtherisClient,
+    tournament_id: ObjectId,
+) -> anyhow::Result<()> {
+    let mut walkovers = vec![];
+    let mut payouts = vec![];
+    let Some(tournament) = db
+        .tournaments()
+        .update(tournament_id, |t| {
+            walkovers.clear();
+            for match_id in t.overdue_matches() {
+                if let Ok(winner) = t.report_walkover(match_id, &mut rand::thread_rng()) {
+                    walkovers.push(winner);
+                }
+            }
+
+            payouts = t.claim_prizes();
+            !walkovers.is_empty()
+        })
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let mut message = walkovers
+        .iter()
+        .map(|winner| {
+            format!(
+                "⏰ **{}** venceu por W.O. uma partida do torneio **{}** que não foi disputada a tempo.",
+                tournament.participant_name(Some(*winner)).unwrap_or_default(),
+                tournament.name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if !payouts.is_empty() {
+        // If it fails, the prizes stay in the tournament and are given by `pay_unpaid_prizes`
+        if let Err(e) = db.tournaments().pay_prizes(tournament.id).await {
+            eprintln!(
+                "Failed to pay the prizes of the tournament {}: {e:?}",
+                tournament.id
+            );
+        }
+
+        message.push_str("\n\n**O torneio acabou!**");
+    }
+    for (index, (character_id, prize)) in payouts.into_iter().enumerate() {
+        message.push_str(&format!(
+            "\n**{}º lugar**: {} (+{} ◎)",
+            index + 1,
+            tournament
+                .participant_name(Some(character_id))
+                .unwrap_or_default(),
+            prize.orbs
+        ));
+    }
+
+    send_channel_message(client, &tournament.channel_id, message).await
+}
+
+/// Gives the tournament prizes and refunds that failed to be given when they were claimed.
+pub async fn pay_unpaid_prizes(db: &EtherisDatabase) -> anyhow::Result<()> {
+    for tournament in db
+        .tournaments()
+        .get_tournaments_with_unpaid_prizes()
+        .await?
+    {
+        if let Err(e) = db.tournaments().pay_prizes(tournament.id).await {
+            eprintln!(
+                "Failed to pay the prizes of the tournament {}: {e:?}",
+                tournament.id
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use etheris_database::tournament_model::{TournamentParticipant, TournamentState};
+
+use crate::prelude::*;
+
+use super::get_guild_tournament;
+
+#[command("Inscreva-se no torneio do servidor!")]
+#[name("torneio entrar")]
+#[character_required(true)]
+pub async fn tournament_join(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(tournament) = get_guild_tournament(&ctx)
+        .await?
+        .filter(|t| t.state == TournamentState::SigningUp && !t.is_signup_over())
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "não há nenhum torneio com inscrições abertas neste servidor!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if tournament.get_participant(character.id).is_some() {
+        ctx.send(
+            Response::new_user_reply(&author, "você já está inscrito nesse torneio!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if tournament.is_full() {
+        ctx.send(
+            Response::new_user_reply(&author, "esse torneio já está cheio!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let participant = TournamentParticipant {
+        character_id: character.id,
+        user_id: author.id.to_string(),
+        name: character.name.clone(),
+    };
+    let joined = ctx
+        .db()
+        .tournaments()
+        .update(tournament.id, |t| {
+            let open = t.state == TournamentState::SigningUp && !t.is_signup_over();
+            if !open || t.is_full() || t.get_participant(character.id).is_some() {
+                return false;
+            }
+
+            t.add_participant(participant.clone());
+            true
+        })
+        .await?;
+
+    let Some(tournament) = joined else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "não foi possível se inscrever: o torneio já começou ou está cheio!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let message = format!(
+        "você se inscreveu no torneio **{}**! Inscritos: `{}`",
+        tournament.name,
+        tournament.participants.len()
+    );
+
+    ctx.send(Response::new_user_reply(&author, message).add_emoji_prefix(emojis::SUCCESS))
+        .await?;
+
+    Ok(())
+}
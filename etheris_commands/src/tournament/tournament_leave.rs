@@ -0,0 +1,71 @@
+use etheris_database::tournament_model::TournamentState;
+
+use crate::prelude::*;
+
+use super::get_guild_tournament;
+
+#[command("Cancele sua inscrição no torneio do servidor!")]
+#[name("torneio sair")]
+#[character_required(true)]
+pub async fn tournament_leave(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+    let character = parse_user_character!(ctx, author);
+
+    let Some(tournament) = get_guild_tournament(&ctx)
+        .await?
+        .filter(|t| t.get_participant(character.id).is_some())
+    else {
+        ctx.send(
+            Response::new_user_reply(&author, "você não está inscrito em nenhum torneio!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if tournament.state != TournamentState::SigningUp {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "o torneio já começou! Agora você só sai dele perdendo.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let left = ctx
+        .db()
+        .tournaments()
+        .update(tournament.id, |t| {
+            if t.state != TournamentState::SigningUp {
+                return false;
+            }
+
+            t.remove_participant(character.id);
+            true
+        })
+        .await?;
+    if left.is_none() {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "o torneio já começou! Agora você só sai dele perdendo.",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let message = format!(
+        "você cancelou sua inscrição no torneio **{}**.",
+        tournament.name
+    );
+
+    ctx.send(Response::new_user_reply(&author, message).add_emoji_prefix(emojis::SUCCESS))
+        .await?;
+
+    Ok(())
+}
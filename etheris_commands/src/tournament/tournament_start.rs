@@ -0,0 +1,78 @@
+use etheris_database::tournament_model::{TournamentModel, TournamentState};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::prelude::*;
+
+use super::get_guild_tournament;
+
+#[command("Encerre as inscrições do seu torneio e sorteie as chaves!")]
+#[name("torneio iniciar")]
+#[character_required(true)]
+pub async fn tournament_start(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+
+    let Some(tournament) = get_guild_tournament(&ctx)
+        .await?
+        .filter(|t| t.host_id == author.id.to_string())
+    else {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                "você não é o organizador do torneio deste servidor!",
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if tournament.state != TournamentState::SigningUp {
+        ctx.send(
+            Response::new_user_reply(&author, "esse torneio já começou!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if tournament.participants.len() < TournamentModel::MIN_PARTICIPANTS {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "o torneio precisa de pelo menos `{}` inscritos para começar!",
+                    TournamentModel::MIN_PARTICIPANTS
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let started = ctx
+        .db()
+        .tournaments()
+        .update(tournament.id, |t| {
+            t.start(&mut StdRng::from_entropy()).is_ok()
+        })
+        .await?;
+    if started.is_none() {
+        ctx.send(
+            Response::new_user_reply(&author, "esse torneio já começou!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let message = format!(
+        "o torneio **{}** começou e as chaves foram sorteadas! Veja sua partida com **/torneio ver** e dispute ela com **/torneio lutar**.",
+        tournament.name
+    );
+
+    ctx.send(Response::new_user_reply(&author, message).add_emoji_prefix("🏆"))
+        .await?;
+
+    Ok(())
+}
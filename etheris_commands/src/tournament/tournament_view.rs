@@ -0,0 +1,104 @@
+use etheris_database::tournament_model::{TournamentModel, TournamentState};
+
+use crate::prelude::*;
+
+use super::{create_bracket_image, format_prizes, get_guild_tournament};
+
+#[command("Veja o torneio do servidor e a chave das partidas!")]
+#[name("torneio ver")]
+#[character_required(true)]
+pub async fn tournament_view(mut ctx: CommandContext) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+
+    let Some(tournament) = get_guild_tournament(&ctx).await? else {
+        ctx.send(
+            Response::new_user_reply(&author, "não há nenhum torneio acontecendo neste servidor!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let mut embed = EmbedBuilder::new_common()
+        .set_color(Color::YELLOW)
+        .set_title(format!("🏆 {}", tournament.name))
+        .add_inlined_field("Formato", tournament.kind.to_string())
+        .add_inlined_field("Inscritos", tournament.participants.len().to_string())
+        .add_not_inlined_field("Prêmios", format_prizes(&tournament));
+
+    let mut attachments = vec![];
+    match tournament.state {
+        TournamentState::SigningUp => {
+            let names = tournament
+                .participants
+                .iter()
+                .map(|p| format!("`{}`", p.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            embed = embed
+                .add_not_inlined_field(
+                    "Inscrições",
+                    format!(
+                        "Acabam em `{}`. Participe com **/torneio entrar**!",
+                        crate::util::format_duration(
+                            tournament.signup_ends_at.0 - chrono::Utc::now()
+                        )
+                    ),
+                )
+                .add_not_inlined_field(
+                    "Participantes",
+                    if names.is_empty() {
+                        "Ninguém ainda.".to_string()
+                    } else {
+                        names
+                    },
+                );
+        }
+        TournamentState::Running | TournamentState::Finished => {
+            let ready_matches = tournament
+                .ready_matches()
+                .into_iter()
+                .map(|m| {
+                    let deadline = TournamentModel::match_deadline(m)
+                        .map(|deadline| {
+                            format!(
+                                " (W.O. em `{}`)",
+                                crate::util::format_duration(deadline.0 - chrono::Utc::now())
+                            )
+                        })
+                        .unwrap_or_default();
+                    format!(
+                        "**{}** vs **{}**{deadline}",
+                        tournament
+                            .participant_name(m.players[0])
+                            .unwrap_or_default(),
+                        tournament
+                            .participant_name(m.players[1])
+                            .unwrap_or_default()
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if !ready_matches.is_empty() {
+                embed = embed.add_not_inlined_field(
+                    "Partidas aguardando (/torneio lutar)",
+                    ready_matches.join("\n"),
+                );
+            }
+
+            let attachment = DiscordAttachment::from_bytes(
+                "bracket.png".to_owned(),
+                create_bracket_image(&tournament),
+                1,
+            );
+            embed = embed.set_image(format!("attachment://{}", attachment.filename));
+            attachments.push(attachment);
+        }
+    }
+
+    ctx.send(Response::from(embed).set_attachments(attachments))
+        .await?;
+
+    Ok(())
+}
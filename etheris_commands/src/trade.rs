@@ -0,0 +1,421 @@
+use std::time::Duration;
+
+use etheris_database::trade::TradeOffer;
+use etheris_discord::twilight_model::channel::message::{component::ButtonStyle, ReactionType};
+use etheris_framework::watcher::WatcherOptions;
+use tokio_stream::StreamExt;
+
+use crate::prelude::*;
+
+#[command("Troque itens e orbs com outro jogador!")]
+#[name("trocar")]
+#[character_required(true)]
+pub async fn trade(
+    mut ctx: CommandContext,
+    #[rename("usuário")]
+    #[description("O usuário com quem você quer trocar")]
+    user: User,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+
+    if user.id == author.id {
+        ctx.send(
+            Response::new_user_reply(&author, "você não pode trocar consigo mesmo!")
+                .add_emoji_prefix(emojis::ERROR)
+                .set_ephemeral(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(channel_id) = ctx.interaction.channel.as_ref().map(|c| c.id) else {
+        return Ok(());
+    };
+
+    let author_character = parse_user_character!(ctx, author);
+    let user_character = parse_user_character!(ctx, user);
+
+    if author_character.region != user_character.region {
+        ctx.reply(
+            Response::new_user_reply(&author, "você precisa estar na mesma região para trocar!")
+                .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            user.id,
+            false,
+            Response::new_user_reply(
+                &user,
+                format!(
+                    "**{}** quer fazer uma troca com você. Você aceita?",
+                    author_character.name
+                ),
+            )
+            .add_emoji_prefix("🤝"),
+        )
+        .await?;
+    if !confirmation {
+        return Ok(());
+    }
+
+    // The characters as they are when the trade starts. If they change before the trade ends, it is aborted
+    let characters = [
+        parse_user_character!(ctx, author),
+        parse_user_character!(ctx, user),
+    ];
+    let users = [author.clone(), user.clone()];
+    let mut offers = [TradeOffer::default(), TradeOffer::default()];
+    let mut confirmed = [false, false];
+
+    let message = ctx
+        .send(
+            Response::from(create_trade_embed(
+                &author,
+                &characters,
+                &offers,
+                &confirmed,
+                None,
+            ))
+            .set_components(create_trade_rows()),
+        )
+        .await?;
+
+    let (author_id, user_id) = (author.id, user.id);
+    let stream = ctx.watcher.create_component_stream(
+        message.id,
+        move |interaction| {
+            interaction.author_id() == Some(author_id) || interaction.author_id() == Some(user_id)
+        },
+        WatcherOptions {
+            timeout: Duration::from_secs(300),
+        },
+    );
+    tokio::pin!(stream);
+
+    while let Some(Ok(interaction)) = stream.next().await {
+        let side = if interaction.author_id() == Some(author_id) {
+            0
+        } else {
+            1
+        };
+
+        let data = interaction.parse_message_component_data()?;
+        let mut ctx = CommandContext::from_with_interaction(&ctx, interaction.into());
+
+        match data.custom_id.as_str() {
+            "cancel" => {
+                ctx.update_message(
+                    Response::from(create_trade_embed(
+                        &author,
+                        &characters,
+                        &offers,
+                        &confirmed,
+                        Some(format!(
+                            "❌ **{}** cancelou a troca.",
+                            characters[side].name
+                        )),
+                    ))
+                    .remove_all_components(),
+                )
+                .await?;
+                return Ok(());
+            }
+            "confirm" => {
+                if offers.iter().all(|o| o.is_empty()) {
+                    ctx.reply(
+                        Response::new_user_reply(
+                            &users[side],
+                            "adicione algum item ou orbs antes de confirmar a troca!",
+                        )
+                        .add_emoji_prefix(emojis::ERROR)
+                        .set_ephemeral(),
+                    )
+                    .await?;
+                    continue;
+                }
+
+                confirmed[side] = true;
+                if confirmed.iter().all(|c| *c) {
+                    ctx.update_message(
+                        Response::from(create_trade_embed(
+                            &author,
+                            &characters,
+                            &offers,
+                            &confirmed,
+                            None,
+                        ))
+                        .remove_all_components(),
+                    )
+                    .await?;
+                    break;
+                }
+
+                ctx.update_message(
+                    Response::from(create_trade_embed(
+                        &author,
+                        &characters,
+                        &offers,
+                        &confirmed,
+                        None,
+                    ))
+                    .set_components(create_trade_rows()),
+                )
+                .await?;
+                continue;
+            }
+            _ => {}
+        }
+
+        let asking_orbs = data.custom_id == "orbs";
+        let prompt = if asking_orbs {
+            format!(
+                "✍️ **{}**, escreva no chat quantos orbs você quer oferecer.",
+                characters[side].name
+            )
+        } else {
+            format!(
+                "✍️ **{}**, escreva no chat o item que você quer oferecer, com a quantia antes do nome (ex: `3 Graveto`).",
+                characters[side].name
+            )
+        };
+
+        ctx.update_message(
+            Response::from(create_trade_embed(
+                &author,
+                &characters,
+                &offers,
+                &confirmed,
+                Some(prompt),
+            ))
+            .remove_all_components(),
+        )
+        .await?;
+
+        let side_user_id = users[side].id;
+        let answer = ctx
+            .watcher
+            .await_single_message(
+                channel_id,
+                move |m| m.author.id == side_user_id,
+                WatcherOptions {
+                    timeout: Duration::from_secs(60),
+                },
+            )
+            .await;
+
+        let mut status = None;
+        if let Ok(Some(answer)) = answer {
+            let result = if asking_orbs {
+                set_offered_orbs(&characters[side], &mut offers[side], &answer.content)
+            } else {
+                add_offered_item(&characters[side], &mut offers[side], &answer.content)
+            };
+
+            match result {
+                // Any change to the offers must be confirmed again by both
+                Ok(()) => confirmed = [false, false],
+                Err(error) => status = Some(format!("{} {error}", emojis::ERROR)),
+            }
+        }
+
+        ctx.update_specific_message(
+            &message,
+            Response::from(create_trade_embed(
+                &author,
+                &characters,
+                &offers,
+                &confirmed,
+                status,
+            ))
+            .set_components(create_trade_rows()),
+        )
+        .await?;
+    }
+
+    if !confirmed.iter().all(|c| *c) {
+        ctx.update_specific_message(
+            &message,
+            Response::from(create_trade_embed(
+                &author,
+                &characters,
+                &offers,
+                &confirmed,
+                Some("⌛ A troca expirou.".to_string()),
+            ))
+            .remove_all_components(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let [author_character, user_character] = &characters;
+    let [author_offer, user_offer] = &offers;
+    let traded = ctx
+        .db()
+        .characters()
+        .commit_trade(author_character, author_offer, user_character, user_offer)
+        .await?;
+
+    if !traded {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "a troca com **{}** foi cancelada porque o inventário ou os orbs de alguém mudaram durante a troca!",
+                    user_character.name
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            &author,
+            format!("a troca com **{}** foi concluída!", user_character.name),
+        )
+        .add_emoji_prefix("🤝"),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn create_trade_embed(
+    author: &User,
+    characters: &[CharacterModel; 2],
+    offers: &[TradeOffer; 2],
+    confirmed: &[bool; 2],
+    status: Option<String>,
+) -> EmbedBuilder {
+    let mut embed = EmbedBuilder::new_common()
+        .set_color(Color::LIGHT_CYAN)
+        .set_author(EmbedAuthor {
+            name: format!(
+                "Troca entre {} e {}",
+                characters[0].name, characters[1].name
+            ),
+            icon_url: Some(author.avatar_url()),
+        })
+        .set_description(status.unwrap_or_else(|| {
+            "Adicione itens e orbs à troca. Ela só acontece quando os dois confirmarem!".to_string()
+        }));
+
+    for ((character, offer), confirmed) in characters.iter().zip(offers).zip(confirmed) {
+        let mut lines = offer
+            .items
+            .iter()
+            .filter_map(|offered| {
+                items::get_item(&offered.identifier).map(|item| {
+                    format!(
+                        "{} {}x {}",
+                        item.emoji,
+                        offered.quantity,
+                        offered.values.name_with_upgrades(item.display_name)
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if offer.orbs > 0 {
+            lines.push(format!("{} {} ◎", emojis::ORB, offer.orbs));
+        }
+
+        embed = embed.add_inlined_field(
+            format!(
+                "{} {}",
+                if *confirmed { "✅" } else { "⏳" },
+                character.name
+            ),
+            if lines.is_empty() {
+                "*Nada*".to_string()
+            } else {
+                lines.join("\n")
+            },
+        );
+    }
+
+    embed
+}
+
+fn create_trade_rows() -> Vec<ActionRowBuilder> {
+    vec![ActionRowBuilder::new().add_buttons(vec![
+        ButtonBuilder::new()
+            .set_custom_id("item")
+            .set_label("Item")
+            .set_emoji(ReactionType::Unicode {
+                name: "📦".into()
+            })
+            .set_style(ButtonStyle::Primary),
+        ButtonBuilder::new()
+            .set_custom_id("orbs")
+            .set_label("Orbs")
+            .set_emoji(ReactionType::Unicode {
+                name: "💰".into()
+            })
+            .set_style(ButtonStyle::Primary),
+        ButtonBuilder::new()
+            .set_custom_id("confirm")
+            .set_label("Confirmar")
+            .set_emoji(ReactionType::Unicode {
+                name: "✔️".into()
+            })
+            .set_style(ButtonStyle::Success),
+        ButtonBuilder::new()
+            .set_custom_id("cancel")
+            .set_label("Cancelar")
+            .set_emoji(ReactionType::Unicode {
+                name: "✖️".into()
+            })
+            .set_style(ButtonStyle::Danger),
+    ])]
+}
+
+fn set_offered_orbs(
+    character: &CharacterModel,
+    offer: &mut TradeOffer,
+    content: &str,
+) -> Result<(), &'static str> {
+    let Ok(orbs) = content.trim().parse::<i64>() else {
+        return Err("isso não é uma quantia de orbs válida!");
+    };
+
+    if orbs < 0 || orbs > character.orbs {
+        return Err("você não tem essa quantia de orbs!");
+    }
+
+    offer.orbs = orbs;
+    Ok(())
+}
+
+fn add_offered_item(
+    character: &CharacterModel,
+    offer: &mut TradeOffer,
+    content: &str,
+) -> Result<(), &'static str> {
+    let content = content.trim();
+    let (quantity, item_name) = match content.split_once(' ') {
+        Some((quantity, name)) if quantity.parse::<usize>().is_ok() => {
+            (quantity.parse::<usize>().unwrap_or(1), name)
+        }
+        _ => (1, content),
+    };
+    let quantity = quantity.max(1);
+
+    let Some(inventory_item) = character.get_inventory_item_by_name(item_name) else {
+        return Err("esse item não existe ou não está no seu inventário!");
+    };
+
+    if inventory_item.quantity < offer.offered_quantity(inventory_item) + quantity {
+        return Err("você não tem essa quantia desse item no inventário!");
+    }
+
+    offer.add_item(inventory_item, quantity);
+    Ok(())
+}
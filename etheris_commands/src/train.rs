@@ -60,13 +60,7 @@ pub async fn train(mut ctx: CommandContext) -> anyhow::Result<()> {
     interaction_ctx.update_message(Response::default().set_components(vec![row])).await?;
 
     // The actual training
-    let mut character = parse_user_character!(ctx, author);
-    if character.action_points == 0 {
-        return Ok(());
-    }
-
-    character.action_points -= 1;
-
+    let character = parse_user_character!(ctx, author);
     match data.custom_id.as_str() {
         "strength" => {
             strength_training(ctx, character).await?;
@@ -92,17 +86,34 @@ const XP_REQUIRED_TO_LEVELUP: u32 = 100;
 
 pub async fn strength_training(
     mut ctx: CommandContext,
-    mut character: CharacterModel,
+    character: CharacterModel,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
     let xp = random_xp_amount(character.stats.strength_level);
-    character.strength_xp += xp;
 
-    let levels_upgraded = character.strength_xp / XP_REQUIRED_TO_LEVELUP;
-    let new_xp = character.strength_xp % XP_REQUIRED_TO_LEVELUP;
+    let mut levels_upgraded = None;
+    let character = ctx
+        .db()
+        .characters()
+        .update(character.id, |character| {
+            levels_upgraded = None;
+            if character.action_points == 0 {
+                return;
+            }
+
+            character.action_points -= 1;
+            character.strength_xp += xp;
+
+            let levels = character.strength_xp / XP_REQUIRED_TO_LEVELUP;
+            character.strength_xp %= XP_REQUIRED_TO_LEVELUP;
+            character.stats.strength_level += levels;
+            levels_upgraded = Some(levels);
+        })
+        .await?;
 
-    character.strength_xp = new_xp;
-    character.stats.strength_level += levels_upgraded;
+    let (Some(character), Some(levels_upgraded)) = (character, levels_upgraded) else {
+        return Ok(());
+    };
 
     let mut messages: Vec<String> = Vec::with_capacity(3);
     let first_message = match character.stats.strength_level - levels_upgraded {
@@ -141,8 +152,6 @@ pub async fn strength_training(
         messages.push(levelup_message.to_string());
     }
 
-    ctx.db().characters().save(character).await?;
-
     ctx.send(Response::new_user_reply(&author, messages.join("\n")))
         .await?;
 
@@ -151,22 +160,39 @@ pub async fn strength_training(
 
 pub async fn health_training(
     mut ctx: CommandContext,
-    mut character: CharacterModel,
+    character: CharacterModel,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
     let xp = random_xp_amount(character.stats.health_level);
-    character.health_xp += xp;
 
-    let levels_upgraded = character.health_xp / XP_REQUIRED_TO_LEVELUP;
-    let new_xp = character.health_xp % XP_REQUIRED_TO_LEVELUP;
+    let mut levels_upgraded = None;
+    let character = ctx
+        .db()
+        .characters()
+        .update(character.id, |character| {
+            levels_upgraded = None;
+            if character.action_points == 0 {
+                return;
+            }
+
+            character.action_points -= 1;
+            character.health_xp += xp;
 
-    character.health_xp = new_xp;
-    character.stats.health_level += levels_upgraded;
-    character.stats.resistance.max += (levels_upgraded * 12) as i32;
-    character.stats.resistance.value += (levels_upgraded * 12) as i32;
+            let levels = character.health_xp / XP_REQUIRED_TO_LEVELUP;
+            character.health_xp %= XP_REQUIRED_TO_LEVELUP;
+            character.stats.health_level += levels;
+            character.stats.resistance.max += (levels * 12) as i32;
+            character.stats.resistance.value += (levels * 12) as i32;
 
-    character.stats.vitality.max += (levels_upgraded * 8) as i32;
-    character.stats.vitality.value += (levels_upgraded * 8) as i32;
+            character.stats.vitality.max += (levels * 8) as i32;
+            character.stats.vitality.value += (levels * 8) as i32;
+            levels_upgraded = Some(levels);
+        })
+        .await?;
+
+    let (Some(character), Some(levels_upgraded)) = (character, levels_upgraded) else {
+        return Ok(());
+    };
 
     let mut messages: Vec<String> = Vec::with_capacity(3);
     let first_message = match character.stats.health_level - levels_upgraded {
@@ -222,8 +248,6 @@ pub async fn health_training(
         messages.push(levelup_message.to_string());
     }
 
-    ctx.db().characters().save(character).await?;
-
     ctx.send(Response::new_user_reply(&author, messages.join("\n")))
         .await?;
 
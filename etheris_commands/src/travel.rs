@@ -99,7 +99,7 @@ pub async fn travel(mut ctx: CommandContext) -> anyhow::Result<()> {
 
     let data = collected.parse_message_component_data()?;
 
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
 
     let buttons = buttons
@@ -163,13 +163,36 @@ pub async fn travel(mut ctx: CommandContext) -> anyhow::Result<()> {
         return Ok(());
     }
 
-    character.action_points -= 5;
-    character.remove_orbs(price);
+    let mut traveled = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |current| {
+            traveled = current.region == character.region
+                && current.action_points >= 5
+                && current.orbs >= price;
+            if !traveled {
+                return;
+            }
 
-    character.visited_regions.insert(*region);
-    character.travel_to(*region);
+            current.action_points -= 5;
+            current.remove_orbs(price);
 
-    ctx.db().characters().save(character).await?;
+            current.visited_regions.insert(*region);
+            current.travel_to(*region);
+        })
+        .await?;
+
+    if !traveled {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!("você não pode mais viajar até **{}**!", region),
+            )
+            .add_emoji_prefix(emojis::ERROR),
+        )
+        .await?;
+        return Ok(());
+    }
 
     ctx.send(
         Response::new_user_reply(&author, format!("você viajou até **{}**!", region))
@@ -1,42 +1,59 @@
 use etheris_data::{
     appearance::Cosmetic,
+    equipment::EquipmentSlot,
     items::{get_item_by_weapon, Item, ALL_ITEMS},
 };
 
 use crate::prelude::*;
 
-#[command("Desequipe todos os cosméticos do seu personagem!")]
+#[command("Desequipe todos os cosméticos, armas e equipamentos do seu personagem!")]
 #[name("desequipar")]
 #[character_required(true)]
 pub async fn unequip(mut ctx: CommandContext) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
 
-    let face_item = get_cosmetic_item(&character.appearance.face_cosmetic);
-    let head_item = get_cosmetic_item(&character.appearance.head_cosmetic);
-    let weapon_item = character.weapon.map(get_item_by_weapon);
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            let face_item = get_cosmetic_item(&character.appearance.face_cosmetic);
+            let head_item = get_cosmetic_item(&character.appearance.head_cosmetic);
+            let weapon_item = character.weapon.map(get_item_by_weapon);
 
-    if let Some(item) = face_item {
-        character.add_item(item, 1, Some(item.default_values.into()));
-    }
+            if let Some(item) = face_item {
+                character.add_item(item, 1, Some(item.default_values.into()));
+            }
 
-    if let Some(item) = head_item {
-        character.add_item(item, 1, Some(item.default_values.into()));
-    }
+            if let Some(item) = head_item {
+                character.add_item(item, 1, Some(item.default_values.into()));
+            }
 
-    if let Some(item) = weapon_item {
-        character.add_item(item, 1, Some(item.default_values.into()));
-    }
+            if let Some(item) = weapon_item {
+                let values = if character.weapon_values.is_empty() {
+                    item.default_values.into()
+                } else {
+                    character.weapon_values.clone()
+                };
+                character.add_item(item, 1, Some(values));
+            }
 
-    character.appearance.face_cosmetic = Cosmetic::default();
-    character.appearance.head_cosmetic = Cosmetic::default();
-    character.weapon = None;
-    ctx.db().characters().save(character).await?;
+            for slot in EquipmentSlot::LIST {
+                if let Some(item) = character.equipment.unequip(slot) {
+                    character.add_item(item, 1, Some(item.default_values.into()));
+                }
+            }
+
+            character.appearance.face_cosmetic = Cosmetic::default();
+            character.appearance.head_cosmetic = Cosmetic::default();
+            character.weapon = None;
+            character.weapon_values = Default::default();
+        })
+        .await?;
 
     ctx.reply(
         Response::new_user_reply(
             &author,
-            "você desequipou com sucesso tudo que seu personagem vestia e usava como arma! Você pode equipar novamente com **/equipar**.",
+            "você desequipou com sucesso tudo que seu personagem vestia, usava como arma e como equipamento! Você pode equipar novamente com **/equipar**.",
         )
         .add_emoji_prefix(emojis::SUCCESS),
     )
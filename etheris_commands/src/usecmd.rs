@@ -18,7 +18,7 @@ pub async fn usecmd(
     user: Option<User>,
 ) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     let Some(inventory_item) = character.get_inventory_item_by_name(&item) else {
         ctx.reply(
             Response::new_user_reply(&author, "você não possui esse item ou esse item não existe! Utilize **/inventário** para ver os seus itens.")
@@ -91,12 +91,19 @@ pub async fn usecmd(
                 return Ok(());
             }
 
-            let mut user_character = parse_user_character!(ctx, user);
-            user_character.health_xp += 150;
-            user_character.knowledge_xp += 80;
-            user_character.strength_xp += 150;
-            user_character.intelligence_xp += 150;
-            ctx.db().characters().save(user_character).await?;
+            let user_character = parse_user_character!(ctx, user);
+            ctx.db()
+                .characters()
+                .add_xp(
+                    user_character.id,
+                    XpGain {
+                        strength: 150,
+                        health: 150,
+                        intelligence: 150,
+                        knowledge: 80,
+                    },
+                )
+                .await?;
 
             ctx.reply(
                 Response::new_user_reply(
@@ -188,6 +195,8 @@ pub async fn usecmd(
                     has_consequences: true,
                     casual: false,
                     max_intruders: 1,
+                    seed: None,
+                    ranked: false,
                 },
                 fighters,
             )?;
@@ -208,8 +217,12 @@ pub async fn usecmd(
         }
     }
 
-    character.remove_item(item, 1);
-    ctx.db().characters().save(character).await?;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            character.remove_item(item, 1);
+        })
+        .await?;
 
     Ok(())
 }
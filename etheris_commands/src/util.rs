@@ -1,3 +1,6 @@
+use etheris_discord::twilight_model::id::Id;
+use etheris_framework::EtherisClient;
+
 pub fn format_duration(date: chrono::Duration) -> String {
     let mut string = String::with_capacity(64);
 
@@ -17,3 +20,37 @@ pub fn format_duration(date: chrono::Duration) -> String {
 
     string
 }
+
+pub async fn send_channel_message(
+    client: &EtherisClient,
+    channel_id: &str,
+    content: String,
+) -> anyhow::Result<()> {
+    client
+        .http
+        .create_message(Id::new(channel_id.parse::<u64>()?))
+        .content(&content)?
+        .await?;
+
+    Ok(())
+}
+
+pub async fn send_direct_message(
+    client: &EtherisClient,
+    user_id: &str,
+    content: String,
+) -> anyhow::Result<()> {
+    let dm_channel = client
+        .http
+        .create_private_channel(Id::new(user_id.parse::<u64>()?))
+        .await?
+        .model()
+        .await?;
+    client
+        .http
+        .create_message(dm_channel.id)
+        .content(&content)?
+        .await?;
+
+    Ok(())
+}
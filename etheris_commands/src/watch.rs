@@ -0,0 +1,80 @@
+use etheris_rpg::spectators::{self, Spectator, WatchResult, MAX_SPECTATORS};
+
+use crate::prelude::*;
+
+#[command("Assista a batalha que alguém está lutando agora!")]
+#[name("assistir")]
+pub async fn watch(
+    mut ctx: CommandContext,
+    #[rename("usuário")]
+    #[description("O usuário que está lutando")]
+    user: User,
+) -> anyhow::Result<()> {
+    let author = ctx.author().await?;
+
+    if user.id == author.id {
+        ctx.send(
+            Response::new_user_reply(&author, "você não pode assistir a sua própria batalha!")
+                .add_emoji_prefix(emojis::ERROR)
+                .set_ephemeral(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if ctx.client.is_user_fighting(author.id).await {
+        ctx.send(
+            Response::new_user_reply(&author, "você não pode assistir uma batalha enquanto luta!")
+                .add_emoji_prefix(emojis::ERROR)
+                .set_ephemeral(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if !spectators::is_battle_watchable(user.id).await {
+        ctx.send(
+            Response::new_user_reply(
+                &author,
+                format!(
+                    "**{}** não está em nenhuma batalha agora!",
+                    user.display_name()
+                ),
+            )
+            .add_emoji_prefix(emojis::ERROR)
+            .set_ephemeral(),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(channel) = ctx.interaction.channel.clone() else {
+        return Ok(());
+    };
+
+    let spectator = Spectator::new(author.clone(), ctx.client.clone(), channel.id);
+    let response = match spectators::watch_battle(user.id, spectator).await {
+        WatchResult::Watching => Response::new_user_reply(
+            &author,
+            format!(
+                "você está assistindo a batalha de **{}**! A luta aparecerá aqui no próximo turno.",
+                user.display_name()
+            ),
+        )
+        .add_emoji_prefix("👀"),
+        WatchResult::NotInBattle => Response::new_user_reply(
+            &author,
+            format!("a batalha de **{}** já acabou!", user.display_name()),
+        )
+        .add_emoji_prefix(emojis::ERROR),
+        WatchResult::Full => Response::new_user_reply(
+            &author,
+            format!("essa batalha já tem o máximo de **{MAX_SPECTATORS}** espectadores!"),
+        )
+        .add_emoji_prefix(emojis::ERROR),
+    };
+
+    ctx.send(response.set_ephemeral()).await?;
+
+    Ok(())
+}
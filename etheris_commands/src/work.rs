@@ -1,4 +1,5 @@
-use rand::{rngs::StdRng, Rng, SeedableRng};
+use etheris_data::building::{BuildingKind, JOB_AGENCY_COMMISSION, JOB_AGENCY_WAGE_BONUS};
+use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
 
 use crate::prelude::*;
 
@@ -7,7 +8,7 @@ use crate::prelude::*;
 #[character_required(true)]
 pub async fn work(mut ctx: CommandContext) -> anyhow::Result<()> {
     let author = ctx.author().await?;
-    let mut character = parse_user_character!(ctx, author);
+    let character = parse_user_character!(ctx, author);
     let region = character.region;
 
     let Some(city) = character.region.city() else {
@@ -43,27 +44,64 @@ pub async fn work(mut ctx: CommandContext) -> anyhow::Result<()> {
 
     let rng = &mut StdRng::from_entropy();
 
-    let salary = rng.gen_range(city.wage.0..=city.wage.1);
+    let mut salary = rng.gen_range(city.wage.0..=city.wage.1);
+
+    // A job agency of the city finds a better paying job and keeps a commission
+    let agency = ctx
+        .db()
+        .buildings()
+        .get_all_by_region_as_vec(region)
+        .await?
+        .into_iter()
+        .filter(|b| b.kind == BuildingKind::JobAgency)
+        .choose(rng);
+    let mut commission = None;
+    if agency.is_some() {
+        salary += salary * JOB_AGENCY_WAGE_BONUS / 100;
+        commission = Some((salary * JOB_AGENCY_COMMISSION / 100).max(1));
+    }
+
     let strength_xp = rng.gen_range(city.work_strength_xp_gain.0..=city.work_strength_xp_gain.1);
     let health_xp = rng.gen_range(city.work_health_xp_gain.0..=city.work_health_xp_gain.1);
     let intelligence_xp =
         rng.gen_range(city.work_intelligence_xp_gain.0..=city.work_intelligence_xp_gain.1);
 
-    character.action_points -= ap_price;
-    character.intelligence_xp += intelligence_xp;
-    character.knowledge_xp += intelligence_xp;
-    character.strength_xp += strength_xp;
-    character.health_xp += health_xp;
-    character.add_orbs(salary);
+    let mut worked = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            worked = character.action_points >= ap_price;
+            if !worked {
+                return;
+            }
 
-    ctx.db().characters().save(character).await?;
+            character.action_points -= ap_price;
+            character.intelligence_xp += intelligence_xp;
+            character.knowledge_xp += intelligence_xp;
+            character.strength_xp += strength_xp;
+            character.health_xp += health_xp;
+            character.add_orbs(salary);
+        })
+        .await?;
+
+    if !worked {
+        return Ok(());
+    }
+    if let (Some(agency), Some(commission)) = (&agency, commission) {
+        ctx.db()
+            .buildings()
+            .record_employment(agency.id, commission)
+            .await?;
+    }
 
     ctx.send(
         Response::new_user_reply(
             &author,
             format!(
-                "você trabalhou na cidade **{}** e recebeu **{} ◎** ao custo de **{} pontos de ação**!\nVocê ganhou **{} XP em força**, **{} XP em vida** e **{} XP em inteligência**.",
-                region, salary, ap_price,
+                "você trabalhou na cidade **{}**{} e recebeu **{} ◎** ao custo de **{} pontos de ação**!\nVocê ganhou **{} XP em força**, **{} XP em vida** e **{} XP em inteligência**.",
+                region,
+                agency.map(|a| format!(" por meio da agência **{}**", a.name)).unwrap_or_default(),
+                salary, ap_price,
                 strength_xp, health_xp, intelligence_xp
             ),
         )
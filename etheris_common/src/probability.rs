@@ -34,9 +34,15 @@ impl Probability {
     }
 
     pub fn generate_random_bool(&self) -> bool {
+        self.generate_random_bool_with(&mut rand::thread_rng())
+    }
+
+    /// Same as `generate_random_bool`, but rolls with the given RNG.
+    /// Battles must use this with their own RNG so they can be reproduced from a seed.
+    pub fn generate_random_bool_with<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
         let probability = self.value_f64();
 
-        rand::thread_rng().gen_bool(probability.clamp(0.0, 1.0))
+        rng.gen_bool(probability.clamp(0.0, 1.0))
     }
 }
 
@@ -20,4 +20,27 @@ pub enum BrainKind {
 
     /// A boss brain thinks more.
     Boss,
+
+    /// A tactical brain simulates its options before choosing one. The difficulty sets how well it does it.
+    Tactical(BrainDifficulty),
+}
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum BrainDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
 }
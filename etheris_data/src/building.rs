@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::ShopItem;
 
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum BuildingKind {
     JobAgency,
     Hospital,
@@ -11,6 +11,8 @@ pub enum BuildingKind {
 }
 
 impl BuildingKind {
+    pub const LIST: [BuildingKind; 3] = [Self::JobAgency, Self::Hospital, Self::Shop];
+
     pub const fn display_name(&self) -> &str {
         match self {
             Self::JobAgency => "Agência de Empregos",
@@ -26,8 +28,40 @@ impl BuildingKind {
             Self::Shop => Emoji::from_unicode("🏪"),
         }
     }
+
+    /// How many orbs it costs to build it, without the price of the land.
+    pub const fn build_price(&self) -> i64 {
+        match self {
+            Self::JobAgency => 12000,
+            Self::Hospital => 15000,
+            Self::Shop => 6000,
+        }
+    }
+
+    /// The data of a building that was just built.
+    pub fn initial_data(&self) -> BuildingData {
+        match self {
+            Self::JobAgency => BuildingData::JobAgency(JobAgencyData {
+                stats_total_employments: 0,
+                stats_total_payouts: 0,
+            }),
+            Self::Hospital => BuildingData::Hospital,
+            Self::Shop => BuildingData::Shop(ShopBuildingData {
+                items: vec![],
+                stats_total_sells: 0,
+                stats_total_profit: 0,
+            }),
+        }
+    }
 }
 
+/// How many orbs a hospital charges for a treatment.
+pub const HOSPITAL_TREATMENT_PRICE: i64 = 120;
+/// The percentage of the wage a job agency receives when it employs someone.
+pub const JOB_AGENCY_COMMISSION: i64 = 15;
+/// The percentage of the wage a worker receives in addition when employed by a job agency.
+pub const JOB_AGENCY_WAGE_BONUS: i64 = 20;
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct JobAgencyData {
     pub stats_total_employments: usize,
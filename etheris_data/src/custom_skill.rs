@@ -0,0 +1,30 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::personality::Personality;
+
+/// What the game needs to know outside of battles about a skill defined in a file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CustomSkillInfo {
+    pub identifier: String,
+    pub intelligence_requirement: u32,
+    pub knowledge_cost: u32,
+    #[serde(default)]
+    pub personalities: Vec<Personality>,
+}
+
+static CUSTOM_SKILLS: OnceLock<Vec<CustomSkillInfo>> = OnceLock::new();
+
+/// Registers the skills defined in files. Returns false if they were already registered.
+pub fn register_custom_skills(skills: Vec<CustomSkillInfo>) -> bool {
+    CUSTOM_SKILLS.set(skills).is_ok()
+}
+
+pub fn custom_skills() -> &'static [CustomSkillInfo] {
+    CUSTOM_SKILLS.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+pub fn get_custom_skill_info(identifier: &str) -> Option<&'static CustomSkillInfo> {
+    custom_skills().iter().find(|s| s.identifier == identifier)
+}
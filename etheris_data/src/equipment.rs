@@ -0,0 +1,88 @@
+use etheris_common::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::ImmunityKind;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EquipmentSlot {
+    Body,
+    Legs,
+    Ring,
+    Amulet,
+}
+
+impl EquipmentSlot {
+    pub const LIST: [EquipmentSlot; 4] = [Self::Body, Self::Legs, Self::Ring, Self::Amulet];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Body => "Corpo",
+            Self::Legs => "Pernas",
+            Self::Ring => "Anel",
+            Self::Amulet => "Amuleto",
+        }
+    }
+}
+
+/// Effects that stay active during the whole battle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EquipmentPassive {
+    /// Health recovered at the end of every round.
+    Regeneration(i32),
+    /// Ether recovered at the end of every round.
+    EtherRecovery(i32),
+    /// Extra balance recovered at the end of every round.
+    Steadiness(u8),
+}
+
+/// What an equipment gives to who wears it.
+/// Multipliers are percentages: `110` means 10% more and `90` means 10% less.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EquipmentStat {
+    /// Resistance percentage against a kind of damage.
+    Resistance(ImmunityKind, u8),
+    DmgMultiplier(u32),
+    DefenseMultiplier(u32),
+    EtherRegenMultiplier(u32),
+    Passive(EquipmentPassive),
+}
+
+impl EquipmentStat {
+    pub fn description(&self) -> String {
+        let multiplier = |name: &str, percentage: u32| {
+            let difference = percentage as i64 - 100;
+            format!(
+                "{}{difference}% de {name}",
+                if difference >= 0 { "+" } else { "" }
+            )
+        };
+
+        match self {
+            Self::Resistance(kind, percentage) => {
+                format!("+{percentage}% de resistência a {}", kind.name())
+            }
+            Self::DmgMultiplier(percentage) => multiplier("dano", *percentage),
+            Self::DefenseMultiplier(percentage) => multiplier("defesa", *percentage),
+            Self::EtherRegenMultiplier(percentage) => {
+                multiplier("regeneração de ether", *percentage)
+            }
+            Self::Passive(EquipmentPassive::Regeneration(amount)) => {
+                format!("Regenera {amount} de vida por rodada")
+            }
+            Self::Passive(EquipmentPassive::EtherRecovery(amount)) => {
+                format!("Recupera {amount} de ether por rodada")
+            }
+            Self::Passive(EquipmentPassive::Steadiness(amount)) => {
+                format!("Recupera {amount} de equilíbrio extra por rodada")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EquipmentProperties {
+    pub slot: EquipmentSlot,
+    /// The color the equipment is drawn with in the character image.
+    pub color: Color,
+    pub stats: &'static [EquipmentStat],
+}
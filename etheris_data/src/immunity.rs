@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ImmunityKind {
+    Fire,
+    Ice,
+    Water,
+    Poison,
+    Bleeding,
+    Physical,
+    Cut,
+    Electric,
+    Special,
+}
+
+impl ImmunityKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fire => "Fogo",
+            Self::Ice => "Gelo",
+            Self::Water => "Água",
+            Self::Poison => "Veneno",
+            Self::Bleeding => "Sangramento",
+            Self::Physical => "Físico",
+            Self::Cut => "Corte",
+            Self::Electric => "Eletricidade",
+            Self::Special => "Especial",
+        }
+    }
+}
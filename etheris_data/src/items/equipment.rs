@@ -0,0 +1,187 @@
+use super::*;
+use crate::{
+    equipment::{EquipmentPassive, EquipmentProperties, EquipmentSlot, EquipmentStat},
+    ImmunityKind,
+};
+use etheris_common::Color;
+use etheris_discord::Emoji;
+
+const EQUIPMENT_TAGS: &[ItemTag] = &[ItemTag::Equipment];
+pub const ALL_ITEMS: &[Item] = &[
+    LEATHER_ARMOR,
+    IRON_ARMOR,
+    FUR_PANTS,
+    IRON_GREAVES,
+    RUBY_RING,
+    ETHER_RING,
+    HERBAL_AMULET,
+    STEADY_AMULET,
+];
+
+pub const LEATHER_ARMOR: Item = Item {
+    identifier: "leather_armor",
+    display_name: "Armadura de Couro",
+    emoji: Emoji::from_unicode("🦺"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 900,
+        base_sell_price: 250,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Body,
+        color: Color::BROWN,
+        stats: &[
+            EquipmentStat::Resistance(ImmunityKind::Physical, 10),
+            EquipmentStat::DefenseMultiplier(110),
+        ],
+    }),
+    ..Item::default()
+};
+
+pub const IRON_ARMOR: Item = Item {
+    identifier: "iron_armor",
+    display_name: "Armadura de Ferro",
+    emoji: Emoji::from_unicode("🛡️"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 3200,
+        base_sell_price: 800,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Body,
+        color: Color::LIGHT_GRAY,
+        stats: &[
+            EquipmentStat::Resistance(ImmunityKind::Physical, 20),
+            EquipmentStat::Resistance(ImmunityKind::Cut, 20),
+            EquipmentStat::DefenseMultiplier(120),
+            EquipmentStat::EtherRegenMultiplier(85),
+        ],
+    }),
+    ..Item::default()
+};
+
+pub const FUR_PANTS: Item = Item {
+    identifier: "fur_pants",
+    display_name: "Calças de Pele",
+    emoji: Emoji::from_unicode("👖"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 600,
+        base_sell_price: 150,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Legs,
+        color: Color::DARK_BROWN,
+        stats: &[EquipmentStat::Resistance(ImmunityKind::Ice, 25)],
+    }),
+    ..Item::default()
+};
+
+pub const IRON_GREAVES: Item = Item {
+    identifier: "iron_greaves",
+    display_name: "Grevas de Ferro",
+    emoji: Emoji::from_unicode("🦿"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 1800,
+        base_sell_price: 450,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Legs,
+        color: Color::LIGHT_GRAY,
+        stats: &[
+            EquipmentStat::Resistance(ImmunityKind::Physical, 10),
+            EquipmentStat::Passive(EquipmentPassive::Steadiness(4)),
+        ],
+    }),
+    ..Item::default()
+};
+
+pub const RUBY_RING: Item = Item {
+    identifier: "ruby_ring",
+    display_name: "Anel de Rubi",
+    emoji: Emoji::from_unicode("💍"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 2500,
+        base_sell_price: 700,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Ring,
+        color: Color::RED,
+        stats: &[
+            EquipmentStat::Resistance(ImmunityKind::Fire, 30),
+            EquipmentStat::DmgMultiplier(105),
+        ],
+    }),
+    ..Item::default()
+};
+
+pub const ETHER_RING: Item = Item {
+    identifier: "ether_ring",
+    display_name: "Anel de Ether",
+    emoji: Emoji::from_unicode("💠"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 4000,
+        base_sell_price: 1000,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Ring,
+        color: Color::CYAN,
+        stats: &[
+            EquipmentStat::EtherRegenMultiplier(130),
+            EquipmentStat::Passive(EquipmentPassive::EtherRecovery(2)),
+        ],
+    }),
+    ..Item::default()
+};
+
+pub const HERBAL_AMULET: Item = Item {
+    identifier: "herbal_amulet",
+    display_name: "Amuleto de Ervas",
+    emoji: Emoji::from_unicode("📿"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 2200,
+        base_sell_price: 550,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Amulet,
+        color: Color::GREEN,
+        stats: &[
+            EquipmentStat::Resistance(ImmunityKind::Poison, 25),
+            EquipmentStat::Passive(EquipmentPassive::Regeneration(3)),
+        ],
+    }),
+    ..Item::default()
+};
+
+pub const STEADY_AMULET: Item = Item {
+    identifier: "steady_amulet",
+    display_name: "Amuleto da Firmeza",
+    emoji: Emoji::from_unicode("🧿"),
+    tags: EQUIPMENT_TAGS,
+    purchase_properties: PurchaseProperties {
+        base_price: 2800,
+        base_sell_price: 700,
+        ..PurchaseProperties::default()
+    },
+    equipment_properties: Some(EquipmentProperties {
+        slot: EquipmentSlot::Amulet,
+        color: Color::YELLOW,
+        stats: &[
+            EquipmentStat::Resistance(ImmunityKind::Bleeding, 20),
+            EquipmentStat::Passive(EquipmentPassive::Steadiness(6)),
+            EquipmentStat::DmgMultiplier(95),
+        ],
+    }),
+    ..Item::default()
+};
@@ -1,7 +1,7 @@
 use etheris_common::Color;
 use etheris_discord::Emoji;
 
-use crate::weapon::WeaponKind;
+use crate::{equipment::EquipmentProperties, weapon::WeaponKind};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ConsumptionProperties {
@@ -70,11 +70,40 @@ pub enum ItemTag {
     Material,
     Ore,
     Cosmetic,
+    Equipment,
     Special,
     Crystal,
     Specific,
 }
 
+impl ItemTag {
+    pub const LIST: [ItemTag; 9] = [
+        Self::Consumable,
+        Self::Tool,
+        Self::Material,
+        Self::Ore,
+        Self::Cosmetic,
+        Self::Equipment,
+        Self::Special,
+        Self::Crystal,
+        Self::Specific,
+    ];
+
+    pub const fn display_name(&self) -> &'static str {
+        match self {
+            Self::Consumable => "Consumível",
+            Self::Tool => "Ferramenta",
+            Self::Material => "Material",
+            Self::Ore => "Minério",
+            Self::Cosmetic => "Cosmético",
+            Self::Equipment => "Equipamento",
+            Self::Special => "Especial",
+            Self::Crystal => "Cristal",
+            Self::Specific => "Específico",
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Page {
     pub title: &'static str,
@@ -104,6 +133,7 @@ pub struct Item {
     pub consumption_properties: Option<ConsumptionProperties>,
     pub purchase_properties: PurchaseProperties,
     pub cosmetic_properties: Option<CosmeticProperties>,
+    pub equipment_properties: Option<EquipmentProperties>,
     pub weapon: Option<WeaponKind>,
     pub tags: &'static [ItemTag],
     pub pages: &'static [Page],
@@ -128,6 +158,7 @@ impl Item {
             weapon: None,
             tags: &[],
             cosmetic_properties: None,
+            equipment_properties: None,
             pages: &[],
             default_values: DefaultItemValues { values: &[] },
         }
@@ -2,6 +2,7 @@ mod item;
 
 pub mod consumable;
 pub mod cosmetic;
+pub mod equipment;
 pub mod lore;
 pub mod material;
 pub mod ore;
@@ -21,6 +22,7 @@ pub static ALL_ITEMS: Lazy<Vec<Item>> = Lazy::new(|| {
     all_items.extend_from_slice(material::ALL_ITEMS);
     all_items.extend_from_slice(ore::ALL_ITEMS);
     all_items.extend_from_slice(cosmetic::ALL_ITEMS);
+    all_items.extend_from_slice(equipment::ALL_ITEMS);
     all_items.extend_from_slice(lore::ALL_ITEMS);
     all_items.extend_from_slice(tool::ALL_ITEMS);
     all_items.extend_from_slice(special::ALL_ITEMS);
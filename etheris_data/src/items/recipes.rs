@@ -1,4 +1,5 @@
 use super::{get_item, Item};
+use crate::weapon::WeaponEnchantment;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ingredient {
@@ -192,3 +193,74 @@ pub const ALL_RECIPES: &[Recipe] = &[
         ],
     },
 ];
+
+/// What a blacksmith can do to a weapon.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlacksmithWork {
+    Upgrade,
+    Repair,
+    Enchant(WeaponEnchantment),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlacksmithRecipe {
+    pub work: BlacksmithWork,
+    pub ingredients: Vec<Ingredient>,
+    pub orbs: i64,
+}
+
+/// The ores a blacksmith asks to work on a weapon. Upgrades get pricier with the level of the weapon.
+pub fn get_blacksmith_recipe(work: BlacksmithWork, upgrade_level: u32) -> BlacksmithRecipe {
+    let level = upgrade_level as usize;
+    let (ingredients, orbs) = match work {
+        BlacksmithWork::Upgrade => {
+            let mut ingredients = vec![
+                Ingredient::new("iron_ore", 2 * (level + 1)),
+                Ingredient::new("coal_ore", level + 1),
+            ];
+            if level >= 2 {
+                ingredients.push(Ingredient::new("gold_ore", level - 1));
+            }
+            if level >= 4 {
+                ingredients.push(Ingredient::new("diamond_ore", 1));
+            }
+
+            (ingredients, 150 * (level as i64 + 1))
+        }
+        BlacksmithWork::Repair => (
+            vec![
+                Ingredient::new("iron_ore", 1),
+                Ingredient::new("coal_ore", 1),
+            ],
+            50,
+        ),
+        BlacksmithWork::Enchant(enchantment) => {
+            let ingredients = match enchantment {
+                WeaponEnchantment::Flaming => vec![
+                    Ingredient::new("coal_ore", 6),
+                    Ingredient::new("gold_ore", 1),
+                ],
+                WeaponEnchantment::Freezing => vec![
+                    Ingredient::new("tin_ore", 3),
+                    Ingredient::new("diamond_ore", 1),
+                ],
+                WeaponEnchantment::Shocking => vec![
+                    Ingredient::new("copper_ore", 5),
+                    Ingredient::new("gold_ore", 1),
+                ],
+                WeaponEnchantment::Venomous => vec![
+                    Ingredient::new("lead_ore", 5),
+                    Ingredient::new("tin_ore", 1),
+                ],
+            };
+
+            (ingredients, 600)
+        }
+    };
+
+    BlacksmithRecipe {
+        work,
+        ingredients,
+        orbs,
+    }
+}
@@ -2,6 +2,7 @@ pub mod appearance;
 mod boss;
 pub mod building;
 pub mod emojis;
+pub mod equipment;
 pub mod items;
 pub mod jobs;
 pub mod personality;
@@ -13,10 +14,20 @@ pub use boss::*;
 mod skill_kind;
 pub use skill_kind::*;
 
+mod custom_skill;
+pub use custom_skill::*;
+
+mod skill_mastery;
+pub use skill_mastery::*;
+
 mod brain;
 pub use brain::*;
 
+mod immunity;
+pub use immunity::*;
+
 use items::{DefaultItemValue, DefaultItemValues, Item};
+use weapon::WeaponEnchantment;
 
 #[derive(
     Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize,
@@ -80,6 +91,9 @@ pub enum ItemValue {
     AlternativeName(String),
     Recipes(Vec<String>),
     Durability(u32),
+    /// How many times a blacksmith upgraded the weapon.
+    UpgradeLevel(u32),
+    Enchantment(WeaponEnchantment),
 }
 
 impl From<DefaultItemValue> for ItemValue {
@@ -94,7 +108,16 @@ impl From<DefaultItemValue> for ItemValue {
     }
 }
 #[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize, Hash,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Deserialize,
+    serde::Serialize,
+    Hash,
+    Default,
 )]
 pub struct ItemValues {
     pub values: Vec<ItemValue>,
@@ -126,6 +149,50 @@ impl ItemValues {
             .collect()
     }
 
+    pub fn durability(&self) -> Option<u32> {
+        self.values.iter().find_map(|value| match value {
+            ItemValue::Durability(durability) => Some(*durability),
+            _ => None,
+        })
+    }
+
+    pub fn upgrade_level(&self) -> u32 {
+        self.values
+            .iter()
+            .find_map(|value| match value {
+                ItemValue::UpgradeLevel(level) => Some(*level),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn enchantment(&self) -> Option<WeaponEnchantment> {
+        self.values.iter().find_map(|value| match value {
+            ItemValue::Enchantment(enchantment) => Some(*enchantment),
+            _ => None,
+        })
+    }
+
+    /// The name of a weapon with its upgrade level and enchantment, like `Espada +2 Flamejante`.
+    pub fn name_with_upgrades(&self, display_name: &str) -> String {
+        let mut name = display_name.to_string();
+        if self.upgrade_level() > 0 {
+            name.push_str(&format!(" +{}", self.upgrade_level()));
+        }
+        if let Some(enchantment) = self.enchantment() {
+            name.push_str(&format!(" {}", enchantment.name()));
+        }
+
+        name
+    }
+
+    /// Replaces the value of the same kind, if any.
+    pub fn set(&mut self, value: ItemValue) {
+        self.values
+            .retain(|v| std::mem::discriminant(v) != std::mem::discriminant(&value));
+        self.values.push(value);
+    }
+
     pub fn recipes(&self) -> Vec<String> {
         self.values
             .iter()
@@ -1,7 +1,7 @@
 use etheris_common::Identifiable;
 use serde::{Deserialize, Serialize};
 
-use crate::{personality::Personality, BrainKind};
+use crate::{custom_skills, get_custom_skill_info, personality::Personality, BrainKind};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Soul {
@@ -63,6 +63,8 @@ pub enum SkillKind {
     Pyrotransmutation,
     PoisonousGas,
     ThermalFists,
+    /// A skill defined in a file, identified by its identifier.
+    Custom(String),
 }
 
 impl SkillKind {
@@ -111,6 +113,13 @@ impl SkillKind {
             Self::PoisonousGas,
             Self::ThermalFists,
         ]
+        .into_iter()
+        .chain(
+            custom_skills()
+                .iter()
+                .map(|s| Self::Custom(s.identifier.clone())),
+        )
+        .collect()
     }
 
     pub fn personalities_affinity(&self) -> &'static [Personality] {
@@ -166,6 +175,9 @@ impl SkillKind {
             Self::Pyrotransmutation => &[Personality::Aggressiveness, Personality::Cowardice],
             Self::PoisonousGas => &[Personality::Aggressiveness, Personality::Cowardice],
             Self::ThermalFists => &[Personality::Arrogance, Personality::Intelligence],
+            Self::Custom(identifier) => get_custom_skill_info(identifier)
+                .map(|s| s.personalities.as_slice())
+                .unwrap_or_default(),
         }
     }
 
@@ -215,6 +227,10 @@ impl SkillKind {
             Self::AgorathForcedDuel => u32::MAX,
             Self::EthriaAdaptation => u32::MAX,
             Self::Debug => u32::MAX,
+
+            Self::Custom(identifier) => get_custom_skill_info(identifier)
+                .map(|s| s.intelligence_requirement)
+                .unwrap_or(u32::MAX),
         }
     }
 
@@ -257,6 +273,9 @@ impl SkillKind {
             Self::TenkuKikan(..) => 6,
             Self::ParalyzingBet | Self::AgorathForcedDuel => 6,
             Self::FinalCrucifix | Self::EthriaAdaptation => 7,
+            Self::Custom(identifier) => get_custom_skill_info(identifier)
+                .map(|s| s.knowledge_cost)
+                .unwrap_or(1),
         }
     }
 }
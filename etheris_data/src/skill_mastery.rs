@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::SkillKind;
+
+pub const MAX_SKILL_MASTERY_LEVEL: u32 = 5;
+/// Mastery XP earned every time a skill is used in a battle.
+pub const SKILL_MASTERY_XP_PER_USE: u32 = 1;
+
+/// The total mastery XP needed to reach each level, starting from the level 2.
+const SKILL_MASTERY_LEVELS_XP: [u32; (MAX_SKILL_MASTERY_LEVEL - 1) as usize] = [15, 50, 120, 250];
+
+/// How used to a skill a character is. Mastered skills cost less ether and may get stronger.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SkillMastery {
+    pub skill: SkillKind,
+    pub xp: u32,
+}
+
+impl SkillMastery {
+    pub fn new(skill: SkillKind) -> Self {
+        Self { skill, xp: 0 }
+    }
+
+    pub fn level(&self) -> u32 {
+        skill_mastery_level(self.xp)
+    }
+
+    /// The XP the skill needs to reach the next level, or `None` if it's already mastered.
+    pub fn xp_to_next_level(&self) -> Option<u32> {
+        SKILL_MASTERY_LEVELS_XP
+            .iter()
+            .find(|required| **required > self.xp)
+            .map(|required| required - self.xp)
+    }
+
+    pub fn is_skill(&self, skill: &SkillKind) -> bool {
+        self.skill == *skill
+    }
+}
+
+pub fn skill_mastery_level(xp: u32) -> u32 {
+    1 + SKILL_MASTERY_LEVELS_XP
+        .iter()
+        .filter(|required| xp >= **required)
+        .count() as u32
+}
+
+/// Every level above the first makes the skill cost 5% less ether.
+pub fn skill_mastery_ether_discount(level: u32) -> f32 {
+    level.clamp(1, MAX_SKILL_MASTERY_LEVEL).saturating_sub(1) as f32 * 0.05
+}
+
+/// Finds the mastery of the skill in a list, like the one stored in a character.
+pub fn find_skill_mastery<'a>(
+    masteries: &'a [SkillMastery],
+    skill: &SkillKind,
+) -> Option<&'a SkillMastery> {
+    masteries.iter().find(|m| m.is_skill(skill))
+}
+
+/// Adds XP to the mastery of the skill in the list. Returns true if the skill reached a new level.
+pub fn add_skill_mastery_xp(masteries: &mut Vec<SkillMastery>, skill: &SkillKind, xp: u32) -> bool {
+    let index = match masteries.iter().position(|m| m.is_skill(skill)) {
+        Some(index) => index,
+        None => {
+            masteries.push(SkillMastery::new(skill.clone()));
+            masteries.len() - 1
+        }
+    };
+
+    let mastery = &mut masteries[index];
+    let level = mastery.level();
+    mastery.xp = mastery.xp.saturating_add(xp);
+    mastery.level() > level
+}
+
+#[test]
+fn skill_mastery_levels_up() {
+    let mut masteries = vec![];
+    assert!(!add_skill_mastery_xp(
+        &mut masteries,
+        &SkillKind::ImbuedPunch,
+        14
+    ));
+    assert!(add_skill_mastery_xp(
+        &mut masteries,
+        &SkillKind::ImbuedPunch,
+        1
+    ));
+
+    let mastery = find_skill_mastery(&masteries, &SkillKind::ImbuedPunch).unwrap();
+    assert_eq!(mastery.level(), 2);
+    assert_eq!(mastery.xp_to_next_level(), Some(35));
+    assert_eq!(skill_mastery_level(10_000), MAX_SKILL_MASTERY_LEVEL);
+    assert!((skill_mastery_ether_discount(MAX_SKILL_MASTERY_LEVEL) - 0.2).abs() < f32::EPSILON);
+}
+
+#[test]
+fn custom_skills_have_their_own_mastery() {
+    let ether_palm = SkillKind::Custom("ether_palm".into());
+    let venomous_kick = SkillKind::Custom("venomous_kick".into());
+
+    let mut masteries = vec![];
+    add_skill_mastery_xp(&mut masteries, &ether_palm, 20);
+    add_skill_mastery_xp(&mut masteries, &venomous_kick, 1);
+
+    assert_eq!(masteries.len(), 2);
+    assert_eq!(find_skill_mastery(&masteries, &ether_palm).unwrap().xp, 20);
+    assert_eq!(
+        find_skill_mastery(&masteries, &venomous_kick).unwrap().xp,
+        1
+    );
+}
@@ -39,3 +39,48 @@ impl WeaponKind {
         }
     }
 }
+
+impl WeaponKind {
+    /// How many attacks the weapon takes before breaking.
+    pub fn max_durability(&self) -> u32 {
+        match self {
+            Self::Stick => 25,
+            Self::Knife => 60,
+            Self::Bat => 80,
+            Self::Umbrella => 45,
+            Self::Spear => 70,
+            Self::Katana => 100,
+            Self::ScorpionFang => 80,
+            Self::EthriaKatana => 150,
+        }
+    }
+}
+
+pub const MAX_WEAPON_UPGRADE_LEVEL: u32 = 5;
+
+/// An element bound to a weapon by a blacksmith.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum WeaponEnchantment {
+    Flaming,
+    Freezing,
+    Shocking,
+    Venomous,
+}
+
+impl WeaponEnchantment {
+    pub const LIST: [WeaponEnchantment; 4] = [
+        Self::Flaming,
+        Self::Freezing,
+        Self::Shocking,
+        Self::Venomous,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Flaming => "Flamejante",
+            Self::Freezing => "Congelante",
+            Self::Shocking => "Elétrico",
+            Self::Venomous => "Venenoso",
+        }
+    }
+}
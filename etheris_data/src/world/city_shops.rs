@@ -38,6 +38,10 @@ pub static METROLIS: Lazy<Vec<ShopItem>> = Lazy::new(|| {
         ShopItem::new_item(5, items::tool::HAMMER, 1.5),
         // Cosmetics
         ShopItem::new_item(135, items::cosmetic::GLASSES, 0.8),
+        // Equipment
+        ShopItem::new_item(20, items::equipment::LEATHER_ARMOR, 1.0),
+        ShopItem::new_item(20, items::equipment::FUR_PANTS, 1.1),
+        ShopItem::new_item(5, items::equipment::HERBAL_AMULET, 1.2),
         // Lore
         ShopItem::new_item(338, items::lore::METROLIS_LAW_338, 1.0),
     ]
@@ -83,6 +87,12 @@ pub static SWORD_TOWN: Lazy<Vec<ShopItem>> = Lazy::new(|| {
         ShopItem::new_item(500, items::tool::KATANA, 0.9),
         // Cosmetics
         ShopItem::new_item(100, items::cosmetic::GLASSES, 1.2),
+        // Equipment
+        ShopItem::new_item(10, items::equipment::IRON_ARMOR, 1.0),
+        ShopItem::new_item(10, items::equipment::IRON_GREAVES, 1.0),
+        ShopItem::new_item(5, items::equipment::RUBY_RING, 1.1),
+        ShopItem::new_item(2, items::equipment::ETHER_RING, 1.3),
+        ShopItem::new_item(3, items::equipment::STEADY_AMULET, 1.2),
         // Lore
         ShopItem::new_item(11, items::lore::THE_MAN_WHO_SEES_SOUNDS, 1.2),
     ]
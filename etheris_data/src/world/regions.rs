@@ -119,6 +119,8 @@ pub struct RegionCity {
     pub work_intelligence_xp_gain: (u32, u32),
     pub work_ap_cost: u32,
     pub shop_items: Vec<ShopItem>,
+    /// How many orbs a piece of land to build on costs.
+    pub land_price: i64,
 }
 
 impl WorldRegion {
@@ -216,6 +218,7 @@ impl WorldRegion {
                 work_strength_xp_gain: (3, 7),
                 work_intelligence_xp_gain: (1, 4),
                 shop_items: city_shops::METROLIS.to_vec(),
+                land_price: 8000,
             }),
             Self::SwordTown => Some(RegionCity {
                 sell_multiplier: 1.2,
@@ -225,6 +228,7 @@ impl WorldRegion {
                 work_strength_xp_gain: (8, 12),
                 work_intelligence_xp_gain: (5, 8),
                 shop_items: city_shops::SWORD_TOWN.to_vec(),
+                land_price: 15000,
             }),
             _ => None,
         }
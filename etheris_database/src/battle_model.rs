@@ -0,0 +1,133 @@
+use bson::{doc, oid::ObjectId, Bson};
+use etheris_data::world::regions::WorldRegion;
+use mongodb::{options::FindOptions, Collection};
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::{common::*, EtherisDatabase};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BattleRecordSettings {
+    pub is_risking_life_allowed: bool,
+    pub casual: bool,
+    pub has_consequences: bool,
+    pub max_intruders: u8,
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BattleParticipant {
+    pub name: String,
+    pub team: u8,
+    pub pl: i64,
+    /// `None` when the fighter is an AI.
+    pub user_id: Option<String>,
+    pub character_id: Option<ObjectId>,
+    pub defeated: bool,
+    pub died: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct BattleRewards {
+    pub orbs: i64,
+    pub xp: u32,
+    pub items: Vec<InventoryItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BattleTurnLog {
+    pub fighter: String,
+    pub messages: Vec<String>,
+    /// The serialized `BattleEvent`s of the turn. They're defined by the RPG crate, so they're kept as BSON.
+    #[serde(default)]
+    pub events: Vec<Bson>,
+}
+
+/// A finished battle that had at least one player in it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BattleModel {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub region: WorldRegion,
+    pub settings: BattleRecordSettings,
+    pub participants: Vec<BattleParticipant>,
+    pub winner_team: u8,
+    pub pvp: bool,
+    pub turns: u32,
+    pub started_at: DatabaseDateTime,
+    pub ended_at: DatabaseDateTime,
+    pub rewards: BattleRewards,
+    /// Left empty when battles are listed, see [`BattleCommands::get_character_battles`].
+    #[serde(default)]
+    pub log: Vec<BattleTurnLog>,
+    /// Where the replay of the battle was saved. Only PvP battles keep their replays.
+    #[serde(default)]
+    pub replay_path: Option<String>,
+}
+
+impl BattleModel {
+    pub fn duration(&self) -> chrono::Duration {
+        self.ended_at.0 - self.started_at.0
+    }
+
+    pub fn get_participant(&self, character_id: ObjectId) -> Option<&BattleParticipant> {
+        self.participants
+            .iter()
+            .find(|p| p.character_id == Some(character_id))
+    }
+
+    pub fn is_winner(&self, character_id: ObjectId) -> bool {
+        self.get_participant(character_id)
+            .is_some_and(|p| p.team == self.winner_team)
+    }
+}
+
+#[allow(unused)]
+pub struct BattleCommands {
+    collection: Collection<BattleModel>,
+    db: EtherisDatabase,
+}
+
+impl BattleCommands {
+    pub const fn new(collection: Collection<BattleModel>, db: EtherisDatabase) -> Self {
+        Self { collection, db }
+    }
+
+    pub async fn save(&self, battle: BattleModel) -> anyhow::Result<()> {
+        self.collection
+            .replace_one(
+                query_by_id(battle.id),
+                &battle,
+                mongodb::options::ReplaceOptions::builder()
+                    .upsert(true)
+                    .build(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_by_id(&self, id: ObjectId) -> anyhow::Result<Option<BattleModel>> {
+        Ok(self.collection.find_one(query_by_id(id), None).await?)
+    }
+
+    /// The most recent battles of a character, newest first. The turn log isn't loaded.
+    pub async fn get_character_battles(
+        &self,
+        character_id: ObjectId,
+        limit: i64,
+    ) -> anyhow::Result<Vec<BattleModel>> {
+        let cursor = self
+            .collection
+            .find(
+                doc! { "participants.character_id": character_id },
+                FindOptions::builder()
+                    .sort(doc! { "ended_at": -1 })
+                    .limit(limit)
+                    .projection(doc! { "log": 0 })
+                    .build(),
+            )
+            .await?;
+
+        Ok(cursor.collect::<Result<Vec<_>, _>>().await?)
+    }
+}
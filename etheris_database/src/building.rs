@@ -3,7 +3,7 @@ use std::hash::Hash;
 use crate::common::_default_now;
 use bson::{doc, oid::ObjectId, Document};
 use etheris_common::{clear_string, Cache};
-use etheris_data::building::*;
+use etheris_data::{building::*, world::regions::WorldRegion, ShopItem};
 use mongodb::{Collection, Cursor};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -15,7 +15,12 @@ use crate::{common::*, EtherisDatabase};
 pub struct BuildingModel {
     #[serde(rename = "_id")]
     pub id: ObjectId,
-    pub city_id: ObjectId,
+    /// `None` for buildings created before they were tied to a region, which only have a `city_id`.
+    #[serde(default)]
+    pub region: Option<WorldRegion>,
+    /// The city of buildings created before regions. Kept so their documents are still readable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city_id: Option<ObjectId>,
     pub character_owner: ObjectId,
     pub name_id: String,
     pub name: String,
@@ -29,7 +34,7 @@ pub struct BuildingModel {
 
 impl BuildingModel {
     pub fn new(
-        city_id: ObjectId,
+        region: WorldRegion,
         character_id: ObjectId,
         orbs: i64,
         name: String,
@@ -37,7 +42,8 @@ impl BuildingModel {
     ) -> Self {
         Self {
             id: ObjectId::new(),
-            city_id,
+            region: Some(region),
+            city_id: None,
             character_owner: character_id,
             name_id: clear_string(&name),
             name,
@@ -73,8 +79,29 @@ impl BuildingModel {
             _ => None,
         }
     }
+
+    pub fn get_job_agency_data_mut(&mut self) -> Option<&mut JobAgencyData> {
+        match &mut self.data {
+            BuildingData::JobAgency(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn get_shop_data_mut(&mut self) -> Option<&mut ShopBuildingData> {
+        match &mut self.data {
+            BuildingData::Shop(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn add_orbs(&mut self, orbs: i64) {
+        self.orbs = self.orbs.saturating_add(orbs);
+    }
 }
 
+/// How many times a targeted update is retried when the stock changes under it.
+const MAX_UPDATE_ATTEMPTS: usize = 5;
+
 static CACHE_ID: Lazy<Cache<ObjectId, BuildingModel>> = Lazy::new(|| Cache::new(1000));
 
 #[allow(unused)]
@@ -120,28 +147,319 @@ impl BuildingCommands {
         self.get(&CACHE_ID, id, query_by_id(id)).await
     }
 
-    pub async fn get_all_by_city_id(
+    pub async fn get_all_by_region(
         &self,
-        city_id: ObjectId,
+        region: WorldRegion,
     ) -> anyhow::Result<Cursor<BuildingModel>> {
         let query = doc! {
-            "city_id": city_id
+            "region": bson::to_bson(&region)?
         };
 
         Ok(self.collection.find(query, None).await?)
     }
 
-    pub async fn get_all_by_city_id_as_vec(
+    pub async fn get_all_by_region_as_vec(
         &self,
-        city_id: ObjectId,
+        region: WorldRegion,
     ) -> anyhow::Result<Vec<BuildingModel>> {
-        let all = self.get_all_by_city_id(city_id).await?;
+        let all = self.get_all_by_region(region).await?;
         Ok(all.collect::<Result<Vec<_>, _>>().await?)
     }
 
-    pub async fn create_building(&self, building: BuildingModel) -> anyhow::Result<()> {
-        self.collection.insert_one(building, None).await?;
+    pub async fn get_by_region_and_name(
+        &self,
+        region: WorldRegion,
+        name: &str,
+    ) -> anyhow::Result<Option<BuildingModel>> {
+        let query = doc! {
+            "region": bson::to_bson(&region)?,
+            "name_id": clear_string(name)
+        };
+
+        Ok(self.collection.find_one(query, None).await?)
+    }
+
+    pub async fn get_by_owner_in_region(
+        &self,
+        character_id: ObjectId,
+        region: WorldRegion,
+    ) -> anyhow::Result<Option<BuildingModel>> {
+        let query = doc! {
+            "region": bson::to_bson(&region)?,
+            "character_owner": character_id
+        };
+
+        Ok(self.collection.find_one(query, None).await?)
+    }
+
+    /// Applies an update operator to the building directly.
+    /// Returns whether a building matched the query.
+    async fn update_fields(&self, query: Document, update: Document) -> anyhow::Result<bool> {
+        let building = self
+            .collection
+            .find_one_and_update(query, update, None)
+            .await?;
+        if let Some(building) = &building {
+            CACHE_ID.remove(&building.id);
+        }
+
+        Ok(building.is_some())
+    }
+
+    pub async fn add_orbs(&self, id: ObjectId, orbs: i64) -> anyhow::Result<()> {
+        self.update_fields(query_by_id(id), doc! { "$inc": { "orbs": orbs } })
+            .await?;
+        Ok(())
+    }
+
+    /// Empties the vault of the building. Returns how many orbs it had.
+    pub async fn take_all_orbs(&self, id: ObjectId) -> anyhow::Result<i64> {
+        let building = self
+            .collection
+            .find_one_and_update(
+                doc! { "_id": id, "orbs": { "$gt": 0 } },
+                doc! { "$set": { "orbs": 0_i64 } },
+                None,
+            )
+            .await?;
+        CACHE_ID.remove(&id);
+
+        Ok(building.map(|b| b.orbs).unwrap_or_default())
+    }
+
+    pub async fn record_employment(&self, id: ObjectId, commission: i64) -> anyhow::Result<()> {
+        self.update_fields(
+            query_by_id(id),
+            doc! {
+                "$inc": {
+                    "orbs": commission,
+                    "data.JobAgency.stats_total_employments": 1_i64,
+                    "data.JobAgency.stats_total_payouts": commission,
+                }
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Takes `amount` of the item from the stock of a shop and puts the price in its vault,
+    /// only if the shop still has all of them. Returns whether they were taken.
+    pub async fn sell_shop_stock(
+        &self,
+        id: ObjectId,
+        identifier: &str,
+        amount: i64,
+        price: i64,
+    ) -> anyhow::Result<bool> {
+        let sold = self
+            .update_fields(
+                doc! {
+                    "_id": id,
+                    "data.Shop.items": {
+                        "$elemMatch": { "identifier": identifier, "quantity": { "$gte": amount } }
+                    }
+                },
+                doc! {
+                    "$inc": {
+                        "data.Shop.items.$.quantity": -amount,
+                        "orbs": price,
+                        "data.Shop.stats_total_sells": amount,
+                        "data.Shop.stats_total_profit": price,
+                    }
+                },
+            )
+            .await?;
+        if sold {
+            self.remove_empty_stock(id).await?;
+        }
+
+        Ok(sold)
+    }
+
+    /// Reverts a `sell_shop_stock` whose buyer couldn't receive the items.
+    pub async fn refund_shop_stock(
+        &self,
+        id: ObjectId,
+        identifier: &str,
+        amount: i64,
+        unit_price: i64,
+    ) -> anyhow::Result<()> {
+        let price = amount * unit_price;
+        self.update_fields(
+            query_by_id(id),
+            doc! {
+                "$inc": {
+                    "orbs": -price,
+                    "data.Shop.stats_total_sells": -amount,
+                    "data.Shop.stats_total_profit": -price,
+                }
+            },
+        )
+        .await?;
+        self.stock_shop_item(id, identifier, amount, Some(unit_price), None)
+            .await?;
+        Ok(())
+    }
+
+    /// Adds `amount` of the item to the stock of a shop, changing its price if one is given.
+    /// Items the shop doesn't sell yet are only added while it sells less than `max_items`, if given.
+    /// Returns whether the item was stocked.
+    pub async fn stock_shop_item(
+        &self,
+        id: ObjectId,
+        identifier: &str,
+        amount: i64,
+        price: Option<i64>,
+        max_items: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        let mut stock_update = doc! { "$inc": { "data.Shop.items.$.quantity": amount } };
+        if let Some(price) = price {
+            stock_update.insert("$set", doc! { "data.Shop.items.$.price": price });
+        }
+
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let stocked = self
+                .update_fields(
+                    doc! { "_id": id, "data.Shop.items.identifier": identifier },
+                    stock_update.clone(),
+                )
+                .await?;
+            if stocked {
+                return Ok(true);
+            }
+
+            let mut query = doc! { "_id": id, "data.Shop.items.identifier": { "$ne": identifier } };
+            if let Some(max_items) = max_items {
+                // There's no element at `max_items - 1` while the shop sells less than `max_items`
+                query.insert(
+                    format!("data.Shop.items.{}", max_items.saturating_sub(1)),
+                    doc! { "$exists": false },
+                );
+            }
+
+            let item = ShopItem::new(
+                amount.clamp(0, i32::MAX as i64) as i32,
+                identifier,
+                price.unwrap_or_default(),
+            );
+            // Someone else may have stocked the item since it wasn't found, so this only pushes if it's still missing
+            let pushed = self
+                .update_fields(
+                    query,
+                    doc! { "$push": { "data.Shop.items": bson::to_bson(&item)? } },
+                )
+                .await?;
+            if pushed {
+                return Ok(true);
+            }
+
+            let still_missing = self
+                .collection
+                .find_one(
+                    doc! { "_id": id, "data.Shop.items.identifier": { "$ne": identifier } },
+                    None,
+                )
+                .await?
+                .is_some();
+            if still_missing {
+                // The shop is full
+                return Ok(false);
+            }
+        }
+
+        anyhow::bail!("the stock of the building {id} kept changing while stocking {identifier}")
+    }
+
+    /// Removes the item from the stock of a shop. Returns what was in stock, if anything.
+    pub async fn unstock_shop_item(
+        &self,
+        id: ObjectId,
+        identifier: &str,
+    ) -> anyhow::Result<Option<ShopItem>> {
+        let building = self
+            .collection
+            .find_one_and_update(
+                doc! { "_id": id, "data.Shop.items.identifier": identifier },
+                doc! { "$pull": { "data.Shop.items": { "identifier": identifier } } },
+                None,
+            )
+            .await?;
+        CACHE_ID.remove(&id);
 
+        Ok(building
+            .and_then(|b| b.get_shop_data())
+            .and_then(|shop| shop.items.into_iter().find(|i| i.identifier == identifier)))
+    }
+
+    async fn remove_empty_stock(&self, id: ObjectId) -> anyhow::Result<()> {
+        self.update_fields(
+            query_by_id(id),
+            doc! { "$pull": { "data.Shop.items": { "quantity": { "$lte": 0 } } } },
+        )
+        .await?;
         Ok(())
     }
+
+    /// Fails with `false` if the owner already has a building in the region or the name is taken there.
+    pub async fn create_building(&self, building: BuildingModel) -> anyhow::Result<bool> {
+        match self.collection.insert_one(building, None).await {
+            Err(e) if is_duplicate_key(&e) => Ok(false),
+            result => {
+                result?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_buildings_start_with_the_data_of_their_kind() {
+        for kind in BuildingKind::LIST {
+            let building = BuildingModel::new(
+                WorldRegion::Metrolis,
+                ObjectId::new(),
+                0,
+                "Casa Nova".into(),
+                kind.initial_data(),
+            );
+
+            assert_eq!(building.kind, kind);
+            assert_eq!(building.name_id, clear_string("Casa Nova"));
+        }
+
+        let mut shop = BuildingModel::new(
+            WorldRegion::SwordTown,
+            ObjectId::new(),
+            0,
+            "Loja".into(),
+            BuildingKind::Shop.initial_data(),
+        );
+        assert!(shop.get_job_agency_data_mut().is_none());
+        shop.get_shop_data_mut().unwrap().stats_total_sells += 3;
+        assert_eq!(shop.get_shop_data().unwrap().stats_total_sells, 3);
+    }
+
+    #[test]
+    fn buildings_saved_with_a_city_id_are_still_readable() {
+        let city_id = ObjectId::new();
+        let legacy = doc! {
+            "_id": ObjectId::new(),
+            "city_id": city_id,
+            "character_owner": ObjectId::new(),
+            "name_id": "loja",
+            "name": "Loja",
+            "orbs": 10i64,
+            "kind": bson::to_bson(&BuildingKind::Hospital).unwrap(),
+            "data": bson::to_bson(&BuildingData::Hospital).unwrap(),
+        };
+
+        let building: BuildingModel = bson::from_document(legacy).unwrap();
+        assert_eq!(building.region, None);
+        assert_eq!(building.city_id, Some(city_id));
+        assert_eq!(building.orbs, 10);
+    }
 }
@@ -1,9 +1,12 @@
-use std::hash::Hash;
+use std::{fmt::Display, hash::Hash};
 
 use bson::{doc, oid::ObjectId, Document};
 use etheris_common::{calculate_power_level, Cache};
 use etheris_data::{
-    items, personality::Personality, world::regions::WorldRegion, ItemValue, ItemValues, SkillKind,
+    items::{self, Item},
+    personality::Personality,
+    world::regions::WorldRegion,
+    ItemValue, ItemValues, SkillKind,
 };
 use etheris_discord::twilight_model::id::{marker::UserMarker, Id};
 use etheris_util::generate_random_character_appearance;
@@ -11,11 +14,31 @@ use mongodb::Collection;
 use once_cell::sync::Lazy;
 use tokio_stream::StreamExt;
 
-use crate::{character_model::CharacterModel, common::*, EtherisDatabase};
+use crate::{
+    character_model::{CharacterModel, XpGain},
+    common::*,
+    trade::{exchange_offers, TradeOffer},
+    EtherisDatabase,
+};
+
+/// How many times a change is retried when someone else saves the character at the same time.
+const MAX_UPDATE_ATTEMPTS: usize = 5;
 
 static CACHE_ID: Lazy<Cache<ObjectId, CharacterModel>> = Lazy::new(|| Cache::new(1000));
 static CACHE_USER_ID: Lazy<Cache<String, CharacterModel>> = Lazy::new(|| Cache::new(1000));
 
+/// The character was saved by someone else since it was loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharacterConflict;
+
+impl Display for CharacterConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Character was changed since it was loaded")
+    }
+}
+
+impl std::error::Error for CharacterConflict {}
+
 #[allow(unused)]
 pub struct CharacterCommands {
     pub collection: Collection<CharacterModel>,
@@ -27,37 +50,209 @@ impl CharacterCommands {
         Self { collection, db }
     }
 
-    pub async fn save(&self, mut character: CharacterModel) -> anyhow::Result<()> {
-        let weighted_skills = {
-            let mut weight = 0.0;
-            for skill in character.skills.iter() {
-                if skill == &SkillKind::Debug {
-                    continue;
+    /// Saves the whole character, failing with [`CharacterConflict`] if it was changed since it was loaded.
+    /// Callers go through [`Self::update`], which retries on conflicts.
+    async fn save(&self, mut character: CharacterModel) -> anyhow::Result<()> {
+        update_power_level(&mut character);
+
+        CACHE_ID.remove(&character.id);
+        CACHE_USER_ID.remove(&character.user_id);
+
+        let query = query_by_version(character.id, character.version);
+        character.version += 1;
+        let result = self.collection.replace_one(query, &character, None).await?;
+        if result.matched_count == 0 {
+            return Err(CharacterConflict.into());
+        }
+
+        Ok(())
+    }
+
+    /// Loads the character straight from the database, changes it and saves it,
+    /// starting over if someone else saved it in the meantime.
+    pub async fn update(
+        &self,
+        id: ObjectId,
+        mut change: impl FnMut(&mut CharacterModel),
+    ) -> anyhow::Result<Option<CharacterModel>> {
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let Some(mut character) = self.collection.find_one(query_by_id(id), None).await? else {
+                return Ok(None);
+            };
+
+            change(&mut character);
+            match self.save(character.clone()).await {
+                Ok(()) => {
+                    character.version += 1;
+                    return Ok(Some(character));
+                }
+                Err(e) if e.is::<CharacterConflict>() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(CharacterConflict.into())
+    }
+
+    pub async fn add_orbs(&self, id: ObjectId, orbs: i64) -> anyhow::Result<()> {
+        self.update_fields(query_by_id(id), doc! { "$inc": { "orbs": orbs } })
+            .await?;
+        Ok(())
+    }
+
+    /// Takes the orbs only if the character has all of them.
+    /// Returns whether they were taken.
+    pub async fn take_orbs(&self, id: ObjectId, orbs: i64) -> anyhow::Result<bool> {
+        self.update_fields(
+            doc! { "_id": id, "orbs": { "$gte": orbs } },
+            doc! { "$inc": { "orbs": -orbs } },
+        )
+        .await
+    }
+
+    pub async fn add_xp(&self, id: ObjectId, xp: XpGain) -> anyhow::Result<()> {
+        self.update_fields(
+            query_by_id(id),
+            doc! {
+                "$inc": {
+                    "strength_xp": xp.strength as i64,
+                    "health_xp": xp.health as i64,
+                    "intelligence_xp": xp.intelligence as i64,
+                    "knowledge_xp": xp.knowledge as i64,
                 }
+            },
+        )
+        .await?;
+        Ok(())
+    }
 
-                let cost = skill.knowledge_cost();
-                weight += (cost as f64) / 0.2;
+    pub async fn add_item(
+        &self,
+        id: ObjectId,
+        item: Item,
+        quantity: usize,
+        values: Option<ItemValues>,
+    ) -> anyhow::Result<()> {
+        // Only items that stack by identifier can be changed in place
+        if !item.stackable || item.weapon.is_some() {
+            self.update(id, |character| {
+                character.add_item(item, quantity, values.clone())
+            })
+            .await?;
+            return Ok(());
+        }
+
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let stacked = self
+                .update_fields(
+                    doc! { "_id": id, "inventory.identifier": item.identifier },
+                    doc! { "$inc": { "inventory.$.quantity": quantity as i64 } },
+                )
+                .await?;
+            if stacked {
+                return Ok(());
             }
 
-            weight / 5.0
-        };
+            let inventory_item = InventoryItem {
+                identifier: item.identifier.to_string(),
+                quantity,
+                values: values.clone().unwrap_or(item.default_values.into()),
+            };
+            // Someone else may have added the item since it wasn't found, so this only pushes if it's still missing
+            let pushed = self
+                .update_fields(
+                    doc! { "_id": id, "inventory.identifier": { "$ne": item.identifier } },
+                    doc! { "$push": { "inventory": bson::to_bson(&inventory_item)? } },
+                )
+                .await?;
+            if pushed
+                || self
+                    .collection
+                    .count_documents(query_by_id(id), None)
+                    .await?
+                    == 0
+            {
+                return Ok(());
+            }
+        }
 
-        character.pl = calculate_power_level(
-            character.stats.vitality.into(),
-            character.stats.resistance.into(),
-            character.stats.ether.into(),
-            character.stats.strength_level,
-            character.stats.intelligence_level,
-            character.potential,
-            weighted_skills,
-        );
+        Err(CharacterConflict.into())
+    }
 
-        CACHE_ID.remove(&character.id);
-        CACHE_USER_ID.remove(&character.user_id);
-        self.collection
-            .replace_one(query_by_id(character.id), &character, None)
+    /// Applies an update operator to the character directly.
+    /// Returns whether a character matched the query.
+    async fn update_fields(&self, query: Document, mut update: Document) -> anyhow::Result<bool> {
+        // Whole saves loaded before this update must conflict with it
+        if !update.contains_key("$inc") {
+            update.insert("$inc", doc! {});
+        }
+        update.get_document_mut("$inc")?.insert("version", 1_i64);
+
+        let character = self
+            .collection
+            .find_one_and_update(query, update, None)
             .await?;
-        Ok(())
+        if let Some(character) = &character {
+            self.remove_from_cache(character);
+        }
+
+        Ok(character.is_some())
+    }
+
+    /// Exchanges both offers in a single transaction.
+    /// `first` and `second` are the characters as they were when the trade was agreed, and the
+    /// trade is aborted (returning `false`) if their orbs or inventories changed since then.
+    pub async fn commit_trade(
+        &self,
+        first: &CharacterModel,
+        first_offer: &TradeOffer,
+        second: &CharacterModel,
+        second_offer: &TradeOffer,
+    ) -> anyhow::Result<bool> {
+        let mut session = self.db.client.start_session(None).await?;
+        session.start_transaction(None).await?;
+
+        let current_first = self
+            .collection
+            .find_one_with_session(query_by_id(first.id), None, &mut session)
+            .await?
+            .filter(|c| is_unchanged_for_trade(c, first));
+        let current_second = self
+            .collection
+            .find_one_with_session(query_by_id(second.id), None, &mut session)
+            .await?
+            .filter(|c| is_unchanged_for_trade(c, second));
+
+        let (Some(mut first), Some(mut second)) = (current_first, current_second) else {
+            session.abort_transaction().await?;
+            return Ok(false);
+        };
+
+        if !exchange_offers(&mut first, first_offer, &mut second, second_offer) {
+            session.abort_transaction().await?;
+            return Ok(false);
+        }
+
+        for character in [&mut first, &mut second] {
+            update_power_level(character);
+            // A save that happened after the read above must abort the trade instead of being overwritten
+            let query = query_by_version(character.id, character.version);
+            character.version += 1;
+            let result = self
+                .collection
+                .replace_one_with_session(query, &*character, None, &mut session)
+                .await?;
+            if result.matched_count == 0 {
+                session.abort_transaction().await?;
+                return Ok(false);
+            }
+        }
+
+        session.commit_transaction().await?;
+
+        self.remove_from_cache(&first);
+        self.remove_from_cache(&second);
+        Ok(true)
     }
 
     pub fn remove_from_cache(&self, character: &CharacterModel) {
@@ -167,6 +362,27 @@ impl CharacterCommands {
         Ok(models)
     }
 
+    /// The best rated characters of the current ranked season.
+    pub async fn get_pvp_ranking(&self) -> anyhow::Result<Vec<CharacterModel>> {
+        let mut cursor = self
+            .collection
+            .find(
+                doc! { "alive": true, "pvp_rating.season": crate::ranked::current_season() },
+                mongodb::options::FindOptions::builder()
+                    .sort(doc! { "pvp_rating.rating": -1 })
+                    .limit(10)
+                    .build(),
+            )
+            .await?;
+
+        let mut models = vec![];
+        while cursor.advance().await? {
+            models.push(cursor.deserialize_current()?);
+        }
+
+        Ok(models)
+    }
+
     pub async fn register_character(
         &self,
         user_id: Id<UserMarker>,
@@ -198,3 +414,33 @@ impl CharacterCommands {
         Ok(character)
     }
 }
+
+fn is_unchanged_for_trade(current: &CharacterModel, expected: &CharacterModel) -> bool {
+    current.orbs == expected.orbs && current.inventory == expected.inventory
+}
+
+fn update_power_level(character: &mut CharacterModel) {
+    let weighted_skills = {
+        let mut weight = 0.0;
+        for skill in character.skills.iter() {
+            if skill == &SkillKind::Debug {
+                continue;
+            }
+
+            let cost = skill.knowledge_cost();
+            weight += (cost as f64) / 0.2;
+        }
+
+        weight / 5.0
+    };
+
+    character.pl = calculate_power_level(
+        character.stats.vitality.into(),
+        character.stats.resistance.into(),
+        character.stats.ether.into(),
+        character.stats.strength_level,
+        character.stats.intelligence_level,
+        character.potential,
+        weighted_skills,
+    );
+}
@@ -2,7 +2,6 @@ use std::{
     collections::HashSet,
     fmt::Display,
     hash::Hash,
-    mem::discriminant,
     ops::{Add, Sub},
 };
 
@@ -10,18 +9,20 @@ use chrono::Duration;
 use etheris_common::{clear_string, config, Attribute};
 use etheris_data::{
     appearance::CharacterAppearance,
+    equipment::EquipmentSlot,
+    find_skill_mastery,
     items::{self, Item},
     personality::Personality,
     weapon::WeaponKind,
     world::regions::WorldRegion,
-    BossKind, ItemValue, ItemValues, SkillKind,
+    BossKind, ItemValue, ItemValues, SkillKind, SkillMastery,
 };
 use etheris_discord::twilight_model::id::{marker::UserMarker, Id};
 use mongodb::bson::oid::ObjectId;
 use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
-use crate::common::*;
+use crate::{common::*, companion::Companion, ranked::PvpRating};
 
 fn _default_born_at() -> DatabaseDateTime {
     let now = chrono::Utc::now() - Duration::from_std(config::YEAR_LENGTH * 18).unwrap();
@@ -233,10 +234,78 @@ impl Display for MentalLevel {
     }
 }
 
+/// The armor and accessories worn by a character, stored by item identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct CharacterEquipment {
+    pub body: Option<String>,
+    pub legs: Option<String>,
+    pub ring: Option<String>,
+    pub amulet: Option<String>,
+}
+
+impl CharacterEquipment {
+    fn slot(&self, slot: EquipmentSlot) -> &Option<String> {
+        match slot {
+            EquipmentSlot::Body => &self.body,
+            EquipmentSlot::Legs => &self.legs,
+            EquipmentSlot::Ring => &self.ring,
+            EquipmentSlot::Amulet => &self.amulet,
+        }
+    }
+
+    fn slot_mut(&mut self, slot: EquipmentSlot) -> &mut Option<String> {
+        match slot {
+            EquipmentSlot::Body => &mut self.body,
+            EquipmentSlot::Legs => &mut self.legs,
+            EquipmentSlot::Ring => &mut self.ring,
+            EquipmentSlot::Amulet => &mut self.amulet,
+        }
+    }
+
+    pub fn get(&self, slot: EquipmentSlot) -> Option<Item> {
+        self.slot(slot)
+            .as_ref()
+            .and_then(|identifier| items::get_item(identifier))
+    }
+
+    /// Puts the item in its slot and returns what was there before.
+    pub fn equip(&mut self, item: Item) -> Option<Item> {
+        let slot = item.equipment_properties?.slot;
+        let previous = self.get(slot);
+        *self.slot_mut(slot) = Some(item.identifier.to_string());
+        previous
+    }
+
+    pub fn unequip(&mut self, slot: EquipmentSlot) -> Option<Item> {
+        let previous = self.get(slot);
+        *self.slot_mut(slot) = None;
+        previous
+    }
+
+    pub fn items(&self) -> Vec<Item> {
+        EquipmentSlot::LIST
+            .iter()
+            .filter_map(|slot| self.get(*slot))
+            .collect()
+    }
+}
+
+/// Experience gained at once in each attribute.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct XpGain {
+    pub strength: u32,
+    pub health: u32,
+    pub intelligence: u32,
+    pub knowledge: u32,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct CharacterModel {
     #[serde(rename = "_id")]
     pub id: ObjectId,
+    /// Bumped on every write, so saving an outdated copy of the character can be detected.
+    #[serde(default)]
+    pub version: i64,
     pub user_id: String,
     pub name: String,
     pub settings: CharacterSettings,
@@ -256,12 +325,22 @@ pub struct CharacterModel {
     pub skills: Vec<SkillKind>,
     pub learned_skills: Vec<SkillKind>,
     pub learnable_skills: Vec<SkillKind>,
+    #[serde(default)]
+    pub skill_mastery: Vec<SkillMastery>,
+    /// Identifiers of the skill combos the character already triggered.
+    #[serde(default)]
+    pub discovered_combos: Vec<String>,
 
     pub defeated_bosses: HashSet<BossKind>,
     pub visited_regions: HashSet<WorldRegion>,
 
     pub region: WorldRegion,
     pub weapon: Option<WeaponKind>,
+    /// Upgrade level, durability and enchantment of the equipped weapon.
+    #[serde(default)]
+    pub weapon_values: ItemValues,
+    #[serde(default)]
+    pub equipment: CharacterEquipment,
 
     pub action_points: u32,
     pub max_action_points: u32,
@@ -294,6 +373,10 @@ pub struct CharacterModel {
     pub stats: CharacterStats,
     #[serde(default = "_default_appearance")]
     pub appearance: CharacterAppearance,
+    #[serde(default)]
+    pub pvp_rating: PvpRating,
+    #[serde(default)]
+    pub companions: Vec<Companion>,
 }
 
 impl CharacterModel {
@@ -313,6 +396,7 @@ impl CharacterModel {
 
         Self {
             id: ObjectId::new(),
+            version: 0,
             name,
             user_id: user_id.get().to_string(),
             settings: CharacterSettings {
@@ -336,11 +420,15 @@ impl CharacterModel {
             learned_skills: skills.clone(),
             learnable_skills: vec![SkillKind::ImbuedPunch],
             skills,
+            skill_mastery: vec![],
+            discovered_combos: vec![],
             personalities,
 
             visited_regions: HashSet::new(),
             region: WorldRegion::Greenagis,
             weapon: None,
+            weapon_values: ItemValues::default(),
+            equipment: CharacterEquipment::default(),
             defeated_bosses: HashSet::new(),
 
             karma: 0,
@@ -364,6 +452,8 @@ impl CharacterModel {
 
             stats: _default_stats(),
             appearance,
+            pvp_rating: PvpRating::default(),
+            companions: vec![],
         }
     }
 
@@ -377,10 +467,30 @@ impl CharacterModel {
         if self.user_id == config::BOT_ID {
             None
         } else {
-            Some(etheris_util::character_image::generate_character_image_buffer(&self.appearance))
+            let equipment = self
+                .equipment
+                .items()
+                .into_iter()
+                .filter_map(|item| item.equipment_properties.map(From::from))
+                .collect::<Vec<_>>();
+
+            Some(
+                etheris_util::character_image::generate_equipped_character_image_buffer(
+                    &self.appearance,
+                    &equipment,
+                ),
+            )
         }
     }
 
+    pub fn active_companion(&self) -> Option<&Companion> {
+        self.companions.iter().find(|c| c.active)
+    }
+
+    pub fn active_companion_mut(&mut self) -> Option<&mut Companion> {
+        self.companions.iter_mut().find(|c| c.active)
+    }
+
     pub fn has_flag(&self, flag: CharacterFlag) -> bool {
         self.flags.contains(&flag)
     }
@@ -464,36 +574,22 @@ impl CharacterModel {
     }
 
     pub fn already_knows_skill(&self, skill: SkillKind) -> bool {
-        let skill = discriminant(&skill);
-
-        self.learned_skills.iter().any(|s| skill == discriminant(s))
-            || self.skills.iter().any(|s| skill == discriminant(s))
-            || self
-                .learnable_skills
-                .iter()
-                .any(|s| skill == discriminant(s))
+        self.learned_skills.contains(&skill)
+            || self.skills.contains(&skill)
+            || self.learnable_skills.contains(&skill)
     }
 
     pub fn learn_skill(&mut self, skill: SkillKind) {
-        if self
-            .learned_skills
-            .iter()
-            .any(|s| discriminant(s) == discriminant(&skill))
-        {
+        if self.learned_skills.contains(&skill) {
             return;
         }
 
-        self.learnable_skills
-            .retain(|s| discriminant(s) != discriminant(&skill));
+        self.learnable_skills.retain(|s| *s != skill);
         self.learned_skills.push(skill);
     }
 
     pub fn equip_skill(&mut self, skill: SkillKind) {
-        if self
-            .skills
-            .iter()
-            .any(|s| discriminant(s) == discriminant(&skill))
-        {
+        if self.skills.contains(&skill) {
             return;
         }
 
@@ -511,21 +607,19 @@ impl CharacterModel {
     }
 
     pub fn aknowledge_skill(&mut self, skill: SkillKind) {
-        if self
-            .learned_skills
-            .iter()
-            .any(|s| discriminant(s) == discriminant(&skill))
-            || self
-                .learnable_skills
-                .iter()
-                .any(|s| discriminant(s) == discriminant(&skill))
-        {
+        if self.learned_skills.contains(&skill) || self.learnable_skills.contains(&skill) {
             return;
         }
 
         self.learnable_skills.push(skill);
     }
 
+    pub fn get_skill_mastery(&self, skill: &SkillKind) -> SkillMastery {
+        find_skill_mastery(&self.skill_mastery, skill)
+            .cloned()
+            .unwrap_or_else(|| SkillMastery::new(skill.clone()))
+    }
+
     pub fn add_orbs(&mut self, orbs: i64) {
         self.orbs += orbs;
     }
@@ -545,16 +639,25 @@ impl CharacterModel {
         }
     }
 
+    /// The inventory entry of the item, preferring weapons that were never upgraded or enchanted.
     pub fn get_inventory_item(&self, item: &Item) -> Option<&InventoryItem> {
-        self.inventory
-            .iter()
-            .find(|it| it.identifier == item.identifier)
+        internal_find_item(&self.inventory, item).map(|index| &self.inventory[index])
     }
 
     pub fn get_inventory_item_mut(&mut self, item: &Item) -> Option<&mut InventoryItem> {
+        internal_find_item(&self.inventory, item).map(|index| &mut self.inventory[index])
+    }
+
+    /// The inventory entry of the item with exactly these values.
+    /// Only weapons are told apart by their values, other items are found by identifier.
+    pub fn get_inventory_item_with_values(
+        &self,
+        item: &Item,
+        values: &ItemValues,
+    ) -> Option<&InventoryItem> {
         self.inventory
-            .iter_mut()
-            .find(|it| it.identifier == item.identifier)
+            .iter()
+            .find(|it| is_same_instance(it, item, values))
     }
 
     pub fn get_battle_inventory_item(&self, item: &Item) -> Option<&InventoryItem> {
@@ -597,12 +700,22 @@ impl CharacterModel {
         }
     }
 
+    pub fn has_item_with_values(&self, item: &Item, values: &ItemValues, quantity: usize) -> bool {
+        self.get_inventory_item_with_values(item, values)
+            .is_some_and(|item| item.quantity >= quantity)
+    }
+
     pub fn add_item(&mut self, item: Item, quantity: usize, values: Option<ItemValues>) {
         internal_add_item(&mut self.inventory, item, quantity, values);
     }
 
     pub fn remove_item(&mut self, item: Item, quantity: usize) {
-        internal_remove_item(&mut self.inventory, item, quantity);
+        internal_remove_item(&mut self.inventory, item, quantity, None);
+    }
+
+    /// Removes the copies of the item with exactly these values, like a specific upgraded weapon.
+    pub fn remove_item_with_values(&mut self, item: Item, values: &ItemValues, quantity: usize) {
+        internal_remove_item(&mut self.inventory, item, quantity, Some(values));
     }
 
     pub fn add_battle_item(&mut self, item: Item, quantity: usize, values: Option<ItemValues>) {
@@ -610,7 +723,7 @@ impl CharacterModel {
     }
 
     pub fn remove_battle_item(&mut self, item: Item, quantity: usize) {
-        internal_remove_item(&mut self.battle_inventory, item, quantity);
+        internal_remove_item(&mut self.battle_inventory, item, quantity, None);
     }
 }
 
@@ -619,6 +732,9 @@ pub(crate) fn internal_get_inventory_item_by_name<'a>(
     item_name: &str,
 ) -> Option<&'a InventoryItem> {
     let item_name = clear_string(item_name);
+    // Weapons are named with their upgrades, so `Espada` is the plain sword even if a `Espada +2` comes first.
+    // Only if there's no item with exactly that name one named differently is picked.
+    let mut same_display_name = None;
     for inventory_item in inventory.iter() {
         let alternative_names = inventory_item.values.alternative_names();
         if !alternative_names.is_empty()
@@ -631,13 +747,41 @@ pub(crate) fn internal_get_inventory_item_by_name<'a>(
         }
 
         if let Some(item) = items::get_item(&inventory_item.identifier) {
-            if clear_string(item.display_name) == item_name {
+            let full_name = inventory_item.values.name_with_upgrades(item.display_name);
+            if clear_string(&full_name) == item_name {
                 return Some(inventory_item);
             }
+
+            if same_display_name.is_none() && clear_string(item.display_name) == item_name {
+                same_display_name = Some(inventory_item);
+            }
         }
     }
 
-    None
+    same_display_name
+}
+
+/// The first entry of the item. When there are many copies of a weapon, a plain one is
+/// preferred so that nothing that isn't about a specific copy takes an upgraded one.
+fn internal_find_item(inventory: &[InventoryItem], item: &Item) -> Option<usize> {
+    let is_plain =
+        |i: &InventoryItem| i.values.upgrade_level() == 0 && i.values.enchantment().is_none();
+
+    inventory
+        .iter()
+        .position(|i| i.identifier == item.identifier && is_plain(i))
+        .or_else(|| {
+            inventory
+                .iter()
+                .position(|i| i.identifier == item.identifier)
+        })
+}
+
+/// Whether the inventory entry is the item with these values.
+/// Only weapons are told apart by their values, other items are found by identifier.
+fn is_same_instance(inventory_item: &InventoryItem, item: &Item, values: &ItemValues) -> bool {
+    inventory_item.identifier == item.identifier
+        && (item.weapon.is_none() || inventory_item.values == *values)
 }
 
 pub(crate) fn internal_add_item(
@@ -646,42 +790,50 @@ pub(crate) fn internal_add_item(
     quantity: usize,
     values: Option<ItemValues>,
 ) {
+    let values = values.unwrap_or(item.default_values.into());
     if !item.stackable {
         for _ in 0..quantity {
             inventory.push(InventoryItem {
                 identifier: item.identifier.to_string(),
                 quantity: 1,
-                values: values.clone().unwrap_or(item.default_values.into()),
+                values: values.clone(),
             });
         }
 
         return;
     }
 
+    // Weapons keep their upgrades and wear, so they only stack with identical ones
     if let Some(inv_item) = inventory
         .iter_mut()
-        .find(|i| i.identifier == item.identifier)
+        .find(|i| is_same_instance(i, &item, &values))
     {
         inv_item.quantity += quantity;
     } else {
         inventory.push(InventoryItem {
             identifier: item.identifier.to_string(),
             quantity,
-            values: values.unwrap_or(item.default_values.into()),
+            values,
         });
     }
 }
 
+/// Removes from the entry found by `internal_find_item`, or from the one with exactly `values` if given.
 pub(crate) fn internal_remove_item(
     inventory: &mut Vec<InventoryItem>,
     item: Item,
     quantity: usize,
+    values: Option<&ItemValues>,
 ) {
-    if let Some((index, inv_item)) = inventory
-        .iter_mut()
-        .enumerate()
-        .find(|(_, i)| i.identifier == item.identifier)
-    {
+    let index = match values {
+        Some(values) => inventory
+            .iter()
+            .position(|i| is_same_instance(i, &item, values)),
+        None => internal_find_item(inventory, &item),
+    };
+
+    if let Some(index) = index {
+        let inv_item = &mut inventory[index];
         inv_item.quantity = inv_item.quantity.saturating_sub(quantity);
 
         if inv_item.quantity == 0 {
@@ -689,3 +841,91 @@ pub(crate) fn internal_remove_item(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use etheris_data::personality::Personality;
+
+    use super::*;
+
+    fn create_character() -> CharacterModel {
+        CharacterModel::new(
+            Id::new(1),
+            "Personagem".into(),
+            vec![Personality::Calm],
+            vec![],
+            Default::default(),
+        )
+    }
+
+    fn upgraded(level: u32) -> ItemValues {
+        let mut values: ItemValues = items::tool::KATANA.default_values.into();
+        values.set(ItemValue::UpgradeLevel(level));
+        values
+    }
+
+    #[test]
+    fn bought_weapons_never_stack_with_upgraded_ones() {
+        let katana = items::tool::KATANA;
+        let mut character = create_character();
+        character.add_item(katana, 1, Some(upgraded(3)));
+
+        character.add_item(katana, 1, None);
+
+        let plain: ItemValues = katana.default_values.into();
+        assert_eq!(character.inventory.len(), 2);
+        assert!(character.has_item_with_values(&katana, &upgraded(3), 1));
+        assert!(!character.has_item_with_values(&katana, &upgraded(3), 2));
+        assert!(character.has_item_with_values(&katana, &plain, 1));
+
+        // Taking a katana without saying which one never takes the upgraded one
+        character.remove_item(katana, 1);
+        assert_eq!(character.inventory.len(), 1);
+        assert!(character.has_item_with_values(&katana, &upgraded(3), 1));
+    }
+
+    #[test]
+    fn different_custom_skills_are_different_skills() {
+        let ether_palm = SkillKind::Custom("ether_palm".into());
+        let venomous_kick = SkillKind::Custom("venomous_kick".into());
+        let mut character = create_character();
+        character.learnable_skills.clear();
+
+        character.aknowledge_skill(ether_palm.clone());
+        character.aknowledge_skill(venomous_kick.clone());
+        assert_eq!(character.learnable_skills.len(), 2);
+
+        character.learn_skill(ether_palm.clone());
+        assert_eq!(character.learnable_skills, vec![venomous_kick.clone()]);
+
+        character.learn_skill(venomous_kick.clone());
+        character.equip_skill(ether_palm.clone());
+        character.equip_skill(venomous_kick.clone());
+        assert!(character.skills.contains(&ether_palm));
+        assert!(character.skills.contains(&venomous_kick));
+        assert!(character.learnable_skills.is_empty());
+    }
+
+    #[test]
+    fn weapons_are_found_and_removed_by_their_upgrades() {
+        let katana = items::tool::KATANA;
+        let mut character = create_character();
+        character.add_item(katana, 1, Some(upgraded(2)));
+        character.add_item(katana, 1, None);
+
+        let plain = character.get_inventory_item_by_name("katana").unwrap();
+        assert_eq!(plain.values.upgrade_level(), 0);
+        let upgraded_katana = character.get_inventory_item_by_name("Katana +2").unwrap();
+        assert_eq!(upgraded_katana.values, upgraded(2));
+
+        character.remove_item_with_values(katana, &ItemValues::from(katana.default_values), 1);
+        assert_eq!(character.inventory.len(), 1);
+        assert_eq!(character.inventory[0].values, upgraded(2));
+        assert_eq!(
+            character
+                .get_inventory_item_by_name("katana")
+                .map(|i| &i.values),
+            Some(&upgraded(2))
+        );
+    }
+}
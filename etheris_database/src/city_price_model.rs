@@ -0,0 +1,215 @@
+use std::{collections::HashMap, fmt::Display};
+
+use bson::{doc, oid::ObjectId};
+use etheris_data::{world::regions::WorldRegion, ShopItem};
+use mongodb::{options::UpdateOptions, Collection};
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::{common::*, EtherisDatabase};
+
+/// How much each unit bought from a city raises its prices, and each unit sold lowers them.
+pub const DEMAND_IMPACT_PER_UNIT: f64 = 0.005;
+pub const MIN_DEMAND: f64 = 0.5;
+pub const MAX_DEMAND: f64 = 2.0;
+/// In how many hours half of the price change is gone.
+pub const DEMAND_RECOVERY_HALF_LIFE_HOURS: f64 = 12.0;
+
+/// The demand of an item in a city. Prices are multiplied by it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CityPriceModel {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub region: WorldRegion,
+    pub identifier: String,
+    /// Above `1.0` the city wants the item, below it the city has too much of it.
+    pub demand: f64,
+    pub updated_at: DatabaseDateTime,
+}
+
+impl CityPriceModel {
+    pub fn new(region: WorldRegion, identifier: String) -> Self {
+        Self {
+            id: ObjectId::new(),
+            region,
+            identifier,
+            demand: 1.0,
+            updated_at: DatabaseDateTime::zeroed(),
+        }
+    }
+
+    /// The demand after recovering towards normal since the last trade.
+    pub fn current_demand(&self, now: chrono::DateTime<chrono::Utc>) -> f64 {
+        let hours = (now - self.updated_at.0).num_seconds().max(0) as f64 / 3600.0;
+        let remaining = 0.5f64.powf(hours / DEMAND_RECOVERY_HALF_LIFE_HOURS);
+
+        1.0 + (self.demand - 1.0) * remaining
+    }
+
+    /// Moves the demand after players bought (positive) or sold (negative) units of the item.
+    /// `CityPriceCommands::record_trade` does the same in the database.
+    pub fn record_trade(&mut self, units_bought: i64, now: chrono::DateTime<chrono::Utc>) {
+        self.demand =
+            (self.current_demand(now) * trade_impact(units_bought)).clamp(MIN_DEMAND, MAX_DEMAND);
+        self.updated_at = DatabaseDateTime(now);
+    }
+}
+
+/// How much a trade multiplies the demand of the item.
+fn trade_impact(units_bought: i64) -> f64 {
+    (1.0 + DEMAND_IMPACT_PER_UNIT * units_bought.signum() as f64)
+        .powi(units_bought.unsigned_abs().min(i32::MAX as u64) as i32)
+}
+
+/// How far a price is from its normal value, in percentage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PriceTrend(pub i32);
+
+impl PriceTrend {
+    pub fn from_demand(demand: f64) -> Self {
+        Self(((demand - 1.0) * 100.0).round() as i32)
+    }
+}
+
+impl Display for PriceTrend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => f.write_str("➖"),
+            p if p > 0 => write!(f, "📈 +{p}%"),
+            p => write!(f, "📉 {p}%"),
+        }
+    }
+}
+
+pub fn price_with_demand(price: i64, demand: f64) -> i64 {
+    ((price as f64 * demand).round() as i64).max(1)
+}
+
+/// The shop item with its prices moved by the demand.
+pub fn shop_item_with_demand(shop_item: &ShopItem, demand: f64) -> ShopItem {
+    ShopItem {
+        price: price_with_demand(shop_item.price, demand),
+        sellable_price: shop_item
+            .sellable_price
+            .map(|price| price_with_demand(price, demand)),
+        ..shop_item.clone()
+    }
+}
+
+#[allow(unused)]
+pub struct CityPriceCommands {
+    collection: Collection<CityPriceModel>,
+    db: EtherisDatabase,
+}
+
+impl CityPriceCommands {
+    pub const fn new(collection: Collection<CityPriceModel>, db: EtherisDatabase) -> Self {
+        Self { collection, db }
+    }
+
+    /// The current demand of every item already traded in the city, by item identifier.
+    /// Items that are missing have a demand of `1.0`.
+    pub async fn get_demands(&self, region: WorldRegion) -> anyhow::Result<HashMap<String, f64>> {
+        let now = DatabaseDateTime::now().0;
+        let cursor = self
+            .collection
+            .find(doc! { "region": bson::to_bson(&region)? }, None)
+            .await?;
+        let prices = cursor.collect::<Result<Vec<_>, _>>().await?;
+
+        Ok(prices
+            .into_iter()
+            .map(|price| {
+                let demand = price.current_demand(now);
+                (price.identifier, demand)
+            })
+            .collect())
+    }
+
+    pub async fn get_demand(&self, region: WorldRegion, identifier: &str) -> anyhow::Result<f64> {
+        let price = self
+            .collection
+            .find_one(
+                doc! { "region": bson::to_bson(&region)?, "identifier": identifier },
+                None,
+            )
+            .await?;
+
+        Ok(price
+            .map(|price| price.current_demand(DatabaseDateTime::now().0))
+            .unwrap_or(1.0))
+    }
+
+    /// Saves that players bought (positive) or sold (negative) units of the item in the city.
+    /// The demand is recovered and moved in a single update, so simultaneous trades are never lost.
+    pub async fn record_trade(
+        &self,
+        region: WorldRegion,
+        identifier: &str,
+        units_bought: i64,
+    ) -> anyhow::Result<()> {
+        let query = doc! { "region": bson::to_bson(&region)?, "identifier": identifier };
+
+        // Same as `CityPriceModel::current_demand`, computed by the database with its own clock
+        let hours = doc! { "$max": [0.0, { "$divide": [
+            { "$subtract": ["$$NOW", { "$ifNull": ["$updated_at", "$$NOW"] }] },
+            3_600_000.0,
+        ] }] };
+        let remaining =
+            doc! { "$pow": [0.5, { "$divide": [hours, DEMAND_RECOVERY_HALF_LIFE_HOURS] }] };
+        let current_demand = doc! { "$add": [1.0, { "$multiply": [
+            { "$subtract": [{ "$ifNull": ["$demand", 1.0] }, 1.0] },
+            remaining,
+        ] }] };
+        let demand = doc! { "$min": [MAX_DEMAND, { "$max": [MIN_DEMAND, {
+            "$multiply": [current_demand, trade_impact(units_bought)]
+        }] }] };
+
+        let update = vec![doc! { "$set": { "demand": demand, "updated_at": "$$NOW" } }];
+        let options = UpdateOptions::builder().upsert(true).build();
+
+        // Two first trades of an item may race to insert it, then the second one updates it
+        match self
+            .collection
+            .update_one(query.clone(), update.clone(), options.clone())
+            .await
+        {
+            Err(e) if is_duplicate_key(&e) => {
+                self.collection.update_one(query, update, options).await?;
+            }
+            result => {
+                result?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demand_moves_with_trades_and_recovers() {
+        let now = chrono::Utc::now();
+        let mut price = CityPriceModel::new(WorldRegion::Metrolis, "stone".into());
+
+        price.record_trade(-100, now);
+        assert!(price.demand < 0.7);
+        assert_eq!(PriceTrend::from_demand(price.demand).0, -39);
+
+        let later = now + chrono::Duration::try_hours(12).unwrap();
+        let recovered = price.current_demand(later);
+        assert!((recovered - (1.0 + (price.demand - 1.0) / 2.0)).abs() < 0.001);
+
+        price.record_trade(100_000, later);
+        assert_eq!(price.demand, MAX_DEMAND);
+    }
+
+    #[test]
+    fn prices_never_drop_to_zero() {
+        assert_eq!(price_with_demand(100, 1.25), 125);
+        assert_eq!(price_with_demand(1, MIN_DEMAND), 1);
+    }
+}
@@ -1,6 +1,7 @@
 use bson::{doc, oid::ObjectId, Document};
 use chrono::TimeZone;
 use etheris_data::ItemValues;
+use mongodb::error::{ErrorKind, WriteFailure};
 use serde::{Deserialize, Serialize};
 
 pub fn query_by_id(id: ObjectId) -> Document {
@@ -9,6 +10,24 @@ pub fn query_by_id(id: ObjectId) -> Document {
     }
 }
 
+/// Matches the document only if it's still in this version.
+/// Documents saved before versioning existed don't have the field and are in version 0.
+pub fn query_by_version(id: ObjectId, version: i64) -> Document {
+    if version == 0 {
+        doc! { "_id": id, "version": { "$in": [0_i64, bson::Bson::Null] } }
+    } else {
+        doc! { "_id": id, "version": version }
+    }
+}
+
+/// Checks if the write failed because it broke a unique index.
+pub fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    matches!(
+        error.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(e)) if e.code == 11000
+    )
+}
+
 pub fn _default_now() -> DatabaseDateTime {
     DatabaseDateTime(chrono::Utc::now())
 }
@@ -0,0 +1,80 @@
+use etheris_data::SkillKind;
+use serde::{Deserialize, Serialize};
+
+use crate::common::DatabaseDateTime;
+
+/// How many companions a character can keep at the same time.
+pub const MAX_COMPANIONS: usize = 3;
+pub const MAX_COMPANION_LEVEL: u32 = 20;
+
+/// An animal tamed by a character after defeating it. Only the active one joins the battles of the character.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Companion {
+    /// The identifier of the enemy the companion was tamed from.
+    pub enemy: String,
+    pub name: String,
+    pub level: u32,
+    pub xp: u32,
+    pub skills: Vec<SkillKind>,
+    pub active: bool,
+    pub tamed_at: DatabaseDateTime,
+}
+
+impl Companion {
+    pub fn new(enemy: impl ToString, name: impl ToString, skills: Vec<SkillKind>) -> Self {
+        Self {
+            enemy: enemy.to_string(),
+            name: name.to_string(),
+            level: 1,
+            xp: 0,
+            skills,
+            active: false,
+            tamed_at: DatabaseDateTime::now(),
+        }
+    }
+
+    pub fn required_xp(&self) -> u32 {
+        50 + self.level * 25
+    }
+
+    /// Multiplies the base stats of the enemy the companion came from.
+    pub fn stats_multiplier(&self) -> f64 {
+        1.0 + (self.level.saturating_sub(1) as f64 * 0.08)
+    }
+
+    /// Adds XP to the companion and returns how many levels it gained.
+    pub fn add_xp(&mut self, xp: u32) -> u32 {
+        let mut levels = 0;
+        self.xp += xp;
+
+        while self.level < MAX_COMPANION_LEVEL && self.xp >= self.required_xp() {
+            self.xp -= self.required_xp();
+            self.level += 1;
+            levels += 1;
+        }
+
+        if self.level == MAX_COMPANION_LEVEL {
+            self.xp = 0;
+        }
+
+        levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn companions_level_up_until_the_max_level() {
+        let mut companion = Companion::new("giant_rat", "Rato Gigante", vec![]);
+
+        assert_eq!(companion.add_xp(80), 1);
+        assert_eq!(companion.level, 2);
+        assert_eq!(companion.xp, 5);
+
+        companion.add_xp(u32::MAX / 2);
+        assert_eq!(companion.level, MAX_COMPANION_LEVEL);
+        assert_eq!(companion.xp, 0);
+    }
+}
@@ -1,17 +1,31 @@
+pub mod battle_model;
 pub mod building;
 pub mod character_commands;
 pub mod character_model;
+pub mod city_price_model;
 pub mod common;
+pub mod companion;
 pub mod cooldown;
+pub mod market_model;
+pub mod party_model;
+pub mod ranked;
+pub mod tournament_model;
+pub mod trade;
 
 use std::sync::Arc;
 
+use battle_model::{BattleCommands, BattleModel};
 use bson::doc;
+use building::{BuildingCommands, BuildingModel};
 use character_commands::CharacterCommands;
 
 use character_model::CharacterModel;
+use city_price_model::{CityPriceCommands, CityPriceModel};
 use cooldown::{CooldownCommands, CooldownModel};
-use mongodb::{Client, Collection, Database, IndexModel};
+use market_model::{MarketCommands, MarketListingModel};
+use mongodb::{options::IndexOptions, Client, Collection, Database, IndexModel};
+use party_model::{PartyCommands, PartyModel};
+use tournament_model::{TournamentCommands, TournamentModel};
 
 pub use mongodb::bson;
 pub use mongodb::error::Error as MongoDBError;
@@ -63,6 +77,101 @@ impl EtherisDatabase {
             )
             .await
             .unwrap();
+
+        // BATTLES INDEXES
+        let battles: Collection<BattleModel> = self.db().collection("battles");
+        battles
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "participants.character_id": 1, "ended_at": -1 })
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // PARTIES INDEXES
+        let parties: Collection<PartyModel> = self.db().collection("parties");
+        parties
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "members": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // TOURNAMENTS INDEXES
+        let tournaments: Collection<TournamentModel> = self.db().collection("tournaments");
+        tournaments
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "guild_id": 1, "state": 1 })
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // BUILDINGS INDEXES
+        let buildings: Collection<BuildingModel> = self.db().collection("buildings");
+        buildings
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "region": 1, "name_id": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+        buildings
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "region": 1, "character_owner": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // CITY PRICES INDEXES
+        let city_prices: Collection<CityPriceModel> = self.db().collection("city_prices");
+        city_prices
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "region": 1, "identifier": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // MARKET INDEXES
+        let market: Collection<MarketListingModel> = self.db().collection("market");
+        market
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "settled": 1, "ends_at": 1 })
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+        market
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! { "code": 1 })
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
     }
 
     pub fn db(&self) -> Database {
@@ -77,6 +186,36 @@ impl EtherisDatabase {
         CharacterCommands::new(collection, self.clone())
     }
 
+    pub fn battles(&self) -> BattleCommands {
+        let collection = self.db().collection("battles");
+        BattleCommands::new(collection, self.clone())
+    }
+
+    pub fn parties(&self) -> PartyCommands {
+        let collection = self.db().collection("parties");
+        PartyCommands::new(collection, self.clone())
+    }
+
+    pub fn tournaments(&self) -> TournamentCommands {
+        let collection = self.db().collection("tournaments");
+        TournamentCommands::new(collection, self.clone())
+    }
+
+    pub fn buildings(&self) -> BuildingCommands {
+        let collection = self.db().collection("buildings");
+        BuildingCommands::new(collection, self.clone())
+    }
+
+    pub fn city_prices(&self) -> CityPriceCommands {
+        let collection = self.db().collection("city_prices");
+        CityPriceCommands::new(collection, self.clone())
+    }
+
+    pub fn market(&self) -> MarketCommands {
+        let collection = self.db().collection("market");
+        MarketCommands::new(collection, self.clone())
+    }
+
     pub fn cooldowns(&self) -> CooldownCommands {
         let collection = self.db().collection("cooldowns");
         CooldownCommands::new(collection, self.clone())
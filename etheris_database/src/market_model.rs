@@ -0,0 +1,545 @@
+use bson::{doc, oid::ObjectId};
+use mongodb::{
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+    Collection,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::{common::*, EtherisDatabase};
+
+/// The percentage of the asked price the market keeps as a fee when an item is listed.
+pub const MARKET_FEE_PERCENTAGE: i64 = 5;
+pub const MARKET_MIN_FEE: i64 = 10;
+/// How long an item stays for sale before going back to the seller.
+pub const MARKET_LISTING_DAYS: i64 = 7;
+/// A new bid must be at least this percentage higher than the current one.
+pub const MARKET_MIN_BID_INCREMENT_PERCENTAGE: i64 = 5;
+
+const CODE_CHARACTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+/// How many codes are tried before giving up on opening a listing.
+const MAX_CODE_ATTEMPTS: usize = 5;
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..6)
+        .map(|_| CODE_CHARACTERS[rng.gen_range(0..CODE_CHARACTERS.len())] as char)
+        .collect()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ListingKind {
+    FixedPrice,
+    Auction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MarketBid {
+    pub character_id: ObjectId,
+    pub user_id: String,
+    pub amount: i64,
+}
+
+/// Something that has to be given when an expired listing is settled.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ListingPayout {
+    /// The listed item, to the winner of the auction or back to the seller.
+    Item {
+        character_id: ObjectId,
+    },
+    Orbs {
+        character_id: ObjectId,
+        amount: i64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MarketListingModel {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    /// A short code players use to refer to the listing.
+    pub code: String,
+    pub seller_id: ObjectId,
+    pub seller_user_id: String,
+    pub seller_name: String,
+    /// The item held in escrow while the listing is open.
+    pub item: InventoryItem,
+    pub kind: ListingKind,
+    /// The price of the item, or the starting bid of an auction.
+    pub price: i64,
+    pub highest_bid: Option<MarketBid>,
+    pub settled: bool,
+    /// What still has to be given after the listing was settled. Each payout is removed as it's given.
+    #[serde(default)]
+    pub unpaid: Vec<ListingPayout>,
+    #[serde(default = "_default_now")]
+    pub created_at: DatabaseDateTime,
+    pub ends_at: DatabaseDateTime,
+}
+
+impl MarketListingModel {
+    pub fn new(
+        seller_id: ObjectId,
+        seller_user_id: String,
+        seller_name: String,
+        item: InventoryItem,
+        kind: ListingKind,
+        price: i64,
+        duration: chrono::Duration,
+    ) -> Self {
+        Self {
+            id: ObjectId::new(),
+            code: generate_code(),
+            seller_id,
+            seller_user_id,
+            seller_name,
+            item,
+            kind,
+            price,
+            highest_bid: None,
+            settled: false,
+            unpaid: vec![],
+            created_at: _default_now(),
+            ends_at: DatabaseDateTime(DatabaseDateTime::now().0 + duration),
+        }
+    }
+
+    /// The orbs charged to list an item with this price.
+    pub fn fee(price: i64) -> i64 {
+        (price.saturating_mul(MARKET_FEE_PERCENTAGE) / 100).max(MARKET_MIN_FEE)
+    }
+
+    /// The lowest amount a new bid can have.
+    pub fn min_next_bid(&self) -> i64 {
+        match &self.highest_bid {
+            Some(bid) => {
+                bid.amount + (bid.amount * MARKET_MIN_BID_INCREMENT_PERCENTAGE / 100).max(1)
+            }
+            None => self.price,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.ends_at.0 <= DatabaseDateTime::now().0
+    }
+
+    /// What has to be given when the listing expires. If the item doesn't exist anymore,
+    /// the highest bidder gets the orbs back instead.
+    pub fn settlement_payouts(&self, item_exists: bool) -> Vec<ListingPayout> {
+        match (&self.highest_bid, item_exists) {
+            (Some(bid), true) => vec![
+                ListingPayout::Item {
+                    character_id: bid.character_id,
+                },
+                ListingPayout::Orbs {
+                    character_id: self.seller_id,
+                    amount: bid.amount,
+                },
+            ],
+            (Some(bid), false) => vec![ListingPayout::Orbs {
+                character_id: bid.character_id,
+                amount: bid.amount,
+            }],
+            (None, true) => vec![ListingPayout::Item {
+                character_id: self.seller_id,
+            }],
+            (None, false) => vec![],
+        }
+    }
+}
+
+#[allow(unused)]
+pub struct MarketCommands {
+    collection: Collection<MarketListingModel>,
+    db: EtherisDatabase,
+}
+
+impl MarketCommands {
+    pub const fn new(collection: Collection<MarketListingModel>, db: EtherisDatabase) -> Self {
+        Self { collection, db }
+    }
+
+    /// Takes the fee and the listed item from the seller and opens the listing in a single transaction.
+    /// Returns `false`, listing nothing, if the seller doesn't have them anymore.
+    /// If the code of the listing is already used by another one, it gets a new code.
+    pub async fn create_listing(
+        &self,
+        listing: &mut MarketListingModel,
+        fee: i64,
+    ) -> anyhow::Result<bool> {
+        for _ in 1..MAX_CODE_ATTEMPTS {
+            match self.try_create_listing(listing, fee).await {
+                Err(e)
+                    if e.downcast_ref::<mongodb::error::Error>()
+                        .is_some_and(is_duplicate_key) =>
+                {
+                    listing.code = generate_code();
+                }
+                result => return result,
+            }
+        }
+
+        self.try_create_listing(listing, fee).await
+    }
+
+    async fn try_create_listing(
+        &self,
+        listing: &MarketListingModel,
+        fee: i64,
+    ) -> anyhow::Result<bool> {
+        let Some(item) = etheris_data::items::get_item(&listing.item.identifier) else {
+            return Ok(false);
+        };
+
+        let characters = self.db.characters();
+        let mut session = self.db.client.start_session(None).await?;
+        session.start_transaction(None).await?;
+
+        let Some(mut seller) = characters
+            .collection
+            .find_one_with_session(query_by_id(listing.seller_id), None, &mut session)
+            .await?
+            .filter(|c| {
+                c.orbs >= fee
+                    && c.has_item_with_values(&item, &listing.item.values, listing.item.quantity)
+            })
+        else {
+            session.abort_transaction().await?;
+            return Ok(false);
+        };
+
+        seller.remove_orbs(fee);
+        seller.remove_item_with_values(item, &listing.item.values, listing.item.quantity);
+
+        // A save that happened after the read above must abort the listing instead of being overwritten
+        let query = query_by_version(seller.id, seller.version);
+        seller.version += 1;
+        let result = characters
+            .collection
+            .replace_one_with_session(query, &seller, None, &mut session)
+            .await?;
+        if result.matched_count == 0 {
+            session.abort_transaction().await?;
+            return Ok(false);
+        }
+
+        // Fails if the code is already used, the caller retries with another one
+        if let Err(e) = self
+            .collection
+            .insert_one_with_session(listing, None, &mut session)
+            .await
+        {
+            session.abort_transaction().await.ok();
+            return Err(e.into());
+        }
+        session.commit_transaction().await?;
+
+        characters.remove_from_cache(&seller);
+        Ok(true)
+    }
+
+    /// The open listing with this code, if there's one.
+    pub async fn get_open_by_code(&self, code: &str) -> anyhow::Result<Option<MarketListingModel>> {
+        Ok(self
+            .collection
+            .find_one(
+                doc! { "code": code.trim().to_uppercase(), "settled": false },
+                None,
+            )
+            .await?)
+    }
+
+    /// A page of the open listings of the items, or of every item if `identifiers` is `None`,
+    /// the ones about to end first. `page` starts at zero.
+    /// Returns the listings of the page and how many listings there are in all pages.
+    pub async fn search_open_listings(
+        &self,
+        identifiers: Option<Vec<&str>>,
+        page: u64,
+        per_page: i64,
+    ) -> anyhow::Result<(Vec<MarketListingModel>, u64)> {
+        let now = bson::DateTime::from_chrono(DatabaseDateTime::now().0);
+        let mut query = doc! { "settled": false, "ends_at": { "$gt": now } };
+        if let Some(identifiers) = identifiers {
+            query.insert("item.identifier", doc! { "$in": identifiers });
+        }
+
+        let total = self.collection.count_documents(query.clone(), None).await?;
+        let cursor = self
+            .collection
+            .find(
+                query,
+                FindOptions::builder()
+                    .sort(doc! { "ends_at": 1 })
+                    .skip(page.saturating_mul(per_page as u64))
+                    .limit(per_page)
+                    .build(),
+            )
+            .await?;
+
+        Ok((cursor.collect::<Result<Vec<_>, _>>().await?, total))
+    }
+
+    /// Listings whose time is over but weren't settled yet.
+    pub async fn get_expired_listings(&self) -> anyhow::Result<Vec<MarketListingModel>> {
+        let now = bson::DateTime::from_chrono(DatabaseDateTime::now().0);
+        let cursor = self
+            .collection
+            .find(doc! { "settled": false, "ends_at": { "$lte": now } }, None)
+            .await?;
+
+        Ok(cursor.collect::<Result<Vec<_>, _>>().await?)
+    }
+
+    /// Closes the listing so nobody else can buy or settle it.
+    /// Returns `None` if it was already closed by someone else or if its time is over,
+    /// in which case only the settlement can close it.
+    pub async fn claim(&self, id: ObjectId) -> anyhow::Result<Option<MarketListingModel>> {
+        let now = bson::DateTime::from_chrono(DatabaseDateTime::now().0);
+        let listing = self
+            .collection
+            .find_one_and_update(
+                doc! { "_id": id, "settled": false, "ends_at": { "$gt": now } },
+                doc! { "$set": { "settled": true } },
+                None,
+            )
+            .await?;
+
+        Ok(listing.map(|listing| MarketListingModel {
+            settled: true,
+            ..listing
+        }))
+    }
+
+    /// Closes an expired listing, recording what has to be paid in the same write.
+    /// Returns `false` if it was already closed or got a new bid since it was loaded.
+    pub async fn settle(
+        &self,
+        listing: &MarketListingModel,
+        payouts: Vec<ListingPayout>,
+    ) -> anyhow::Result<bool> {
+        let result = self
+            .collection
+            .update_one(
+                doc! {
+                    "_id": listing.id,
+                    "settled": false,
+                    "highest_bid": bson::to_bson(&listing.highest_bid)?,
+                },
+                doc! {
+                    "$set": { "settled": true },
+                    "$push": { "unpaid": { "$each": bson::to_bson(&payouts)? } },
+                },
+                None,
+            )
+            .await?;
+
+        Ok(result.modified_count > 0)
+    }
+
+    /// Gives every unpaid payout of the listing. Each one is taken out of the listing before it's given
+    /// and put back if giving it fails, so it's retried later and never given twice.
+    pub async fn pay(&self, id: ObjectId) -> anyhow::Result<()> {
+        loop {
+            let Some(listing) = self
+                .collection
+                .find_one_and_update(
+                    doc! { "_id": id, "unpaid.0": { "$exists": true } },
+                    doc! { "$pop": { "unpaid": -1 } },
+                    FindOneAndUpdateOptions::builder()
+                        .return_document(ReturnDocument::Before)
+                        .build(),
+                )
+                .await?
+            else {
+                return Ok(());
+            };
+
+            let Some(payout) = listing.unpaid.first() else {
+                return Ok(());
+            };
+
+            if let Err(e) = self.give_payout(&listing, payout).await {
+                self.collection
+                    .update_one(
+                        query_by_id(id),
+                        doc! { "$push": { "unpaid": bson::to_bson(payout)? } },
+                        None,
+                    )
+                    .await?;
+                return Err(e);
+            }
+        }
+    }
+
+    async fn give_payout(
+        &self,
+        listing: &MarketListingModel,
+        payout: &ListingPayout,
+    ) -> anyhow::Result<()> {
+        let characters = self.db.characters();
+        match payout {
+            ListingPayout::Item { character_id } => {
+                if let Some(item) = etheris_data::items::get_item(&listing.item.identifier) {
+                    characters
+                        .add_item(
+                            *character_id,
+                            item,
+                            listing.item.quantity,
+                            Some(listing.item.values.clone()),
+                        )
+                        .await?;
+                }
+            }
+            ListingPayout::Orbs {
+                character_id,
+                amount,
+            } => characters.add_orbs(*character_id, *amount).await?,
+        }
+
+        Ok(())
+    }
+
+    /// Settled listings with payouts that couldn't be given yet.
+    pub async fn get_listings_with_unpaid_payouts(
+        &self,
+    ) -> anyhow::Result<Vec<MarketListingModel>> {
+        let cursor = self
+            .collection
+            .find(doc! { "unpaid.0": { "$exists": true } }, None)
+            .await?;
+
+        Ok(cursor.collect::<Result<Vec<_>, _>>().await?)
+    }
+
+    /// Opens a listing again after it was claimed by something that failed before finishing.
+    pub async fn unclaim(&self, id: ObjectId) -> anyhow::Result<()> {
+        self.collection
+            .update_one(
+                doc! { "_id": id, "settled": true },
+                doc! { "$set": { "settled": false } },
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces the highest bid of an open auction, only if it is still `previous` and the auction
+    /// didn't end. Returns whether the bid was placed.
+    /// The refund of `previous` is recorded as unpaid in the same write, so it's never lost.
+    pub async fn place_bid(
+        &self,
+        id: ObjectId,
+        previous: Option<&MarketBid>,
+        bid: MarketBid,
+    ) -> anyhow::Result<bool> {
+        let now = bson::DateTime::from_chrono(DatabaseDateTime::now().0);
+        let mut query = doc! { "_id": id, "settled": false, "ends_at": { "$gt": now } };
+        let mut update = doc! { "$set": { "highest_bid": bson::to_bson(&bid)? } };
+        match previous {
+            Some(previous) => {
+                query.insert("highest_bid.amount", previous.amount);
+                let refund = ListingPayout::Orbs {
+                    character_id: previous.character_id,
+                    amount: previous.amount,
+                };
+                update.insert("$push", doc! { "unpaid": bson::to_bson(&refund)? });
+            }
+            None => {
+                query.insert("highest_bid", bson::Bson::Null);
+            }
+        };
+
+        let result = self.collection.update_one(query, update, None).await?;
+
+        Ok(result.modified_count > 0)
+    }
+
+    /// Gives orbs back to a bidder through the unpaid payouts of the listing,
+    /// so they are given again by the market job if it fails now.
+    pub async fn refund_bid(&self, id: ObjectId, bid: &MarketBid) -> anyhow::Result<()> {
+        let refund = ListingPayout::Orbs {
+            character_id: bid.character_id,
+            amount: bid.amount,
+        };
+        self.collection
+            .update_one(
+                query_by_id(id),
+                doc! { "$push": { "unpaid": bson::to_bson(&refund)? } },
+                None,
+            )
+            .await?;
+
+        self.pay(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_auction(price: i64) -> MarketListingModel {
+        MarketListingModel::new(
+            ObjectId::new(),
+            "0".into(),
+            "Vendedor".into(),
+            InventoryItem {
+                identifier: "stick".into(),
+                quantity: 1,
+                values: Default::default(),
+            },
+            ListingKind::Auction,
+            price,
+            chrono::Duration::try_hours(1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn bids_must_outbid_the_current_one() {
+        let mut auction = create_auction(100);
+        assert_eq!(auction.code.len(), 6);
+        assert!(!auction.is_expired());
+        assert_eq!(auction.min_next_bid(), 100);
+
+        auction.highest_bid = Some(MarketBid {
+            character_id: ObjectId::new(),
+            user_id: "1".into(),
+            amount: 200,
+        });
+        assert_eq!(auction.min_next_bid(), 210);
+    }
+
+    #[test]
+    fn unknown_items_refund_the_highest_bid() {
+        let mut auction = create_auction(100);
+        assert_eq!(
+            auction.settlement_payouts(true),
+            vec![ListingPayout::Item {
+                character_id: auction.seller_id
+            }]
+        );
+        assert!(auction.settlement_payouts(false).is_empty());
+
+        let bidder = ObjectId::new();
+        auction.highest_bid = Some(MarketBid {
+            character_id: bidder,
+            user_id: "1".into(),
+            amount: 150,
+        });
+        assert_eq!(auction.settlement_payouts(true).len(), 2);
+        assert_eq!(
+            auction.settlement_payouts(false),
+            vec![ListingPayout::Orbs {
+                character_id: bidder,
+                amount: 150
+            }]
+        );
+    }
+
+    #[test]
+    fn listing_fee_has_a_minimum() {
+        assert_eq!(MarketListingModel::fee(50), MARKET_MIN_FEE);
+        assert_eq!(MarketListingModel::fee(10_000), 500);
+    }
+}
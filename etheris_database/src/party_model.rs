@@ -0,0 +1,185 @@
+use bson::{doc, oid::ObjectId};
+use mongodb::Collection;
+use serde::{Deserialize, Serialize};
+
+use crate::{common::*, EtherisDatabase};
+
+pub const MAX_PARTY_MEMBERS: usize = 4;
+const MAX_LEAVE_ATTEMPTS: usize = 3;
+
+/// A group of characters that explore and hunt together. Members are stored by their character IDs.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PartyModel {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    pub leader: ObjectId,
+    /// Every member of the party, including the leader.
+    pub members: Vec<ObjectId>,
+    pub invites: Vec<ObjectId>,
+    pub created_at: DatabaseDateTime,
+}
+
+impl PartyModel {
+    pub fn new(leader: ObjectId) -> Self {
+        Self {
+            id: ObjectId::new(),
+            leader,
+            members: vec![leader],
+            invites: vec![],
+            created_at: DatabaseDateTime::now(),
+        }
+    }
+
+    pub fn is_leader(&self, character_id: ObjectId) -> bool {
+        self.leader == character_id
+    }
+
+    pub fn is_member(&self, character_id: ObjectId) -> bool {
+        self.members.contains(&character_id)
+    }
+
+    pub fn is_invited(&self, character_id: ObjectId) -> bool {
+        self.invites.contains(&character_id)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= MAX_PARTY_MEMBERS
+    }
+}
+
+#[allow(unused)]
+pub struct PartyCommands {
+    collection: Collection<PartyModel>,
+    db: EtherisDatabase,
+}
+
+impl PartyCommands {
+    pub const fn new(collection: Collection<PartyModel>, db: EtherisDatabase) -> Self {
+        Self { collection, db }
+    }
+
+    /// Creates the party, failing with `false` if the leader joined another party in the meantime.
+    pub async fn create(&self, party: PartyModel) -> anyhow::Result<bool> {
+        match self.collection.insert_one(&party, None).await {
+            Err(e) if is_duplicate_key(&e) => Ok(false),
+            result => {
+                result?;
+                Ok(true)
+            }
+        }
+    }
+
+    pub async fn invite(&self, party_id: ObjectId, character_id: ObjectId) -> anyhow::Result<()> {
+        self.collection
+            .update_one(
+                doc! { "_id": party_id, "members": { "$ne": character_id } },
+                doc! { "$addToSet": { "invites": character_id } },
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Adds an invited character to the party if it still exists and isn't full.
+    /// Fails with `false` otherwise, or if the character joined another party in the meantime.
+    pub async fn join(&self, party_id: ObjectId, character_id: ObjectId) -> anyhow::Result<bool> {
+        let full = format!("members.{}", MAX_PARTY_MEMBERS - 1);
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": party_id, "invites": character_id, full: { "$exists": false } },
+                doc! {
+                    "$addToSet": { "members": character_id },
+                    "$pull": { "invites": character_id },
+                },
+                None,
+            )
+            .await;
+
+        match result {
+            Err(e) if is_duplicate_key(&e) => Ok(false),
+            result => Ok(result?.modified_count > 0),
+        }
+    }
+
+    /// Removes a member, deleting the party if it was the last one.
+    /// If it was the leader, the oldest member becomes the new leader.
+    pub async fn leave(&self, party_id: ObjectId, character_id: ObjectId) -> anyhow::Result<bool> {
+        // Both writes depend on how many members there are, so they're tried again if it changed in between
+        for _ in 0..MAX_LEAVE_ATTEMPTS {
+            let deleted = self
+                .collection
+                .delete_one(doc! { "_id": party_id, "members": [character_id] }, None)
+                .await?;
+            if deleted.deleted_count > 0 {
+                return Ok(true);
+            }
+
+            let members = doc! { "$filter": {
+                "input": "$members",
+                "cond": { "$ne": ["$$this", character_id] },
+            } };
+            let leader = doc! { "$cond": [
+                { "$eq": ["$leader", character_id] },
+                { "$arrayElemAt": ["$members", 0] },
+                "$leader",
+            ] };
+            let updated = self
+                .collection
+                .update_one(
+                    doc! { "_id": party_id, "members": character_id, "members.1": { "$exists": true } },
+                    vec![
+                        doc! { "$set": { "members": members } },
+                        doc! { "$set": { "leader": leader } },
+                    ],
+                    None,
+                )
+                .await?;
+            if updated.matched_count > 0 {
+                return Ok(true);
+            }
+
+            let still_member = self
+                .collection
+                .find_one(doc! { "_id": party_id, "members": character_id }, None)
+                .await?
+                .is_some();
+            if !still_member {
+                return Ok(false);
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub async fn delete(&self, party: &PartyModel) -> anyhow::Result<()> {
+        self.collection
+            .delete_one(query_by_id(party.id), None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_by_id(&self, id: ObjectId) -> anyhow::Result<Option<PartyModel>> {
+        Ok(self.collection.find_one(query_by_id(id), None).await?)
+    }
+
+    pub async fn get_by_member(
+        &self,
+        character_id: ObjectId,
+    ) -> anyhow::Result<Option<PartyModel>> {
+        Ok(self
+            .collection
+            .find_one(doc! { "members": character_id }, None)
+            .await?)
+    }
+
+    pub async fn get_by_leader(
+        &self,
+        character_id: ObjectId,
+    ) -> anyhow::Result<Option<PartyModel>> {
+        Ok(self
+            .collection
+            .find_one(doc! { "leader": character_id }, None)
+            .await?)
+    }
+}
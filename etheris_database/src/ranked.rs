@@ -0,0 +1,192 @@
+use std::fmt::Display;
+
+use chrono::TimeZone;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_RATING: i32 = 1000;
+pub const SEASON_LENGTH_DAYS: i64 = 60;
+/// Rated duels between characters whose PL ratio is bigger than this are refused.
+pub const MAX_RANKED_PL_RATIO: f64 = 1.5;
+
+const PLACEMENT_MATCHES: u32 = 10;
+
+/// The number of the current ranked season. Seasons are counted from 2024-01-01.
+pub fn current_season() -> u32 {
+    let epoch = chrono::Utc
+        .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+        .single()
+        .unwrap_or_default();
+    let days = (chrono::Utc::now() - epoch).num_days().max(0);
+    (days / SEASON_LENGTH_DAYS) as u32 + 1
+}
+
+/// The chance of a player with `rating` beating one with `opponent_rating`, from 0.0 to 1.0.
+pub fn expected_score(rating: i32, opponent_rating: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) as f64 / 400.0))
+}
+
+pub fn is_ranked_pl_gap_allowed(pl: i64, opponent_pl: i64) -> bool {
+    let (min, max) = (pl.min(opponent_pl).max(1), pl.max(opponent_pl));
+    max as f64 / min as f64 <= MAX_RANKED_PL_RATIO
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum RankTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Diamond,
+    Master,
+    Legend,
+}
+
+impl RankTier {
+    pub const LIST: [RankTier; 7] = [
+        Self::Bronze,
+        Self::Silver,
+        Self::Gold,
+        Self::Platinum,
+        Self::Diamond,
+        Self::Master,
+        Self::Legend,
+    ];
+
+    pub const fn min_rating(&self) -> i32 {
+        match self {
+            Self::Bronze => i32::MIN,
+            Self::Silver => 1100,
+            Self::Gold => 1250,
+            Self::Platinum => 1400,
+            Self::Diamond => 1550,
+            Self::Master => 1750,
+            Self::Legend => 2000,
+        }
+    }
+
+    pub fn from_rating(rating: i32) -> Self {
+        Self::LIST
+            .into_iter()
+            .rev()
+            .find(|tier| rating >= tier.min_rating())
+            .unwrap_or(Self::Bronze)
+    }
+
+    pub const fn emoji(&self) -> &'static str {
+        match self {
+            Self::Bronze => "🟫",
+            Self::Silver => "⬜",
+            Self::Gold => "🟨",
+            Self::Platinum => "🟦",
+            Self::Diamond => "💎",
+            Self::Master => "🟪",
+            Self::Legend => "👑",
+        }
+    }
+}
+
+impl Display for RankTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bronze => f.write_str("Bronze"),
+            Self::Silver => f.write_str("Prata"),
+            Self::Gold => f.write_str("Ouro"),
+            Self::Platinum => f.write_str("Platina"),
+            Self::Diamond => f.write_str("Diamante"),
+            Self::Master => f.write_str("Mestre"),
+            Self::Legend => f.write_str("Lenda"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PvpRating {
+    pub rating: i32,
+    pub peak: i32,
+    pub season: u32,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl Default for PvpRating {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_RATING,
+            peak: DEFAULT_RATING,
+            season: current_season(),
+            wins: 0,
+            losses: 0,
+        }
+    }
+}
+
+impl PvpRating {
+    pub fn tier(&self) -> RankTier {
+        RankTier::from_rating(self.rating)
+    }
+
+    pub fn matches(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Moves the rating halfway back to the default one if a new season started.
+    pub fn update_season(&mut self) {
+        let season = current_season();
+        if self.season == season {
+            return;
+        }
+
+        self.rating = (self.rating + DEFAULT_RATING) / 2;
+        self.peak = self.rating;
+        self.season = season;
+        self.wins = 0;
+        self.losses = 0;
+    }
+
+    /// Applies the result of a ranked duel and returns how much the rating changed.
+    pub fn add_result(&mut self, opponent_rating: i32, won: bool) -> i32 {
+        self.update_season();
+
+        let k = if self.matches() < PLACEMENT_MATCHES {
+            48.0
+        } else {
+            32.0
+        };
+
+        let score = if won { 1.0 } else { 0.0 };
+        let delta = (k * (score - expected_score(self.rating, opponent_rating))).round() as i32;
+
+        self.rating = (self.rating + delta).max(0);
+        self.peak = self.peak.max(self.rating);
+        if won {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+
+        delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratings_move_by_the_same_amount() {
+        let mut winner = PvpRating::default();
+        let mut loser = PvpRating {
+            rating: 1200,
+            ..Default::default()
+        };
+
+        let (winner_rating, loser_rating) = (winner.rating, loser.rating);
+        let gained = winner.add_result(loser_rating, true);
+        let lost = loser.add_result(winner_rating, false);
+
+        assert!(gained > 0);
+        assert_eq!(gained, -lost);
+        assert_eq!(RankTier::from_rating(winner.rating), RankTier::Bronze);
+        assert_eq!(RankTier::from_rating(2500), RankTier::Legend);
+    }
+}
@@ -0,0 +1,1030 @@
+use std::fmt::Display;
+
+use anyhow::bail;
+use bson::{doc, oid::ObjectId};
+use mongodb::{
+    options::{FindOneAndUpdateOptions, ReturnDocument},
+    Collection,
+};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::{common::*, EtherisDatabase};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum TournamentKind {
+    SingleElimination,
+    DoubleElimination,
+}
+
+impl Display for TournamentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SingleElimination => f.write_str("Eliminação Simples"),
+            Self::DoubleElimination => f.write_str("Eliminação Dupla"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum TournamentState {
+    SigningUp,
+    Running,
+    Finished,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum BracketSide {
+    Winners,
+    Losers,
+    GrandFinal,
+}
+
+/// Where a player of a match comes from. `Player(None)` is a bye.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MatchSource {
+    Player(Option<ObjectId>),
+    WinnerOf(usize),
+    LoserOf(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TournamentMatch {
+    pub id: usize,
+    pub side: BracketSide,
+    pub round: u32,
+    pub sources: [MatchSource; 2],
+    pub players: [Option<ObjectId>; 2],
+    pub winner: Option<ObjectId>,
+    pub loser: Option<ObjectId>,
+    pub resolved: bool,
+    /// When both players became known, which starts the deadline of the match.
+    #[serde(default)]
+    pub ready_at: Option<DatabaseDateTime>,
+    /// The player that showed up to fight, who wins the match if the deadline passes.
+    #[serde(default)]
+    pub showed_up: Option<ObjectId>,
+}
+
+impl TournamentMatch {
+    fn new(id: usize, side: BracketSide, round: u32, sources: [MatchSource; 2]) -> Self {
+        Self {
+            id,
+            side,
+            round,
+            sources,
+            players: [None, None],
+            winner: None,
+            loser: None,
+            resolved: false,
+            ready_at: None,
+            showed_up: None,
+        }
+    }
+
+    /// Both players are known and the match wasn't fought yet.
+    pub fn is_ready(&self) -> bool {
+        !self.resolved && self.players.iter().all(|p| p.is_some())
+    }
+
+    pub fn has_player(&self, character_id: ObjectId) -> bool {
+        self.players.contains(&Some(character_id))
+    }
+
+    pub fn opponent_of(&self, character_id: ObjectId) -> Option<ObjectId> {
+        match self.players {
+            [Some(first), second] if first == character_id => second,
+            [first, Some(second)] if second == character_id => first,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TournamentParticipant {
+    pub character_id: ObjectId,
+    pub user_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct TournamentPrize {
+    pub orbs: i64,
+    pub items: Vec<InventoryItem>,
+}
+
+/// Part of a prize (or of a refund) that was claimed but not given yet.
+/// Each one is given with a single write: either some orbs or one item.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UnpaidPrize {
+    pub character_id: ObjectId,
+    pub orbs: i64,
+    pub item: Option<InventoryItem>,
+}
+
+impl UnpaidPrize {
+    fn split(character_id: ObjectId, prize: &TournamentPrize) -> Vec<Self> {
+        let orbs = (prize.orbs > 0).then_some(Self {
+            character_id,
+            orbs: prize.orbs,
+            item: None,
+        });
+
+        orbs.into_iter()
+            .chain(prize.items.iter().map(|item| Self {
+                character_id,
+                orbs: 0,
+                item: Some(item.clone()),
+            }))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TournamentModel {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+    /// Bumped on every write, so saving an outdated copy of the tournament can be detected.
+    #[serde(default)]
+    pub version: i64,
+    pub guild_id: String,
+    pub channel_id: String,
+    pub host_id: String,
+    /// The character that paid the prizes, who gets them back if the tournament is cancelled.
+    #[serde(default)]
+    pub host_character_id: Option<ObjectId>,
+    pub name: String,
+    pub kind: TournamentKind,
+    pub state: TournamentState,
+    pub participants: Vec<TournamentParticipant>,
+    pub matches: Vec<TournamentMatch>,
+    /// The prize of each placement, from the champion to the last paid placement.
+    pub prizes: Vec<TournamentPrize>,
+    pub prizes_paid: bool,
+    /// Prizes that were claimed and still have to be given. They're removed one by one as they're paid.
+    #[serde(default)]
+    pub unpaid_prizes: Vec<UnpaidPrize>,
+    pub signup_ends_at: DatabaseDateTime,
+    pub created_at: DatabaseDateTime,
+}
+
+impl TournamentModel {
+    pub const MIN_PARTICIPANTS: usize = 2;
+    pub const MAX_PARTICIPANTS: usize = 32;
+    /// How long the players of a match have to fight it before it's settled as a walkover.
+    pub const MATCH_DEADLINE_HOURS: i64 = 24;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        guild_id: String,
+        channel_id: String,
+        host_id: String,
+        host_character_id: ObjectId,
+        name: String,
+        kind: TournamentKind,
+        prizes: Vec<TournamentPrize>,
+        signup_duration: chrono::Duration,
+    ) -> Self {
+        Self {
+            id: ObjectId::new(),
+            version: 0,
+            guild_id,
+            channel_id,
+            host_id,
+            host_character_id: Some(host_character_id),
+            name,
+            kind,
+            state: TournamentState::SigningUp,
+            participants: vec![],
+            matches: vec![],
+            prizes,
+            prizes_paid: false,
+            unpaid_prizes: vec![],
+            signup_ends_at: DatabaseDateTime(DatabaseDateTime::now().0 + signup_duration),
+            created_at: DatabaseDateTime::now(),
+        }
+    }
+
+    /// Splits the prize pool between the placements. The items always go to the champion.
+    pub fn create_prizes(
+        kind: TournamentKind,
+        orbs: i64,
+        items: Vec<InventoryItem>,
+    ) -> Vec<TournamentPrize> {
+        let shares: &[i64] = match kind {
+            TournamentKind::SingleElimination => &[70, 30],
+            TournamentKind::DoubleElimination => &[60, 30, 10],
+        };
+
+        let mut prizes = shares
+            .iter()
+            .map(|share| TournamentPrize {
+                orbs: orbs * share / 100,
+                items: vec![],
+            })
+            .collect::<Vec<_>>();
+
+        // The orbs lost by rounding go to the champion
+        let distributed = prizes.iter().map(|p| p.orbs).sum::<i64>();
+        prizes[0].orbs += orbs - distributed;
+        prizes[0].items = items;
+
+        prizes
+    }
+
+    pub fn total_prize_orbs(&self) -> i64 {
+        self.prizes.iter().map(|p| p.orbs).sum()
+    }
+
+    pub fn is_signup_over(&self) -> bool {
+        DatabaseDateTime::now() >= self.signup_ends_at
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.participants.len() >= Self::MAX_PARTICIPANTS
+    }
+
+    pub fn get_participant(&self, character_id: ObjectId) -> Option<&TournamentParticipant> {
+        self.participants
+            .iter()
+            .find(|p| p.character_id == character_id)
+    }
+
+    pub fn participant_name(&self, character_id: Option<ObjectId>) -> Option<String> {
+        character_id
+            .and_then(|id| self.get_participant(id))
+            .map(|p| p.name.clone())
+    }
+
+    pub fn add_participant(&mut self, participant: TournamentParticipant) {
+        if self.get_participant(participant.character_id).is_none() {
+            self.participants.push(participant);
+        }
+    }
+
+    pub fn remove_participant(&mut self, character_id: ObjectId) {
+        self.participants.retain(|p| p.character_id != character_id);
+    }
+
+    /// Shuffles the participants, builds the bracket and resolves the byes.
+    pub fn start(&mut self, rng: &mut impl Rng) -> anyhow::Result<()> {
+        if self.state != TournamentState::SigningUp {
+            bail!("the tournament already started");
+        }
+
+        if self.participants.len() < Self::MIN_PARTICIPANTS {
+            bail!("not enough participants");
+        }
+
+        let mut players = self
+            .participants
+            .iter()
+            .map(|p| p.character_id)
+            .collect::<Vec<_>>();
+        players.shuffle(rng);
+
+        self.matches = match self.kind {
+            TournamentKind::SingleElimination => build_single_elimination(&players).0,
+            TournamentKind::DoubleElimination => build_double_elimination(&players),
+        };
+        self.state = TournamentState::Running;
+        self.update_bracket();
+
+        Ok(())
+    }
+
+    pub fn ready_match_of(&self, character_id: ObjectId) -> Option<&TournamentMatch> {
+        self.matches
+            .iter()
+            .find(|m| m.is_ready() && m.has_player(character_id))
+    }
+
+    pub fn ready_matches(&self) -> Vec<&TournamentMatch> {
+        self.matches.iter().filter(|m| m.is_ready()).collect()
+    }
+
+    pub fn final_match(&self) -> Option<&TournamentMatch> {
+        self.matches.last()
+    }
+
+    pub fn report_result(&mut self, match_id: usize, winner: ObjectId) -> anyhow::Result<()> {
+        let Some(tournament_match) = self.matches.get_mut(match_id) else {
+            bail!("match {match_id} doesn't exist");
+        };
+
+        if !tournament_match.is_ready() || !tournament_match.has_player(winner) {
+            bail!("match {match_id} can't be won by {winner}");
+        }
+
+        tournament_match.loser = tournament_match.opponent_of(winner);
+        tournament_match.winner = Some(winner);
+        tournament_match.resolved = true;
+
+        self.update_bracket();
+        Ok(())
+    }
+
+    /// Records that the player is ready to fight the match, so a walkover is in their favor.
+    pub fn mark_showed_up(&mut self, match_id: usize, character_id: ObjectId) -> bool {
+        let Some(tournament_match) = self
+            .matches
+            .get_mut(match_id)
+            .filter(|m| m.is_ready() && m.has_player(character_id))
+        else {
+            return false;
+        };
+
+        tournament_match.showed_up = Some(character_id);
+        true
+    }
+
+    pub fn match_deadline(tournament_match: &TournamentMatch) -> Option<DatabaseDateTime> {
+        let ready_at = tournament_match.ready_at?;
+        Some(DatabaseDateTime(
+            ready_at.0 + chrono::Duration::hours(Self::MATCH_DEADLINE_HOURS),
+        ))
+    }
+
+    /// The ready matches that weren't fought before their deadline.
+    pub fn overdue_matches(&self) -> Vec<usize> {
+        let now = DatabaseDateTime::now();
+        self.matches
+            .iter()
+            .filter(|m| m.is_ready() && Self::match_deadline(m).is_some_and(|d| d <= now))
+            .map(|m| m.id)
+            .collect()
+    }
+
+    /// Settles a match that wasn't fought in time. The player that showed up wins it,
+    /// or a random one if neither did. Returns the winner.
+    pub fn report_walkover(
+        &mut self,
+        match_id: usize,
+        rng: &mut impl Rng,
+    ) -> anyhow::Result<ObjectId> {
+        let Some(tournament_match) = self.matches.get(match_id).filter(|m| m.is_ready()) else {
+            bail!("match {match_id} isn't waiting to be fought");
+        };
+        let [Some(first), Some(second)] = tournament_match.players else {
+            bail!("match {match_id} doesn't have both players");
+        };
+
+        let winner = match tournament_match.showed_up {
+            Some(player) => player,
+            None if rng.gen_bool(0.5) => first,
+            None => second,
+        };
+
+        self.report_result(match_id, winner)?;
+        Ok(winner)
+    }
+
+    /// Moves the prizes of a finished tournament to `unpaid_prizes` and returns who gets each one.
+    /// Returns nothing if they were already claimed, so they're never paid twice.
+    pub fn claim_prizes(&mut self) -> Vec<(ObjectId, TournamentPrize)> {
+        if self.state != TournamentState::Finished || self.prizes_paid {
+            return vec![];
+        }
+
+        self.prizes_paid = true;
+        let payouts = self.prize_payouts();
+        for (character_id, prize) in &payouts {
+            self.unpaid_prizes
+                .extend(UnpaidPrize::split(*character_id, prize));
+        }
+
+        payouts
+    }
+
+    /// Finishes the tournament without a champion and moves the prizes to `unpaid_prizes`, to be refunded
+    /// to the host. Returns the refunded prizes, or nothing if it was already finished or the prizes were
+    /// already claimed.
+    pub fn cancel(&mut self) -> Vec<TournamentPrize> {
+        if self.state == TournamentState::Finished || self.prizes_paid {
+            return vec![];
+        }
+
+        self.state = TournamentState::Finished;
+        self.prizes_paid = true;
+        if let Some(host_character_id) = self.host_character_id {
+            for prize in &self.prizes {
+                self.unpaid_prizes
+                    .extend(UnpaidPrize::split(host_character_id, prize));
+            }
+        }
+
+        self.prizes.clone()
+    }
+
+    /// The champion first, then the runner-up and, in double elimination, the third place.
+    pub fn placements(&self) -> Vec<ObjectId> {
+        let Some(final_match) = self.final_match().filter(|m| m.resolved) else {
+            return vec![];
+        };
+
+        let mut placements = vec![final_match.winner, final_match.loser];
+        let grand_final = self
+            .matches
+            .iter()
+            .find(|m| m.side == BracketSide::GrandFinal && m.round == 1);
+        if let Some(MatchSource::WinnerOf(losers_final)) = grand_final.map(|m| m.sources[1]) {
+            placements.push(self.matches[losers_final].loser);
+        }
+
+        placements.into_iter().flatten().collect()
+    }
+
+    /// Who gets each prize. Prizes of placements nobody reached go to the champion.
+    pub fn prize_payouts(&self) -> Vec<(ObjectId, TournamentPrize)> {
+        let placements = self.placements();
+        let mut prizes = self.prizes.clone();
+
+        if placements.len() < prizes.len() {
+            for unclaimed in prizes.split_off(placements.len().max(1)) {
+                prizes[0].orbs += unclaimed.orbs;
+                prizes[0].items.extend(unclaimed.items);
+            }
+        }
+
+        placements.into_iter().zip(prizes).collect()
+    }
+
+    fn source_value(&self, source: MatchSource) -> Option<Option<ObjectId>> {
+        match source {
+            MatchSource::Player(player) => Some(player),
+            MatchSource::WinnerOf(id) => {
+                self.matches[id].resolved.then_some(self.matches[id].winner)
+            }
+            MatchSource::LoserOf(id) => self.matches[id].resolved.then_some(self.matches[id].loser),
+        }
+    }
+
+    /// If the match is the reset of a grand final that the player from the winners bracket won,
+    /// returns that grand final. The reset isn't played then, as the loser already lost twice.
+    fn unneeded_reset_of(&self, match_id: usize) -> Option<TournamentMatch> {
+        let reset = &self.matches[match_id];
+        if reset.side != BracketSide::GrandFinal || reset.round != 2 {
+            return None;
+        }
+
+        let MatchSource::WinnerOf(grand_final) = reset.sources[0] else {
+            return None;
+        };
+
+        self.matches
+            .get(grand_final)
+            .filter(|m| m.resolved && m.winner == m.players[0])
+            .cloned()
+    }
+
+    /// Moves players to the matches they're waiting for and gives byes their free wins.
+    fn update_bracket(&mut self) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for i in 0..self.matches.len() {
+                if self.matches[i].resolved {
+                    continue;
+                }
+
+                if let Some(grand_final) = self.unneeded_reset_of(i) {
+                    let tournament_match = &mut self.matches[i];
+                    tournament_match.winner = grand_final.winner;
+                    tournament_match.loser = grand_final.loser;
+                    tournament_match.resolved = true;
+                    changed = true;
+                    continue;
+                }
+
+                let sources = self.matches[i].sources;
+                let (Some(first), Some(second)) =
+                    (self.source_value(sources[0]), self.source_value(sources[1]))
+                else {
+                    continue;
+                };
+
+                let tournament_match = &mut self.matches[i];
+                if tournament_match.players != [first, second] {
+                    tournament_match.players = [first, second];
+                    changed = true;
+                }
+
+                match (first, second) {
+                    (Some(_), Some(_)) => {
+                        if tournament_match.ready_at.is_none() {
+                            tournament_match.ready_at = Some(DatabaseDateTime::now());
+                        }
+                    }
+                    (player, None) | (None, player) => {
+                        tournament_match.winner = player;
+                        tournament_match.loser = None;
+                        tournament_match.resolved = true;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if self.final_match().is_some_and(|m| m.resolved) {
+            self.state = TournamentState::Finished;
+        }
+    }
+}
+
+/// Returns the matches and the IDs of the matches of every round.
+fn build_single_elimination(players: &[ObjectId]) -> (Vec<TournamentMatch>, Vec<Vec<usize>>) {
+    let size = players.len().next_power_of_two().max(2);
+    let mut matches = vec![];
+    let mut rounds = vec![];
+
+    // Byes face the first players, so two byes never face each other
+    let mut round = vec![];
+    for i in 0..size / 2 {
+        let id = matches.len();
+        matches.push(TournamentMatch::new(
+            id,
+            BracketSide::Winners,
+            1,
+            [
+                MatchSource::Player(players.get(i).copied()),
+                MatchSource::Player(players.get(size - 1 - i).copied()),
+            ],
+        ));
+        round.push(id);
+    }
+    rounds.push(round);
+
+    while rounds.last().is_some_and(|r| r.len() > 1) {
+        let previous = rounds.last().cloned().unwrap_or_default();
+        let mut round = vec![];
+        for pair in previous.chunks(2) {
+            let id = matches.len();
+            matches.push(TournamentMatch::new(
+                id,
+                BracketSide::Winners,
+                rounds.len() as u32 + 1,
+                [
+                    MatchSource::WinnerOf(pair[0]),
+                    MatchSource::WinnerOf(pair[1]),
+                ],
+            ));
+            round.push(id);
+        }
+        rounds.push(round);
+    }
+
+    (matches, rounds)
+}
+
+fn build_double_elimination(players: &[ObjectId]) -> Vec<TournamentMatch> {
+    let (mut matches, winners_rounds) = build_single_elimination(players);
+    let mut losers_round = 0;
+
+    let mut push_losers_round =
+        |matches: &mut Vec<TournamentMatch>, pairs: Vec<[MatchSource; 2]>| -> Vec<MatchSource> {
+            losers_round += 1;
+            pairs
+                .into_iter()
+                .map(|sources| {
+                    let id = matches.len();
+                    matches.push(TournamentMatch::new(
+                        id,
+                        BracketSide::Losers,
+                        losers_round,
+                        sources,
+                    ));
+                    MatchSource::WinnerOf(id)
+                })
+                .collect()
+        };
+
+    let pair_up = |sources: &[MatchSource]| -> Vec<[MatchSource; 2]> {
+        sources.chunks(2).map(|pair| [pair[0], pair[1]]).collect()
+    };
+
+    let mut survivors = winners_rounds[0]
+        .iter()
+        .map(|id| MatchSource::LoserOf(*id))
+        .collect::<Vec<_>>();
+    if survivors.len() > 1 {
+        survivors = push_losers_round(&mut matches, pair_up(&survivors));
+    }
+
+    for round in winners_rounds.iter().skip(1) {
+        // The losers dropping from the winners bracket face the survivors in the reverse order to avoid rematches
+        let pairs = survivors
+            .iter()
+            .zip(round.iter().rev())
+            .map(|(survivor, id)| [*survivor, MatchSource::LoserOf(*id)])
+            .collect();
+        survivors = push_losers_round(&mut matches, pairs);
+
+        if survivors.len() > 1 {
+            survivors = push_losers_round(&mut matches, pair_up(&survivors));
+        }
+    }
+
+    let winners_final = *winners_rounds.last().and_then(|r| r.first()).unwrap_or(&0);
+    let grand_final = matches.len();
+    matches.push(TournamentMatch::new(
+        grand_final,
+        BracketSide::GrandFinal,
+        1,
+        [MatchSource::WinnerOf(winners_final), survivors[0]],
+    ));
+
+    // If the player from the losers bracket wins the grand final, both lost once and play again
+    let id = matches.len();
+    matches.push(TournamentMatch::new(
+        id,
+        BracketSide::GrandFinal,
+        2,
+        [
+            MatchSource::WinnerOf(grand_final),
+            MatchSource::LoserOf(grand_final),
+        ],
+    ));
+
+    matches
+}
+
+/// How many times a change is retried when someone else saves the tournament at the same time.
+const MAX_UPDATE_ATTEMPTS: usize = 5;
+
+#[allow(unused)]
+pub struct TournamentCommands {
+    collection: Collection<TournamentModel>,
+    db: EtherisDatabase,
+}
+
+impl TournamentCommands {
+    pub const fn new(collection: Collection<TournamentModel>, db: EtherisDatabase) -> Self {
+        Self { collection, db }
+    }
+
+    pub async fn create(&self, tournament: TournamentModel) -> anyhow::Result<()> {
+        self.collection.insert_one(tournament, None).await?;
+        Ok(())
+    }
+
+    /// Loads the tournament straight from the database, changes it and saves it,
+    /// starting over if someone else saved it in the meantime.
+    /// `change` returns whether the tournament should be saved; `None` is returned if it isn't.
+    pub async fn update(
+        &self,
+        id: ObjectId,
+        mut change: impl FnMut(&mut TournamentModel) -> bool,
+    ) -> anyhow::Result<Option<TournamentModel>> {
+        for _ in 0..MAX_UPDATE_ATTEMPTS {
+            let Some(mut tournament) = self.collection.find_one(query_by_id(id), None).await?
+            else {
+                return Ok(None);
+            };
+
+            if !change(&mut tournament) {
+                return Ok(None);
+            }
+
+            let query = query_by_version(tournament.id, tournament.version);
+            tournament.version += 1;
+            let result = self
+                .collection
+                .replace_one(query, &tournament, None)
+                .await?;
+            if result.matched_count > 0 {
+                return Ok(Some(tournament));
+            }
+        }
+
+        bail!("the tournament {id} kept changing while it was being updated")
+    }
+
+    /// Gives every unpaid prize of the tournament. Each one is taken out of the tournament before
+    /// it's given and put back if giving it fails, so it's retried later and never paid twice.
+    pub async fn pay_prizes(&self, id: ObjectId) -> anyhow::Result<()> {
+        loop {
+            let Some(tournament) = self
+                .collection
+                .find_one_and_update(
+                    doc! { "_id": id, "unpaid_prizes.0": { "$exists": true } },
+                    doc! { "$pop": { "unpaid_prizes": -1 }, "$inc": { "version": 1 } },
+                    FindOneAndUpdateOptions::builder()
+                        .return_document(ReturnDocument::Before)
+                        .build(),
+                )
+                .await?
+            else {
+                return Ok(());
+            };
+
+            let Some(prize) = tournament.unpaid_prizes.into_iter().next() else {
+                return Ok(());
+            };
+
+            if let Err(e) = self.give_prize(&prize).await {
+                self.collection
+                    .update_one(
+                        query_by_id(id),
+                        doc! {
+                            "$push": { "unpaid_prizes": bson::to_bson(&prize)? },
+                            "$inc": { "version": 1 }
+                        },
+                        None,
+                    )
+                    .await?;
+                return Err(e);
+            }
+        }
+    }
+
+    async fn give_prize(&self, prize: &UnpaidPrize) -> anyhow::Result<()> {
+        let characters = self.db.characters();
+        if prize.orbs != 0 {
+            characters.add_orbs(prize.character_id, prize.orbs).await?;
+        }
+
+        if let Some(inventory_item) = &prize.item {
+            if let Some(item) = etheris_data::items::get_item(&inventory_item.identifier) {
+                characters
+                    .add_item(
+                        prize.character_id,
+                        item,
+                        inventory_item.quantity,
+                        Some(inventory_item.values.clone()),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tournaments with prizes that were claimed but couldn't be given yet.
+    pub async fn get_tournaments_with_unpaid_prizes(&self) -> anyhow::Result<Vec<TournamentModel>> {
+        let cursor = self
+            .collection
+            .find(doc! { "unpaid_prizes.0": { "$exists": true } }, None)
+            .await?;
+
+        Ok(cursor.collect::<Result<Vec<_>, _>>().await?)
+    }
+
+    pub async fn get_by_id(&self, id: ObjectId) -> anyhow::Result<Option<TournamentModel>> {
+        Ok(self.collection.find_one(query_by_id(id), None).await?)
+    }
+
+    /// The tournament of the guild that isn't finished yet, if there's one.
+    pub async fn get_active_by_guild(
+        &self,
+        guild_id: &str,
+    ) -> anyhow::Result<Option<TournamentModel>> {
+        Ok(self
+            .collection
+            .find_one(
+                doc! {
+                    "guild_id": guild_id,
+                    "state": { "$ne": bson::to_bson(&TournamentState::Finished)? }
+                },
+                None,
+            )
+            .await?)
+    }
+
+    /// Tournaments whose sign-up time is over but didn't start yet.
+    pub async fn get_due_tournaments(&self) -> anyhow::Result<Vec<TournamentModel>> {
+        let now = bson::DateTime::from_chrono(DatabaseDateTime::now().0);
+        let cursor = self
+            .collection
+            .find(
+                doc! {
+                    "state": bson::to_bson(&TournamentState::SigningUp)?,
+                    "signup_ends_at": { "$lt": now }
+                },
+                None,
+            )
+            .await?;
+
+        Ok(cursor.collect::<Result<Vec<_>, _>>().await?)
+    }
+
+    pub async fn get_running_tournaments(&self) -> anyhow::Result<Vec<TournamentModel>> {
+        let cursor = self
+            .collection
+            .find(
+                doc! { "state": bson::to_bson(&TournamentState::Running)? },
+                None,
+            )
+            .await?;
+
+        Ok(cursor.collect::<Result<Vec<_>, _>>().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn create_tournament(kind: TournamentKind, participants: usize) -> TournamentModel {
+        let mut tournament = TournamentModel::new(
+            "0".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            ObjectId::new(),
+            "Teste".to_string(),
+            kind,
+            TournamentModel::create_prizes(kind, 1001, vec![]),
+            chrono::Duration::zero(),
+        );
+
+        for i in 0..participants {
+            tournament.add_participant(TournamentParticipant {
+                character_id: ObjectId::new(),
+                user_id: i.to_string(),
+                name: format!("Lutador {i}"),
+            });
+        }
+
+        tournament
+            .start(&mut StdRng::seed_from_u64(participants as u64))
+            .unwrap();
+        tournament
+    }
+
+    /// Plays every match, always making the first player win. Returns how many matches were fought.
+    fn play(tournament: &mut TournamentModel) -> usize {
+        let mut fought = 0;
+        while let Some(tournament_match) = tournament.ready_matches().first().cloned().cloned() {
+            tournament
+                .report_result(tournament_match.id, tournament_match.players[0].unwrap())
+                .unwrap();
+            fought += 1;
+        }
+
+        fought
+    }
+
+    #[test]
+    fn single_elimination_finishes() {
+        for participants in 2..=9 {
+            let mut tournament = create_tournament(TournamentKind::SingleElimination, participants);
+            let fought = play(&mut tournament);
+
+            assert_eq!(tournament.state, TournamentState::Finished);
+            assert_eq!(fought, participants - 1);
+            assert_eq!(tournament.placements().len(), 2);
+        }
+    }
+
+    #[test]
+    fn double_elimination_finishes() {
+        for participants in 2..=9 {
+            let mut tournament = create_tournament(TournamentKind::DoubleElimination, participants);
+            let fought = play(&mut tournament);
+
+            assert_eq!(tournament.state, TournamentState::Finished);
+            // Everyone but the champion loses twice, and the grand final isn't reset
+            assert_eq!(fought, 2 * (participants - 1));
+            assert!(tournament.placements().len() >= 2);
+
+            let prizes = tournament
+                .prize_payouts()
+                .iter()
+                .map(|(_, p)| p.orbs)
+                .sum::<i64>();
+            assert_eq!(prizes, tournament.total_prize_orbs());
+        }
+    }
+
+    /// How many matches each player lost, counting only the matches that were fought.
+    fn losses(tournament: &TournamentModel, character_id: ObjectId) -> usize {
+        tournament
+            .matches
+            .iter()
+            .filter(|m| m.resolved && m.players.iter().all(|p| p.is_some()))
+            .filter(|m| m.loser == Some(character_id))
+            .count()
+    }
+
+    /// The loser of the losers bracket final.
+    fn third_place(tournament: &TournamentModel) -> Option<ObjectId> {
+        let grand_final = &tournament.matches[tournament.matches.len() - 2];
+        let MatchSource::WinnerOf(losers_final) = grand_final.sources[1] else {
+            return None;
+        };
+
+        assert_eq!(tournament.matches[losers_final].side, BracketSide::Losers);
+        tournament.matches[losers_final].loser
+    }
+
+    #[test]
+    fn double_elimination_is_won_without_a_reset() {
+        for (participants, matches) in [(4, 6), (8, 14)] {
+            let mut tournament = create_tournament(TournamentKind::DoubleElimination, participants);
+            assert_eq!(play(&mut tournament), matches);
+
+            let grand_final = tournament.matches[tournament.matches.len() - 2].clone();
+            let placements = tournament.placements();
+            assert_eq!(placements.len(), 3);
+            assert_eq!(Some(placements[0]), grand_final.players[0]);
+            assert_eq!(Some(placements[1]), grand_final.players[1]);
+            assert_eq!(Some(placements[2]), third_place(&tournament));
+
+            assert_eq!(losses(&tournament, placements[0]), 0);
+            for participant in &tournament.participants {
+                if participant.character_id != placements[0] {
+                    assert_eq!(losses(&tournament, participant.character_id), 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn double_elimination_resets_when_the_losers_bracket_wins() {
+        for (participants, matches) in [(4, 7), (8, 15)] {
+            let mut tournament = create_tournament(TournamentKind::DoubleElimination, participants);
+
+            // The second player wins both grand finals, everyone else plays as usual
+            let mut fought = 0;
+            while let Some(tournament_match) = tournament.ready_matches().first().cloned().cloned()
+            {
+                let winner = match tournament_match.side {
+                    BracketSide::GrandFinal if tournament_match.round == 1 => 1,
+                    _ => 0,
+                };
+                tournament
+                    .report_result(
+                        tournament_match.id,
+                        tournament_match.players[winner].unwrap(),
+                    )
+                    .unwrap();
+                fought += 1;
+            }
+            assert_eq!(fought, matches);
+            assert_eq!(tournament.state, TournamentState::Finished);
+
+            let grand_final = tournament.matches[tournament.matches.len() - 2].clone();
+            let placements = tournament.placements();
+            assert_eq!(placements.len(), 3);
+            assert_eq!(Some(placements[0]), grand_final.winner);
+            assert_eq!(Some(placements[1]), grand_final.loser);
+            assert_eq!(Some(placements[2]), third_place(&tournament));
+
+            // The champion came from the losers bracket, so it already lost once
+            assert_eq!(losses(&tournament, placements[0]), 1);
+            for participant in &tournament.participants {
+                if participant.character_id != placements[0] {
+                    assert_eq!(losses(&tournament, participant.character_id), 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overdue_matches_are_won_by_whoever_showed_up() {
+        let mut tournament = create_tournament(TournamentKind::SingleElimination, 2);
+        let tournament_match = tournament.ready_matches()[0].clone();
+        let player = tournament_match.players[1].unwrap();
+        assert!(tournament.overdue_matches().is_empty());
+
+        assert!(tournament.mark_showed_up(tournament_match.id, player));
+        let late = DatabaseDateTime::now().0
+            - chrono::Duration::hours(TournamentModel::MATCH_DEADLINE_HOURS);
+        tournament.matches[tournament_match.id].ready_at = Some(DatabaseDateTime(late));
+        assert_eq!(tournament.overdue_matches(), vec![tournament_match.id]);
+
+        let winner = tournament
+            .report_walkover(tournament_match.id, &mut StdRng::seed_from_u64(0))
+            .unwrap();
+        assert_eq!(winner, player);
+        assert_eq!(tournament.state, TournamentState::Finished);
+    }
+
+    #[test]
+    fn prizes_are_claimed_once() {
+        let mut tournament = create_tournament(TournamentKind::SingleElimination, 4);
+        assert!(tournament.claim_prizes().is_empty());
+
+        play(&mut tournament);
+        assert_eq!(tournament.claim_prizes().len(), 2);
+        assert!(tournament.claim_prizes().is_empty());
+        assert!(tournament.cancel().is_empty());
+        assert_eq!(tournament.unpaid_prizes.len(), 2);
+
+        let mut cancelled = create_tournament(TournamentKind::SingleElimination, 4);
+        assert_eq!(cancelled.cancel().len(), 2);
+        assert!(cancelled.cancel().is_empty());
+        assert!(cancelled.claim_prizes().is_empty());
+
+        let refunded = cancelled
+            .unpaid_prizes
+            .iter()
+            .inspect(|p| assert_eq!(Some(p.character_id), cancelled.host_character_id))
+            .map(|p| p.orbs)
+            .sum::<i64>();
+        assert_eq!(refunded, cancelled.total_prize_orbs());
+    }
+}
@@ -0,0 +1,178 @@
+use etheris_data::items;
+
+use crate::{character_model::CharacterModel, common::InventoryItem};
+
+/// What one side of a trade gives to the other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TradeOffer {
+    pub orbs: i64,
+    pub items: Vec<InventoryItem>,
+}
+
+impl TradeOffer {
+    pub fn is_empty(&self) -> bool {
+        self.orbs == 0 && self.items.is_empty()
+    }
+
+    /// Adds some of an inventory item to the offer, merging it with what was already offered.
+    /// Copies of a weapon with different upgrades are offered separately.
+    pub fn add_item(&mut self, inventory_item: &InventoryItem, quantity: usize) {
+        if let Some(offered) = self
+            .items
+            .iter_mut()
+            .find(|i| is_same_entry(i, inventory_item))
+        {
+            offered.quantity += quantity;
+        } else {
+            self.items.push(InventoryItem {
+                quantity,
+                ..inventory_item.clone()
+            });
+        }
+    }
+
+    /// The quantity of the inventory item already in the offer.
+    pub fn offered_quantity(&self, inventory_item: &InventoryItem) -> usize {
+        self.items
+            .iter()
+            .find(|i| is_same_entry(i, inventory_item))
+            .map(|i| i.quantity)
+            .unwrap_or(0)
+    }
+
+    /// Whether the character has everything the offer gives.
+    pub fn is_affordable_by(&self, character: &CharacterModel) -> bool {
+        character.orbs >= self.orbs
+            && self.items.iter().all(|offered| {
+                items::get_item(&offered.identifier).is_some_and(|item| {
+                    character.has_item_with_values(&item, &offered.values, offered.quantity)
+                })
+            })
+    }
+
+    fn transfer(&self, from: &mut CharacterModel, to: &mut CharacterModel) {
+        from.remove_orbs(self.orbs);
+        to.add_orbs(self.orbs);
+
+        for offered in self.items.iter() {
+            let Some(item) = items::get_item(&offered.identifier) else {
+                continue;
+            };
+
+            from.remove_item_with_values(item, &offered.values, offered.quantity);
+            to.add_item(item, offered.quantity, Some(offered.values.clone()));
+        }
+    }
+}
+
+fn is_same_entry(first: &InventoryItem, second: &InventoryItem) -> bool {
+    first.identifier == second.identifier && first.values == second.values
+}
+
+/// Gives each offer to the other character.
+/// Returns `false` without changing anyone if one of them can't pay for their offer.
+pub fn exchange_offers(
+    first: &mut CharacterModel,
+    first_offer: &TradeOffer,
+    second: &mut CharacterModel,
+    second_offer: &TradeOffer,
+) -> bool {
+    if !first_offer.is_affordable_by(first) || !second_offer.is_affordable_by(second) {
+        return false;
+    }
+
+    first_offer.transfer(first, second);
+    second_offer.transfer(second, first);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use etheris_data::{personality::Personality, ItemValue, ItemValues};
+    use etheris_discord::twilight_model::id::Id;
+
+    use super::*;
+
+    fn create_character(id: u64) -> CharacterModel {
+        CharacterModel::new(
+            Id::new(id),
+            format!("Personagem {id}"),
+            vec![Personality::Calm],
+            vec![],
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn offers_are_exchanged_only_if_both_can_pay() {
+        let mut first = create_character(1);
+        let mut second = create_character(2);
+        first.add_item(items::material::STICK, 3, None);
+        second.add_orbs(100);
+
+        let mut first_offer = TradeOffer::default();
+        first_offer.add_item(
+            first.get_inventory_item(&items::material::STICK).unwrap(),
+            2,
+        );
+        let second_offer = TradeOffer {
+            orbs: 150,
+            items: vec![],
+        };
+
+        assert!(!exchange_offers(
+            &mut first,
+            &first_offer,
+            &mut second,
+            &second_offer
+        ));
+        assert_eq!(second.orbs, 100);
+        assert!(first.has_item(&items::material::STICK, 3));
+
+        let second_offer = TradeOffer {
+            orbs: 60,
+            items: vec![],
+        };
+        assert!(exchange_offers(
+            &mut first,
+            &first_offer,
+            &mut second,
+            &second_offer
+        ));
+        assert_eq!(first.orbs, 60);
+        assert_eq!(second.orbs, 40);
+        assert!(first.has_item(&items::material::STICK, 1));
+        assert!(second.has_item(&items::material::STICK, 2));
+    }
+
+    #[test]
+    fn offered_weapons_keep_their_upgrades() {
+        let katana = items::tool::KATANA;
+        let plain: ItemValues = katana.default_values.into();
+        let mut upgraded = plain.clone();
+        upgraded.set(ItemValue::UpgradeLevel(5));
+
+        let mut first = create_character(1);
+        let mut second = create_character(2);
+        first.add_item(katana, 1, Some(upgraded.clone()));
+        first.add_item(katana, 1, None);
+
+        let mut first_offer = TradeOffer::default();
+        first_offer.add_item(first.get_inventory_item_by_name("katana").unwrap(), 1);
+        assert_eq!(
+            first_offer.offered_quantity(first.get_inventory_item_by_name("katana +5").unwrap()),
+            0
+        );
+
+        assert!(exchange_offers(
+            &mut first,
+            &first_offer,
+            &mut second,
+            &TradeOffer::default()
+        ));
+        assert!(first.has_item_with_values(&katana, &upgraded, 1));
+        assert!(!first.has_item_with_values(&katana, &plain, 1));
+        assert!(second.has_item_with_values(&katana, &plain, 1));
+        assert!(!second.has_item_with_values(&katana, &upgraded, 1));
+    }
+}
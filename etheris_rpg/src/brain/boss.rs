@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use brain::Brain;
 use etheris_common::Probability;
@@ -12,7 +12,7 @@ use crate::*;
 #[derive(Debug, Clone)]
 pub struct BossBrain {
     phase: BossPhase,
-    action_weights: HashMap<BossPhase, HashMap<BattleInputKind, f64>>,
+    action_weights: HashMap<BossPhase, BTreeMap<BattleInputKind, f64>>,
     memory: Vec<BattleState>,
 }
 
@@ -44,7 +44,7 @@ impl BossBrain {
             BossPhase::Defensive,
             BossPhase::Desperate,
         ] {
-            let mut phase_weights = HashMap::new();
+            let mut phase_weights = BTreeMap::new();
             for input_kind in BattleInputKind::LIST.iter() {
                 phase_weights.insert(*input_kind, 1.0);
             }
@@ -79,7 +79,7 @@ impl BossBrain {
 
         // Reset weights for actions that can't be used in current composure
         for (input_kind, weight) in phase_weights.iter_mut() {
-            if !input_kind.can_use(BattleApi::new(api.controller)) {
+            if !input_kind.can_use(BattleApi::new(api.engine)) {
                 *weight = 0.0;
             }
         }
@@ -95,11 +95,12 @@ impl BossBrain {
 
         // Check if there's a high probability of a skill being used
         for skill in fighter.skills.iter().cloned() {
-            let prob = skill
-                .dynamic_skill
-                .lock()
-                .await
-                .ai_chance_to_pick(BattleApi::new(api.controller));
+            let dynamic_skill = skill.dynamic_skill.lock().await;
+            if !dynamic_skill.is_ready(&fighter) {
+                continue;
+            }
+
+            let prob = dynamic_skill.ai_chance_to_pick(BattleApi::new(api.engine));
 
             if prob.value() >= 99 {
                 *phase_weights.get_mut(&BattleInputKind::UseSkill).unwrap() = 10.0;
@@ -144,6 +145,10 @@ impl BossBrain {
                 Personality::Calm => {
                     *phase_weights.get_mut(&BattleInputKind::Defend).unwrap() += 0.3;
                 }
+                Personality::Arrogance => {
+                    let defend = phase_weights.get_mut(&BattleInputKind::Defend).unwrap();
+                    *defend = (*defend - 0.5).max(0.0);
+                }
                 // Add more personality-based adjustments as needed
                 _ => {}
             }
@@ -174,15 +179,13 @@ impl BossBrain {
         }
     }
 
-    fn select_action(&self, api: BattleApi<'_>) -> BattleInputKind {
+    fn select_action(&self, mut api: BattleApi<'_>) -> BattleInputKind {
         let phase_weights = self.action_weights.get(&self.phase).unwrap();
-        let mut rng = rand::thread_rng();
-
         let total_weight: f64 = phase_weights.values().sum();
-        let mut random_value = rng.gen::<f64>() * total_weight;
+        let mut random_value = api.rng().gen::<f64>() * total_weight;
 
         for (action, weight) in phase_weights {
-            if action.can_use(BattleApi::new(api.controller)) {
+            if action.can_use(BattleApi::new(api.engine)) {
                 random_value -= weight;
                 if random_value <= 0.0 {
                     return *action;
@@ -205,7 +208,7 @@ impl Brain for BossBrain {
         BrainKind::Boss
     }
 
-    async fn should_risk_life(&mut self, api: BattleApi<'_>) -> bool {
+    async fn should_risk_life(&mut self, mut api: BattleApi<'_>) -> bool {
         if api.fighter().boss.is_some() {
             return true;
         }
@@ -224,7 +227,7 @@ impl Brain for BossBrain {
             prob.add(30);
         }
 
-        prob.generate_random_bool()
+        prob.generate_random_bool_with(api.rng())
     }
 
     async fn select_input(&mut self, mut api: BattleApi<'_>) -> BattleInput {
@@ -243,10 +246,10 @@ impl Brain for BossBrain {
             composure: fighter.composure,
         };
 
-        self.adjust_weights(&battle_state, &fighter, BattleApi::new(api.controller))
+        self.adjust_weights(&battle_state, &fighter, BattleApi::new(api.engine))
             .await;
 
-        let selected_action = self.select_action(BattleApi::new(api.controller));
+        let selected_action = self.select_action(BattleApi::new(api.engine));
 
         match selected_action {
             BattleInputKind::UseSkill => {
@@ -315,21 +318,21 @@ impl Brain for BossBrain {
 }
 
 impl BossBrain {
-    async fn select_skill(&self, api: BattleApi<'_>) -> Option<FighterSkill> {
+    async fn select_skill(&self, mut api: BattleApi<'_>) -> Option<FighterSkill> {
         let fighter = api.fighter();
         let mut evaluated_skills = vec![];
 
         for skill in fighter.skills.clone() {
             let dyn_skill = skill.dynamic_skill.lock().await;
-            if dyn_skill.can_use(BattleApi::new(api.controller)) {
-                let mut chance = dyn_skill.ai_chance_to_pick(BattleApi::new(api.controller));
+            if dyn_skill.can_use(BattleApi::new(api.engine)) {
+                let mut chance = dyn_skill.ai_chance_to_pick(BattleApi::new(api.engine));
 
                 // Adjust chance based on boss phase
                 if self.phase == BossPhase::Aggressive {
                     chance.add(20);
                 }
 
-                if chance.generate_random_bool() {
+                if chance.generate_random_bool_with(api.rng()) {
                     evaluated_skills.push((skill.clone(), chance.value()));
                 }
             }
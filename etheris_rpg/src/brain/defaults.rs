@@ -1,12 +1,16 @@
 use std::cmp::Ordering;
 
 use etheris_common::Probability;
-use etheris_data::personality::Personality;
+use etheris_data::{personality::Personality, SkillKind};
+use etheris_database::character_model::BattleAction;
 use rand::{seq::SliceRandom, Rng};
 
 use crate::*;
 
-pub async fn should_risk_life(api: BattleApi<'_>) -> bool {
+/// Skills that give health back to whoever uses them.
+const HEALING_SKILLS: &[SkillKind] = &[SkillKind::WoundHealing, SkillKind::BloodTheft];
+
+pub async fn should_risk_life(mut api: BattleApi<'_>) -> bool {
     let fighter = api.fighter();
     let target = api.target();
     let pl_diff = fighter.pl - target.pl;
@@ -53,7 +57,7 @@ pub async fn should_risk_life(api: BattleApi<'_>) -> bool {
     let ether_ratio = fighter.ether.value as f64 / fighter.ether.max as f64;
     prob = prob.saturating_add((ether_ratio * 15.0) as u8);
 
-    Probability::new(prob).generate_random_bool()
+    Probability::new(prob).generate_random_bool_with(api.rng())
 }
 
 pub async fn allow_fighter_to_enter_his_team(api: BattleApi<'_>, _index: FighterIndex) -> bool {
@@ -87,10 +91,21 @@ pub async fn select_target(api: &mut BattleApi<'_>) {
         .filter(|f| f.team != fighter.team && !f.is_defeated)
         .collect();
 
+    // Cowards pick on the weakest enemy, arrogant fighters want to beat the strongest one
+    let priority = |target: &Fighter| {
+        if fighter.has_personality(Personality::Cowardice) {
+            -(target.pl as f64)
+        } else if fighter.has_personality(Personality::Arrogance) {
+            target.pl as f64
+        } else {
+            calculate_threat_level(api, target)
+        }
+    };
+
     potential_targets.sort_by(|a, b| {
-        let a_threat = calculate_threat_level(api, a);
-        let b_threat = calculate_threat_level(api, b);
-        b_threat.partial_cmp(&a_threat).unwrap_or(Ordering::Equal)
+        priority(b)
+            .partial_cmp(&priority(a))
+            .unwrap_or(Ordering::Equal)
     });
 
     if let Some(new_target) = potential_targets.first() {
@@ -98,6 +113,8 @@ pub async fn select_target(api: &mut BattleApi<'_>) {
     } else {
         api.battle_mut().reallocate_fighter_target(fighter.index);
     }
+
+    api.target_index = api.fighter().target;
 }
 
 pub fn calculate_threat_level(api: &BattleApi<'_>, target: &Fighter) -> f64 {
@@ -121,8 +138,12 @@ pub async fn select_a_input(mut api: BattleApi<'_>) -> BattleInput {
         return select_finisher(&fighter, api.rng());
     }
 
+    if let Some(input) = select_personality_input(&mut api).await {
+        return input;
+    }
+
     if fighter.composure == Composure::OnGround {
-        return handle_ground_situation(&fighter, &target);
+        return handle_ground_situation(&fighter, &target, api.rng());
     }
 
     let skills = evaluate_skills(&fighter, &mut api).await;
@@ -131,13 +152,76 @@ pub async fn select_a_input(mut api: BattleApi<'_>) -> BattleInput {
         return BattleInput::UseSkill(skills[0].clone());
     }
 
-    if should_defend(&fighter, &target) {
+    if should_defend(&fighter, &target, api.rng()) {
         return BattleInput::Defend;
     }
 
     BattleInput::Attack
 }
 
+/// The input the personalities of the fighter push it to, if any of them is stronger than the reason.
+pub async fn select_personality_input(api: &mut BattleApi<'_>) -> Option<BattleInput> {
+    let fighter = api.fighter().clone();
+    let target = api.target().clone();
+    let health_ratio = fighter.health().value as f64 / fighter.health().max as f64;
+    let is_ai = fighter.user.is_none();
+
+    if is_ai
+        && fighter.has_personality(Personality::Cowardice)
+        && fighter.boss.is_none()
+        && health_ratio < 0.35
+        && !fighter.flags.contains(FighterFlags::GAVE_UP)
+        && Probability::new(60).generate_random_bool_with(api.rng())
+    {
+        // Nobody runs away while lying on the ground, so it begs for mercy instead
+        return Some(if fighter.composure == Composure::OnGround {
+            BattleInput::UseAction(BattleAction::GiveUp)
+        } else {
+            BattleInput::Flee
+        });
+    }
+
+    if is_ai
+        && fighter.has_personality(Personality::Arrogance)
+        && fighter.composure != Composure::OnGround
+    {
+        let taunt_probability = if fighter.pl >= target.pl && health_ratio > 0.5 {
+            Probability::new(20)
+        } else {
+            Probability::new(5)
+        };
+
+        if taunt_probability.generate_random_bool_with(api.rng()) {
+            return Some(BattleInput::Taunt);
+        }
+    }
+
+    if fighter.has_personality(Personality::Calm) && health_ratio < 0.4 {
+        if let Some(item) = fighter.inventory.iter().find(|i| {
+            i.item
+                .consumption_properties
+                .is_some_and(|p| p.health_regenation > 0)
+        }) {
+            return Some(BattleInput::UseItem(item.item));
+        }
+
+        if fighter.composure != Composure::OnGround {
+            for skill in fighter
+                .skills
+                .iter()
+                .filter(|s| HEALING_SKILLS.contains(&s.base_kind))
+            {
+                let dynamic_skill = skill.dynamic_skill.lock().await;
+                if dynamic_skill.can_use(BattleApi::new(api.engine)) {
+                    return Some(BattleInput::UseSkill(skill.clone()));
+                }
+            }
+        }
+    }
+
+    None
+}
+
 fn select_finisher(fighter: &Fighter, rng: &mut impl Rng) -> BattleInput {
     let finisher = fighter
         .finishers
@@ -155,7 +239,7 @@ fn select_finisher(fighter: &Fighter, rng: &mut impl Rng) -> BattleInput {
     BattleInput::Finish(*finisher)
 }
 
-fn handle_ground_situation(fighter: &Fighter, target: &Fighter) -> BattleInput {
+fn handle_ground_situation(fighter: &Fighter, target: &Fighter, rng: &mut impl Rng) -> BattleInput {
     let mut upkick_prob = if target.health().value < (target.health().max / 4) {
         Probability::new(30)
     } else {
@@ -168,7 +252,7 @@ fn handle_ground_situation(fighter: &Fighter, target: &Fighter) -> BattleInput {
         upkick_prob.add(15);
     }
 
-    if upkick_prob.generate_random_bool() {
+    if upkick_prob.generate_random_bool_with(rng) {
         BattleInput::Upkick
     } else {
         BattleInput::GetUp
@@ -180,9 +264,9 @@ async fn evaluate_skills(fighter: &Fighter, api: &mut BattleApi<'_>) -> Vec<Figh
 
     for skill in fighter.skills.iter() {
         let dyn_skill = skill.dynamic_skill.lock().await;
-        if dyn_skill.can_use(BattleApi::new(api.controller)) {
-            let chance = dyn_skill.ai_chance_to_pick(BattleApi::new(api.controller));
-            if chance.generate_random_bool() {
+        if dyn_skill.can_use(BattleApi::new(api.engine)) {
+            let chance = dyn_skill.ai_chance_to_pick(BattleApi::new(api.engine));
+            if chance.generate_random_bool_with(api.rng()) {
                 evaluated_skills.push((skill.clone(), chance.value()));
             }
         }
@@ -211,7 +295,7 @@ fn should_use_skill(fighter: &Fighter, skills: &[FighterSkill], rng: &mut impl R
     rng.gen_bool(skill_use_chance)
 }
 
-fn should_defend(fighter: &Fighter, target: &Fighter) -> bool {
+fn should_defend(fighter: &Fighter, target: &Fighter, rng: &mut impl Rng) -> bool {
     if fighter.has_personality(Personality::Insanity) {
         return false;
     }
@@ -220,6 +304,14 @@ fn should_defend(fighter: &Fighter, target: &Fighter) -> bool {
     let ether_ratio = fighter.ether.value as f64 / fighter.ether.max as f64;
     let target_health_ratio = target.health().value as f64 / target.health().max as f64;
 
+    // Calm fighters defend when it pays off: to recover posture and ether, or to avoid a prepared reaction
+    if fighter.has_personality(Personality::Calm)
+        && target_health_ratio > 0.25
+        && (fighter.balance < 50 || ether_ratio < 0.3 || target.reaction.is_some())
+    {
+        return true;
+    }
+
     let base_defend_chance = if fighter.has_personality(Personality::Calm)
         || fighter.has_personality(Personality::Intelligence)
     {
@@ -232,7 +324,12 @@ fn should_defend(fighter: &Fighter, target: &Fighter) -> bool {
     let ether_factor = if ether_ratio <= 0.5 { 0.2 } else { 0.05 };
     let target_factor = target_health_ratio * 0.15;
 
-    let total_chance = base_defend_chance + health_factor + ether_factor - target_factor;
+    let mut total_chance = base_defend_chance + health_factor + ether_factor - target_factor;
+
+    // Arrogant fighters think they don't need to defend
+    if fighter.has_personality(Personality::Arrogance) {
+        total_chance *= 0.3;
+    }
 
-    rand::thread_rng().gen_bool(total_chance)
+    rng.gen_bool(total_chance.clamp(0.0, 1.0))
 }
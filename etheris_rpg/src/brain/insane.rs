@@ -14,7 +14,8 @@ impl Brain for InsaneBrain {
         true
     }
 
-    async fn select_input(&mut self, api: BattleApi<'_>) -> BattleInput {
+    async fn select_input(&mut self, mut api: BattleApi<'_>) -> BattleInput {
+        defaults::select_target(&mut api).await;
         defaults::select_a_input(api).await
     }
 
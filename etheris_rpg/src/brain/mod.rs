@@ -2,12 +2,14 @@ mod boss;
 pub mod defaults;
 mod insane;
 mod simple;
+mod tactical;
 
 pub use boss::BossBrain;
 pub use insane::InsaneBrain;
 pub use simple::SimpleBrain;
+pub use tactical::TacticalBrain;
 
-pub use etheris_data::BrainKind;
+pub use etheris_data::{BrainDifficulty, BrainKind};
 
 use crate::*;
 
@@ -28,5 +30,6 @@ pub fn make_brain(kind: BrainKind) -> Box<dyn Brain + Send + 'static> {
         BrainKind::Simple => Box::new(SimpleBrain),
         BrainKind::Insane => Box::new(InsaneBrain),
         BrainKind::Boss => Box::new(BossBrain::new()),
+        BrainKind::Tactical(difficulty) => Box::new(TacticalBrain::new(difficulty)),
     }
 }
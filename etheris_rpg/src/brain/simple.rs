@@ -14,7 +14,8 @@ impl Brain for SimpleBrain {
         defaults::should_risk_life(api).await
     }
 
-    async fn select_input(&mut self, api: BattleApi<'_>) -> BattleInput {
+    async fn select_input(&mut self, mut api: BattleApi<'_>) -> BattleInput {
+        defaults::select_target(&mut api).await;
         defaults::select_a_input(api).await
     }
 
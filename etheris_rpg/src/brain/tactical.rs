@@ -0,0 +1,295 @@
+use etheris_data::{personality::Personality, BrainDifficulty};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{defaults, Brain, BrainKind};
+use crate::*;
+
+const ENEMY_HEALTH_WEIGHT: f64 = 10.0;
+const ALLY_HEALTH_WEIGHT: f64 = 8.0;
+const DEFEAT_WEIGHT: f64 = 12.0;
+const EFFECT_WEIGHT: f64 = 3.0;
+const ETHER_WEIGHT: f64 = 4.0;
+const FINISHER_WEIGHT: f64 = 2.5;
+const GROUND_WEIGHT: f64 = 1.5;
+
+/// A brain that plays every option it has in a copy of the battle and picks the one that ended up best.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TacticalBrain {
+    difficulty: BrainDifficulty,
+}
+
+impl TacticalBrain {
+    pub fn new(difficulty: BrainDifficulty) -> Self {
+        Self { difficulty }
+    }
+
+    /// How many times each option is simulated. The average smooths the luck of the rolls.
+    fn samples(&self) -> usize {
+        match self.difficulty {
+            BrainDifficulty::Easy => 1,
+            BrainDifficulty::Normal => 2,
+            BrainDifficulty::Hard => 4,
+        }
+    }
+
+    /// The chance of ignoring the simulations and picking any option.
+    fn mistake_chance(&self) -> f64 {
+        match self.difficulty {
+            BrainDifficulty::Easy => 0.3,
+            BrainDifficulty::Normal => 0.1,
+            BrainDifficulty::Hard => 0.0,
+        }
+    }
+
+    /// Whether the brain also simulates the target attacking back after the option.
+    fn looks_ahead(&self) -> bool {
+        self.difficulty == BrainDifficulty::Hard
+    }
+
+    async fn simulate(&self, battle: &Battle, input: &BattleInput, seed: u64) -> f64 {
+        let fighter_index = battle.current_fighter;
+        let mut engine = BattleEngine::new_headless(simulation_battle(battle, seed));
+
+        let Some(input) = simulation_input(&engine.battle, input) else {
+            return f64::MIN;
+        };
+
+        if engine.process_input(input).await.is_err() {
+            return f64::MIN;
+        }
+
+        let target_index = engine.battle.get_fighter(fighter_index).target;
+        let target = engine.battle.get_fighter(target_index).clone();
+        if self.looks_ahead()
+            && target_index != fighter_index
+            && !target.is_defeated
+            && target.composure != Composure::OnGround
+        {
+            engine.battle.current_fighter = target_index;
+            engine.battle.get_fighter_mut(target_index).target = fighter_index;
+            let _ = engine.process_input(BattleInput::Attack).await;
+            engine.battle.current_fighter = fighter_index;
+            engine.battle.get_fighter_mut(target_index).target = target.target;
+        }
+
+        let can_finish_target = !engine.battle.get_fighter(target_index).is_defeated
+            && BattleApi::new(&mut engine).can_finish_target();
+
+        score_outcome(battle, &engine.battle, fighter_index, can_finish_target)
+    }
+}
+
+#[async_trait::async_trait]
+impl Brain for TacticalBrain {
+    fn kind(&self) -> BrainKind {
+        BrainKind::Tactical(self.difficulty)
+    }
+
+    async fn should_risk_life(&mut self, api: BattleApi<'_>) -> bool {
+        defaults::should_risk_life(api).await
+    }
+
+    async fn select_input(&mut self, mut api: BattleApi<'_>) -> BattleInput {
+        defaults::select_target(&mut api).await;
+
+        if let Some(input) = defaults::select_personality_input(&mut api).await {
+            return input;
+        }
+
+        let candidates = candidate_inputs(&mut api).await;
+        if candidates.is_empty() {
+            return defaults::select_a_input(api).await;
+        }
+
+        if api.rng().gen_bool(self.mistake_chance()) {
+            if let Some(input) = candidates.choose(api.rng()) {
+                return input.clone();
+            }
+        }
+
+        let seeds = (0..self.samples())
+            .map(|_| api.rng().gen())
+            .collect::<Vec<u64>>();
+
+        let mut best: Option<(f64, BattleInput)> = None;
+        for input in candidates {
+            let mut score = 0.0;
+            for seed in seeds.iter() {
+                score += self.simulate(api.battle(), &input, *seed).await;
+            }
+            let score = score / seeds.len() as f64;
+
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _)| score > *best_score)
+            {
+                best = Some((score, input));
+            }
+        }
+
+        best.map(|(_, input)| input).unwrap_or(BattleInput::Attack)
+    }
+
+    async fn allow_fighter_to_enter_his_team(
+        &mut self,
+        mut api: BattleApi<'_>,
+        fighter: FighterIndex,
+    ) -> bool {
+        defaults::select_target(&mut api).await;
+        defaults::allow_fighter_to_enter_his_team(api, fighter).await
+    }
+}
+
+/// Every input worth simulating in the current situation.
+async fn candidate_inputs(api: &mut BattleApi<'_>) -> Vec<BattleInput> {
+    let fighter = api.fighter().clone();
+
+    if api.can_finish_target() {
+        return fighter
+            .finishers
+            .iter()
+            .map(|f| BattleInput::Finish(*f))
+            .collect();
+    }
+
+    if fighter.composure == Composure::OnGround {
+        return vec![BattleInput::GetUp, BattleInput::Upkick];
+    }
+
+    let mut candidates = vec![BattleInput::Attack];
+
+    // Arrogant fighters only think about defending when they are really hurt
+    let health_ratio = fighter.health().value as f64 / fighter.health().max as f64;
+    if !fighter.has_personality(Personality::Arrogance) || health_ratio < 0.3 {
+        candidates.push(BattleInput::Defend);
+    }
+
+    for skill in fighter.skills.iter() {
+        let dynamic_skill = skill.dynamic_skill.lock().await;
+        if dynamic_skill.can_use(BattleApi::new(api.engine)) {
+            candidates.push(BattleInput::UseSkill(skill.clone()));
+        }
+    }
+
+    candidates.extend(
+        fighter
+            .inventory
+            .iter()
+            .filter(|i| i.item.consumption_properties.is_some())
+            .map(|i| BattleInput::UseItem(i.item)),
+    );
+
+    candidates
+}
+
+/// A copy of the battle that can be played without touching the real one.
+/// The skills are rebuilt so their inner state isn't shared with the real fighters.
+fn simulation_battle(battle: &Battle, seed: u64) -> Battle {
+    let mut battle = battle.clone();
+    battle.rng = StdRng::seed_from_u64(seed);
+
+    for fighter in battle.fighters.iter_mut() {
+        for skill in fighter.skills.iter_mut() {
+            skill.dynamic_skill = FighterSkill::from(skill.base_kind.clone()).dynamic_skill;
+        }
+    }
+
+    battle
+}
+
+/// Points the input to the skills of the simulated battle instead of the real ones.
+fn simulation_input(battle: &Battle, input: &BattleInput) -> Option<BattleInput> {
+    match input {
+        BattleInput::UseSkill(skill) => battle
+            .get_current_fighter()
+            .skills
+            .iter()
+            .find(|s| s.identifier == skill.identifier)
+            .cloned()
+            .map(BattleInput::UseSkill),
+        input => Some(input.clone()),
+    }
+}
+
+fn health_ratio(fighter: &Fighter) -> f64 {
+    fighter.health().value as f64 / fighter.health().max.max(1) as f64
+}
+
+fn ether_ratio(fighter: &Fighter) -> f64 {
+    fighter.ether.value as f64 / fighter.ether.max.max(1) as f64
+}
+
+/// How much the effects caused by `culprit` weigh on the fighter.
+fn effect_pressure(fighter: &Fighter, culprit: FighterIndex) -> f64 {
+    fighter
+        .effects
+        .iter()
+        .filter(|e| e.culprit == culprit)
+        .map(|e| e.amount.clamp(0, 100) as f64 / 100.0)
+        .sum()
+}
+
+/// How much the effects caused by anyone else weigh on the fighter.
+fn hostile_effect_pressure(fighter: &Fighter) -> f64 {
+    fighter
+        .effects
+        .iter()
+        .filter(|e| e.culprit != fighter.index)
+        .map(|e| e.amount.clamp(0, 100) as f64 / 100.0)
+        .sum()
+}
+
+/// Scores how much better the battle got for `fighter_index` from `before` to `after`.
+fn score_outcome(
+    before: &Battle,
+    after: &Battle,
+    fighter_index: FighterIndex,
+    can_finish_target: bool,
+) -> f64 {
+    let fighter_before = before.get_fighter(fighter_index);
+    let fighter_after = after.get_fighter(fighter_index);
+    let mut score = 0.0;
+
+    // Fighters that joined during the simulation have nothing to compare to
+    for (previous, current) in before.fighters.iter().zip(after.fighters.iter()) {
+        if previous.is_defeated {
+            continue;
+        }
+
+        let health_lost = health_ratio(previous) - health_ratio(current);
+
+        if previous.team == fighter_before.team {
+            score -= health_lost * ALLY_HEALTH_WEIGHT;
+            if current.is_defeated {
+                score -= DEFEAT_WEIGHT;
+            }
+        } else {
+            // Hurting a weakened enemy is worth more than scratching a healthy one
+            score += health_lost * ENEMY_HEALTH_WEIGHT * (2.0 - health_ratio(previous));
+            score += (effect_pressure(current, fighter_index)
+                - effect_pressure(previous, fighter_index))
+                * EFFECT_WEIGHT;
+            if current.is_defeated {
+                score += DEFEAT_WEIGHT;
+            }
+        }
+    }
+
+    score -= (hostile_effect_pressure(fighter_after) - hostile_effect_pressure(fighter_before))
+        * EFFECT_WEIGHT;
+
+    // Spending ether hurts more the less of it is left
+    let ether_scarcity = 1.5 - ether_ratio(fighter_before);
+    score +=
+        (ether_ratio(fighter_after) - ether_ratio(fighter_before)) * ETHER_WEIGHT * ether_scarcity;
+
+    if can_finish_target {
+        score += FINISHER_WEIGHT;
+    }
+
+    if fighter_after.composure == Composure::OnGround {
+        score -= GROUND_WEIGHT;
+    }
+
+    score
+}
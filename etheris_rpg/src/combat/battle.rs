@@ -1,18 +1,20 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, VecDeque},
     fmt::Display,
     ops::{Add, Sub},
 };
 
-use etheris_data::world::regions::WorldRegion;
+use etheris_data::{equipment::EquipmentPassive, world::regions::WorldRegion, SkillKind};
 use rand::{
     rngs::StdRng,
     seq::{IteratorRandom, SliceRandom},
     SeedableRng,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    common::DamageSpecifier, EffectKind, Fighter, FighterData, FighterFlags, FighterIndex,
+    common::DamageSpecifier, BattleEvent, EffectKind, Fighter, FighterData, FighterFlags,
+    FighterIndex,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -30,12 +32,21 @@ impl Display for BattleError {
 
 impl std::error::Error for BattleError {}
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct TurnHistory {
     pub round: usize,
     pub fighter: FighterIndex,
     pub target: FighterIndex,
-    pub messages: Vec<String>,
+    pub events: Vec<BattleEvent>,
+}
+
+impl TurnHistory {
+    pub fn messages(&self, battle: &Battle) -> Vec<String> {
+        self.events
+            .iter()
+            .filter_map(|event| event.render(battle))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -47,12 +58,17 @@ pub enum BattleState {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct BattleSettings {
     pub is_risking_life_allowed: bool,
     pub casual: bool,
     pub has_consequences: bool,
     pub max_intruders: u8,
+    /// Seed of the battle RNG. A random one is picked when it's `None`.
+    pub seed: Option<u64>,
+    /// Ranked duels update the PvP rating of the players. They're never to the death.
+    #[serde(default)]
+    pub ranked: bool,
 }
 
 impl Default for BattleSettings {
@@ -62,6 +78,8 @@ impl Default for BattleSettings {
             has_consequences: true,
             casual: false,
             max_intruders: 0,
+            seed: None,
+            ranked: false,
         }
     }
 }
@@ -74,6 +92,7 @@ pub struct TurnEndQueues {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Battle {
+    pub seed: u64,
     pub rng: StdRng,
     pub fighters: Vec<Fighter>,
     pub alive_fighters: Vec<FighterIndex>,
@@ -81,6 +100,8 @@ pub struct Battle {
     pub fighters_queue: VecDeque<FighterIndex>,
     pub current_fighter: FighterIndex,
     pub region: WorldRegion,
+    /// The fighters the battle started with, kept to build replays.
+    pub initial_fighters: Vec<FighterData>,
 
     pub history: Vec<TurnHistory>,
     pub turn_counter: usize,
@@ -105,6 +126,7 @@ impl Battle {
         }
 
         let number_of_fighters = fighters.len();
+        let initial_fighters = fighters.clone();
 
         let alive_fighters = (0..fighters.len()).map(FighterIndex).collect::<Vec<_>>();
         let fighters = fighters
@@ -120,15 +142,19 @@ impl Battle {
             })
             .collect::<Vec<_>>();
 
+        let seed = settings.seed.unwrap_or_else(rand::random);
+
         let mut battle = Battle {
             settings,
-            rng: StdRng::from_entropy(),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
             fighters,
             fighters_queue: alive_fighters.clone().into(),
             alive_fighters,
             defeated_fighters: vec![],
             current_fighter: FighterIndex(usize::MAX / 2), // Dummy value
             region,
+            initial_fighters,
             history: vec![],
             turn_counter: 0,
             cycle_counter: 0,
@@ -153,8 +179,20 @@ impl Battle {
         Ok(battle)
     }
 
-    pub fn full_teams(&self) -> HashMap<u8, Vec<Fighter>> {
-        let mut map: HashMap<u8, Vec<Fighter>> = HashMap::with_capacity(self.fighters.len());
+    pub fn winner_team(&self) -> Option<u8> {
+        match self.state {
+            BattleState::Ended { winner_team, .. } => Some(winner_team),
+            BattleState::Running => None,
+        }
+    }
+
+    /// Every event of the battle, in order.
+    pub fn events(&self) -> impl Iterator<Item = &BattleEvent> {
+        self.history.iter().flat_map(|turn| turn.events.iter())
+    }
+
+    pub fn full_teams(&self) -> BTreeMap<u8, Vec<Fighter>> {
+        let mut map: BTreeMap<u8, Vec<Fighter>> = BTreeMap::new();
         for fighter in self.fighters.iter() {
             let fighters = map.entry(fighter.team).or_default();
             fighters.push(fighter.clone());
@@ -163,8 +201,9 @@ impl Battle {
         map
     }
 
-    pub fn teams(&self) -> HashMap<u8, Vec<FighterIndex>> {
-        let mut map: HashMap<u8, Vec<FighterIndex>> = HashMap::with_capacity(self.fighters.len());
+    /// Teams are kept in a `BTreeMap` so iterating them doesn't break seeded battles.
+    pub fn teams(&self) -> BTreeMap<u8, Vec<FighterIndex>> {
+        let mut map: BTreeMap<u8, Vec<FighterIndex>> = BTreeMap::new();
         for fighter in self.alive_fighters.iter() {
             let fighter = self.get_fighter(*fighter);
             let fighters = map.entry(fighter.team).or_default();
@@ -218,12 +257,35 @@ impl Battle {
         }
     }
 
+    /// The skills used by the fighter in its last consecutive turns, from the oldest to the newest.
+    /// A turn without a skill breaks the sequence.
+    pub fn last_skills_used_by(&self, fighter: FighterIndex, turns: usize) -> Vec<SkillKind> {
+        let mut skills = self
+            .history
+            .iter()
+            .rev()
+            .filter(|turn| turn.fighter == fighter)
+            .take(turns)
+            .map_while(|turn| {
+                turn.events.iter().rev().find_map(|event| match event {
+                    BattleEvent::SkillUsed { fighter: f, skill } if *f == fighter => {
+                        Some(skill.clone())
+                    }
+                    _ => None,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        skills.reverse();
+        skills
+    }
+
     pub fn next_turn(&mut self, this_turn_history: &mut TurnHistory) {
         self.turn_counter += 1;
 
         // Deferred turn messages
         for message in self.deferred_turn_messages.clone() {
-            this_turn_history.messages.push(message);
+            this_turn_history.events.push(BattleEvent::Message(message));
         }
 
         self.deferred_turn_messages.clear();
@@ -249,16 +311,19 @@ impl Battle {
             self.alive_fighters.retain(|f| f.0 != fighter.0);
             self.fighters_queue.retain(|f| f.0 != fighter.0);
 
-            let fighter = self.get_fighter(fighter).clone();
-            this_turn_history.messages.push(format!(
-                "**{}** {}.",
-                fighter.name,
-                if fighter.vitality.value <= 0 {
-                    "morreu"
+            let fighter = self.get_fighter(fighter);
+            this_turn_history
+                .events
+                .push(if fighter.flags.contains(FighterFlags::FLED) {
+                    BattleEvent::FighterFled {
+                        fighter: fighter.index,
+                    }
                 } else {
-                    "desmaiou"
-                }
-            ));
+                    BattleEvent::FighterDefeated {
+                        fighter: fighter.index,
+                        died: fighter.vitality.value <= 0,
+                    }
+                });
         }
 
         let teams = self.teams();
@@ -269,9 +334,9 @@ impl Battle {
                 .copied()
                 .unwrap_or(self.fighters[0].team);
             if teams.get(&winner_team).is_none() {
-                this_turn_history
-                    .messages
-                    .push(format!("ERRO: Time {winner_team} não encontrado."));
+                this_turn_history.events.push(BattleEvent::Message(format!(
+                    "ERRO: Time {winner_team} não encontrado."
+                )));
                 return;
             }
 
@@ -282,25 +347,10 @@ impl Battle {
                 winners: winners.clone(),
             };
 
-            if winners.len() == 1 {
-                let winner = self.get_fighter(winners[0]);
-                this_turn_history
-                    .messages
-                    .push(format!("> :medal: **{}** venceu!", winner.name));
-            } else {
-                let team_winners = winners
-                    .iter()
-                    .map(|w| self.get_fighter(*w).clone())
-                    .collect::<Vec<_>>();
-                this_turn_history.messages.push(format!(
-                    "> :medal: O time **[{winner_team}]** venceu! Os vencedores são: `{}`",
-                    team_winners
-                        .iter()
-                        .map(|w| w.name.to_owned())
-                        .collect::<Vec<_>>()
-                        .join("`, `")
-                ));
-            }
+            this_turn_history.events.push(BattleEvent::TeamWon {
+                team: winner_team,
+                winners,
+            });
         }
 
         // Tick every fighter
@@ -336,6 +386,16 @@ impl Battle {
             fighter.defense = fighter.defense.saturating_sub(1);
             fighter.ether.add(ether_rec);
 
+            for passive in fighter.passives.clone() {
+                match passive {
+                    EquipmentPassive::Regeneration(amount) => fighter.heal(alive_fighter, amount),
+                    EquipmentPassive::EtherRecovery(amount) => fighter.ether.add(amount),
+                    EquipmentPassive::Steadiness(amount) => {
+                        fighter.balance = fighter.balance.saturating_add(amount).min(100)
+                    }
+                }
+            }
+
             if self.get_fighter(target).is_defeated
                 || (self.get_fighter(target).team == fighter_team && fighter_is_ai)
             {
@@ -376,6 +436,16 @@ impl Battle {
         &mut self.fighters[index.0]
     }
 
+    /// Deals damage directly to a fighter, rolling any chance with the battle RNG.
+    pub fn damage_fighter(
+        &mut self,
+        index: FighterIndex,
+        culprit: FighterIndex,
+        damage: DamageSpecifier,
+    ) {
+        self.fighters[index.0].take_damage(&mut self.rng, culprit, damage);
+    }
+
     pub fn get_current_fighter(&self) -> &Fighter {
         self.get_fighter(self.current_fighter)
     }
@@ -0,0 +1,89 @@
+use etheris_data::SkillKind;
+use serde::{Deserialize, Serialize};
+
+use crate::{common::DamageKind, data::finishers::Finisher, Battle, EffectKind, FighterIndex};
+
+/// Something that happened in a battle. The text history of a turn is rendered from its events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BattleEvent {
+    /// Free text, like the flavour messages of skills and weapons.
+    Message(String),
+    DamageDealt {
+        culprit: FighterIndex,
+        target: FighterIndex,
+        kind: DamageKind,
+        amount: i32,
+        missed: bool,
+        dodged: bool,
+        defended: bool,
+    },
+    EffectApplied {
+        culprit: FighterIndex,
+        target: FighterIndex,
+        kind: EffectKind,
+        amount: i32,
+    },
+    OverloadAdded {
+        fighter: FighterIndex,
+        amount: f64,
+        total: f64,
+    },
+    SkillUsed {
+        fighter: FighterIndex,
+        skill: SkillKind,
+    },
+    FinisherUsed {
+        fighter: FighterIndex,
+        target: FighterIndex,
+        finisher: Finisher,
+        failed: bool,
+    },
+    FighterDefeated {
+        fighter: FighterIndex,
+        died: bool,
+    },
+    FighterFled {
+        fighter: FighterIndex,
+    },
+    TeamWon {
+        team: u8,
+        winners: Vec<FighterIndex>,
+    },
+}
+
+impl BattleEvent {
+    /// The text shown in the turn history, if the event has one.
+    /// Damage, effects, overload, skills and finishers are already described by the messages of whoever caused them.
+    pub fn render(&self, battle: &Battle) -> Option<String> {
+        match self {
+            Self::Message(message) => Some(message.to_owned()),
+            Self::FighterDefeated { fighter, died } => Some(format!(
+                "**{}** {}.",
+                battle.get_fighter(*fighter).name,
+                if *died { "morreu" } else { "desmaiou" }
+            )),
+            Self::FighterFled { fighter } => Some(format!(
+                "**{}** fugiu da batalha.",
+                battle.get_fighter(*fighter).name
+            )),
+            Self::TeamWon { team, winners } => {
+                if winners.len() == 1 {
+                    Some(format!(
+                        "> :medal: **{}** venceu!",
+                        battle.get_fighter(winners[0]).name
+                    ))
+                } else {
+                    Some(format!(
+                        "> :medal: O time **[{team}]** venceu! Os vencedores são: `{}`",
+                        winners
+                            .iter()
+                            .map(|w| battle.get_fighter(*w).name.to_owned())
+                            .collect::<Vec<_>>()
+                            .join("`, `")
+                    ))
+                }
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::fmt::Display;
+
+use crate::{common::DamageKind, EffectKind};
+
+/// Damage dealt every turn to the fighter with the effect: `base` plus a ratio of its max health.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectTickDamage {
+    pub kind: DamageKind,
+    pub base: i32,
+    pub max_health_ratio: f32,
+    pub balance_effectiveness: u8,
+    pub message: &'static str,
+}
+
+/// What happens when an effect reaches a certain amount.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EffectThreshold {
+    /// The effect is replaced by another one.
+    Transform {
+        amount: i32,
+        into: EffectKind,
+        into_amount: i32,
+        message: &'static str,
+    },
+    /// The effect is consumed to deal `base` plus a ratio of the fighter's max health.
+    Burst {
+        amount: i32,
+        base: i32,
+        max_health_ratio: f32,
+        message: &'static str,
+    },
+}
+
+impl EffectThreshold {
+    pub const fn amount(&self) -> i32 {
+        match self {
+            Self::Transform { amount, .. } | Self::Burst { amount, .. } => *amount,
+        }
+    }
+}
+
+/// How an effect reacts every turn to another effect on the same fighter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EffectInteraction {
+    /// Removes `amount` of the other effect.
+    Weaken { other: EffectKind, amount: i32 },
+    /// Turns up to `amount` of the other effect into `into`.
+    Convert {
+        other: EffectKind,
+        amount: i32,
+        into: EffectKind,
+    },
+    /// Consumes the other effect to deal damage. `tiers` maps the maximum amount of the other
+    /// effect to the ratio of the max health dealt as damage.
+    Amplify {
+        other: EffectKind,
+        damage_kind: DamageKind,
+        tiers: &'static [(i32, f32)],
+        balance_effectiveness: u8,
+        /// An effect that may be applied after the damage, with the probability of it happening.
+        follow_up: Option<(EffectKind, u8)>,
+        message: &'static str,
+    },
+}
+
+/// The behaviour of an effect during the battle. Messages can use `{name}` and `{dmg}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectRules {
+    pub max_stacks: i32,
+    /// How much of the effect goes away every turn.
+    pub decay: i32,
+    pub tick_damage: Option<EffectTickDamage>,
+    pub threshold: Option<EffectThreshold>,
+    pub interactions: &'static [EffectInteraction],
+    pub applied_message: &'static str,
+    pub expired_message: Option<&'static str>,
+}
+
+impl EffectRules {
+    const fn new(max_stacks: i32, decay: i32, applied_message: &'static str) -> Self {
+        Self {
+            max_stacks,
+            decay,
+            tick_damage: None,
+            threshold: None,
+            interactions: &[],
+            applied_message,
+            expired_message: None,
+        }
+    }
+}
+
+pub fn format_effect_message(message: &str, name: &str) -> String {
+    message.replace("{name}", name)
+}
+
+pub fn format_effect_damage_message(message: &str, name: &str, damage: impl Display) -> String {
+    format_effect_message(message, name).replace("{dmg}", &damage.to_string())
+}
+
+impl EffectKind {
+    pub const fn rules(&self) -> EffectRules {
+        match self {
+            Self::Flaming => EffectRules {
+                tick_damage: Some(EffectTickDamage {
+                    kind: DamageKind::Fire,
+                    base: 2,
+                    max_health_ratio: 0.01,
+                    balance_effectiveness: 1,
+                    message: "***{name}** queimou e recebeu **{dmg}**!",
+                }),
+                threshold: Some(EffectThreshold::Transform {
+                    amount: 100,
+                    into: Self::Burning,
+                    into_amount: 20,
+                    message: "**{name}** queimou tanto que entrou em combustão!",
+                }),
+                interactions: &[
+                    EffectInteraction::Convert {
+                        other: Self::Frozen,
+                        amount: 10,
+                        into: Self::Wet,
+                    },
+                    EffectInteraction::Convert {
+                        other: Self::Ice,
+                        amount: 5,
+                        into: Self::Wet,
+                    },
+                ],
+                ..EffectRules::new(100, 5, "**{name}** está queimando!")
+            },
+            Self::Burning => EffectRules {
+                tick_damage: Some(EffectTickDamage {
+                    kind: DamageKind::Fire,
+                    base: 8,
+                    max_health_ratio: 0.03,
+                    balance_effectiveness: 4,
+                    message: "***{name}** está em combustão e recebeu **{dmg}**!",
+                }),
+                interactions: &[
+                    EffectInteraction::Convert {
+                        other: Self::Frozen,
+                        amount: 20,
+                        into: Self::Wet,
+                    },
+                    EffectInteraction::Convert {
+                        other: Self::Ice,
+                        amount: 15,
+                        into: Self::Wet,
+                    },
+                ],
+                ..EffectRules::new(60, 5, "**{name}** entrou em combustão!")
+            },
+            Self::Shocked => EffectRules {
+                threshold: Some(EffectThreshold::Transform {
+                    amount: 100,
+                    into: Self::Paralyzed,
+                    into_amount: 1,
+                    message: "**{name}** paralisou devido ao choque!",
+                }),
+                ..EffectRules::new(100, 10, "**{name}** está em choque!")
+            },
+            Self::Ice => EffectRules {
+                threshold: Some(EffectThreshold::Transform {
+                    amount: 100,
+                    into: Self::Frozen,
+                    into_amount: 1,
+                    message: "**{name}** congelou completamente!",
+                }),
+                ..EffectRules::new(100, 5, "**{name}** está congelando!")
+            },
+            Self::Wet => EffectRules {
+                interactions: &[
+                    EffectInteraction::Weaken {
+                        other: Self::Flaming,
+                        amount: 60,
+                    },
+                    EffectInteraction::Weaken {
+                        other: Self::Burning,
+                        amount: 20,
+                    },
+                    EffectInteraction::Amplify {
+                        other: Self::Shocked,
+                        damage_kind: DamageKind::Special,
+                        tiers: &[(30, 0.05), (60, 0.08), (90, 0.1), (i32::MAX, 0.2)],
+                        balance_effectiveness: 10,
+                        follow_up: Some((Self::Paralyzed, 20)),
+                        message: "**{name}** eletrocutou devido a água e recebeu **{dmg}**!",
+                    },
+                ],
+                ..EffectRules::new(100, 15, "**{name}** está molhado!")
+            },
+            Self::Frozen => EffectRules {
+                interactions: &[
+                    EffectInteraction::Weaken {
+                        other: Self::Flaming,
+                        amount: 20,
+                    },
+                    EffectInteraction::Weaken {
+                        other: Self::Burning,
+                        amount: 10,
+                    },
+                ],
+                expired_message: Some("***{name}** descongelou*"),
+                ..EffectRules::new(10, 5, "**{name}** congelou!")
+            },
+            Self::Bleeding => EffectRules {
+                threshold: Some(EffectThreshold::Burst {
+                    amount: 100,
+                    base: 20,
+                    max_health_ratio: 0.15,
+                    message: "**{name}** teve uma hemorragia que causou **{dmg} dano**!",
+                }),
+                ..EffectRules::new(100, 5, "**{name}** começou a sangrar!")
+            },
+            Self::Poisoned => EffectRules {
+                tick_damage: Some(EffectTickDamage {
+                    kind: DamageKind::Poisonous,
+                    base: 6,
+                    max_health_ratio: 0.025,
+                    balance_effectiveness: 3,
+                    message: "**{name}** recebeu **{dmg}** do veneno!",
+                }),
+                ..EffectRules::new(90, 5, "**{name}** está envenenado!")
+            },
+            // Paralysis goes away as the fighter loses turns, the engine takes care of it
+            Self::Paralyzed => EffectRules::new(3, 0, "**{name}** paralisou!"),
+            Self::Curse => EffectRules {
+                expired_message: Some("***{name}** não está mais com uma maldição!"),
+                ..EffectRules::new(40, 10, "**{name}** está com uma maldição!")
+            },
+            Self::Exhausted => EffectRules::new(
+                5,
+                1,
+                "**{name}** está exausto e não pode mais regenerar ether!",
+            ),
+            Self::LowProtection => EffectRules {
+                expired_message: Some("***{name}** perdeu a proteção leve extra!*"),
+                ..EffectRules::new(
+                    10,
+                    1,
+                    "**{name}** está com uma proteção extra a danos leves!",
+                )
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Effect, Fighter, FighterIndex};
+
+    use super::*;
+
+    #[test]
+    fn effects_stack_up_to_their_max() {
+        let mut fighter = Fighter::default();
+        let culprit = FighterIndex::default();
+
+        assert!(fighter.apply_effect(Effect::new(EffectKind::Poisoned, 70, culprit)));
+        assert_eq!(fighter.get_effect(EffectKind::Poisoned).unwrap().amount, 70);
+
+        assert!(!fighter.apply_effect(Effect::new(EffectKind::Poisoned, 70, culprit)));
+        assert_eq!(fighter.get_effect(EffectKind::Poisoned).unwrap().amount, 90);
+
+        assert!(fighter.apply_effect(Effect::new(EffectKind::Paralyzed, 5, culprit)));
+        assert_eq!(fighter.get_effect(EffectKind::Paralyzed).unwrap().amount, 3);
+    }
+
+    #[test]
+    fn thresholds_are_within_reach() {
+        for kind in [
+            EffectKind::Flaming,
+            EffectKind::Shocked,
+            EffectKind::Ice,
+            EffectKind::Bleeding,
+        ] {
+            let rules = kind.rules();
+            if let Some(threshold) = rules.threshold {
+                assert!(threshold.amount() <= rules.max_stacks, "{kind:?}");
+            }
+        }
+    }
+}
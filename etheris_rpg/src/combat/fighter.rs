@@ -7,24 +7,33 @@ use std::{
 
 use bitflags::bitflags;
 use etheris_common::{calculate_power_level, Attribute, Probability};
+pub use etheris_data::ImmunityKind;
 use etheris_data::{
+    add_skill_mastery_xp,
+    equipment::{EquipmentPassive, EquipmentStat},
+    find_skill_mastery,
     items::{self, Item},
     personality::Personality,
-    weapon::WeaponKind,
-    BossKind, SkillKind,
+    weapon::{WeaponEnchantment, WeaponKind},
+    BossKind, ItemValue, ItemValues, SkillKind, SkillMastery,
 };
 use etheris_database::character_model::BattleAction;
 use etheris_discord::{twilight_model::user::User, ButtonBuilder, Emoji};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use tokio::sync::Mutex;
 
 use crate::{
     brain::{make_brain, Brain, BrainKind},
-    common::{BoxedSkill, DamageSpecifier},
+    common::{BoxedSkill, DamageKind, DamageSpecifier},
     data::{finishers::Finisher, Reward},
     list::*,
-    FighterData, Modifiers,
+    FighterData, ModKind, Modifier, Modifiers, Reaction, SkillLimits,
 };
 
+/// The tag of the modifiers that come from the equipment of the fighter.
+pub const EQUIPMENT_MODIFIER_TAG: &str = "equipment";
+
 use self::prelude::BattleItem;
 
 bitflags! {
@@ -36,10 +45,13 @@ bitflags! {
         const CANNOT_REGEN_ETHER = 1 << 3;
         const CANNOT_REGEN_ETHER_OVERLOAD = 1 << 4;
         const GAVE_UP = 1 << 5;
+        const FLED = 1 << 6;
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 pub struct FighterIndex(pub usize);
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -55,6 +67,10 @@ pub struct FighterSkill {
     pub identifier: &'static str,
     pub dynamic_skill: Arc<Mutex<BoxedSkill>>,
     pub base_kind: SkillKind,
+    /// Turns left until the skill can be used again.
+    pub cooldown: u32,
+    /// How many times the skill was used in the battle.
+    pub uses: u32,
 }
 
 impl From<SkillKind> for FighterSkill {
@@ -70,8 +86,20 @@ impl FighterSkill {
             identifier: skill.data(&Default::default()).identifier,
             base_kind: skill.kind(),
             dynamic_skill: Arc::new(Mutex::new(skill)),
+            cooldown: 0,
+            uses: 0,
         }
     }
+
+    pub fn remaining_charges(&self, limits: SkillLimits) -> Option<u32> {
+        limits
+            .charges
+            .map(|charges| charges.saturating_sub(self.uses))
+    }
+
+    pub fn is_ready(&self, limits: SkillLimits) -> bool {
+        self.cooldown == 0 && self.remaining_charges(limits) != Some(0)
+    }
 }
 
 impl Debug for FighterSkill {
@@ -113,7 +141,7 @@ impl PartialEq for FighterBrain {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum EffectKind {
     Flaming,
     Burning,
@@ -165,15 +193,44 @@ impl Effect {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FighterWeapon {
     pub kind: WeaponKind,
+    pub upgrade_level: u32,
+    /// Attacks left until the weapon breaks.
+    pub durability: u32,
+    pub enchantment: Option<WeaponEnchantment>,
 }
 
 impl From<WeaponKind> for FighterWeapon {
     fn from(value: WeaponKind) -> Self {
-        Self { kind: value }
+        Self::new(value, &ItemValues::empty())
     }
 }
 
 impl FighterWeapon {
+    /// A weapon with the upgrades and wear stored in the values of its item.
+    pub fn new(kind: WeaponKind, values: &ItemValues) -> Self {
+        Self {
+            kind,
+            upgrade_level: values.upgrade_level(),
+            durability: values.durability().unwrap_or(kind.max_durability()),
+            enchantment: values.enchantment(),
+        }
+    }
+
+    pub fn values(&self) -> ItemValues {
+        let mut values = vec![
+            ItemValue::Durability(self.durability),
+            ItemValue::UpgradeLevel(self.upgrade_level),
+        ];
+        values.extend(self.enchantment.map(ItemValue::Enchantment));
+
+        ItemValues::new(values)
+    }
+
+    /// Every upgrade level makes the weapon hit 10% harder.
+    pub fn upgrade_multiplier(&self) -> f32 {
+        1.0 + self.upgrade_level as f32 * 0.1
+    }
+
     pub fn action(&self) -> &'static str {
         match self.kind {
             WeaponKind::Knife => "Cortar",
@@ -198,20 +255,7 @@ impl FighterWeapon {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum ImmunityKind {
-    Fire,
-    Ice,
-    Water,
-    Poison,
-    Bleeding,
-    Physical,
-    Cut,
-    Electric,
-    Special,
-}
-
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct BodyImmunities {
     /// Resistance is a number from 0.0 to 1.0, where 1.0 is the maximum resistance (immune) and 0.0 is no resistance at all (default)
     pub resistance_map: HashMap<ImmunityKind, f64>,
@@ -335,9 +379,16 @@ pub struct Fighter {
     pub inventory: Vec<BattleItem>,
     pub personalities: Vec<Personality>,
     pub skills: Vec<FighterSkill>,
+    pub skill_mastery: Vec<SkillMastery>,
+    /// Identifiers of the skill combos the fighter triggered in this battle.
+    pub triggered_combos: Vec<&'static str>,
     pub effects: Vec<Effect>,
     pub modifiers: Modifiers,
     pub body_immunities: BodyImmunities,
+    /// Passive effects of the equipment, applied at the end of every round.
+    pub passives: Vec<EquipmentPassive>,
+    /// The reaction prepared for the attacks received until the next turn of the fighter.
+    pub reaction: Option<Reaction>,
 
     pub is_defeated: bool,
     pub defeated_by: Option<FighterIndex>,
@@ -373,6 +424,37 @@ impl Fighter {
 
         let is_ai = data.brain.is_some();
 
+        let mut modifiers = Modifiers::new();
+        let mut body_immunities = data.immunities.clone();
+        let mut passives = Vec::new();
+        for stat in data
+            .equipment
+            .iter()
+            .filter_map(|item| item.equipment_properties)
+            .flat_map(|properties| properties.stats.iter())
+        {
+            let percentage = |value: u32| value as f32 / 100.0;
+            let modifier = match *stat {
+                EquipmentStat::Resistance(kind, resistance) => {
+                    body_immunities.increase_resistance(kind, resistance as f64 / 100.0);
+                    continue;
+                }
+                EquipmentStat::Passive(passive) => {
+                    passives.push(passive);
+                    continue;
+                }
+                EquipmentStat::DmgMultiplier(value) => ModKind::DmgMultiplier(percentage(value)),
+                EquipmentStat::DefenseMultiplier(value) => {
+                    ModKind::DefenseMultiplier(percentage(value))
+                }
+                EquipmentStat::EtherRegenMultiplier(value) => {
+                    ModKind::EtherRegenMultiplier(percentage(value))
+                }
+            };
+
+            modifiers.add(Modifier::new(modifier, None).with_tag(EQUIPMENT_MODIFIER_TAG));
+        }
+
         Self {
             team,
             index,
@@ -411,14 +493,20 @@ impl Fighter {
                 .iter()
                 .map(|s| FighterSkill::from(s.clone()))
                 .collect(),
+            skill_mastery: data.skill_mastery,
+            triggered_combos: Vec::new(),
             effects: Vec::new(),
-            modifiers: Modifiers::new(),
-            body_immunities: data.immunities,
+            modifiers,
+            body_immunities,
+            passives,
+            reaction: None,
 
             strength_level: data.strength_level,
             intelligence_level: data.intelligence_level,
 
-            weapon: data.weapon.map(From::from),
+            weapon: data
+                .weapon
+                .map(|weapon| FighterWeapon::new(weapon, &data.weapon_values)),
 
             overload: 0.0,
             resistance: data.resistance,
@@ -472,7 +560,62 @@ impl Fighter {
         self.mixed_multiplier(
             weapon.kind.strength_weight(),
             weapon.kind.intelligence_weight(),
-        )
+        ) * weapon.upgrade_multiplier()
+    }
+
+    pub fn get_skill(&self, identifier: &str) -> Option<&FighterSkill> {
+        self.skills.iter().find(|s| s.identifier == identifier)
+    }
+
+    pub fn register_skill_use(&mut self, identifier: &str, limits: SkillLimits) {
+        if let Some(skill) = self.skills.iter_mut().find(|s| s.identifier == identifier) {
+            skill.uses += 1;
+            // The cooldown is ticked at the start of every turn of the fighter, including the next one
+            skill.cooldown = if limits.cooldown > 0 {
+                limits.cooldown + 1
+            } else {
+                0
+            };
+        }
+    }
+
+    pub fn prepare_reaction(&mut self, reaction: Reaction) {
+        self.reaction = Some(reaction);
+    }
+
+    /// Takes the prepared reaction if an attack of `culprit` with the damage kind triggers it.
+    pub fn take_triggered_reaction(
+        &mut self,
+        culprit: FighterIndex,
+        kind: DamageKind,
+        landed: bool,
+    ) -> Option<Reaction> {
+        if self
+            .reaction
+            .as_ref()
+            .is_some_and(|r| r.is_triggered_by(self.index, culprit, kind, landed))
+        {
+            self.reaction.take()
+        } else {
+            None
+        }
+    }
+
+    pub fn tick_skill_cooldowns(&mut self) {
+        for skill in self.skills.iter_mut() {
+            skill.cooldown = skill.cooldown.saturating_sub(1);
+        }
+    }
+
+    pub fn skill_mastery_level(&self, skill: &SkillKind) -> u32 {
+        find_skill_mastery(&self.skill_mastery, skill)
+            .map(|m| m.level())
+            .unwrap_or(1)
+    }
+
+    /// Returns true if the skill reached a new mastery level.
+    pub fn add_skill_mastery_xp(&mut self, skill: &SkillKind, xp: u32) -> bool {
+        add_skill_mastery_xp(&mut self.skill_mastery, skill, xp)
     }
 
     pub fn add_balance(&mut self, balance: u8) {
@@ -491,10 +634,16 @@ impl Fighter {
 
     pub fn apply_effect(&mut self, effect: Effect) -> bool {
         if let Some(e) = self.effects.iter_mut().find(|ef| ef.kind == effect.kind) {
-            e.amount = e.amount.add(effect.amount).min(100);
+            e.amount = e
+                .amount
+                .add(effect.amount)
+                .min(effect.kind.rules().max_stacks);
             false
         } else {
-            self.effects.push(effect);
+            self.effects.push(Effect {
+                amount: effect.amount.min(effect.kind.rules().max_stacks),
+                ..effect
+            });
             true
         }
     }
@@ -580,7 +729,12 @@ impl Fighter {
         }
     }
 
-    pub fn take_damage(&mut self, culprit: FighterIndex, damage: DamageSpecifier) {
+    pub fn take_damage(
+        &mut self,
+        rng: &mut impl Rng,
+        culprit: FighterIndex,
+        damage: DamageSpecifier,
+    ) {
         let vital_damage = damage.amount - self.resistance.value;
 
         if vital_damage > 0 {
@@ -590,7 +744,9 @@ impl Fighter {
             self.resistance.value = self.resistance.value.sub(damage.amount).max(0);
         }
 
-        if damage.amount > 0 && culprit != self.index && Probability::new(50).generate_random_bool()
+        if damage.amount > 0
+            && culprit != self.index
+            && Probability::new(50).generate_random_bool_with(rng)
         {
             if let Some(mut state) = self.ai_state {
                 state.focused_in = culprit;
@@ -603,7 +759,7 @@ impl Fighter {
             && self.vitality.value == 0
             && vital_damage > 0
             && vital_damage < (self.vitality.max / 4)
-            && Probability::new(80).generate_random_bool()
+            && Probability::new(80).generate_random_bool_with(rng)
         {
             self.flags.insert(FighterFlags::HAD_A_NEAR_DEATH_EXPERIENCE);
             self.vitality.value += (vital_damage % 4) + 1;
@@ -620,3 +776,55 @@ impl Fighter {
         }
     }
 }
+
+#[test]
+fn equipment_stats_are_applied_to_the_fighter() {
+    use crate::data::enemies;
+
+    let enemy = enemies::get_enemy_by_id("giant_rat").unwrap();
+    let mut data = FighterData::new_from_enemy(0, Default::default(), enemy);
+    data.equipment = vec![
+        items::equipment::IRON_ARMOR,
+        items::equipment::HERBAL_AMULET,
+    ];
+
+    let fighter = Fighter::dummy(data);
+    assert_eq!(
+        fighter
+            .body_immunities
+            .get_resistance(ImmunityKind::Physical),
+        0.2
+    );
+    // Stacks with the natural poison resistance of the rat
+    let poison = fighter.body_immunities.get_resistance(ImmunityKind::Poison);
+    assert!((poison - 0.35).abs() < 1e-9);
+    assert_eq!(fighter.modifiers.overall_defense_multiplier(), 1.2);
+    assert_eq!(fighter.modifiers.overall_ether_regen_multiplier(), 0.85);
+    assert_eq!(fighter.passives, vec![EquipmentPassive::Regeneration(3)]);
+}
+
+#[test]
+fn weapon_keeps_its_upgrades_and_wear() {
+    use crate::data::enemies;
+    use etheris_data::weapon::WeaponEnchantment;
+
+    let enemy = enemies::get_enemy_by_id("giant_rat").unwrap();
+    let mut data = FighterData::new_from_enemy(0, Default::default(), enemy);
+    data.weapon = Some(WeaponKind::Knife);
+    data.weapon_values = ItemValues::new(vec![
+        ItemValue::UpgradeLevel(2),
+        ItemValue::Durability(10),
+        ItemValue::Enchantment(WeaponEnchantment::Flaming),
+    ]);
+
+    let weapon = Fighter::dummy(data).weapon.unwrap();
+    assert_eq!(weapon.upgrade_level, 2);
+    assert_eq!(weapon.durability, 10);
+    assert_eq!(weapon.enchantment, Some(WeaponEnchantment::Flaming));
+    assert!((weapon.upgrade_multiplier() - 1.2).abs() < 1e-6);
+    assert_eq!(FighterWeapon::new(weapon.kind, &weapon.values()), weapon);
+
+    let new_weapon = FighterWeapon::from(WeaponKind::Knife);
+    assert_eq!(new_weapon.durability, WeaponKind::Knife.max_durability());
+    assert_eq!(new_weapon.upgrade_level, 0);
+}
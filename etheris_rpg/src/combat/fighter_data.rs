@@ -1,11 +1,22 @@
 use etheris_common::{calculate_power_level, Attribute};
-use etheris_data::{items, personality::Personality, weapon::WeaponKind, BossKind, SkillKind};
-use etheris_database::character_model::{BattleAction, CharacterModel};
+use etheris_data::{
+    items::{self, Item},
+    personality::Personality,
+    weapon::WeaponKind,
+    BossKind, ItemValues, SkillKind, SkillMastery,
+};
+use etheris_database::{
+    character_model::{BattleAction, CharacterModel},
+    companion::Companion,
+};
 use etheris_discord::twilight_model::user::User;
 
 use crate::{
     brain::BrainKind,
-    data::{enemies::Enemy, Reward},
+    data::{
+        enemies::{self, Enemy},
+        Reward,
+    },
     list::prelude::BattleItem,
     BodyImmunities,
 };
@@ -23,6 +34,7 @@ pub struct FighterData {
     pub inventory: Vec<BattleItem>,
     pub personalities: Vec<Personality>,
     pub skills: Vec<SkillKind>,
+    pub skill_mastery: Vec<SkillMastery>,
 
     pub strength_level: u32,
     pub intelligence_level: u32,
@@ -30,6 +42,10 @@ pub struct FighterData {
     pub immunities: BodyImmunities,
 
     pub weapon: Option<WeaponKind>,
+    /// Upgrade level, durability and enchantment of the weapon.
+    pub weapon_values: ItemValues,
+    /// Armor and accessories. Their stats are applied when the fighter is created.
+    pub equipment: Vec<Item>,
 
     pub resistance: Attribute,
     pub vitality: Attribute,
@@ -44,14 +60,34 @@ impl FighterData {
         character: &CharacterModel,
         user: User,
         drop: Reward,
+    ) -> Self {
+        Self::from_character(team, character, Some(user), None, drop)
+    }
+
+    /// A character controlled by an AI brain instead of its user, used by simulations.
+    pub fn new_from_character_with_brain(
+        team: u8,
+        character: &CharacterModel,
+        brain: BrainKind,
+        drop: Reward,
+    ) -> Self {
+        Self::from_character(team, character, None, Some(brain), drop)
+    }
+
+    fn from_character(
+        team: u8,
+        character: &CharacterModel,
+        user: Option<User>,
+        brain: Option<BrainKind>,
+        drop: Reward,
     ) -> Self {
         Self {
             team,
             name: character.name.to_owned(),
-            user: Some(user),
+            user,
             boss: None,
 
-            brain: None,
+            brain,
             actions: character.actions.iter().copied().collect(),
 
             inventory: character
@@ -65,6 +101,7 @@ impl FighterData {
                 .collect(),
             personalities: character.personalities.clone(),
             skills: character.skills.clone(),
+            skill_mastery: character.skill_mastery.clone(),
 
             strength_level: character.stats.strength_level,
             intelligence_level: character.stats.intelligence_level,
@@ -75,6 +112,8 @@ impl FighterData {
             ether: character.stats.ether.into(),
 
             weapon: character.weapon,
+            weapon_values: character.weapon_values.clone(),
+            equipment: character.equipment.items(),
             immunities: BodyImmunities::new(),
 
             drop,
@@ -99,11 +138,32 @@ impl FighterData {
             resistance: Attribute::from(enemy.resistance),
             vitality: Attribute::from(enemy.vitality),
             weapon: enemy.weapon,
+            weapon_values: ItemValues::empty(),
+            equipment: vec![],
             skills: enemy.skills.to_vec(),
+            skill_mastery: vec![],
             immunities: enemy.immunities,
         }
     }
 
+    /// The fighter of a tamed companion: the enemy it came from, strengthened by its level.
+    pub fn new_from_companion(team: u8, companion: &Companion) -> Option<Self> {
+        let enemy = enemies::get_enemy_by_id(&companion.enemy)?;
+        let multiplier = companion.stats_multiplier();
+        let scale = |value: i32| (value as f64 * multiplier) as i32;
+
+        let mut data = Self::new_from_enemy(team, Reward::default(), enemy);
+        data.name = companion.name.clone();
+        data.skills = companion.skills.clone();
+        data.strength_level = (data.strength_level as f64 * multiplier) as u32;
+        data.intelligence_level = (data.intelligence_level as f64 * multiplier) as u32;
+        data.resistance = Attribute::from(scale(data.resistance.max));
+        data.vitality = Attribute::from(scale(data.vitality.max));
+        data.ether = Attribute::from(scale(data.ether.max));
+
+        Some(data)
+    }
+
     pub fn power_level(&self) -> i64 {
         let weighted_skills = {
             let mut weight = 0.0;
@@ -1,9 +1,15 @@
 mod battle;
+mod battle_event;
+mod effect_rules;
 mod fighter;
 mod fighter_data;
 mod modifiers;
+mod reaction;
 
 pub use battle::*;
+pub use battle_event::*;
+pub use effect_rules::*;
 pub use fighter::*;
 pub use fighter_data::*;
 pub use modifiers::*;
+pub use reaction::*;
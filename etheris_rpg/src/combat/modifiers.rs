@@ -1,6 +1,8 @@
 #![allow(clippy::single_match)]
 use std::collections::HashSet;
 
+use serde::{Deserialize, Serialize};
+
 use crate::EffectKind;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -10,7 +12,7 @@ pub struct Modifier {
     pub turns_remaining: Option<u32>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ModKind {
     DmgMultiplier(f32),
     DefenseMultiplier(f32),
@@ -0,0 +1,93 @@
+use crate::{common::DamageKind, FighterIndex};
+
+/// What a prepared reaction does when an attack triggers it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReactionKind {
+    /// Blocks the attack, multiplying the damage received.
+    Parry { damage_multiplier: f32 },
+    /// Dodges the attack completely.
+    Evade,
+    /// Returns part of the damage received to the attacker.
+    Reflect { ratio: f32 },
+    /// Attacks back after being hit.
+    Counter { kind: DamageKind, amount: i32 },
+}
+
+/// A reaction prepared by a fighter in its turn, resolved when it's attacked before its next turn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reaction {
+    /// The name of the skill that prepared the reaction.
+    pub name: String,
+    pub kind: ReactionKind,
+    /// The damage kinds that trigger the reaction.
+    pub triggers: &'static [DamageKind],
+    /// The probability of the reaction working when triggered.
+    pub chance: u8,
+}
+
+impl Reaction {
+    pub const PHYSICAL_TRIGGERS: &'static [DamageKind] = &[
+        DamageKind::Physical,
+        DamageKind::PhysicalCut,
+        DamageKind::SpecialPhysical,
+    ];
+
+    pub fn new(name: impl ToString, kind: ReactionKind, triggers: &'static [DamageKind]) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            triggers,
+            chance: 100,
+        }
+    }
+
+    pub fn with_chance(mut self, chance: u8) -> Self {
+        self.chance = chance;
+        self
+    }
+
+    /// Whether an attack of `culprit` against `owner` triggers the reaction.
+    /// Self-inflicted damage and attacks that missed never do.
+    pub fn is_triggered_by(
+        &self,
+        owner: FighterIndex,
+        culprit: FighterIndex,
+        kind: DamageKind,
+        landed: bool,
+    ) -> bool {
+        landed && owner != culprit && self.triggers.contains(&kind)
+    }
+}
+
+#[test]
+fn reactions_ignore_self_damage_and_other_kinds() {
+    let reaction = Reaction::new("Teste", ReactionKind::Evade, Reaction::PHYSICAL_TRIGGERS);
+
+    assert!(reaction.is_triggered_by(FighterIndex(0), FighterIndex(1), DamageKind::Physical, true));
+    assert!(!reaction.is_triggered_by(
+        FighterIndex(0),
+        FighterIndex(0),
+        DamageKind::Physical,
+        true
+    ));
+    assert!(!reaction.is_triggered_by(FighterIndex(0), FighterIndex(1), DamageKind::Fire, true));
+}
+
+#[test]
+fn reactions_ignore_missed_attacks() {
+    let reaction = Reaction::new(
+        "Teste",
+        ReactionKind::Counter {
+            kind: DamageKind::Physical,
+            amount: 10,
+        },
+        Reaction::PHYSICAL_TRIGGERS,
+    );
+
+    assert!(!reaction.is_triggered_by(
+        FighterIndex(0),
+        FighterIndex(1),
+        DamageKind::Physical,
+        false
+    ));
+}
@@ -1,4 +1,5 @@
 use etheris_data::{items::Item, ItemValues};
+use serde::{Deserialize, Serialize};
 
 use crate::{skills::Skill, Effect, FighterIndex};
 
@@ -14,7 +15,9 @@ pub struct DamageSpecifier {
     pub culprit: FighterIndex,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
 pub enum DamageKind {
     #[default]
     Physical,
@@ -1,5 +1,4 @@
 use etheris_database::character_model::BattleAction;
-use etheris_framework::Response;
 use rand::prelude::SliceRandom;
 
 use crate::*;
@@ -29,19 +28,16 @@ pub async fn execute_action(action: BattleAction, api: &mut BattleApi<'_>) -> an
             Ok(())
         }
         BattleAction::ControlPower => {
-            let power_choosen = match api.fighter().user.clone() {
-                Some(user) => {
-                    let response = Response::new_user_reply(
-                        &user,
-                        format!(
-                            "escreva quando poder você quer liberar (**0** até **{}**):",
-                            (api.fighter().potential * 100.0).round() as i32
-                        ),
+            let power_choosen = match api.fighter().user {
+                Some(_) => {
+                    let prompt = format!(
+                        "escreva quando poder você quer liberar (**0** até **{}**):",
+                        (api.fighter().potential * 100.0).round() as i32
                     );
 
                     let Ok(input) = api_input::input_number(
                         api,
-                        response,
+                        prompt,
                         (0.0, (api.fighter().potential * 100.0).round()),
                     )
                     .await
@@ -1,78 +1,69 @@
-use std::{
-    ops::{Add, Sub},
-    time::Duration,
-};
+use std::{sync::Arc, time::Duration};
 
-use anyhow::bail;
-use etheris_common::{Color, Probability};
-use etheris_data::{items::get_item_by_weapon, ItemValues};
+use etheris_common::Color;
+use etheris_data::ItemValues;
 use etheris_database::{
-    character_model::{DeathCause, DeathInfo},
+    battle_model::{
+        BattleModel, BattleParticipant, BattleRecordSettings, BattleRewards, BattleTurnLog,
+    },
+    bson::{self, oid::ObjectId},
+    character_model::XpGain,
     common::{DatabaseDateTime, InventoryItem},
 };
 use etheris_discord::{
     twilight_model::{
-        channel::{message::component::ButtonStyle, *},
+        channel::message::component::ButtonStyle,
         id::{marker::UserMarker, Id},
     },
     *,
 };
 use etheris_framework::{watcher::WatcherOptions, CommandContext, EmbedPagination, Response};
-use etheris_util::math;
-use rand::{seq::SliceRandom, Rng};
-
-use crate::{common::*, get_input, *};
+use tokio::sync::Mutex;
 
-use self::{
-    controller_helper::create_fighter_embed_fields,
-    data::{weapons::execute_weapon_attack, Reward},
-};
+use crate::*;
 
-#[derive(Debug, Clone, Default)]
-pub struct BattleResult {
-    pub reward: Reward,
-    pub winners: Vec<Fighter>,
-    pub losers: Vec<Fighter>,
-}
+use self::data::Reward;
 
+/// The Discord frontend of a battle: it takes care of the database and the messages,
+/// while the `BattleEngine` runs the battle itself.
 pub struct BattleController {
-    pub battle: Battle,
-    pub ctx: CommandContext,
-    pub last_message: Option<Message>,
-    pub last_interaction: Option<Interaction>,
-    pub current_turn_history: TurnHistory,
-
-    should_reinput: bool,
+    pub engine: BattleEngine,
+    pub io: Arc<Mutex<DiscordBattleIo>>,
+    pub started_at: DatabaseDateTime,
 }
 
 impl BattleController {
     pub fn new(battle: Battle, ctx: CommandContext) -> Self {
+        let io = Arc::new(Mutex::new(DiscordBattleIo::new(ctx)));
+
         Self {
-            battle,
-            ctx,
-            last_message: None,
-            last_interaction: None,
-            current_turn_history: TurnHistory::default(),
-            should_reinput: false,
+            engine: BattleEngine::new(battle, io.clone()),
+            io,
+            started_at: DatabaseDateTime::now(),
         }
     }
 
+    pub async fn ctx(&self) -> CommandContext {
+        self.io.lock().await.ctx.clone()
+    }
+
     pub async fn send_full_history(
         &mut self,
         allowed_users: Vec<Id<UserMarker>>,
     ) -> anyhow::Result<()> {
         const TURNS_PER_PAGE: usize = 5;
-        if self.battle.history.is_empty() {
+        let battle = &self.engine.battle;
+        if battle.history.is_empty() {
             return Ok(());
         }
 
-        let mut pages = vec![];
+        let mut pages = vec![Self::create_summary_embed(battle)];
 
-        for i in (0..self.battle.history.len()).step_by(TURNS_PER_PAGE) {
+        for i in (0..battle.history.len()).step_by(TURNS_PER_PAGE) {
             let mut embed = EmbedBuilder::new_common()
                 .set_color(Color::LIGHT_YELLOW)
                 .set_description(
-                    self.battle
+                    battle
                         .fighters
                         .iter()
                         .map(|f| bold(&f.name))
@@ -85,42 +76,258 @@ impl BattleController {
                 });
 
             for j in 0..TURNS_PER_PAGE {
-                let Some(turn) = self.battle.history.get(i + j) else {
+                let Some(turn) = battle.history.get(i + j) else {
                     break;
                 };
 
-                let turn_fighter = self.battle.get_fighter(turn.fighter);
+                let turn_fighter = battle.get_fighter(turn.fighter);
 
                 embed = embed.add_not_inlined_field(
                     format!("• [{}] Turno de {}", i + j + 1, turn_fighter.name),
-                    turn.messages.join("\n"),
+                    turn.messages(battle).join("\n"),
                 );
             }
 
             pages.push(embed);
         }
 
-        EmbedPagination::new(self.ctx.clone(), pages)
+        EmbedPagination::new(self.ctx().await, pages)
             .set_allowed_users(allowed_users)
             .set_timeout(Duration::from_secs(1000))
             .send()
             .await
     }
 
+    fn create_summary_embed(battle: &Battle) -> EmbedBuilder {
+        let mut embed = EmbedBuilder::new_common()
+            .set_color(Color::LIGHT_YELLOW)
+            .set_author(EmbedAuthor {
+                name: "Resumo da Luta".to_string(),
+                icon_url: None,
+            })
+            .set_description(format!("**{}** turnos", battle.history.len()));
+
+        for fighter in battle.fighters.iter() {
+            let (mut dealt, mut taken, mut skills, mut missed) = (0, 0, 0, 0);
+            for event in battle.events() {
+                match event {
+                    BattleEvent::DamageDealt {
+                        culprit,
+                        target,
+                        amount,
+                        missed: damage_missed,
+                        ..
+                    } if !damage_missed => {
+                        if *culprit == fighter.index && *target != fighter.index {
+                            dealt += amount;
+                        }
+
+                        if *target == fighter.index {
+                            taken += amount;
+                        }
+                    }
+                    BattleEvent::DamageDealt { culprit, .. } if *culprit == fighter.index => {
+                        missed += 1;
+                    }
+                    BattleEvent::SkillUsed { fighter: user, .. } if *user == fighter.index => {
+                        skills += 1;
+                    }
+                    _ => (),
+                }
+            }
+
+            embed = embed.add_inlined_field(
+                &fighter.name,
+                format!(
+                    "Dano causado: **{dealt}**\nDano recebido: **{taken}**\nHabilidades usadas: **{skills}**\nAtaques errados: **{missed}**"
+                ),
+            );
+        }
+
+        embed
+    }
+
+    async fn notify_spectators_of_the_end(&self) {
+        let io = self.io.lock().await;
+        let mut spectators = io.spectators.lock().await;
+
+        let response = Response::from(io.create_spectator_embed(&self.engine))
+            .add_string_content("🏁 A batalha que você estava assistindo acabou.");
+        for spectator in spectators.iter_mut() {
+            spectator.update(response.clone()).await.ok();
+        }
+
+        spectators.clear();
+    }
+
+    /// Updates the PvP rating of every player of a ranked battle and describes the changes.
+    async fn update_ranked_ratings(&self, winner_team: u8) -> anyhow::Result<Option<String>> {
+        let battle = &self.engine.battle;
+        if !battle.settings.ranked {
+            return Ok(None);
+        }
+
+        let ctx = self.ctx().await;
+        let mut characters = vec![];
+        for fighter in battle.fighters.iter() {
+            let Some(user) = &fighter.user else {
+                continue;
+            };
+
+            if let Some(mut character) = ctx
+                .db()
+                .characters()
+                .get_by_user(&user.id.to_string())
+                .await?
+            {
+                character.pvp_rating.update_season();
+                characters.push((fighter.team == winner_team, character));
+            }
+        }
+
+        let [(first_won, first), (second_won, second)] = characters.as_slice() else {
+            return Ok(None);
+        };
+
+        if first_won == second_won {
+            return Ok(None);
+        }
+
+        // Both results are based on the ratings from before the duel, and each player is updated
+        // on its own so a failure on one side doesn't stop the other from being rated
+        let opponent_ratings = [second.pvp_rating.rating, first.pvp_rating.rating];
+        let mut description = String::new();
+        for ((won, character), opponent_rating) in characters.iter().zip(opponent_ratings) {
+            let mut delta = 0;
+            let updated = ctx
+                .db()
+                .characters()
+                .update(character.id, |character| {
+                    delta = character.pvp_rating.add_result(opponent_rating, *won);
+                })
+                .await;
+
+            let character = match updated {
+                Ok(Some(character)) => character,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Failed to update the PvP rating of {}: {e}", character.name);
+                    continue;
+                }
+            };
+
+            let rating = character.pvp_rating;
+            description.push_str(&format!(
+                "{} **{}**: `{}` (`{delta:+}`) - {}\n",
+                rating.tier().emoji(),
+                character.name,
+                rating.rating,
+                rating.tier()
+            ));
+        }
+
+        Ok(Some(description).filter(|d| !d.is_empty()))
+    }
+
+    /// Creates the record of the battle that's stored in the database. Battles without players aren't stored.
+    async fn create_battle_record(
+        &self,
+        winner_team: u8,
+        rewards: &Reward,
+    ) -> anyhow::Result<Option<BattleModel>> {
+        let battle = &self.engine.battle;
+        if battle.fighters.iter().all(|f| f.user.is_none()) {
+            return Ok(None);
+        }
+
+        let ctx = self.ctx().await;
+        let mut participants = Vec::with_capacity(battle.fighters.len());
+        for fighter in battle.fighters.iter() {
+            let character_id = match &fighter.user {
+                Some(user) => ctx
+                    .db()
+                    .characters()
+                    .get_by_user(&user.id.to_string())
+                    .await?
+                    .map(|c| c.id),
+                None => None,
+            };
+
+            participants.push(BattleParticipant {
+                name: fighter.name.clone(),
+                team: fighter.team,
+                pl: fighter.pl,
+                user_id: fighter.user.as_ref().map(|u| u.id.to_string()),
+                character_id,
+                defeated: fighter.is_defeated,
+                died: fighter.killed_by.is_some(),
+            });
+        }
+
+        let pvp = battle.fighters.iter().filter(|f| f.user.is_some()).count() > 1;
+
+        Ok(Some(BattleModel {
+            id: ObjectId::new(),
+            region: battle.region,
+            settings: BattleRecordSettings {
+                is_risking_life_allowed: battle.settings.is_risking_life_allowed,
+                casual: battle.settings.casual,
+                has_consequences: battle.settings.has_consequences,
+                max_intruders: battle.settings.max_intruders,
+                seed: battle.seed,
+            },
+            participants,
+            winner_team,
+            pvp,
+            turns: battle.history.len() as u32,
+            started_at: self.started_at,
+            ended_at: DatabaseDateTime::now(),
+            rewards: BattleRewards {
+                orbs: rewards.orbs as i64,
+                xp: rewards.xp as u32,
+                items: rewards
+                    .items
+                    .iter()
+                    .map(|i| InventoryItem {
+                        identifier: i.item.identifier.to_string(),
+                        quantity: i.amount as usize,
+                        values: ItemValues::empty(),
+                    })
+                    .collect(),
+            },
+            log: battle
+                .history
+                .iter()
+                .map(|turn| {
+                    Ok(BattleTurnLog {
+                        fighter: battle.get_fighter(turn.fighter).name.clone(),
+                        messages: turn.messages(battle),
+                        events: turn
+                            .events
+                            .iter()
+                            .map(bson::to_bson)
+                            .collect::<Result<_, _>>()?,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            replay_path: None,
+        }))
+    }
+
     async fn prepare(&mut self) -> anyhow::Result<()> {
-        controller_helper::on_start(self).await?;
+        let ctx = self.ctx().await;
 
         for (user, fighter) in self
+            .engine
             .battle
             .fighters
             .iter()
             .filter_map(|f| f.user.as_ref().map(|u| (u, f)))
         {
-            self.ctx.client.mark_user_as_fighter(user.id).await;
+            ctx.client.mark_user_as_fighter(user.id).await;
 
-            if self.battle.settings.has_consequences {
-                let Some(mut character) = self
-                    .ctx
+            if self.engine.battle.settings.has_consequences {
+                let Some(character) = ctx
                     .db()
                     .characters()
                     .get_by_user(&user.id.to_string())
@@ -129,101 +336,94 @@ impl BattleController {
                     continue;
                 };
 
-                character.remove_orbs(fighter.drop.orbs.into());
-                for item in &fighter.drop.items {
-                    character.remove_item(item.item, item.amount as usize);
-                }
-
-                self.ctx.db().characters().save(character).await?;
+                ctx.db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.remove_orbs(fighter.drop.orbs.into());
+                        for item in &fighter.drop.items {
+                            character.remove_item(item.item, item.amount as usize);
+                        }
+                    })
+                    .await?;
             }
         }
 
-        self.battle.reallocate_all_targets();
-
         Ok(())
     }
 
     pub async fn run(&mut self) -> anyhow::Result<BattleResult> {
         self.prepare().await?;
+        self.started_at = DatabaseDateTime::now();
 
-        let mut turn_errors = 0;
+        let users = self
+            .engine
+            .battle
+            .fighters
+            .iter()
+            .filter_map(|f| f.user.as_ref().map(|u| u.id))
+            .collect::<Vec<_>>();
+        let spectators = self.io.lock().await.spectators.clone();
+        spectators::register_battle(&users, spectators).await;
 
-        while self.battle.state == BattleState::Running {
-            match self.turn().await {
-                Ok(()) => (),
-                Err(e) => {
-                    eprintln!("battle_controller::run Error: {}", e);
-                    // Error tolerancy of 10
-                    if turn_errors < 10 {
-                        turn_errors += 1;
-                        continue;
-                    }
+        let result = self.engine.run().await;
 
-                    for user in self.battle.fighters.iter().filter_map(|f| f.user.as_ref()) {
-                        self.ctx.client.remove_user_fighting_mark(user.id).await;
-                    }
+        spectators::unregister_battle(&users).await;
+        self.notify_spectators_of_the_end().await;
 
-                    return Err(e);
-                }
-            }
+        let ctx = self.ctx().await;
+        for user in self
+            .engine
+            .battle
+            .fighters
+            .iter()
+            .filter_map(|f| f.user.as_ref())
+        {
+            ctx.client.remove_user_fighting_mark(user.id).await;
+        }
 
-            if self.battle.state != BattleState::Running {
-                self.save_user_characters().await.ok();
-            }
+        let result = result?;
+        self.finish_battle(result.reward.clone(), &result.winners)
+            .await?;
 
-            tokio::time::sleep(Duration::from_millis(300)).await;
+        Ok(result)
+    }
 
-            if self.battle.turn_counter % 10 == 0 {
-                self.save_user_characters().await.ok();
-            }
-        }
+    async fn finish_battle(&mut self, rewards: Reward, winners: &[Fighter]) -> anyhow::Result<()> {
+        let io = self.io.lock().await;
+        io.save_user_skill_progress(&self.engine.battle).await?;
+        io.save_user_characters_stats(&self.engine.battle).await?;
+        drop(io);
 
-        for user in self.battle.fighters.iter().filter_map(|f| f.user.as_ref()) {
-            self.ctx.client.remove_user_fighting_mark(user.id).await;
-        }
+        let mut ctx = self.ctx().await;
+        let winner_team = winners[0].team;
 
-        let winners: Vec<Fighter> = match &self.battle.state {
-            BattleState::Running => {
-                unreachable!()
-            }
-            BattleState::Ended { winners, .. } => winners
-                .iter()
-                .map(|w| self.battle.get_fighter(*w).to_owned())
-                .collect(),
+        let recorded_rewards = if winners.iter().any(|w| w.user.is_some()) {
+            rewards.clone()
+        } else {
+            Reward::default()
         };
+        if let Some(mut record) = self
+            .create_battle_record(winner_team, &recorded_rewards)
+            .await?
+        {
+            // PvP fights may be disputed later, so their replays are kept
+            if record.pvp {
+                let path = replays_folder().join(format!("{}.json", record.id.to_hex()));
+                match self.engine.replay().save(&path).await {
+                    Ok(()) => record.replay_path = Some(path.to_string_lossy().into_owned()),
+                    Err(e) => eprintln!("Failed to save battle replay: {e}"),
+                }
+            }
 
-        if winners.is_empty() {
-            bail!("A battle can't draw");
+            ctx.db().battles().save(record).await?;
         }
 
-        let losers = self
+        let mut teams = self
+            .engine
             .battle
-            .fighters
-            .iter()
-            .filter(|f| !winners.contains(f))
-            .cloned()
-            .collect();
-
-        let rewards = self
-            .battle
-            .defeated_fighters
-            .iter()
-            .map(|f| self.battle.get_fighter(*f).drop.clone())
-            .fold(Reward::default(), |acc, r| acc.add(r));
-
-        self.finish_battle(rewards.clone(), &winners).await?;
-
-        Ok(BattleResult {
-            reward: rewards,
-            winners,
-            losers,
-        })
-    }
-
-    async fn finish_battle(&mut self, rewards: Reward, winners: &[Fighter]) -> anyhow::Result<()> {
-        self.save_user_characters_stats().await?;
-        let winner_team = winners[0].team;
-        let mut teams = self.battle.full_teams().into_iter().collect::<Vec<_>>();
+            .full_teams()
+            .into_iter()
+            .collect::<Vec<_>>();
         teams.sort_unstable_by_key(|(x, _)| *x);
 
         let mut embed = EmbedBuilder::new_common()
@@ -247,66 +447,93 @@ impl BattleController {
                     .join("\n"),
             );
 
-        if let Some(human_winner) = winners.iter().find_map(|w| w.user.clone()) {
-            if !rewards.is_empty() {
-                if let Some(mut character) = self
-                    .ctx
-                    .db()
-                    .characters()
-                    .get_by_user(&human_winner.id.to_string())
-                    .await?
-                {
-                    let orbs = rewards.orbs as i64;
-                    let xp = rewards.xp as u32;
-                    let items = rewards.items;
-
-                    character.add_orbs(orbs);
-                    character.health_xp += (xp + 2) / 3;
-                    character.intelligence_xp += (xp + 2) / 3;
-                    character.knowledge_xp += (xp + 2) / 5;
-                    character.strength_xp += (xp + 2) / 3;
-
-                    for item in items.iter() {
-                        character.add_item(item.item, item.amount as usize, None);
-                    }
+        // Rewards are split between every player that won, like the members of a party
+        let human_winners = winners
+            .iter()
+            .filter_map(|w| w.user.clone())
+            .collect::<Vec<_>>();
+        if !human_winners.is_empty() && !rewards.is_empty() {
+            let mut description = String::new();
+
+            for (human_winner, reward) in
+                human_winners.iter().zip(rewards.split(human_winners.len()))
+            {
+                let characters = ctx.db().characters();
+                let Some(character) = characters.get_by_user(&human_winner.id.to_string()).await?
+                else {
+                    continue;
+                };
 
-                    self.ctx.db().characters().save(character).await?;
-
-                    embed = embed.add_field_with_emoji(
-                        "💰",
-                        EmbedField {
-                            name: "Recompensas".into(),
-                            value: format!(
-                                "-> {}\n**Orbs**: {}\n**XP**: {}\n{}",
-                                human_winner.mention(),
-                                orbs,
-                                xp,
-                                items
-                                    .iter()
-                                    .map(|i| format!(
-                                        "**{} {}x {}**",
-                                        i.item.emoji, i.amount, i.item.display_name
-                                    ))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            ),
-                            inline: true,
+                let orbs = reward.orbs as i64;
+                let xp = reward.xp as u32;
+                let items = reward.items;
+
+                characters.add_orbs(character.id, orbs).await?;
+                characters
+                    .add_xp(
+                        character.id,
+                        XpGain {
+                            strength: xp.div_ceil(3),
+                            health: xp.div_ceil(3),
+                            intelligence: xp.div_ceil(3),
+                            knowledge: (xp + 2) / 5,
                         },
-                    );
+                    )
+                    .await?;
+
+                for item in items.iter() {
+                    characters
+                        .add_item(character.id, item.item, item.amount as usize, None)
+                        .await?;
                 }
+
+                description.push_str(&format!(
+                    "-> {}\n**Orbs**: {}\n**XP**: {}\n{}\n",
+                    human_winner.mention(),
+                    orbs,
+                    xp,
+                    items
+                        .iter()
+                        .map(|i| format!(
+                            "**{} {}x {}**",
+                            i.item.emoji, i.amount, i.item.display_name
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+
+            if !description.is_empty() {
+                embed = embed.add_field_with_emoji(
+                    "💰",
+                    EmbedField {
+                        name: "Recompensas".into(),
+                        value: description,
+                        inline: true,
+                    },
+                );
+            }
+        }
+
+        match self.update_ranked_ratings(winner_team).await {
+            Ok(Some(ranked_results)) => {
+                embed = embed.add_not_inlined_field("🏆 Rank PvP", ranked_results);
             }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to update the PvP ratings: {e}"),
         }
 
         let history_button = ButtonBuilder::new()
             .set_label("Ver Histórico")
             .set_custom_id("show_history");
         let row = ActionRowBuilder::new().add_button(history_button.clone());
-        let message = self
-            .ctx
+        let message = ctx
             .send(Response::from(embed).set_components(vec![row]))
             .await?;
+        self.io.lock().await.ctx = ctx.clone();
 
         let allowed_users = self
+            .engine
             .battle
             .fighters
             .iter()
@@ -315,14 +542,11 @@ impl BattleController {
 
         {
             let allowed = allowed_users.clone();
-            let ctx = self.ctx.clone();
-            let battle = self.battle.clone();
+            let battle = self.engine.battle.clone();
             tokio::spawn(async move {
-                let controller_ctx = ctx.clone();
-                let mut controller = Self::new(battle, controller_ctx);
+                let mut controller = Self::new(battle, ctx.clone());
 
-                let Ok(Some(component)) = controller
-                    .ctx
+                let Ok(Some(component)) = ctx
                     .watcher
                     .await_single_component(
                         message.id,
@@ -340,8 +564,7 @@ impl BattleController {
                     return Ok::<(), anyhow::Error>(());
                 };
 
-                let mut temp_ctx =
-                    CommandContext::from_with_interaction(&controller.ctx, Box::new(component));
+                let mut temp_ctx = CommandContext::from_with_interaction(&ctx, Box::new(component));
                 temp_ctx
                     .update_message(Response::default().set_components(
                         vec![ActionRowBuilder::new().add_button(
@@ -360,644 +583,4 @@ impl BattleController {
 
         Ok(())
     }
-
-    pub async fn turn(&mut self) -> anyhow::Result<()> {
-        let fighter = self.battle.get_current_fighter().clone();
-
-        self.current_turn_history = TurnHistory {
-            round: self.battle.history.len() + 1,
-            fighter: fighter.index,
-            target: fighter.target,
-            messages: vec![],
-        };
-
-        if fighter.has_effect(EffectKind::Frozen) {
-            self.emit_turn_message(format!(
-                "**{}** está congelado e não pode lutar.",
-                fighter.name
-            ));
-            self.should_reinput = false;
-        } else if let Some(paralyzed) = fighter.get_effect(EffectKind::Paralyzed) {
-            self.battle
-                .get_current_fighter_mut()
-                .remove_effect(Effect::new(EffectKind::Paralyzed, 1, paralyzed.culprit));
-            self.emit_turn_message(format!(
-                "**{}** está paralisado e não pode lutar. Turnos de paralisia restantes: `{}`",
-                fighter.name,
-                paralyzed.amount - 1
-            ));
-            self.should_reinput = false;
-        } else {
-            let input = self.get_input().await?;
-            self.process_input(input).await?;
-        }
-
-        if !self.should_reinput {
-            self.update_turn_history_message().await?;
-            self.next_turn().await?;
-        }
-
-        self.should_reinput = false;
-
-        Ok(())
-    }
-
-    pub async fn update_turn_history_message(&mut self) -> anyhow::Result<()> {
-        let response = Response::from(self.create_turn_embed()).remove_all_components();
-
-        if let Some(message) = &self.last_message {
-            self.ctx.update_specific_message(message, response).await?;
-            tokio::time::sleep(Duration::from_secs(
-                2 + (self.current_turn_history.messages.len() as u64).saturating_sub(2),
-            ))
-            .await;
-        } else {
-            let message = self.ctx.send(response).await?;
-            self.last_message = Some(message);
-        }
-
-        Ok(())
-    }
-
-    pub async fn save_user_characters(&mut self) -> anyhow::Result<()> {
-        if !self.battle.settings.has_consequences {
-            return Ok(());
-        }
-
-        for (user, fighter) in self
-            .battle
-            .fighters
-            .iter()
-            .filter_map(|f| f.user.as_ref().map(|user| (user.clone(), f.clone())))
-        {
-            let fighter_index = fighter.index;
-            let Some(mut character) = self
-                .ctx
-                .db()
-                .characters()
-                .get_by_user(&user.id.to_string())
-                .await?
-            else {
-                continue;
-            };
-
-            // Check if the character defeated a boss
-            for fighter in self.battle.fighters.clone() {
-                if fighter.defeated_by != Some(fighter_index)
-                    && fighter.killed_by != Some(fighter_index)
-                {
-                    continue;
-                }
-
-                if let Some(boss) = fighter.boss {
-                    character.defeated_bosses.insert(boss);
-                }
-            }
-
-            // Kill the character
-            if character.alive && fighter.killed_by.is_some() {
-                let killer = fighter.killed_by.unwrap();
-                let killer_name = self.battle.get_fighter(killer).name.clone();
-                character.alive = false;
-                character.death_info = Some(DeathInfo {
-                    cause: DeathCause::KilledBy(killer_name),
-                    date: DatabaseDateTime::now(),
-                });
-            }
-
-            for fighter in self.battle.fighters.clone() {
-                if fighter.is_defeated {
-                    continue;
-                }
-
-                for skill in fighter.skills.clone() {
-                    let skill = skill.dynamic_skill.lock().await;
-                    let kind = skill.save_kind();
-                    if skill
-                        .data(&fighter)
-                        .complexity
-                        .prob_of_aknowleding()
-                        .generate_random_bool()
-                        && Probability::new(30).generate_random_bool()
-                    {
-                        character.aknowledge_skill(kind);
-                    }
-                }
-            }
-
-            let mut skills = Vec::with_capacity(fighter.skills.capacity());
-            for skill in fighter.skills.clone() {
-                skills.push(skill.dynamic_skill.lock().await.save_kind());
-            }
-
-            character.weapon = fighter.weapon.map(|w| w.kind);
-            character.stats.resistance = fighter.resistance.into();
-            character.stats.vitality = fighter.vitality.into();
-            character.stats.ether = fighter.ether.into();
-            character.battle_inventory = fighter
-                .inventory
-                .iter()
-                .map(|i| InventoryItem {
-                    identifier: i.item.identifier.to_string(),
-                    quantity: i.quantity,
-                    values: i.values.clone(),
-                })
-                .collect();
-            character.skills = skills;
-            self.ctx.db().characters().save(character).await?;
-        }
-
-        Ok(())
-    }
-
-    pub async fn save_user_characters_stats(&mut self) -> anyhow::Result<()> {
-        if !self.battle.settings.has_consequences {
-            return Ok(());
-        }
-
-        for (user, fighter) in self
-            .battle
-            .fighters
-            .iter()
-            .filter_map(|f| f.user.as_ref().map(|user| (user.clone(), f.clone())))
-        {
-            let fighter_index = fighter.index;
-            let Some(mut character) = self
-                .ctx
-                .db()
-                .characters()
-                .get_by_user(&user.id.to_string())
-                .await?
-            else {
-                continue;
-            };
-
-            let is_pvp = self
-                .battle
-                .fighters
-                .iter()
-                .filter_map(|f| f.user.clone())
-                .filter(|u| u.id != user.id)
-                .count()
-                > 0;
-            let is_pve = self
-                .battle
-                .fighters
-                .iter()
-                .filter(|f| f.user.is_none())
-                .count()
-                > 0;
-
-            // Update stats
-            for fighter in self.battle.fighters.clone() {
-                if fighter.index == fighter_index {
-                    continue;
-                }
-
-                let is_ai = fighter.user.is_none();
-
-                if fighter.killed_by == Some(fighter_index) {
-                    if is_ai {
-                        character.stats.pve.kills += 1;
-                    } else {
-                        character.stats.pvp.kills += 1;
-                    }
-                } else if fighter.defeated_by == Some(fighter_index) {
-                    if is_ai {
-                        character.stats.pve.knockouts += 1;
-                    } else {
-                        character.stats.pvp.knockouts += 1;
-                    }
-                }
-            }
-
-            if let BattleState::Ended { winners, .. } = &self.battle.state {
-                if winners.contains(&fighter.index) {
-                    if is_pvp {
-                        character.stats.pvp.wins += 1;
-                    } else if is_pve {
-                        character.stats.pve.wins += 1;
-                    }
-                } else if is_pvp {
-                    character.stats.pvp.losses += 1;
-                } else {
-                    character.stats.pve.losses += 1;
-                }
-            }
-
-            if fighter.flags.contains(FighterFlags::RISKING_LIFE) {
-                if is_pvp {
-                    character.stats.pvp.life_risks += 1;
-                } else if is_pve {
-                    character.stats.pve.life_risks += 1;
-                }
-            } else if fighter.is_defeated {
-                if is_pvp {
-                    character.stats.pvp.withdrawals += 1;
-                } else if is_pve {
-                    character.stats.pve.withdrawals += 1;
-                }
-            }
-
-            self.ctx.db().characters().save(character).await?;
-        }
-
-        Ok(())
-    }
-
-    pub async fn next_turn(&mut self) -> anyhow::Result<()> {
-        let alive_fighters = self.battle.alive_fighters.clone();
-
-        controller_helper::tick_every_modifier(&alive_fighters, self).await?;
-        controller_helper::tick_every_effect(&alive_fighters, self).await?;
-        controller_helper::passives(self).await?;
-
-        // Ask every fighter to check if it should risk life
-        controller_helper::should_risk_life(&alive_fighters, self).await?;
-
-        // Move to next turn
-        let before_message_len = self.current_turn_history.messages.len();
-        self.battle.next_turn(&mut self.current_turn_history);
-
-        // Update the turn message
-        if before_message_len != self.current_turn_history.messages.len() {
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            self.update_turn_history_message().await?;
-            tokio::time::sleep(Duration::from_secs(1)).await;
-        }
-
-        let before_message_len = self.current_turn_history.messages.len();
-        if self.battle.fighters_queue.is_empty() {
-            self.battle.cycle_counter += 1;
-            controller_helper::tick_cycle(&self.battle.alive_fighters.clone(), self).await?;
-        }
-
-        // Update the turn message - cycle tick
-        if before_message_len != self.current_turn_history.messages.len() {
-            self.update_turn_history_message().await?;
-            tokio::time::sleep(Duration::from_secs(1)).await;
-        }
-
-        self.battle.next_fighter();
-
-        Ok(())
-    }
-
-    pub async fn process_input(&mut self, input: BattleInput) -> anyhow::Result<()> {
-        let fighter = self.battle.get_current_fighter().clone();
-        let fighter_name = fighter.name.clone();
-
-        match input {
-            BattleInput::Nothing => {
-                self.emit_turn_message(format!("**{}** não fez nada!", fighter.name));
-            }
-            BattleInput::Reinput => {
-                self.should_reinput = true;
-                return Ok(());
-            }
-            BattleInput::ChangeTarget(target) => {
-                self.should_reinput = true;
-                if target != fighter.index {
-                    self.battle.get_current_fighter_mut().target = target;
-                }
-
-                return Ok(());
-            }
-            BattleInput::ChangeTeam(team) => {
-                self.should_reinput = true;
-                self.battle.get_current_fighter_mut().team = team;
-                self.battle.reallocate_all_targets();
-
-                return Ok(());
-            }
-            BattleInput::Attack | BattleInput::Upkick => {
-                let is_kick = input == BattleInput::Upkick;
-                let mut api = BattleApi::new(self);
-
-                if !is_kick && api.fighter().composure != Composure::OnGround {
-                    if let Some(weapon) = api.fighter().weapon {
-                        execute_weapon_attack(api, weapon).await?;
-                        return Ok(());
-                    }
-                }
-
-                // Default attack
-                let mut damage = api.rng().gen_range(10..=15);
-                if is_kick {
-                    damage += api.rng().gen_range(3..=6);
-                }
-
-                let damage = (damage as f32 * api.fighter().strength_multiplier() * 1.1) as i32;
-
-                let damage = api
-                    .apply_damage(
-                        api.target_index,
-                        DamageSpecifier {
-                            culprit: fighter.index,
-                            amount: damage,
-                            kind: DamageKind::Physical,
-                            balance_effectiveness: 5 + if is_kick { 10 } else { 0 },
-                            accuracy: if is_kick { 60 } else { 90 },
-                            effect: None,
-                        },
-                    )
-                    .await;
-
-                let target_name = api.target().name.to_owned();
-                api.emit_message(format!(
-                    "**{}** {} **{}** e causou **{damage}**",
-                    fighter_name,
-                    if is_kick { "chutou" } else { "atacou" },
-                    target_name
-                ));
-            }
-            BattleInput::Defend => {
-                if fighter.composure == Composure::OnGround {
-                    self.emit_random_turn_message(&[
-                        format!("**{}** tentou se defender mas está no chão!", fighter_name),
-                        format!("**{}** não conseguiu se defender no chão!", fighter_name),
-                    ]);
-                } else {
-                    let mut message = format!("**{}** está defendendo!", fighter_name);
-                    if fighter.balance < 90 {
-                        message.push_str(" Um pouco de sua postura foi restaurada.");
-                    }
-
-                    self.emit_turn_message(message);
-
-                    let fighter = self.battle.get_current_fighter_mut();
-                    fighter.defense += 2; // One will be removed at the end of the turn, so we add an extra defense turn
-                    fighter.balance = fighter.balance.saturating_add(10).min(100);
-                    fighter.ether.add((fighter.ether.max as f32 * 0.05) as i32);
-                }
-            }
-            BattleInput::UseSkill(skill) => {
-                let fighter = self.battle.get_current_fighter_mut();
-                let mut dynamic_skill = skill.dynamic_skill.lock().await;
-                fighter.ether.value = fighter
-                    .ether
-                    .value
-                    .sub(dynamic_skill.data(fighter).use_cost.ether)
-                    .max(0);
-
-                dynamic_skill.on_use(BattleApi::new(self)).await?;
-            }
-            BattleInput::Finish(finisher) => {
-                if finisher.fail_probability().generate_random_bool() {
-                    self.emit_turn_message(format!(
-                        "**{}** tentou executar uma finalização mas não conseguiu!",
-                        fighter.name
-                    ));
-                    return Ok(());
-                }
-
-                finisher.execute_finisher(BattleApi::new(self)).await?;
-                tokio::time::sleep(Duration::from_millis(300)).await;
-                self.update_turn_history_message().await?;
-                tokio::time::sleep(Duration::from_secs(1)).await;
-            }
-            BattleInput::GetUp => {
-                let fighter = self.battle.get_current_fighter_mut();
-                let extreme_low_balance = fighter.balance < 40;
-                let prob_to_fail = Probability::new(if extreme_low_balance { 30 } else { 15 });
-
-                if prob_to_fail.generate_random_bool() {
-                    fighter.balance = fighter.balance.add(10).min(100);
-
-                    self.emit_random_turn_message(&[
-                        format!("**{}** tentou levantar e caiu novamente!", fighter_name),
-                        format!(
-                            "**{}** tentou ficar em pé e perdeu o equilíbrio!",
-                            fighter_name
-                        ),
-                        format!("**{}** falhou em levantar!", fighter_name),
-                    ]);
-                } else {
-                    fighter.composure = Composure::Standing;
-                    fighter.balance = fighter.balance.add(30).min(100);
-
-                    self.emit_random_turn_message(&[
-                        format!("**{}** recuperou sua postura e ficou em pé!", fighter_name),
-                        format!("**{}** levantou do chão!", fighter_name),
-                    ]);
-                }
-            }
-            BattleInput::UseItem(item) => {
-                if !fighter
-                    .inventory
-                    .iter()
-                    .any(|i| i.item.identifier == item.identifier)
-                {
-                    self.emit_turn_message(format!(
-                        "**{}** não possui o item {}!",
-                        fighter.name, item.display_name
-                    ));
-                    return Ok(());
-                }
-
-                if let Some(weapon) = item.weapon {
-                    {
-                        let fighter = self.battle.get_fighter_mut(fighter.index);
-                        if let Some(weapon) = fighter.weapon {
-                            let weapon_item = get_item_by_weapon(weapon.kind);
-                            fighter.inventory.push(BattleItem {
-                                item: weapon_item,
-                                quantity: 1,
-                                values: ItemValues::empty(),
-                            });
-                        }
-
-                        fighter.weapon = Some(FighterWeapon { kind: weapon });
-                    }
-
-                    let weapon_item = get_item_by_weapon(weapon);
-                    self.battle
-                        .get_fighter_mut(fighter.index)
-                        .remove_item(weapon_item, 1);
-
-                    self.emit_turn_message(format!(
-                        "**{}** equipou a arma **{}**",
-                        fighter.name, weapon_item.display_name
-                    ));
-                    return Ok(());
-                }
-
-                // Special items
-                match item.display_name {
-                    "invigorating_crystal" => {
-                        let hp = (fighter.health().max as f32 * 0.25) as i32;
-                        let ether = (fighter.ether.max as f32 * 0.65) as i32;
-
-                        {
-                            let fighter = self.battle.get_fighter_mut(fighter.index);
-                            fighter.heal(fighter.index, hp);
-                            fighter.ether.add(ether);
-                        }
-
-                        self.emit_turn_message(format!(
-                            "**{}** usou um cristal revigorante e se revigorou!",
-                            fighter.name
-                        ));
-
-                        self.update_turn_history_message().await?;
-                        tokio::time::sleep(Duration::from_secs(1)).await;
-
-                        self.should_reinput = true;
-                        return Ok(());
-                    }
-                    "intelligence_crystal" => {
-                        {
-                            let fighter = self.battle.get_fighter_mut(fighter.index);
-                            fighter.intelligence_level += 10;
-                        }
-
-                        self.emit_turn_message(format!(
-                            "**{}** usou um cristal da inteligência e sentiu seu cérebro mais rápido e inteligente!",
-                            fighter.name
-                        ));
-
-                        self.update_turn_history_message().await?;
-                        tokio::time::sleep(Duration::from_secs(1)).await;
-
-                        self.should_reinput = true;
-                        return Ok(());
-                    }
-                    _ => {}
-                };
-
-                // Consumable items
-                let Some(consumption_properties) = item.consumption_properties else {
-                    self.emit_turn_message(format!(
-                        "**{}** tentou usar um item que não pode ser usado em batalha!",
-                        fighter.name
-                    ));
-                    return Ok(());
-                };
-
-                let health_regeneration = math::calculate_health_regeneration(
-                    consumption_properties,
-                    1,
-                    fighter.health().max,
-                );
-                let ether_regeneration = math::calculate_ether_regeneration(
-                    consumption_properties,
-                    1,
-                    fighter.ether.max,
-                );
-
-                let mut messages = vec![];
-                if health_regeneration > 0 {
-                    messages.push(format!("**{health_regeneration} vida**"));
-                }
-
-                if ether_regeneration > 0 {
-                    messages.push(format!("**{ether_regeneration} ether**"));
-                }
-
-                {
-                    let fighter = self.battle.get_fighter_mut(fighter.index);
-                    fighter.heal(fighter.index, health_regeneration);
-                    fighter.ether.add(ether_regeneration);
-                    fighter.remove_item(item, 1);
-                }
-
-                self.emit_turn_message(format!(
-                    "**{}** usou o item **{}** e regenerou {}!",
-                    fighter.name,
-                    item.display_name,
-                    messages.join(" e ")
-                ));
-
-                self.update_turn_history_message().await?;
-                tokio::time::sleep(Duration::from_secs(1)).await;
-
-                self.should_reinput = true;
-            }
-            BattleInput::UseAction(action) => {
-                let mut api = BattleApi::new(self);
-                api.fighter_index = fighter.index;
-                api.target_index = fighter.target;
-                action_handler::execute_action(action, &mut api).await?;
-            }
-        }
-
-        Ok(())
-    }
-
-    pub async fn get_input(&mut self) -> anyhow::Result<BattleInput> {
-        get_input(self).await
-    }
-
-    pub fn emit_turn_message(&mut self, message: impl Into<String>) {
-        self.current_turn_history.messages.push(message.into());
-    }
-
-    pub fn defer_message(&mut self, message: impl Into<String>) {
-        self.battle.deferred_turn_messages.push(message.into());
-    }
-
-    pub fn emit_random_turn_message(&mut self, messages: &[impl ToString]) {
-        let messages = messages.iter().map(|m| m.to_string()).collect::<Vec<_>>();
-        let message = messages
-            .choose(&mut self.battle.rng)
-            .cloned()
-            .unwrap_or_default();
-        self.current_turn_history.messages.push(message);
-    }
-
-    pub fn create_turn_embed(&self) -> EmbedBuilder {
-        let current_fighter = self.battle.get_current_fighter();
-        let target_fighter = self.battle.get_target_fighter();
-        let turn_num = self.current_turn_history.round;
-
-        EmbedBuilder::new_common()
-            .set_color(if turn_num % 2 == 0 {
-                Color::ORANGE
-            } else {
-                Color::DARK_ORANGE
-            })
-            .set_author(EmbedAuthor {
-                name: format!("Rodada N° {turn_num} - {}", current_fighter.name),
-                icon_url: current_fighter.user.as_ref().map(|u| u.avatar_url()),
-            })
-            .set_title(format!(
-                "{} x {}",
-                current_fighter.name, target_fighter.name
-            ))
-            .set_description(self.current_turn_history.messages.join("\n"))
-    }
-
-    pub fn create_battle_embed(&mut self) -> EmbedBuilder {
-        let current_fighter = self.battle.get_current_fighter().clone();
-        let mut embed = EmbedBuilder::new_common().set_author(EmbedAuthor {
-            name: format!("Rodada de {}", current_fighter.name),
-            icon_url: current_fighter.user.as_ref().map(|u| u.avatar_url()),
-        });
-
-        let mut alive_fighters = self
-            .battle
-            .alive_fighters
-            .clone()
-            .into_iter()
-            .map(|f| self.battle.get_fighter(f).clone())
-            .collect::<Vec<_>>();
-        alive_fighters.sort_unstable_by_key(|f| {
-            if f.index == current_fighter.index {
-                0
-            } else if f.index == current_fighter.target {
-                1
-            } else {
-                2 + f.index.0
-            }
-        });
-
-        for fighter in alive_fighters {
-            embed = embed.add_field(create_fighter_embed_fields(
-                &fighter,
-                Some(current_fighter.target),
-            ));
-        }
-
-        embed
-    }
 }
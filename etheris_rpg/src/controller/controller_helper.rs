@@ -1,42 +1,14 @@
-use std::ops::Mul;
-
 use crate::*;
 use common::*;
 use etheris_common::Probability;
 use etheris_data::emojis;
 use etheris_discord::EmbedField;
-use etheris_framework::Response;
-
-fn melt_ice(melt_power: i32, api: &mut BattleApi<'_>) {
-    if let Some(effect) = api.fighter().get_effect(EffectKind::Frozen) {
-        api.fighter_mut().remove_effect(Effect::new(
-            EffectKind::Frozen,
-            melt_power,
-            effect.culprit,
-        ));
-        api.fighter_mut()
-            .apply_effect(Effect::new(EffectKind::Wet, melt_power, effect.culprit));
-    }
-
-    if let Some(effect) = api.fighter().get_effect(EffectKind::Ice) {
-        api.fighter_mut().remove_effect(Effect::new(
-            EffectKind::Ice,
-            melt_power - 5,
-            effect.culprit,
-        ));
-        api.fighter_mut().apply_effect(Effect::new(
-            EffectKind::Wet,
-            melt_power - 5,
-            effect.culprit,
-        ));
-    }
-}
 
-pub async fn on_start(controller: &mut BattleController) -> anyhow::Result<()> {
-    for fighter_index in controller.battle.alive_fighters.clone() {
-        let fighter = controller.battle.get_fighter(fighter_index).clone();
+pub async fn on_start(engine: &mut BattleEngine) -> anyhow::Result<()> {
+    for fighter_index in engine.battle.alive_fighters.clone() {
+        let fighter = engine.battle.get_fighter(fighter_index).clone();
         for skill in fighter.skills.clone() {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = fighter_index;
             api.target_index = fighter.target;
 
@@ -49,10 +21,10 @@ pub async fn on_start(controller: &mut BattleController) -> anyhow::Result<()> {
 
 pub async fn tick_every_modifier(
     fighters: &[FighterIndex],
-    controller: &mut BattleController,
+    engine: &mut BattleEngine,
 ) -> anyhow::Result<()> {
     for fighter_index in fighters {
-        let fighter = controller.battle.get_fighter_mut(*fighter_index);
+        let fighter = engine.battle.get_fighter_mut(*fighter_index);
 
         for modifier in fighter.modifiers.list.iter_mut() {
             if let Some(turns_remaining) = modifier.turns_remaining {
@@ -71,266 +43,162 @@ pub async fn tick_every_modifier(
 
 pub async fn tick_every_effect(
     fighters: &[FighterIndex],
-    controller: &mut BattleController,
+    engine: &mut BattleEngine,
 ) -> anyhow::Result<()> {
     for fighter_index in fighters {
-        let fighter = controller.battle.get_fighter_mut(*fighter_index);
-        let immunities = fighter.body_immunities.clone();
+        let fighter = engine.battle.get_fighter_mut(*fighter_index);
         let fighter_name = fighter.name.clone();
 
         for effect in fighter.effects.clone() {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = *fighter_index;
             api.target_index = *fighter_index;
 
-            let immunity_dmg_multiplier = if let Some(immunity) = effect.kind.affected_immunity() {
-                immunities.dmg_multiplier_from_immunity(immunity)
-            } else {
-                1.0
+            // Another effect may have consumed this one earlier in the turn
+            let Some(effect) = api.fighter().get_effect(effect.kind) else {
+                continue;
             };
 
-            match effect.kind {
-                EffectKind::Flaming => {
-                    api.fighter_mut()
-                        .remove_effect(Effect::new(effect.kind, 5, effect.culprit));
-                    melt_ice(10, &mut api);
-
-                    let dmg = 2 + (api.fighter().health().max as f32 * 0.01) as i32;
-
-                    let dmg = api
-                        .apply_damage(
-                            api.fighter_index,
-                            DamageSpecifier {
-                                kind: DamageKind::Fire,
-                                amount: (dmg as f64 * immunity_dmg_multiplier) as i32,
-                                balance_effectiveness: 1,
-                                accuracy: 100,
-                                ..Default::default()
-                            },
-                        )
-                        .await;
-
-                    api.emit_message(format!(
-                        "***{}** queimou e recebeu **{dmg}**!",
-                        fighter_name
-                    ));
-                }
-                EffectKind::Burning => {
-                    api.fighter_mut()
-                        .remove_effect(Effect::new(effect.kind, 5, effect.culprit));
-
-                    melt_ice(20, &mut api);
-
-                    let dmg = 8 + (api.fighter().health().max as f32 * 0.03) as i32;
-
-                    let dmg = api
-                        .apply_damage(
-                            api.fighter_index,
-                            DamageSpecifier {
-                                kind: DamageKind::Fire,
-                                amount: (dmg as f64 * immunity_dmg_multiplier) as i32,
-                                balance_effectiveness: 4,
-                                accuracy: 100,
-                                ..Default::default()
-                            },
-                        )
-                        .await;
-
-                    api.emit_message(format!(
-                        "***{}** está em combustão e recebeu **{dmg}**!",
-                        fighter_name
-                    ));
-                }
-                EffectKind::Shocked => {
-                    api.fighter_mut()
-                        .remove_effect(Effect::new(effect.kind, 10, effect.culprit));
-                    // Handled by the controller
-                }
-                EffectKind::Paralyzed => {}
-                EffectKind::Curse => {
-                    let safe = api.fighter_mut().remove_effect(Effect::new(
-                        effect.kind,
-                        10,
-                        effect.culprit,
-                    ));
-                    if safe {
-                        api.emit_message(format!(
-                            "***{}** não está mais com uma maldição!",
-                            fighter_name
-                        ));
-                    }
-                }
-                EffectKind::LowProtection => {
-                    let unprotected = api.fighter_mut().remove_effect(Effect::new(
-                        effect.kind,
-                        1,
-                        effect.culprit,
-                    ));
-
-                    if unprotected {
-                        api.emit_message(format!(
-                            "***{}** perdeu a proteção leve extra!*",
-                            api.fighter().name
-                        ));
-                    }
-                }
-                EffectKind::Ice => {
-                    api.fighter_mut()
-                        .remove_effect(Effect::new(effect.kind, 5, effect.culprit));
-                }
-                EffectKind::Wet => {
-                    api.fighter_mut()
-                        .remove_effect(Effect::new(effect.kind, 15, effect.culprit));
-                    api.fighter_mut().remove_effect(Effect::new(
-                        EffectKind::Flaming,
-                        60,
-                        effect.culprit,
-                    ));
-                    api.fighter_mut().remove_effect(Effect::new(
-                        EffectKind::Burning,
-                        20,
-                        effect.culprit,
-                    ));
-
-                    if let Some(shock) = api.fighter().get_effect(EffectKind::Shocked) {
-                        let dmg = match shock.amount {
-                            0..=30 => 0.05,
-                            31..=60 => 0.08,
-                            61..=90 => 0.1,
-                            _ => 0.2,
-                        };
-
-                        let dmg = ((api.fighter().health().max as f32) * dmg).floor() as i32;
-
-                        api.fighter_mut().remove_effect(shock);
-                        let dmg = api
-                            .apply_damage(
-                                api.fighter_index,
-                                DamageSpecifier {
-                                    culprit: effect.culprit,
-                                    kind: DamageKind::Special,
-                                    amount: dmg,
-                                    balance_effectiveness: 10,
-                                    accuracy: 100,
-                                    effect: None,
-                                },
-                            )
-                            .await;
-
-                        api.emit_message(format!(
-                            "**{}** eletrocutou devido a água e recebeu **{dmg}**!",
-                            api.fighter().name
-                        ));
-
-                        if Probability::new(20).generate_random_bool() {
-                            api.apply_effect(
-                                api.fighter().index,
-                                Effect::new(EffectKind::Paralyzed, 1, effect.culprit),
-                            )
-                            .await;
-                        }
-                    }
-                }
-                EffectKind::Frozen => {
-                    let melted = api.fighter_mut().remove_effect(Effect::new(
-                        effect.kind,
-                        5,
-                        effect.culprit,
-                    ));
-                    api.fighter_mut().remove_effect(Effect::new(
-                        EffectKind::Flaming,
-                        20,
-                        effect.culprit,
-                    ));
-                    api.fighter_mut().remove_effect(Effect::new(
-                        EffectKind::Burning,
-                        10,
-                        effect.culprit,
-                    ));
-
-                    if melted {
-                        api.emit_message(format!("***{}** descongelou*", api.fighter().name));
-                    }
-                }
-                EffectKind::Bleeding => {
-                    if effect.amount >= 100 {
-                        api.fighter_mut().remove_effect(Effect::new(
-                            effect.kind,
-                            effect.amount,
-                            Default::default(),
-                        ));
-
-                        let dmg = 20 + (api.fighter().health().max as f32).mul(0.15) as i32;
-                        api.fighter_mut().take_damage(
-                            effect.culprit,
-                            DamageSpecifier {
-                                culprit: effect.culprit,
-                                kind: DamageKind::Special,
-                                amount: (dmg as f64 * immunity_dmg_multiplier) as i32,
-                                balance_effectiveness: 0,
-                                accuracy: 100,
-                                effect: None,
-                            },
-                        );
-                        api.defer_message(format!(
-                            "**{fighter_name}** teve uma hemorragia que causou **{dmg} dano**!"
-                        ));
-                    } else {
-                        api.fighter_mut().remove_effect(Effect::new(
-                            effect.kind,
-                            5,
-                            Default::default(),
-                        ));
-                    }
-                }
-                EffectKind::Poisoned => {
-                    api.fighter_mut().remove_effect(Effect::new(
-                        effect.kind,
-                        5,
-                        Default::default(),
-                    ));
-
-                    let dmg = 6 + (api.fighter().health().max as f32 * 0.025) as i32;
-                    let dmg = api
-                        .apply_damage(
-                            api.fighter_index,
-                            DamageSpecifier {
-                                kind: DamageKind::Poisonous,
-                                amount: (dmg as f64 * immunity_dmg_multiplier) as i32,
-                                balance_effectiveness: 3,
-                                accuracy: 100,
-                                ..Default::default()
-                            },
-                        )
-                        .await;
-
-                    api.defer_message(format!("**{}** recebeu **{dmg}** do veneno!", fighter_name));
-                }
-                EffectKind::Exhausted => {
-                    api.fighter_mut().remove_effect(Effect::new(
-                        effect.kind,
-                        1,
-                        Default::default(),
-                    ));
-                }
+            if api.resolve_effect_threshold(*fighter_index, effect.kind) {
+                continue;
+            }
+
+            let rules = effect.kind.rules();
+            let expired = rules.decay > 0
+                && api.fighter_mut().remove_effect(Effect::new(
+                    effect.kind,
+                    rules.decay,
+                    effect.culprit,
+                ));
+
+            for interaction in rules.interactions {
+                apply_effect_interaction(&mut api, effect, *interaction).await;
+            }
+
+            if let Some(tick) = rules.tick_damage {
+                let dmg =
+                    tick.base + (api.fighter().health().max as f32 * tick.max_health_ratio) as i32;
+
+                let dmg = api
+                    .apply_damage(
+                        api.fighter_index,
+                        DamageSpecifier {
+                            culprit: effect.culprit,
+                            kind: tick.kind,
+                            amount: dmg,
+                            balance_effectiveness: tick.balance_effectiveness,
+                            accuracy: 100,
+                            effect: None,
+                        },
+                    )
+                    .await;
+
+                api.emit_message(format_effect_damage_message(
+                    tick.message,
+                    &fighter_name,
+                    dmg,
+                ));
+            }
+
+            if let Some(message) = rules.expired_message.filter(|_| expired) {
+                api.emit_message(format_effect_message(message, &fighter_name));
             }
         }
     }
     Ok(())
 }
 
+async fn apply_effect_interaction(
+    api: &mut BattleApi<'_>,
+    effect: Effect,
+    interaction: EffectInteraction,
+) {
+    match interaction {
+        EffectInteraction::Weaken { other, amount } => {
+            api.fighter_mut()
+                .remove_effect(Effect::new(other, amount, effect.culprit));
+        }
+        EffectInteraction::Convert {
+            other,
+            amount,
+            into,
+        } => {
+            let Some(other_effect) = api.fighter().get_effect(other) else {
+                return;
+            };
+
+            let converted = amount.min(other_effect.amount);
+            api.fighter_mut()
+                .remove_effect(Effect::new(other, converted, other_effect.culprit));
+            api.fighter_mut()
+                .apply_effect(Effect::new(into, converted, other_effect.culprit));
+        }
+        EffectInteraction::Amplify {
+            other,
+            damage_kind,
+            tiers,
+            balance_effectiveness,
+            follow_up,
+            message,
+        } => {
+            let Some(other_effect) = api.fighter().get_effect(other) else {
+                return;
+            };
+
+            let ratio = tiers
+                .iter()
+                .find(|(max_amount, _)| other_effect.amount <= *max_amount)
+                .map(|(_, ratio)| *ratio)
+                .unwrap_or(0.0);
+            let dmg = ((api.fighter().health().max as f32) * ratio).floor() as i32;
+
+            api.fighter_mut().delete_effect(other);
+            let dmg = api
+                .apply_damage(
+                    api.fighter_index,
+                    DamageSpecifier {
+                        culprit: effect.culprit,
+                        kind: damage_kind,
+                        amount: dmg,
+                        balance_effectiveness,
+                        accuracy: 100,
+                        effect: None,
+                    },
+                )
+                .await;
+
+            api.emit_message(format_effect_damage_message(
+                message,
+                &api.fighter().name,
+                dmg,
+            ));
+
+            if let Some((follow_up, probability)) = follow_up {
+                if Probability::new(probability).generate_random_bool_with(api.rng()) {
+                    api.apply_effect(
+                        api.fighter().index,
+                        Effect::new(follow_up, 1, effect.culprit),
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+}
+
 pub async fn should_risk_life(
     fighters: &[FighterIndex],
-    controller: &mut BattleController,
+    engine: &mut BattleEngine,
 ) -> anyhow::Result<()> {
-    let settings = controller.battle.settings.clone();
+    let settings = engine.battle.settings.clone();
     for fighter_index in fighters {
-        let fighter = controller.battle.get_fighter_mut(*fighter_index);
+        let fighter = engine.battle.get_fighter_mut(*fighter_index);
         let fighter_name = fighter.name.clone();
 
         if fighter.resistance.value > 0
             || fighter.flags.contains(FighterFlags::ASKED_TO_RISK_LIFE)
             || fighter.flags.contains(FighterFlags::GAVE_UP)
+            || fighter.flags.contains(FighterFlags::FLED)
         {
             continue;
         }
@@ -343,40 +211,31 @@ pub async fn should_risk_life(
         let confirmation = if !settings.is_risking_life_allowed {
             false
         } else if let Some(brain) = fighter.brain.clone() {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = fighter.index;
             api.target_index = fighter.target;
 
             brain.dynamic_brain.lock().await.should_risk_life(api).await
-        } else if let Some(user) = &fighter.user {
-            controller.ctx.helper()
-                .create_confirmation(user.id, true, Response::new_user_reply(
-                    user,
-                    if settings.casual {
-                        "sua resistência chegou a zero! Quer apostar sua vida na batalha? (Essa é uma batalha CASUAL. Seu personagem não vai morrer de verdade)"
-                    } else {
-                        "sua resistência chegou a zero! Isso significa que seu personagem está perto do nocaute. Você deseja continuar lutando e apostar sua vida nessa batalha?\nSe não aceitar, você perderá por nocaute. Se aceitar, você continuará lutando mas perderá **vitalidade** ao invés de resistência. Se a vitalidade zerar seu personagem morre pra sempre."
-                    },
-                ))
-                .await?
+        } else if fighter.user.is_some() {
+            engine.confirm_risk_life(fighter.index).await?
         } else {
             false
         };
 
-        let fighter = controller.battle.get_fighter_mut(*fighter_index);
+        let fighter = engine.battle.get_fighter_mut(*fighter_index);
         fighter.flags.insert(FighterFlags::ASKED_TO_RISK_LIFE);
 
         if confirmation {
             fighter.flags.intersects(FighterFlags::RISKING_LIFE);
             fighter.defeated_by = None;
             fighter.is_defeated = false;
-            controller.defer_message(format!(
+            engine.defer_message(format!(
                 "**{}** está arriscando sua vida agora!",
                 fighter_name
             ));
         } else {
             fighter.is_defeated = true;
-            controller.defer_message(format!(
+            engine.defer_message(format!(
                 "**{}** não teve motivação para arriscar sua vida e perdeu a consciência!",
                 fighter_name
             ));
@@ -386,15 +245,15 @@ pub async fn should_risk_life(
     Ok(())
 }
 
-pub async fn passives(controller: &mut BattleController) -> anyhow::Result<()> {
+pub async fn passives(engine: &mut BattleEngine) -> anyhow::Result<()> {
     // Passives: on_damage
-    for (damage, fighter) in controller.battle.turn_end_queues.damages.clone() {
+    for (damage, fighter) in engine.battle.turn_end_queues.damages.clone() {
         let fighter_index = fighter;
         let target_index = damage.culprit;
 
-        let skills = controller.battle.get_fighter(fighter).skills.clone();
+        let skills = engine.battle.get_fighter(fighter).skills.clone();
         for skill in skills {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = fighter_index;
             api.target_index = target_index;
 
@@ -409,13 +268,13 @@ pub async fn passives(controller: &mut BattleController) -> anyhow::Result<()> {
     }
 
     // Passives: on_damage_miss
-    for (damage, fighter) in controller.battle.turn_end_queues.damage_misses.clone() {
+    for (damage, fighter) in engine.battle.turn_end_queues.damage_misses.clone() {
         let fighter_index = fighter;
         let target_index = damage.culprit;
 
-        let skills = controller.battle.get_fighter(fighter).skills.clone();
+        let skills = engine.battle.get_fighter(fighter).skills.clone();
         for skill in skills {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = fighter_index;
             api.target_index = target_index;
 
@@ -430,13 +289,13 @@ pub async fn passives(controller: &mut BattleController) -> anyhow::Result<()> {
     }
 
     // Passives: tick fighter skills
-    for fighter in controller.battle.alive_fighters.clone() {
-        let fighter = controller.battle.get_fighter_mut(fighter);
+    for fighter in engine.battle.alive_fighters.clone() {
+        let fighter = engine.battle.get_fighter_mut(fighter);
         let fighter_index = fighter.index;
         let target_index = fighter.target;
 
         for skill in fighter.skills.clone() {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = fighter_index;
             api.target_index = target_index;
 
@@ -451,16 +310,16 @@ pub async fn passives(controller: &mut BattleController) -> anyhow::Result<()> {
     }
 
     // Passives: on_kill
-    for fighter_index in controller.battle.alive_fighters.clone() {
-        let fighter = controller.battle.get_fighter(fighter_index).clone();
+    for fighter_index in engine.battle.alive_fighters.clone() {
+        let fighter = engine.battle.get_fighter(fighter_index).clone();
         let Some(killer) = fighter.killed_by else {
             continue;
         };
 
-        let killer_skills = controller.battle.get_fighter(killer).skills.clone();
+        let killer_skills = engine.battle.get_fighter(killer).skills.clone();
 
         for skill in killer_skills {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = killer;
             api.target_index = fighter.index;
             skill
@@ -478,29 +337,29 @@ pub async fn passives(controller: &mut BattleController) -> anyhow::Result<()> {
 
 pub async fn tick_cycle(
     fighters: &[FighterIndex],
-    controller: &mut BattleController,
+    engine: &mut BattleEngine,
 ) -> anyhow::Result<()> {
     for fighter_index in fighters {
-        let fighter = controller.battle.get_fighter(*fighter_index).clone();
+        let fighter = engine.battle.get_fighter(*fighter_index).clone();
         let fighter_name = fighter.name.clone();
 
         if let Composure::OnAir(meters) = fighter.composure {
             if meters <= 1 {
-                controller.battle.get_fighter_mut(*fighter_index).composure = Composure::Standing;
-                controller.emit_turn_message(format!("***{}** pousou no chão!*", fighter_name));
+                engine.battle.get_fighter_mut(*fighter_index).composure = Composure::Standing;
+                engine.emit_turn_message(format!("***{}** pousou no chão!*", fighter_name));
             } else {
-                controller.battle.get_fighter_mut(*fighter_index).composure =
+                engine.battle.get_fighter_mut(*fighter_index).composure =
                     Composure::OnAir(meters - 1);
             }
         }
 
         // Passives: on_cycle
-        for fighter_index in controller.battle.alive_fighters.clone() {
-            let fighter = controller.battle.get_fighter(fighter_index).clone();
+        for fighter_index in engine.battle.alive_fighters.clone() {
+            let fighter = engine.battle.get_fighter(fighter_index).clone();
             let skills = fighter.skills.clone();
 
             for skill in skills {
-                let mut api = BattleApi::new(controller);
+                let mut api = BattleApi::new(engine);
                 api.fighter_index = fighter_index;
                 api.target_index = fighter.target;
                 skill
@@ -616,3 +475,15 @@ pub fn create_fighter_embed_fields(
         inline: true,
     }
 }
+
+/// The fields spectators see. They show the team and the power of each fighter,
+/// but never their inventories or anything only the fighter should know.
+pub fn create_spectator_fighter_embed_fields(
+    fighter: &Fighter,
+    target_index: Option<FighterIndex>,
+) -> EmbedField {
+    let mut field = create_fighter_embed_fields(fighter, target_index);
+    field.name = format!("[{}] {}", fighter.team, field.name);
+    field.value = format!("`{} PL`\n{}", fighter.pl, field.value);
+    field
+}
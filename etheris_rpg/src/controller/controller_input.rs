@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use anyhow::bail;
 use etheris_common::Color;
 use etheris_database::character_model::BattleAction;
 use etheris_discord::{
@@ -11,13 +12,13 @@ use etheris_framework::{util::make_multiple_rows, CommandContext, Response};
 use crate::*;
 
 fn make_input_rows(
-    controller: &mut BattleController,
+    engine: &mut BattleEngine,
     valid_inputs: &[BattleInputKind],
     selected_custom_id: Option<String>,
 ) -> Vec<ActionRowBuilder> {
     let buttons = valid_inputs
         .iter()
-        .map(|b| (b.button(BattleApi::new(controller)), b.second_row()))
+        .map(|b| (b.button(BattleApi::new(engine)), b.second_row()))
         .map(|(b, second_row)| {
             let data = b.data.custom_id.clone().unwrap_or_default();
             if let Some(selected) = &selected_custom_id {
@@ -61,81 +62,72 @@ fn make_input_rows(
     rows
 }
 
-pub async fn get_input(controller: &mut BattleController) -> anyhow::Result<BattleInput> {
-    let current_fighter_user_id = match &controller.battle.get_current_fighter().user {
+pub async fn get_input(
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
+) -> anyhow::Result<BattleInput> {
+    let current_fighter_user_id = match &engine.battle.get_current_fighter().user {
         Some(user) => user.id,
         None => {
-            let Some(brain) = controller.battle.get_current_fighter().brain.clone() else {
-                panic!("No brain for the current fighter that also has no user attached to it!");
-            };
-
-            return Ok(brain
-                .dynamic_brain
-                .lock()
-                .await
-                .select_input(BattleApi::new(controller))
-                .await);
+            bail!("get_input expected a user fighter, not a AI");
         }
     };
 
     let mut valid_inputs = vec![];
 
     for input in BattleInputKind::LIST {
-        if input.can_use(BattleApi::new(controller)) {
+        if input.can_use(BattleApi::new(engine)) {
             valid_inputs.push(input);
         }
     }
 
-    let rows = make_input_rows(controller, &valid_inputs, None);
-    let mut response = Response::from(controller.create_battle_embed())
+    let rows = make_input_rows(engine, &valid_inputs, None);
+    let mut response = Response::from(io.create_battle_embed(&engine.battle))
         .add_string_content(format!("<@{}>", current_fighter_user_id))
         .set_components(rows);
 
-    if let Some(last_message) = &controller.last_message {
-        if controller.battle.turn_counter % 6 == 0 {
-            controller
-                .ctx
+    if let Some(last_message) = &io.last_message {
+        if engine.battle.turn_counter % 6 == 0 {
+            io.ctx
                 .client
                 .http
                 .delete_message(last_message.channel_id, last_message.id)
                 .await
                 .ok();
-            controller.last_message = None;
+            io.last_message = None;
         }
     }
 
-    let message = match &controller.last_message {
+    let message = match &io.last_message {
         Some(message) => {
-            controller
-                .ctx
+            io.ctx
                 .update_specific_message(message, response.clone())
                 .await?;
             message.clone()
         }
         None => {
-            let message = controller.ctx.send(response.clone()).await?;
-            controller.last_message = Some(message.clone());
+            let message = io.ctx.send(response.clone()).await?;
+            io.last_message = Some(message.clone());
             message
         }
     };
 
-    let mut ctx = controller.ctx.clone();
+    let mut ctx = io.ctx.clone();
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
     let data = collected.parse_message_component_data()?;
     let input_kind = BattleInputKind::LIST.iter().find(|input| input.id() == data.custom_id)
-        .expect("BattleController::get_input should never fail at retrieving input kind from the collected component data custom id.");
+        .expect("get_input should never fail at retrieving input kind from the collected component data custom id.");
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(collected.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(collected.clone()));
 
-    let rows = make_input_rows(controller, &valid_inputs, Some(data.custom_id.clone()));
+    let rows = make_input_rows(engine, &valid_inputs, Some(data.custom_id.clone()));
     response = response.set_components(rows);
 
     if !matches!(
@@ -153,29 +145,30 @@ pub async fn get_input(controller: &mut BattleController) -> anyhow::Result<Batt
 
     Ok(match input_kind {
         BattleInputKind::ChangeTarget => {
-            get_change_target_input(controller, collected, message).await?
+            get_change_target_input(io, engine, collected, message).await?
         }
         BattleInputKind::ChangeTeam => {
-            get_change_team_input(controller, collected, message).await?
+            get_change_team_input(io, engine, collected, message).await?
         }
         BattleInputKind::Attack => BattleInput::Attack,
         BattleInputKind::Defend => BattleInput::Defend,
-        BattleInputKind::UseSkill => get_skill_input(controller, collected, message).await?,
-        BattleInputKind::Finish => get_finisher_input(controller, collected, message).await?,
+        BattleInputKind::UseSkill => get_skill_input(io, engine, collected, message).await?,
+        BattleInputKind::Finish => get_finisher_input(io, engine, collected, message).await?,
         BattleInputKind::GetUp => BattleInput::GetUp,
         BattleInputKind::Upkick => BattleInput::Upkick,
-        BattleInputKind::UseItem => get_item_input(controller, collected, message).await?,
-        BattleInputKind::Actions => get_action_input(controller, collected, message).await?,
+        BattleInputKind::UseItem => get_item_input(io, engine, collected, message).await?,
+        BattleInputKind::Actions => get_action_input(io, engine, collected, message).await?,
     })
 }
 
 pub async fn get_skill_input(
-    controller: &mut BattleController,
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
     interaction: Interaction,
     message: Message,
 ) -> anyhow::Result<BattleInput> {
-    let fighter = controller.battle.get_current_fighter().clone();
-    let skills = controller.battle.get_current_fighter().skills.clone();
+    let fighter = engine.battle.get_current_fighter().clone();
+    let skills = engine.battle.get_current_fighter().skills.clone();
 
     let mut skill_displays = vec![];
     for skill in skills.iter() {
@@ -188,9 +181,9 @@ pub async fn get_skill_input(
         .set_author(EmbedAuthor {
             name: format!(
                 "Habilidades de {}",
-                controller.battle.get_current_fighter().name
+                engine.battle.get_current_fighter().name
             ),
-            icon_url: controller
+            icon_url: engine
                 .battle
                 .get_current_fighter()
                 .user
@@ -214,11 +207,26 @@ pub async fn get_skill_input(
 
     for skill in skills.iter() {
         let dynamic_skill = skill.dynamic_skill.lock().await;
+        let data = dynamic_skill.data(&fighter);
+        let label = if skill.cooldown > 0 {
+            format!("{} (⏳ {})", data.name, skill.cooldown)
+        } else if let Some(charges) = skill.remaining_charges(data.limits) {
+            format!(
+                "{} ({charges}/{})",
+                data.name,
+                data.limits.charges.unwrap_or_default()
+            )
+        } else if dynamic_skill.reaction(&fighter).is_some() {
+            format!("{} (🔁)", data.name)
+        } else {
+            data.name.to_owned()
+        };
+
         let button = ButtonBuilder::new()
             .set_custom_id(skill.identifier)
-            .set_label(dynamic_skill.data(&fighter).name)
+            .set_label(label)
             .set_style(ButtonStyle::Primary)
-            .set_disabled(!dynamic_skill.can_use(BattleApi::new(controller)));
+            .set_disabled(!dynamic_skill.can_use(BattleApi::new(engine)));
         buttons.push(button);
     }
 
@@ -229,8 +237,7 @@ pub async fn get_skill_input(
             .set_label("Voltar"),
     );
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(interaction.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(interaction.clone()));
 
     ctx.update_message(
         Response::from(embed.clone()).set_components(make_multiple_rows(buttons.clone())),
@@ -238,12 +245,12 @@ pub async fn get_skill_input(
     .await?;
 
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
     let data = collected.parse_message_component_data()?;
 
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
@@ -279,20 +286,21 @@ pub async fn get_skill_input(
 }
 
 pub async fn get_finisher_input(
-    controller: &mut BattleController,
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
     interaction: Interaction,
     message: Message,
 ) -> anyhow::Result<BattleInput> {
-    let finishers = controller.battle.get_current_fighter().finishers.clone();
+    let finishers = engine.battle.get_current_fighter().finishers.clone();
 
     let embed = EmbedBuilder::new_common()
         .set_color(Color::LIGHT_RED)
         .set_author(EmbedAuthor {
             name: format!(
                 "Finalizações de {}",
-                controller.battle.get_current_fighter().name
+                engine.battle.get_current_fighter().name
             ),
-            icon_url: controller
+            icon_url: engine
                 .battle
                 .get_current_fighter()
                 .user
@@ -301,7 +309,7 @@ pub async fn get_finisher_input(
         })
         .set_description(format!(
             "## **Você irá finalizar:** {}\nEscolha um método de finalização.\n\n{}",
-            controller.battle.get_target_fighter().name,
+            engine.battle.get_target_fighter().name,
             finishers
                 .iter()
                 .map(|f| format!(
@@ -331,8 +339,7 @@ pub async fn get_finisher_input(
             .set_label("Voltar"),
     );
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(interaction.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(interaction.clone()));
 
     ctx.update_message(
         Response::from(embed.clone()).set_components(make_multiple_rows(buttons.clone())),
@@ -340,12 +347,12 @@ pub async fn get_finisher_input(
     .await?;
 
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
     let data = collected.parse_message_component_data()?;
 
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
@@ -381,20 +388,18 @@ pub async fn get_finisher_input(
 }
 
 pub async fn get_item_input(
-    controller: &mut BattleController,
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
     interaction: Interaction,
     message: Message,
 ) -> anyhow::Result<BattleInput> {
-    let inventory = controller.battle.get_current_fighter().inventory.clone();
+    let inventory = engine.battle.get_current_fighter().inventory.clone();
 
     let embed = EmbedBuilder::new_common()
         .set_color(Color::LIGHT_ORANGE)
         .set_author(EmbedAuthor {
-            name: format!(
-                "Inventário de {}",
-                controller.battle.get_current_fighter().name
-            ),
-            icon_url: controller
+            name: format!("Inventário de {}", engine.battle.get_current_fighter().name),
+            icon_url: engine
                 .battle
                 .get_current_fighter()
                 .user
@@ -428,8 +433,7 @@ pub async fn get_item_input(
             .set_label("Voltar"),
     );
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(interaction.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(interaction.clone()));
 
     ctx.update_message(
         Response::from(embed.clone()).set_components(make_multiple_rows(buttons.clone())),
@@ -437,12 +441,12 @@ pub async fn get_item_input(
     .await?;
 
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
     let data = collected.parse_message_component_data()?;
 
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
@@ -481,14 +485,15 @@ pub async fn get_item_input(
 }
 
 pub async fn get_action_input(
-    controller: &mut BattleController,
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
     interaction: Interaction,
     message: Message,
 ) -> anyhow::Result<BattleInput> {
-    let fighter = controller.battle.get_current_fighter().clone();
+    let fighter = engine.battle.get_current_fighter().clone();
 
     let mut actions = vec![];
-    for action in controller.battle.get_current_fighter().actions.clone() {
+    for action in engine.battle.get_current_fighter().actions.clone() {
         actions.push(action);
     }
 
@@ -534,8 +539,7 @@ pub async fn get_action_input(
             .set_label("Voltar"),
     );
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(interaction.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(interaction.clone()));
 
     ctx.update_message(
         Response::from(embed.clone())
@@ -544,12 +548,12 @@ pub async fn get_action_input(
     .await?;
 
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
     let data = collected.parse_message_component_data()?;
 
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected.clone()));
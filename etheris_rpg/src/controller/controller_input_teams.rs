@@ -10,21 +10,22 @@ use etheris_framework::{util::make_multiple_rows, *};
 use crate::*;
 
 pub async fn get_change_team_input(
-    controller: &mut BattleController,
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
     interaction: Interaction,
     message: Message,
 ) -> anyhow::Result<BattleInput> {
-    let teams = controller.battle.teams();
+    let teams = engine.battle.teams();
     let mut teams = teams.into_iter().collect::<Vec<_>>();
     teams.sort_by_key(|(t, ..)| *t);
 
     let is_fighter_team_empty = teams
         .iter()
-        .find(|(t, ..)| *t == controller.battle.get_current_fighter().team)
+        .find(|(t, ..)| *t == engine.battle.get_current_fighter().team)
         .map(|(.., f)| f.len() <= 1)
         .unwrap_or(true);
 
-    let new_team = controller
+    let new_team = engine
         .battle
         .fighters
         .iter()
@@ -36,9 +37,9 @@ pub async fn get_change_team_input(
         .set_author(EmbedAuthor {
             name: format!(
                 "{} está trocando de time!",
-                controller.battle.get_current_fighter().name
+                engine.battle.get_current_fighter().name
             ),
-            icon_url: controller
+            icon_url: engine
                 .battle
                 .get_current_fighter()
                 .user
@@ -51,7 +52,7 @@ pub async fn get_change_team_input(
                 .map(|(team, fighters)| {
                     let fighters = fighters
                         .iter()
-                        .map(|index| controller.battle.get_fighter(*index))
+                        .map(|index| engine.battle.get_fighter(*index))
                         .collect::<Vec<_>>();
                     format!(
                         "### Time {}\n{}",
@@ -68,14 +69,14 @@ pub async fn get_change_team_input(
         )
         .add_footer_text(format!(
             "{} está no time {}",
-            controller.battle.get_current_fighter().name,
-            controller.battle.get_current_fighter().team
+            engine.battle.get_current_fighter().name,
+            engine.battle.get_current_fighter().team
         ));
 
     let mut buttons = vec![];
 
     for (team, ..) in teams.iter() {
-        if *team == controller.battle.get_current_fighter().team {
+        if *team == engine.battle.get_current_fighter().team {
             continue;
         }
 
@@ -103,8 +104,7 @@ pub async fn get_change_team_input(
         );
     }
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(interaction.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(interaction.clone()));
 
     ctx.update_message(
         Response::from(embed.clone()).set_components(make_multiple_rows(buttons.clone())),
@@ -112,12 +112,12 @@ pub async fn get_change_team_input(
     .await?;
 
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
     let data = collected.parse_message_component_data()?;
 
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
@@ -154,7 +154,7 @@ pub async fn get_change_team_input(
         return Ok(BattleInput::Nothing);
     };
 
-    let current_fighter = controller.battle.get_current_fighter().clone();
+    let current_fighter = engine.battle.get_current_fighter().clone();
     if current_fighter.team == team {
         return Ok(BattleInput::ChangeTeam(team));
     }
@@ -162,7 +162,7 @@ pub async fn get_change_team_input(
     // Get authorization of every fighter in this team
     let mut fighter_that_disallowed = None;
     for fighter in fighters {
-        let fighter = controller.battle.get_fighter(fighter).clone();
+        let fighter = engine.battle.get_fighter(fighter).clone();
 
         if let Some(user) = fighter.user.clone() {
             let confirmation = ctx.helper()
@@ -176,7 +176,7 @@ pub async fn get_change_team_input(
                 break;
             }
         } else if let Some(brain) = fighter.brain.clone() {
-            let mut api = BattleApi::new(controller);
+            let mut api = BattleApi::new(engine);
             api.fighter_index = fighter.index;
             api.target_index = fighter.target;
 
@@ -197,11 +197,11 @@ pub async fn get_change_team_input(
     }
 
     if let Some(fighter) = fighter_that_disallowed {
-        controller.emit_turn_message(format!(
+        engine.emit_turn_message(format!(
             "**{}** não deu permissão para **{}** entrar em seu time!",
             fighter.name, current_fighter.name
         ));
-        controller.update_turn_history_message().await?;
+        io.update_turn(engine).await?;
         tokio::time::sleep(Duration::from_secs(2)).await;
         return Ok(BattleInput::Reinput);
     }
@@ -210,16 +210,17 @@ pub async fn get_change_team_input(
 }
 
 pub async fn get_change_target_input(
-    controller: &mut BattleController,
+    io: &mut DiscordBattleIo,
+    engine: &mut BattleEngine,
     interaction: Interaction,
     message: Message,
 ) -> anyhow::Result<BattleInput> {
-    let mut alive_fighters = controller
+    let mut alive_fighters = engine
         .battle
         .alive_fighters
         .iter()
-        .filter(|index| **index != controller.battle.get_current_fighter().index)
-        .map(|index| controller.battle.get_fighter(*index).clone())
+        .filter(|index| **index != engine.battle.get_current_fighter().index)
+        .map(|index| engine.battle.get_fighter(*index).clone())
         .collect::<Vec<_>>();
 
     alive_fighters.sort_by_key(|f| f.team);
@@ -229,9 +230,9 @@ pub async fn get_change_target_input(
         .set_author(EmbedAuthor {
             name: format!(
                 "{} está trocando de alvo!",
-                controller.battle.get_current_fighter().name
+                engine.battle.get_current_fighter().name
             ),
-            icon_url: controller
+            icon_url: engine
                 .battle
                 .get_current_fighter()
                 .user
@@ -246,7 +247,7 @@ pub async fn get_change_target_input(
                         "- **{}** (time {}){}",
                         f.name,
                         f.team,
-                        if f.team == controller.battle.get_current_fighter().team {
+                        if f.team == engine.battle.get_current_fighter().team {
                             " (ALIADO)"
                         } else {
                             ""
@@ -258,8 +259,8 @@ pub async fn get_change_target_input(
         )
         .add_footer_text(format!(
             "{} está no time {}",
-            controller.battle.get_current_fighter().name,
-            controller.battle.get_current_fighter().team
+            engine.battle.get_current_fighter().name,
+            engine.battle.get_current_fighter().team
         ));
 
     let mut buttons = vec![];
@@ -280,8 +281,7 @@ pub async fn get_change_target_input(
             .set_label("Voltar"),
     );
 
-    let mut ctx =
-        CommandContext::from_with_interaction(&controller.ctx, Box::new(interaction.clone()));
+    let mut ctx = CommandContext::from_with_interaction(&io.ctx, Box::new(interaction.clone()));
 
     ctx.update_message(
         Response::from(embed.clone()).set_components(make_multiple_rows(buttons.clone())),
@@ -289,12 +289,12 @@ pub async fn get_change_target_input(
     .await?;
 
     let Ok(collected) =
-        input_util::await_component_allowing_intruders(message.id, &mut ctx, controller).await
+        input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
     else {
         return Ok(BattleInput::Nothing);
     };
 
-    controller.last_interaction = Some(collected.clone());
+    io.last_interaction = Some(collected.clone());
     let data = collected.parse_message_component_data()?;
 
     let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
@@ -322,7 +322,7 @@ pub async fn get_change_target_input(
         return Ok(BattleInput::Reinput);
     }
 
-    let Some(fighter) = controller
+    let Some(fighter) = engine
         .battle
         .fighters
         .iter()
@@ -0,0 +1,587 @@
+use std::time::Duration;
+
+use anyhow::bail;
+use etheris_common::{Color, Probability};
+use etheris_database::{
+    character_model::{CharacterModel, DeathCause, DeathInfo},
+    common::{DatabaseDateTime, InventoryItem},
+};
+use etheris_discord::{
+    twilight_model::channel::{message::component::ButtonStyle, *},
+    *,
+};
+use etheris_framework::{util::make_multiple_rows, watcher::WatcherOptions, *};
+
+use crate::*;
+
+use self::{
+    controller_helper::{create_fighter_embed_fields, create_spectator_fighter_embed_fields},
+    spectators::SharedSpectators,
+};
+
+/// The `BattleIo` used by `BattleController`: every input is a button and every turn is an embed.
+pub struct DiscordBattleIo {
+    pub ctx: CommandContext,
+    pub last_message: Option<Message>,
+    pub last_interaction: Option<Interaction>,
+    pub spectators: SharedSpectators,
+}
+
+impl DiscordBattleIo {
+    pub fn new(ctx: CommandContext) -> Self {
+        Self {
+            ctx,
+            last_message: None,
+            last_interaction: None,
+            spectators: Default::default(),
+        }
+    }
+
+    /// The turn embed with the state of every fighter, as spectators see it.
+    pub fn create_spectator_embed(&self, engine: &BattleEngine) -> EmbedBuilder {
+        let battle = &engine.battle;
+        let current_fighter = battle.get_current_fighter();
+
+        let mut embed = self.create_turn_embed(engine);
+        for fighter in battle.alive_fighters.iter() {
+            embed = embed.add_field(create_spectator_fighter_embed_fields(
+                battle.get_fighter(*fighter),
+                Some(current_fighter.target),
+            ));
+        }
+
+        embed
+    }
+
+    /// Sends the turn to the spectators in the background, so they never slow the battle down.
+    /// If the last turn is still being sent, this one is skipped.
+    pub async fn update_spectators(&self, engine: &BattleEngine) {
+        if self.spectators.lock().await.is_empty() {
+            return;
+        }
+
+        let response = Response::from(self.create_spectator_embed(engine))
+            .add_string_content("👀 Você está assistindo essa batalha.");
+
+        let spectators = self.spectators.clone();
+        tokio::spawn(async move {
+            let Ok(mut spectators) = spectators.try_lock() else {
+                return;
+            };
+
+            for spectator in spectators.iter_mut() {
+                // A spectator that can't be updated doesn't stop the others
+                spectator.update(response.clone()).await.ok();
+            }
+        });
+    }
+
+    pub fn create_turn_embed(&self, engine: &BattleEngine) -> EmbedBuilder {
+        let current_fighter = engine.battle.get_current_fighter();
+        let target_fighter = engine.battle.get_target_fighter();
+        let turn_num = engine.current_turn_history.round;
+
+        EmbedBuilder::new_common()
+            .set_color(if turn_num % 2 == 0 {
+                Color::ORANGE
+            } else {
+                Color::DARK_ORANGE
+            })
+            .set_author(EmbedAuthor {
+                name: format!("Rodada N° {turn_num} - {}", current_fighter.name),
+                icon_url: current_fighter.user.as_ref().map(|u| u.avatar_url()),
+            })
+            .set_title(format!(
+                "{} x {}",
+                current_fighter.name, target_fighter.name
+            ))
+            .set_description(
+                engine
+                    .current_turn_history
+                    .messages(&engine.battle)
+                    .join("\n"),
+            )
+    }
+
+    pub fn create_battle_embed(&self, battle: &Battle) -> EmbedBuilder {
+        let current_fighter = battle.get_current_fighter().clone();
+        let mut embed = EmbedBuilder::new_common().set_author(EmbedAuthor {
+            name: format!("Rodada de {}", current_fighter.name),
+            icon_url: current_fighter.user.as_ref().map(|u| u.avatar_url()),
+        });
+
+        let mut alive_fighters = battle
+            .alive_fighters
+            .clone()
+            .into_iter()
+            .map(|f| battle.get_fighter(f).clone())
+            .collect::<Vec<_>>();
+        alive_fighters.sort_unstable_by_key(|f| {
+            if f.index == current_fighter.index {
+                0
+            } else if f.index == current_fighter.target {
+                1
+            } else {
+                2 + f.index.0
+            }
+        });
+
+        for fighter in alive_fighters {
+            embed = embed.add_field(create_fighter_embed_fields(
+                &fighter,
+                Some(current_fighter.target),
+            ));
+        }
+
+        embed
+    }
+
+    pub async fn save_user_characters(&self, battle: &Battle) -> anyhow::Result<()> {
+        if !battle.settings.has_consequences {
+            return Ok(());
+        }
+
+        for (user, fighter) in battle
+            .fighters
+            .iter()
+            .filter_map(|f| f.user.as_ref().map(|user| (user.clone(), f.clone())))
+        {
+            let fighter_index = fighter.index;
+            let Some(character) = self
+                .ctx
+                .db()
+                .characters()
+                .get_by_user(&user.id.to_string())
+                .await?
+            else {
+                continue;
+            };
+
+            // Check if the character defeated a boss
+            let defeated_bosses = battle
+                .fighters
+                .iter()
+                .filter(|f| {
+                    f.defeated_by == Some(fighter_index) || f.killed_by == Some(fighter_index)
+                })
+                .filter_map(|f| f.boss)
+                .collect::<Vec<_>>();
+
+            let killer_name = fighter
+                .killed_by
+                .map(|killer| battle.get_fighter(killer).name.clone());
+
+            let mut aknowledged_skills = vec![];
+            for fighter in battle.fighters.clone() {
+                if fighter.is_defeated {
+                    continue;
+                }
+
+                for skill in fighter.skills.clone() {
+                    let skill = skill.dynamic_skill.lock().await;
+                    let kind = skill.save_kind();
+                    if skill
+                        .data(&fighter)
+                        .complexity
+                        .prob_of_aknowleding()
+                        .generate_random_bool()
+                        && Probability::new(30).generate_random_bool()
+                    {
+                        aknowledged_skills.push(kind);
+                    }
+                }
+            }
+
+            let mut skills = Vec::with_capacity(fighter.skills.capacity());
+            for skill in fighter.skills.clone() {
+                skills.push(skill.dynamic_skill.lock().await.save_kind());
+            }
+
+            // The battle took a while, so the result is applied to the character as it is now
+            self.ctx
+                .db()
+                .characters()
+                .update(character.id, |character| {
+                    character
+                        .defeated_bosses
+                        .extend(defeated_bosses.iter().cloned());
+
+                    // Kill the character
+                    if let (true, Some(killer_name)) = (character.alive, &killer_name) {
+                        character.alive = false;
+                        character.death_info = Some(DeathInfo {
+                            cause: DeathCause::KilledBy(killer_name.clone()),
+                            date: DatabaseDateTime::now(),
+                        });
+                    }
+
+                    for kind in aknowledged_skills.iter() {
+                        character.aknowledge_skill(kind.clone());
+                    }
+
+                    character.weapon = fighter.weapon.map(|w| w.kind);
+                    character.weapon_values =
+                        fighter.weapon.map(|w| w.values()).unwrap_or_default();
+                    character.stats.resistance = fighter.resistance.into();
+                    character.stats.vitality = fighter.vitality.into();
+                    character.stats.ether = fighter.ether.into();
+                    character.battle_inventory = fighter
+                        .inventory
+                        .iter()
+                        .map(|i| InventoryItem {
+                            identifier: i.item.identifier.to_string(),
+                            quantity: i.quantity,
+                            values: i.values.clone(),
+                        })
+                        .collect();
+                    character.skills.clone_from(&skills);
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Skill mastery and discovered combos are kept even in battles without consequences.
+    pub async fn save_user_skill_progress(&self, battle: &Battle) -> anyhow::Result<()> {
+        for fighter in battle.fighters.iter() {
+            let Some(user) = &fighter.user else {
+                continue;
+            };
+
+            let Some(character) = self
+                .ctx
+                .db()
+                .characters()
+                .get_by_user(&user.id.to_string())
+                .await?
+            else {
+                continue;
+            };
+
+            let new_combos = |character: &CharacterModel| {
+                fighter
+                    .triggered_combos
+                    .iter()
+                    .filter(|combo| !character.discovered_combos.iter().any(|c| c == **combo))
+                    .map(|combo| combo.to_string())
+                    .collect::<Vec<_>>()
+            };
+
+            if character.skill_mastery == fighter.skill_mastery && new_combos(&character).is_empty()
+            {
+                continue;
+            }
+
+            self.ctx
+                .db()
+                .characters()
+                .update(character.id, |character| {
+                    character.skill_mastery = fighter.skill_mastery.clone();
+                    let new_combos = new_combos(character);
+                    character.discovered_combos.extend(new_combos);
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn save_user_characters_stats(&self, battle: &Battle) -> anyhow::Result<()> {
+        if !battle.settings.has_consequences {
+            return Ok(());
+        }
+
+        for (user, fighter) in battle
+            .fighters
+            .iter()
+            .filter_map(|f| f.user.as_ref().map(|user| (user.clone(), f.clone())))
+        {
+            let fighter_index = fighter.index;
+            let Some(character) = self
+                .ctx
+                .db()
+                .characters()
+                .get_by_user(&user.id.to_string())
+                .await?
+            else {
+                continue;
+            };
+
+            let is_pvp = battle
+                .fighters
+                .iter()
+                .filter_map(|f| f.user.clone())
+                .filter(|u| u.id != user.id)
+                .count()
+                > 0;
+            let is_pve = battle.fighters.iter().filter(|f| f.user.is_none()).count() > 0;
+
+            self.ctx
+                .db()
+                .characters()
+                .update(character.id, |character| {
+                    // Update stats
+                    for fighter in battle.fighters.clone() {
+                        if fighter.index == fighter_index {
+                            continue;
+                        }
+
+                        let is_ai = fighter.user.is_none();
+
+                        if fighter.killed_by == Some(fighter_index) {
+                            if is_ai {
+                                character.stats.pve.kills += 1;
+                            } else {
+                                character.stats.pvp.kills += 1;
+                            }
+                        } else if fighter.defeated_by == Some(fighter_index) {
+                            if is_ai {
+                                character.stats.pve.knockouts += 1;
+                            } else {
+                                character.stats.pvp.knockouts += 1;
+                            }
+                        }
+                    }
+
+                    if let BattleState::Ended { winners, .. } = &battle.state {
+                        if winners.contains(&fighter.index) {
+                            if is_pvp {
+                                character.stats.pvp.wins += 1;
+                            } else if is_pve {
+                                character.stats.pve.wins += 1;
+                            }
+                        } else if is_pvp {
+                            character.stats.pvp.losses += 1;
+                        } else {
+                            character.stats.pve.losses += 1;
+                        }
+                    }
+
+                    if fighter.flags.contains(FighterFlags::RISKING_LIFE) {
+                        if is_pvp {
+                            character.stats.pvp.life_risks += 1;
+                        } else if is_pve {
+                            character.stats.pve.life_risks += 1;
+                        }
+                    } else if fighter.is_defeated {
+                        if is_pvp {
+                            character.stats.pvp.withdrawals += 1;
+                        } else if is_pve {
+                            character.stats.pve.withdrawals += 1;
+                        }
+                    }
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl BattleIo for DiscordBattleIo {
+    async fn select_input(&mut self, engine: &mut BattleEngine) -> anyhow::Result<BattleInput> {
+        get_input(self, engine).await
+    }
+
+    async fn select_option(
+        &mut self,
+        engine: &mut BattleEngine,
+        selection: IoSelection,
+    ) -> anyhow::Result<Option<String>> {
+        let fighter = engine.battle.get_fighter(selection.fighter).clone();
+        let Some(user_id) = fighter.user.map(|u| u.id) else {
+            bail!("select_option expected a user fighter, not a AI");
+        };
+
+        let mut buttons = vec![];
+        for option in &selection.options {
+            let mut button = ButtonBuilder::new()
+                .set_custom_id(&option.id)
+                .set_label(&option.label)
+                .set_disabled(!option.active);
+            if let Some(emoji) = &option.emoji {
+                button = button.set_emoji(Emoji::new(emoji));
+            }
+
+            buttons.push(button);
+        }
+
+        let response = Response::from(create_prompt_embed(selection.prompt))
+            .add_string_content(format!("<@{user_id}>"))
+            .set_components(make_multiple_rows(buttons.clone()));
+
+        let message = if let Some(message) = self.last_message.clone() {
+            self.ctx
+                .update_specific_message(&message, response)
+                .await
+                .ok();
+            message
+        } else {
+            let message = self.ctx.send(response).await?;
+            self.last_message = Some(message.clone());
+            message
+        };
+
+        let mut ctx = self.ctx.clone();
+        let Ok(collected) =
+            input_util::await_component_allowing_intruders(message.id, &mut ctx, engine).await
+        else {
+            return Ok(None);
+        };
+
+        let data = collected.parse_message_component_data()?;
+
+        let mut ctx = CommandContext::from_with_interaction(&ctx, Box::new(collected));
+
+        let buttons = buttons
+            .into_iter()
+            .map(|b| {
+                let custom_id = b.data.custom_id.clone().unwrap_or_default();
+                let style = b.data.style;
+                b.set_disabled(true)
+                    .set_style(if custom_id == data.custom_id {
+                        ButtonStyle::Success
+                    } else {
+                        style
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        ctx.update_message(Response::default().set_components(make_multiple_rows(buttons)))
+            .await?;
+
+        Ok(Some(data.custom_id))
+    }
+
+    async fn input_number(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+        prompt: String,
+        valid_range: (f64, f64),
+    ) -> anyhow::Result<f64> {
+        let Some(fighter_user) = engine.battle.get_fighter(fighter).user.clone() else {
+            bail!("input_number expected a user fighter, not a AI");
+        };
+        let fighter_user_id = fighter_user.id;
+
+        let m = self
+            .ctx
+            .send(Response::new_user_reply(&fighter_user, prompt))
+            .await?;
+
+        let Some(message) = self
+            .ctx
+            .watcher
+            .await_single_message(
+                m.channel_id,
+                move |message| {
+                    message.author.id == fighter_user_id
+                        && message
+                            .content
+                            .trim()
+                            .parse::<f64>()
+                            .is_ok_and(|n| n >= valid_range.0 && n <= valid_range.1)
+                },
+                WatcherOptions {
+                    timeout: Duration::from_secs(60),
+                },
+            )
+            .await?
+        else {
+            bail!("input_number timed out");
+        };
+
+        let number = message.content.trim().parse::<f64>()?;
+
+        if let Some(last_message) = self.last_message.clone() {
+            self.ctx
+                .client
+                .http
+                .delete_message(last_message.channel_id, last_message.id)
+                .await
+                .ok();
+
+            self.last_message = None;
+        }
+
+        Ok(number)
+    }
+
+    async fn confirm_risk_life(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+    ) -> anyhow::Result<bool> {
+        let Some(user) = engine.battle.get_fighter(fighter).user.clone() else {
+            return Ok(false);
+        };
+
+        self.ctx.helper()
+            .create_confirmation(user.id, true, Response::new_user_reply(
+                &user,
+                if engine.battle.settings.casual {
+                    "sua resistência chegou a zero! Quer apostar sua vida na batalha? (Essa é uma batalha CASUAL. Seu personagem não vai morrer de verdade)"
+                } else {
+                    "sua resistência chegou a zero! Isso significa que seu personagem está perto do nocaute. Você deseja continuar lutando e apostar sua vida nessa batalha?\nSe não aceitar, você perderá por nocaute. Se aceitar, você continuará lutando mas perderá **vitalidade** ao invés de resistência. Se a vitalidade zerar seu personagem morre pra sempre."
+                },
+            ))
+            .await
+    }
+
+    async fn update_turn(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+        self.update_spectators(engine).await;
+
+        let response = Response::from(self.create_turn_embed(engine)).remove_all_components();
+
+        if let Some(message) = &self.last_message {
+            self.ctx.update_specific_message(message, response).await?;
+            tokio::time::sleep(Duration::from_secs(
+                2 + (engine.current_turn_history.messages(&engine.battle).len() as u64)
+                    .saturating_sub(2),
+            ))
+            .await;
+        } else {
+            let message = self.ctx.send(response).await?;
+            self.last_message = Some(message);
+        }
+
+        Ok(())
+    }
+
+    async fn on_turn_end(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+        if engine.battle.state != BattleState::Running {
+            self.save_user_characters(&engine.battle).await.ok();
+        }
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        if engine.battle.turn_counter % 10 == 0 {
+            self.save_user_characters(&engine.battle).await.ok();
+        }
+
+        Ok(())
+    }
+
+    async fn wait(&mut self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+fn create_prompt_embed(prompt: IoPrompt) -> EmbedBuilder {
+    let mut embed = EmbedBuilder::new_common().set_author(EmbedAuthor {
+        name: prompt.title,
+        icon_url: prompt.icon_url,
+    });
+    if let Some(color) = prompt.color {
+        embed = embed.set_color(color);
+    }
+
+    for (name, value) in prompt.fields {
+        embed = embed.add_inlined_field(name, value);
+    }
+
+    embed
+}
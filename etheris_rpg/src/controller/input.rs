@@ -21,6 +21,10 @@ pub enum BattleInput {
     UseItem(Item),
     UseAction(BattleAction),
     Finish(Finisher),
+    /// Only for AI fighters: tries to run away from the battle.
+    Flee,
+    /// Only for AI fighters: wastes the turn provoking the target.
+    Taunt,
 }
 
 #[derive(List, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
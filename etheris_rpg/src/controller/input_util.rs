@@ -8,14 +8,14 @@ use etheris_discord::{
 use etheris_framework::{watcher::WatcherOptions, *};
 use tokio_stream::StreamExt;
 
-use crate::{BattleController, FighterData};
+use crate::{BattleEngine, FighterData};
 
 pub async fn await_component_allowing_intruders(
     message_id: Id<MessageMarker>,
     ctx: &mut CommandContext,
-    controller: &mut BattleController,
+    engine: &mut BattleEngine,
 ) -> anyhow::Result<Interaction> {
-    let current_fighter_user_id = match &controller.battle.get_current_fighter().user {
+    let current_fighter_user_id = match &engine.battle.get_current_fighter().user {
         Some(u) => u.id,
         None => bail!("User expected as current fighter"),
     };
@@ -36,7 +36,7 @@ pub async fn await_component_allowing_intruders(
             return Ok(collected);
         }
 
-        let is_another_fighter_input = controller.battle.fighters.iter().any(|f| {
+        let is_another_fighter_input = engine.battle.fighters.iter().any(|f| {
             f.user
                 .as_ref()
                 .is_some_and(|u| Some(u.id) == collected.author_id())
@@ -46,15 +46,16 @@ pub async fn await_component_allowing_intruders(
             continue;
         }
 
-        let mut ctx =
-            CommandContext::from_with_interaction(&controller.ctx, Box::new(collected.clone()));
+        let mut ctx = CommandContext::from_with_interaction(ctx, Box::new(collected.clone()));
 
         let Some(author_id) = collected.author_id() else {
             continue;
         };
         let author = ctx.author().await?;
 
-        if controller.battle.intruder_count >= controller.battle.settings.max_intruders as usize {
+        if engine.battle.intruder_count + engine.pending_intruders.len()
+            >= engine.battle.settings.max_intruders as usize
+        {
             ctx.reply(
                 Response::new_user_reply(&author, "essa batalha não suporta mais invasores!")
                     .set_ephemeral(),
@@ -63,7 +64,7 @@ pub async fn await_component_allowing_intruders(
             continue;
         }
 
-        if controller.ctx.client.is_user_fighting(author.id).await {
+        if ctx.client.is_user_fighting(author.id).await {
             ctx.reply(
                 Response::new_user_reply(
                     &author,
@@ -81,19 +82,19 @@ pub async fn await_component_allowing_intruders(
             .get_by_user(&author_id.to_string())
             .await?
         {
-            if character.region != controller.battle.region {
+            if character.region != engine.battle.region {
                 ctx.reply_interaction(
                     Response::new_user_reply(
                         &author,
                         format!("essa batalha está localizada na região **{}**! Você precisa estar nessa região para invadir a batalha.",
-                         controller.battle.region)
+                         engine.battle.region)
                     ).set_ephemeral(),
                 )
                 .await?;
                 continue;
             }
 
-            let new_team = controller
+            let new_team = engine
                 .battle
                 .teams()
                 .keys()
@@ -105,9 +106,8 @@ pub async fn await_component_allowing_intruders(
                 author.clone(),
                 Default::default(),
             );
-            controller.emit_turn_message(format!("**{}** entrou na luta!", fighter.name));
-            controller.battle.join_intruder(fighter);
-            controller.ctx.client.mark_user_as_fighter(author.id).await;
+            engine.join_intruder(fighter);
+            ctx.client.mark_user_as_fighter(author.id).await;
 
             ctx.reply_interaction(
                 Response::new_user_reply(&author, "você invadiu a batalha!").set_ephemeral(),
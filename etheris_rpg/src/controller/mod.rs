@@ -1,13 +1,16 @@
 pub mod action_handler;
 pub mod controller_helper;
 pub mod input_util;
+pub mod spectators;
 
 mod battle_controller;
 mod controller_input;
 mod controller_input_teams;
+mod discord_io;
 mod input;
 
 pub use battle_controller::*;
 pub use controller_input::*;
 pub use controller_input_teams::*;
+pub use discord_io::*;
 pub use input::*;
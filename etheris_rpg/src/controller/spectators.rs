@@ -0,0 +1,110 @@
+use std::{collections::HashMap, sync::Arc};
+
+use etheris_discord::twilight_model::{
+    channel::Message,
+    id::{
+        marker::{ChannelMarker, UserMarker},
+        Id,
+    },
+    user::User,
+};
+use etheris_framework::{EtherisClient, Response};
+use once_cell::sync::Lazy;
+use tokio::sync::{Mutex, RwLock};
+
+/// How many users can watch the same battle.
+pub const MAX_SPECTATORS: usize = 10;
+
+pub type SharedSpectators = Arc<Mutex<Vec<Spectator>>>;
+
+/// The spectators of every running battle, indexed by the users fighting in it.
+static BATTLE_SPECTATORS: Lazy<RwLock<HashMap<Id<UserMarker>, SharedSpectators>>> =
+    Lazy::new(Default::default);
+
+/// Someone watching a battle. Spectators can't interact with it, they only get a message
+/// that is updated every turn.
+/// The message is sent to the channel by the bot, so it keeps updating after the interaction token expires.
+pub struct Spectator {
+    pub user: User,
+    pub client: Arc<EtherisClient>,
+    pub channel_id: Id<ChannelMarker>,
+    pub message: Option<Message>,
+}
+
+impl Spectator {
+    pub fn new(user: User, client: Arc<EtherisClient>, channel_id: Id<ChannelMarker>) -> Self {
+        Self {
+            user,
+            client,
+            channel_id,
+            message: None,
+        }
+    }
+
+    pub async fn update(&mut self, response: Response) -> anyhow::Result<()> {
+        let json = response.to_json();
+        match &self.message {
+            Some(message) => {
+                self.client
+                    .http
+                    .update_message(message.channel_id, message.id)
+                    .payload_json(&json)
+                    .await?;
+            }
+            None => {
+                let message = self
+                    .client
+                    .http
+                    .create_message(self.channel_id)
+                    .payload_json(&json)
+                    .await?
+                    .model()
+                    .await?;
+                self.message = Some(message);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum WatchResult {
+    Watching,
+    NotInBattle,
+    Full,
+}
+
+/// Makes the battle of the spectators watchable through the given fighters.
+pub async fn register_battle(users: &[Id<UserMarker>], spectators: SharedSpectators) {
+    let mut battles = BATTLE_SPECTATORS.write().await;
+    for user in users {
+        battles.insert(*user, spectators.clone());
+    }
+}
+
+pub async fn unregister_battle(users: &[Id<UserMarker>]) {
+    let mut battles = BATTLE_SPECTATORS.write().await;
+    for user in users {
+        battles.remove(user);
+    }
+}
+
+pub async fn is_battle_watchable(fighter_user: Id<UserMarker>) -> bool {
+    BATTLE_SPECTATORS.read().await.contains_key(&fighter_user)
+}
+
+/// Adds a spectator to the battle the user is fighting in, if it isn't full.
+pub async fn watch_battle(fighter_user: Id<UserMarker>, spectator: Spectator) -> WatchResult {
+    let Some(spectators) = BATTLE_SPECTATORS.read().await.get(&fighter_user).cloned() else {
+        return WatchResult::NotInBattle;
+    };
+
+    let mut spectators = spectators.lock().await;
+    spectators.retain(|s| s.user.id != spectator.user.id);
+    if spectators.len() >= MAX_SPECTATORS {
+        return WatchResult::Full;
+    }
+
+    spectators.push(spectator);
+    WatchResult::Watching
+}
@@ -6,7 +6,10 @@ use etheris_data::{
 use once_cell::sync::Lazy;
 use rand::Rng;
 
-use crate::{brain::BrainKind, BodyImmunities, FighterData};
+use crate::{
+    brain::{BrainDifficulty, BrainKind},
+    BodyImmunities, FighterData,
+};
 
 use super::{Reward, RewardItem};
 
@@ -35,6 +38,16 @@ impl EnemyReward {
         let base_orbs = rng.gen_range(self.orbs.0..=self.orbs.1) as i64;
         let base_xp = rng.gen_range(self.xp.0..=self.xp.1) as i64;
 
+        let mut items = vec![];
+        for item in self.items.iter() {
+            if item.probability.generate_random_bool_with(rng) {
+                items.push(RewardItem {
+                    amount: rng.gen_range(item.amount.0..=item.amount.1),
+                    item: item.item,
+                });
+            }
+        }
+
         Reward {
             orbs: if is_boss {
                 base_orbs as i32
@@ -46,15 +59,7 @@ impl EnemyReward {
             } else {
                 calculate_xp_gain(player_pl, enemy_pl, base_xp) as i32
             },
-            items: self
-                .items
-                .iter()
-                .filter(|i| i.probability.generate_random_bool())
-                .map(|i| RewardItem {
-                    amount: rng.gen_range(i.amount.0..=i.amount.1),
-                    item: i.item,
-                })
-                .collect(),
+            items,
         }
     }
 }
@@ -267,7 +267,7 @@ make_enemy!(
         identifier: "frost_shaman",
         name: "Xamã do Gelo",
         base_probability: Probability::ALWAYS,
-        brain: BrainKind::Simple,
+        brain: BrainKind::Tactical(BrainDifficulty::Easy),
         boss: None,
         regions: &[(WorldRegion::Icefields, 2), (WorldRegion::Wornpeaks, 1)],
         personalities: &[Personality::Intelligence, Personality::Calm],
@@ -315,7 +315,7 @@ make_enemy!(
         identifier: "rock_thrower",
         name: "Arremessador de Pedras",
         base_probability: Probability::ALWAYS,
-        brain: BrainKind::Simple,
+        brain: BrainKind::Tactical(BrainDifficulty::Normal),
         boss: None,
         regions: &[(WorldRegion::Wornpeaks, 6), (WorldRegion::Icefields, 2)],
         personalities: &[Personality::Intelligence, Personality::Calm],
@@ -405,7 +405,7 @@ make_enemy!(
         name: "Monge Corrupto",
         base_probability: Probability::ALWAYS,
         boss: None,
-        brain: BrainKind::Simple,
+        brain: BrainKind::Tactical(BrainDifficulty::Normal),
         regions: &[
             (WorldRegion::Ethergrove, 1),
             (WorldRegion::Starbreeze, 1),
@@ -451,7 +451,7 @@ make_enemy!(
         identifier: "etherking",
         name: "Etherking",
         base_probability: Probability::new(50),
-        brain: BrainKind::Simple,
+        brain: BrainKind::Tactical(BrainDifficulty::Hard),
         boss: None,
         regions: &[
             (WorldRegion::Ethergrove, 1),
@@ -1,9 +1,10 @@
 use etheris_common::Probability;
 use etheris_data::weapon::WeaponKind;
+use serde::{Deserialize, Serialize};
 
 use crate::{BattleApi, Effect, EffectKind, FighterFlags};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Finisher {
     Knockout,
     BreakNeck,
@@ -29,3 +29,21 @@ fn enemies_pl() {
         println!("{} -> {} PL", enemy.name, pl);
     }
 }
+
+#[test]
+fn reward_split() {
+    let reward = Reward {
+        orbs: 101,
+        xp: 30,
+        items: vec![RewardItem {
+            item: etheris_data::items::consumable::SLICE_OF_BREAD,
+            amount: 1,
+        }],
+    };
+
+    let shares = reward.split(3);
+    assert_eq!(shares.iter().map(|r| r.orbs).sum::<i32>(), 101);
+    assert_eq!(shares.iter().map(|r| r.xp).sum::<i32>(), 30);
+    assert_eq!(shares[0].items.len(), 1);
+    assert!(shares[1].items.is_empty());
+}
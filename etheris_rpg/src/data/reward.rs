@@ -35,7 +35,42 @@ impl Reward {
         }
     }
 
+    /// The orbs and xp of the reward multiplied by `ratio`. Items are not kept.
+    pub fn scaled(&self, ratio: f32) -> Reward {
+        Reward {
+            orbs: (self.orbs as f32 * ratio) as i32,
+            xp: (self.xp as f32 * ratio) as i32,
+            items: vec![],
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self == &Self::default()
     }
+
+    /// Splits the reward between `parts` fighters. What can't be split evenly goes to the first ones.
+    pub fn split(&self, parts: usize) -> Vec<Reward> {
+        let parts = parts.max(1);
+        let share = |amount: i32, index: usize| {
+            let base = amount / parts as i32;
+            let remainder = (amount % parts as i32) as usize;
+            base + if index < remainder { 1 } else { 0 }
+        };
+
+        (0..parts)
+            .map(|index| Reward {
+                orbs: share(self.orbs, index),
+                xp: share(self.xp, index),
+                items: self
+                    .items
+                    .iter()
+                    .map(|i| RewardItem {
+                        item: i.item,
+                        amount: share(i.amount, index),
+                    })
+                    .filter(|i| i.amount > 0)
+                    .collect(),
+            })
+            .collect()
+    }
 }
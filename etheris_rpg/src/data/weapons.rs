@@ -1,9 +1,32 @@
 use etheris_common::Probability;
-use etheris_data::weapon::WeaponKind;
+use etheris_data::{
+    items,
+    weapon::{WeaponEnchantment, WeaponKind},
+};
 use rand::Rng;
 
 use crate::list::prelude::*;
 
+/// Changes a hit of the weapon by its enchantment. Ice and poison take the place of the natural effect of the weapon.
+fn enchanted(weapon: Option<FighterWeapon>, mut specifier: DamageSpecifier) -> DamageSpecifier {
+    let Some(enchantment) = weapon.and_then(|w| w.enchantment) else {
+        return specifier;
+    };
+
+    match enchantment {
+        WeaponEnchantment::Flaming => specifier.kind = DamageKind::Fire,
+        WeaponEnchantment::Shocking => specifier.kind = DamageKind::Electric,
+        WeaponEnchantment::Freezing => {
+            specifier.effect = Some(Effect::new(EffectKind::Ice, 20, specifier.culprit))
+        }
+        WeaponEnchantment::Venomous => {
+            specifier.effect = Some(Effect::new(EffectKind::Poisoned, 20, specifier.culprit))
+        }
+    }
+
+    specifier
+}
+
 async fn weapon_stick(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let base_damage = api.rng().gen_range(2..=6);
 
@@ -13,14 +36,17 @@ async fn weapon_stick(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage,
-                kind: DamageKind::Physical,
-                balance_effectiveness: 5,
-                accuracy: 95,
-                effect: None,
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage,
+                    kind: DamageKind::Physical,
+                    balance_effectiveness: 5,
+                    accuracy: 95,
+                    effect: None,
+                },
+            ),
         )
         .await;
 
@@ -43,14 +69,17 @@ async fn weapon_knife(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage,
-                kind: DamageKind::PhysicalCut,
-                balance_effectiveness: 3,
-                accuracy: 80,
-                effect: Some(Effect::new(EffectKind::Bleeding, 20, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage,
+                    kind: DamageKind::PhysicalCut,
+                    balance_effectiveness: 3,
+                    accuracy: 80,
+                    effect: Some(Effect::new(EffectKind::Bleeding, 20, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
@@ -76,28 +105,34 @@ async fn weapon_katana(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage_1 = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage_1,
-                kind: DamageKind::PhysicalCut,
-                balance_effectiveness: 5,
-                accuracy: 80,
-                effect: Some(Effect::new(EffectKind::Bleeding, 25, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage_1,
+                    kind: DamageKind::PhysicalCut,
+                    balance_effectiveness: 5,
+                    accuracy: 80,
+                    effect: Some(Effect::new(EffectKind::Bleeding, 25, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
     let damage_2 = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage_2,
-                kind: DamageKind::PhysicalCut,
-                balance_effectiveness: 5,
-                accuracy: 60,
-                effect: Some(Effect::new(EffectKind::Bleeding, 25, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage_2,
+                    kind: DamageKind::PhysicalCut,
+                    balance_effectiveness: 5,
+                    accuracy: 60,
+                    effect: Some(Effect::new(EffectKind::Bleeding, 25, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
@@ -123,28 +158,34 @@ async fn weapon_ethria_katana(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage_1 = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage_1,
-                kind: DamageKind::PhysicalCut,
-                balance_effectiveness: 8,
-                accuracy: 85,
-                effect: Some(Effect::new(EffectKind::Bleeding, 29, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage_1,
+                    kind: DamageKind::PhysicalCut,
+                    balance_effectiveness: 8,
+                    accuracy: 85,
+                    effect: Some(Effect::new(EffectKind::Bleeding, 29, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
     let damage_2 = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage_2,
-                kind: DamageKind::PhysicalCut,
-                balance_effectiveness: 7,
-                accuracy: 60,
-                effect: Some(Effect::new(EffectKind::Flaming, 37, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage_2,
+                    kind: DamageKind::PhysicalCut,
+                    balance_effectiveness: 7,
+                    accuracy: 60,
+                    effect: Some(Effect::new(EffectKind::Flaming, 37, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
@@ -166,14 +207,17 @@ async fn weapon_spear(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage,
-                kind: DamageKind::PhysicalCut,
-                balance_effectiveness: 10,
-                accuracy: 60,
-                effect: Some(Effect::new(EffectKind::Bleeding, 30, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage,
+                    kind: DamageKind::PhysicalCut,
+                    balance_effectiveness: 10,
+                    accuracy: 60,
+                    effect: Some(Effect::new(EffectKind::Bleeding, 30, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
@@ -192,7 +236,7 @@ async fn weapon_bat(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api.rng().gen_range(15..=18);
     let damage = base_damage + (damage as f32 * api.fighter().weapon_multiplier()) as i32;
 
-    let effect = if Probability::new(10).generate_random_bool() {
+    let effect = if Probability::new(10).generate_random_bool_with(api.rng()) {
         Some(Effect::new(EffectKind::Bleeding, 60, api.fighter_index))
     } else {
         None
@@ -201,14 +245,17 @@ async fn weapon_bat(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage,
-                kind: DamageKind::Physical,
-                balance_effectiveness: if effect.is_some() { 20 } else { 10 },
-                accuracy: 60,
-                effect,
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage,
+                    kind: DamageKind::Physical,
+                    balance_effectiveness: if effect.is_some() { 20 } else { 10 },
+                    accuracy: 60,
+                    effect,
+                },
+            ),
         )
         .await;
 
@@ -247,7 +294,7 @@ async fn weapon_umbrella(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api.rng().gen_range(10..=18);
     let damage = base_damage + (damage as f32 * api.fighter().weapon_multiplier()) as i32;
 
-    let effect = if Probability::new(40).generate_random_bool() {
+    let effect = if Probability::new(40).generate_random_bool_with(api.rng()) {
         Some(Effect::new(EffectKind::Bleeding, 60, api.fighter_index))
     } else {
         None
@@ -256,14 +303,17 @@ async fn weapon_umbrella(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api
         .apply_damage(
             api.target_index,
-            DamageSpecifier {
-                culprit: api.fighter_index,
-                amount: damage,
-                kind: DamageKind::Physical,
-                balance_effectiveness: if effect.is_some() { 20 } else { 10 },
-                accuracy: 80,
-                effect,
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter_index,
+                    amount: damage,
+                    kind: DamageKind::Physical,
+                    balance_effectiveness: if effect.is_some() { 20 } else { 10 },
+                    accuracy: 80,
+                    effect,
+                },
+            ),
         )
         .await;
 
@@ -320,14 +370,17 @@ async fn weapon_scorpion_fang(mut api: BattleApi<'_>) -> anyhow::Result<()> {
     let damage = api
         .apply_damage(
             target.index,
-            DamageSpecifier {
-                culprit: api.fighter().index,
-                kind: DamageKind::Physical,
-                amount: damage,
-                balance_effectiveness: 5,
-                accuracy: 90,
-                effect: Some(Effect::new(EffectKind::Poisoned, 7, api.fighter_index)),
-            },
+            enchanted(
+                api.fighter().weapon,
+                DamageSpecifier {
+                    culprit: api.fighter().index,
+                    kind: DamageKind::Physical,
+                    amount: damage,
+                    balance_effectiveness: 5,
+                    accuracy: 90,
+                    effect: Some(Effect::new(EffectKind::Poisoned, 7, api.fighter_index)),
+                },
+            ),
         )
         .await;
 
@@ -341,17 +394,46 @@ async fn weapon_scorpion_fang(mut api: BattleApi<'_>) -> anyhow::Result<()> {
 }
 
 pub async fn execute_weapon_attack(
-    api: BattleApi<'_>,
+    mut api: BattleApi<'_>,
     weapon: FighterWeapon,
 ) -> anyhow::Result<()> {
+    let attack_api = BattleApi {
+        fighter_index: api.fighter_index,
+        target_index: api.target_index,
+        engine: &mut *api.engine,
+    };
+
     match weapon.kind {
-        WeaponKind::Stick => weapon_stick(api).await,
-        WeaponKind::Knife => weapon_knife(api).await,
-        WeaponKind::Bat => weapon_bat(api).await,
-        WeaponKind::Umbrella => weapon_umbrella(api).await,
-        WeaponKind::Spear => weapon_spear(api).await,
-        WeaponKind::Katana => weapon_katana(api).await,
-        WeaponKind::ScorpionFang => weapon_scorpion_fang(api).await,
-        WeaponKind::EthriaKatana => weapon_ethria_katana(api).await,
+        WeaponKind::Stick => weapon_stick(attack_api).await,
+        WeaponKind::Knife => weapon_knife(attack_api).await,
+        WeaponKind::Bat => weapon_bat(attack_api).await,
+        WeaponKind::Umbrella => weapon_umbrella(attack_api).await,
+        WeaponKind::Spear => weapon_spear(attack_api).await,
+        WeaponKind::Katana => weapon_katana(attack_api).await,
+        WeaponKind::ScorpionFang => weapon_scorpion_fang(attack_api).await,
+        WeaponKind::EthriaKatana => weapon_ethria_katana(attack_api).await,
+    }?;
+
+    wear_weapon(&mut api);
+    Ok(())
+}
+
+/// Every attack wears the weapon down. Once it has no durability left, it breaks and is lost.
+fn wear_weapon(api: &mut BattleApi<'_>) {
+    let Some(weapon) = api.fighter_mut().weapon.as_mut() else {
+        return;
+    };
+
+    weapon.durability = weapon.durability.saturating_sub(1);
+    if weapon.durability > 0 {
+        return;
     }
+
+    let item = items::get_item_by_weapon(weapon.kind);
+    api.fighter_mut().weapon = None;
+    let fighter_name = api.fighter().name.clone();
+    api.emit_message(format!(
+        "A arma **{}** de **{fighter_name}** quebrou!",
+        item.display_name
+    ));
 }
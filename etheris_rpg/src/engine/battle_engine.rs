@@ -0,0 +1,1026 @@
+use std::{
+    ops::{Add, Sub},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::bail;
+use etheris_common::Probability;
+use etheris_data::{items::get_item_by_weapon, ItemValues, SkillKind, SKILL_MASTERY_XP_PER_USE};
+use etheris_util::math;
+use rand::{seq::SliceRandom, Rng};
+use tokio::sync::Mutex;
+
+use crate::{common::*, *};
+
+use self::data::{weapons::execute_weapon_attack, Reward};
+
+/// The part of its drop that a fighter who fled from the battle still leaves to the winners.
+pub const FLED_FIGHTER_REWARD_RATIO: f32 = 0.5;
+
+#[derive(Debug, Clone, Default)]
+pub struct BattleResult {
+    pub reward: Reward,
+    pub winners: Vec<Fighter>,
+    pub losers: Vec<Fighter>,
+}
+
+/// Runs a `Battle` from start to end without knowing anything about who is watching it.
+/// Every input and output that needs a frontend goes through the `BattleIo`.
+pub struct BattleEngine {
+    pub battle: Battle,
+    pub io: BattleIoHandle,
+    pub current_turn_history: TurnHistory,
+    /// Everything that came from the outside during the battle, used to build its replay.
+    pub replay_entries: Vec<ReplayEntry>,
+    /// Fighters that invaded the battle and will join it when the next turn starts.
+    pub pending_intruders: Vec<FighterData>,
+
+    should_reinput: bool,
+}
+
+impl BattleEngine {
+    pub fn new(battle: Battle, io: BattleIoHandle) -> Self {
+        Self {
+            battle,
+            io,
+            current_turn_history: TurnHistory::default(),
+            replay_entries: vec![],
+            pending_intruders: vec![],
+            should_reinput: false,
+        }
+    }
+
+    pub fn new_headless(battle: Battle) -> Self {
+        let io = HeadlessIo::new(battle.seed);
+        Self::new(battle, Arc::new(Mutex::new(io)))
+    }
+
+    pub async fn run(&mut self) -> anyhow::Result<BattleResult> {
+        self.battle.reallocate_all_targets();
+        controller_helper::on_start(self).await?;
+
+        let mut turn_errors = 0;
+
+        while self.battle.state == BattleState::Running {
+            match self.turn().await {
+                Ok(()) => (),
+                Err(e) => {
+                    eprintln!("battle_engine::run Error: {}", e);
+                    // Error tolerancy of 10
+                    if turn_errors < 10 {
+                        turn_errors += 1;
+                        continue;
+                    }
+
+                    return Err(e);
+                }
+            }
+
+            let io = self.io.clone();
+            io.lock().await.on_turn_end(self).await?;
+        }
+
+        let winners: Vec<Fighter> = match &self.battle.state {
+            BattleState::Running => {
+                unreachable!()
+            }
+            BattleState::Ended { winners, .. } => winners
+                .iter()
+                .map(|w| self.battle.get_fighter(*w).to_owned())
+                .collect(),
+        };
+
+        if winners.is_empty() {
+            bail!("A battle can't draw");
+        }
+
+        let losers = self
+            .battle
+            .fighters
+            .iter()
+            .filter(|f| !winners.contains(f))
+            .cloned()
+            .collect();
+
+        let reward = self
+            .battle
+            .defeated_fighters
+            .iter()
+            .map(|f| {
+                let fighter = self.battle.get_fighter(*f);
+                if fighter.flags.contains(FighterFlags::FLED) {
+                    fighter.drop.scaled(FLED_FIGHTER_REWARD_RATIO)
+                } else {
+                    fighter.drop.clone()
+                }
+            })
+            .fold(Reward::default(), |acc, r| acc.add(r));
+
+        Ok(BattleResult {
+            reward,
+            winners,
+            losers,
+        })
+    }
+
+    pub async fn turn(&mut self) -> anyhow::Result<()> {
+        let fighter = self.battle.get_current_fighter().clone();
+
+        self.current_turn_history = TurnHistory {
+            round: self.battle.history.len() + 1,
+            fighter: fighter.index,
+            target: fighter.target,
+            events: vec![],
+        };
+
+        for intruder in std::mem::take(&mut self.pending_intruders) {
+            self.emit_turn_message(format!("**{}** entrou na luta!", intruder.name));
+            self.replay_entries
+                .push(ReplayEntry::Intruder(Box::new(ReplayFighter::from(
+                    &intruder,
+                ))));
+            self.battle.join_intruder(intruder);
+        }
+
+        if fighter.has_effect(EffectKind::Frozen) {
+            self.emit_turn_message(format!(
+                "**{}** está congelado e não pode lutar.",
+                fighter.name
+            ));
+            self.should_reinput = false;
+        } else if let Some(paralyzed) = fighter.get_effect(EffectKind::Paralyzed) {
+            self.battle
+                .get_current_fighter_mut()
+                .remove_effect(Effect::new(EffectKind::Paralyzed, 1, paralyzed.culprit));
+            self.emit_turn_message(format!(
+                "**{}** está paralisado e não pode lutar. Turnos de paralisia restantes: `{}`",
+                fighter.name,
+                paralyzed.amount - 1
+            ));
+            self.should_reinput = false;
+        } else {
+            let input = self.get_input().await?;
+            self.process_input(input).await?;
+        }
+
+        if !self.should_reinput {
+            self.update_turn_history_message().await?;
+            self.next_turn().await?;
+        }
+
+        self.should_reinput = false;
+
+        Ok(())
+    }
+
+    pub async fn update_turn_history_message(&mut self) -> anyhow::Result<()> {
+        let io = self.io.clone();
+        let mut io = io.lock().await;
+        io.update_turn(self).await
+    }
+
+    pub async fn wait(&mut self, duration: Duration) {
+        self.io.lock().await.wait(duration).await;
+    }
+
+    pub async fn next_turn(&mut self) -> anyhow::Result<()> {
+        let alive_fighters = self.battle.alive_fighters.clone();
+
+        controller_helper::tick_every_modifier(&alive_fighters, self).await?;
+        controller_helper::tick_every_effect(&alive_fighters, self).await?;
+        controller_helper::passives(self).await?;
+
+        // Ask every fighter to check if it should risk life
+        controller_helper::should_risk_life(&alive_fighters, self).await?;
+
+        // Move to next turn
+        let before_events_len = self.current_turn_history.events.len();
+        self.battle.next_turn(&mut self.current_turn_history);
+
+        // Update the turn message
+        if before_events_len != self.current_turn_history.events.len() {
+            self.wait(Duration::from_secs(1)).await;
+            self.update_turn_history_message().await?;
+            self.wait(Duration::from_secs(1)).await;
+        }
+
+        let before_events_len = self.current_turn_history.events.len();
+        if self.battle.fighters_queue.is_empty() {
+            self.battle.cycle_counter += 1;
+            controller_helper::tick_cycle(&self.battle.alive_fighters.clone(), self).await?;
+        }
+
+        // Update the turn message - cycle tick
+        if before_events_len != self.current_turn_history.events.len() {
+            self.update_turn_history_message().await?;
+            self.wait(Duration::from_secs(1)).await;
+        }
+
+        self.battle.next_fighter();
+
+        // Not done in `turn`: reinputs call it again for the same fighter
+        let current_fighter = self.battle.get_current_fighter_mut();
+        current_fighter.tick_skill_cooldowns();
+        // Reactions only last until the next turn of whoever prepared them
+        current_fighter.reaction = None;
+
+        Ok(())
+    }
+
+    pub async fn process_input(&mut self, input: BattleInput) -> anyhow::Result<()> {
+        let fighter = self.battle.get_current_fighter().clone();
+        let fighter_name = fighter.name.clone();
+
+        match input {
+            BattleInput::Nothing => {
+                self.emit_turn_message(format!("**{}** não fez nada!", fighter.name));
+            }
+            BattleInput::Reinput => {
+                self.should_reinput = true;
+                return Ok(());
+            }
+            BattleInput::ChangeTarget(target) => {
+                self.should_reinput = true;
+                if target != fighter.index {
+                    self.battle.get_current_fighter_mut().target = target;
+                }
+
+                return Ok(());
+            }
+            BattleInput::ChangeTeam(team) => {
+                self.should_reinput = true;
+                self.battle.get_current_fighter_mut().team = team;
+                self.battle.reallocate_all_targets();
+
+                return Ok(());
+            }
+            BattleInput::Attack | BattleInput::Upkick => {
+                let is_kick = input == BattleInput::Upkick;
+                let mut api = BattleApi::new(self);
+
+                if !is_kick && api.fighter().composure != Composure::OnGround {
+                    if let Some(weapon) = api.fighter().weapon {
+                        execute_weapon_attack(api, weapon).await?;
+                        return Ok(());
+                    }
+                }
+
+                // Default attack
+                let mut damage = api.rng().gen_range(10..=15);
+                if is_kick {
+                    damage += api.rng().gen_range(3..=6);
+                }
+
+                let damage = (damage as f32 * api.fighter().strength_multiplier() * 1.1) as i32;
+
+                let damage = api
+                    .apply_damage(
+                        api.target_index,
+                        DamageSpecifier {
+                            culprit: fighter.index,
+                            amount: damage,
+                            kind: DamageKind::Physical,
+                            balance_effectiveness: 5 + if is_kick { 10 } else { 0 },
+                            accuracy: if is_kick { 60 } else { 90 },
+                            effect: None,
+                        },
+                    )
+                    .await;
+
+                let target_name = api.target().name.to_owned();
+                api.emit_message(format!(
+                    "**{}** {} **{}** e causou **{damage}**",
+                    fighter_name,
+                    if is_kick { "chutou" } else { "atacou" },
+                    target_name
+                ));
+            }
+            BattleInput::Defend => {
+                if fighter.composure == Composure::OnGround {
+                    self.emit_random_turn_message(&[
+                        format!("**{}** tentou se defender mas está no chão!", fighter_name),
+                        format!("**{}** não conseguiu se defender no chão!", fighter_name),
+                    ]);
+                } else {
+                    let mut message = format!("**{}** está defendendo!", fighter_name);
+                    if fighter.balance < 90 {
+                        message.push_str(" Um pouco de sua postura foi restaurada.");
+                    }
+
+                    self.emit_turn_message(message);
+
+                    let fighter = self.battle.get_current_fighter_mut();
+                    fighter.defense += 2; // One will be removed at the end of the turn, so we add an extra defense turn
+                    fighter.balance = fighter.balance.saturating_add(10).min(100);
+                    fighter.ether.add((fighter.ether.max as f32 * 0.05) as i32);
+                }
+            }
+            BattleInput::UseSkill(skill) => {
+                let fighter_index = self.battle.current_fighter;
+                let fighter = self.battle.get_current_fighter_mut();
+                let mut dynamic_skill = skill.dynamic_skill.lock().await;
+                fighter.ether.value = fighter
+                    .ether
+                    .value
+                    .sub(dynamic_skill.ether_cost(fighter))
+                    .max(0);
+                let limits = dynamic_skill.data(fighter).limits;
+                fighter.register_skill_use(skill.identifier, limits);
+
+                self.emit_event(BattleEvent::SkillUsed {
+                    fighter: self.battle.current_fighter,
+                    skill: skill.base_kind.clone(),
+                });
+                dynamic_skill.on_use(BattleApi::new(self)).await?;
+
+                let skill_name = dynamic_skill
+                    .data(self.battle.get_fighter(fighter_index))
+                    .name;
+                let fighter = self.battle.get_fighter_mut(fighter_index);
+                if fighter.add_skill_mastery_xp(&skill.base_kind, SKILL_MASTERY_XP_PER_USE)
+                    && fighter.user.is_some()
+                {
+                    let message = format!(
+                        "**{}** dominou mais a habilidade **{}**! (Maestria nível {})",
+                        fighter.name,
+                        skill_name,
+                        fighter.skill_mastery_level(&skill.base_kind)
+                    );
+                    self.emit_turn_message(message);
+                }
+
+                self.trigger_skill_combos(fighter_index, &skill.base_kind)
+                    .await;
+            }
+            BattleInput::Finish(finisher) => {
+                let failed = finisher
+                    .fail_probability()
+                    .generate_random_bool_with(&mut self.battle.rng);
+                self.emit_event(BattleEvent::FinisherUsed {
+                    fighter: fighter.index,
+                    target: fighter.target,
+                    finisher,
+                    failed,
+                });
+
+                if failed {
+                    self.emit_turn_message(format!(
+                        "**{}** tentou executar uma finalização mas não conseguiu!",
+                        fighter.name
+                    ));
+                    return Ok(());
+                }
+
+                finisher.execute_finisher(BattleApi::new(self)).await?;
+                self.wait(Duration::from_millis(300)).await;
+                self.update_turn_history_message().await?;
+                self.wait(Duration::from_secs(1)).await;
+            }
+            BattleInput::GetUp => {
+                let current_fighter = self.battle.current_fighter;
+                let fighter = &mut self.battle.fighters[current_fighter.0];
+                let extreme_low_balance = fighter.balance < 40;
+                let prob_to_fail = Probability::new(if extreme_low_balance { 30 } else { 15 });
+
+                if prob_to_fail.generate_random_bool_with(&mut self.battle.rng) {
+                    fighter.balance = fighter.balance.add(10).min(100);
+
+                    self.emit_random_turn_message(&[
+                        format!("**{}** tentou levantar e caiu novamente!", fighter_name),
+                        format!(
+                            "**{}** tentou ficar em pé e perdeu o equilíbrio!",
+                            fighter_name
+                        ),
+                        format!("**{}** falhou em levantar!", fighter_name),
+                    ]);
+                } else {
+                    fighter.composure = Composure::Standing;
+                    fighter.balance = fighter.balance.add(30).min(100);
+
+                    self.emit_random_turn_message(&[
+                        format!("**{}** recuperou sua postura e ficou em pé!", fighter_name),
+                        format!("**{}** levantou do chão!", fighter_name),
+                    ]);
+                }
+            }
+            BattleInput::UseItem(item) => {
+                if !fighter
+                    .inventory
+                    .iter()
+                    .any(|i| i.item.identifier == item.identifier)
+                {
+                    self.emit_turn_message(format!(
+                        "**{}** não possui o item {}!",
+                        fighter.name, item.display_name
+                    ));
+                    return Ok(());
+                }
+
+                if let Some(weapon) = item.weapon {
+                    {
+                        let fighter = self.battle.get_fighter_mut(fighter.index);
+
+                        // Only one of the weapons leaves the inventory, keeping its own wear and upgrades
+                        let mut values = ItemValues::empty();
+                        if let Some(battle_item) = fighter
+                            .inventory
+                            .iter_mut()
+                            .find(|i| i.item.identifier == item.identifier)
+                        {
+                            battle_item.quantity = battle_item.quantity.saturating_sub(1);
+                            values = battle_item.values.clone();
+                        }
+                        fighter.inventory.retain(|i| i.quantity > 0);
+
+                        if let Some(weapon) = fighter.weapon {
+                            let weapon_item = get_item_by_weapon(weapon.kind);
+                            fighter.inventory.push(BattleItem {
+                                item: weapon_item,
+                                quantity: 1,
+                                values: weapon.values(),
+                            });
+                        }
+
+                        fighter.weapon = Some(FighterWeapon::new(weapon, &values));
+                    }
+
+                    let weapon_item = get_item_by_weapon(weapon);
+
+                    self.emit_turn_message(format!(
+                        "**{}** equipou a arma **{}**",
+                        fighter.name, weapon_item.display_name
+                    ));
+                    return Ok(());
+                }
+
+                // Special items
+                match item.display_name {
+                    "invigorating_crystal" => {
+                        let hp = (fighter.health().max as f32 * 0.25) as i32;
+                        let ether = (fighter.ether.max as f32 * 0.65) as i32;
+
+                        {
+                            let fighter = self.battle.get_fighter_mut(fighter.index);
+                            fighter.heal(fighter.index, hp);
+                            fighter.ether.add(ether);
+                        }
+
+                        self.emit_turn_message(format!(
+                            "**{}** usou um cristal revigorante e se revigorou!",
+                            fighter.name
+                        ));
+
+                        self.update_turn_history_message().await?;
+                        self.wait(Duration::from_secs(1)).await;
+
+                        self.should_reinput = true;
+                        return Ok(());
+                    }
+                    "intelligence_crystal" => {
+                        {
+                            let fighter = self.battle.get_fighter_mut(fighter.index);
+                            fighter.intelligence_level += 10;
+                        }
+
+                        self.emit_turn_message(format!(
+                            "**{}** usou um cristal da inteligência e sentiu seu cérebro mais rápido e inteligente!",
+                            fighter.name
+                        ));
+
+                        self.update_turn_history_message().await?;
+                        self.wait(Duration::from_secs(1)).await;
+
+                        self.should_reinput = true;
+                        return Ok(());
+                    }
+                    _ => {}
+                };
+
+                // Consumable items
+                let Some(consumption_properties) = item.consumption_properties else {
+                    self.emit_turn_message(format!(
+                        "**{}** tentou usar um item que não pode ser usado em batalha!",
+                        fighter.name
+                    ));
+                    return Ok(());
+                };
+
+                let health_regeneration = math::calculate_health_regeneration(
+                    consumption_properties,
+                    1,
+                    fighter.health().max,
+                );
+                let ether_regeneration = math::calculate_ether_regeneration(
+                    consumption_properties,
+                    1,
+                    fighter.ether.max,
+                );
+
+                let mut messages = vec![];
+                if health_regeneration > 0 {
+                    messages.push(format!("**{health_regeneration} vida**"));
+                }
+
+                if ether_regeneration > 0 {
+                    messages.push(format!("**{ether_regeneration} ether**"));
+                }
+
+                {
+                    let fighter = self.battle.get_fighter_mut(fighter.index);
+                    fighter.heal(fighter.index, health_regeneration);
+                    fighter.ether.add(ether_regeneration);
+                    fighter.remove_item(item, 1);
+                }
+
+                self.emit_turn_message(format!(
+                    "**{}** usou o item **{}** e regenerou {}!",
+                    fighter.name,
+                    item.display_name,
+                    messages.join(" e ")
+                ));
+
+                self.update_turn_history_message().await?;
+                self.wait(Duration::from_secs(1)).await;
+
+                self.should_reinput = true;
+            }
+            BattleInput::Flee => {
+                let escape_probability = Probability::new(match fighter.composure {
+                    Composure::OnGround => 0,
+                    _ if fighter.balance < 50 => 35,
+                    _ => 65,
+                });
+
+                if escape_probability.generate_random_bool_with(&mut self.battle.rng) {
+                    let fighter = self.battle.get_current_fighter_mut();
+                    fighter.flags.insert(FighterFlags::FLED);
+                    fighter.is_defeated = true;
+
+                    self.emit_turn_message(format!(
+                        "**{}** deu as costas e correu para fugir da batalha!",
+                        fighter_name
+                    ));
+                } else {
+                    self.emit_random_turn_message(&[
+                        format!("**{}** tentou fugir mas não conseguiu!", fighter_name),
+                        format!("**{}** tropeçou enquanto tentava fugir!", fighter_name),
+                    ]);
+                }
+            }
+            BattleInput::Taunt => {
+                let target = self.battle.get_fighter_mut(fighter.target);
+                target.balance = target.balance.saturating_sub(5);
+                let target_name = target.name.clone();
+
+                self.emit_random_turn_message(&[
+                    format!(
+                        "**{}** riu de **{}** e o chamou de fraco!",
+                        fighter_name, target_name
+                    ),
+                    format!(
+                        "**{}** provocou **{}**, dizendo que essa luta é fácil demais!",
+                        fighter_name, target_name
+                    ),
+                    format!(
+                        "**{}** abriu os braços e desafiou **{}** a acertá-lo!",
+                        fighter_name, target_name
+                    ),
+                ]);
+            }
+            BattleInput::UseAction(action) => {
+                let mut api = BattleApi::new(self);
+                api.fighter_index = fighter.index;
+                api.target_index = fighter.target;
+                action_handler::execute_action(action, &mut api).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_input(&mut self) -> anyhow::Result<BattleInput> {
+        let fighter = self.battle.get_current_fighter().index;
+
+        let input = if self.battle.get_current_fighter().user.is_none() {
+            let Some(brain) = self.battle.get_current_fighter().brain.clone() else {
+                panic!("No brain for the current fighter that also has no user attached to it!");
+            };
+
+            let mut brain = brain.dynamic_brain.lock().await;
+            brain.select_input(BattleApi::new(self)).await
+        } else {
+            let io = self.io.clone();
+            let mut io = io.lock().await;
+            io.select_input(self).await?
+        };
+
+        self.replay_entries.push(ReplayEntry::Input {
+            fighter: fighter.0,
+            input: ReplayInput::from(&input),
+        });
+
+        Ok(input)
+    }
+
+    pub async fn select_option(
+        &mut self,
+        selection: IoSelection,
+    ) -> anyhow::Result<Option<String>> {
+        let io = self.io.clone();
+        let option = io.lock().await.select_option(self, selection).await?;
+        self.replay_entries
+            .push(ReplayEntry::Option(option.clone()));
+
+        Ok(option)
+    }
+
+    pub async fn input_number(
+        &mut self,
+        fighter: FighterIndex,
+        prompt: String,
+        valid_range: (f64, f64),
+    ) -> anyhow::Result<f64> {
+        let io = self.io.clone();
+        let number = io
+            .lock()
+            .await
+            .input_number(self, fighter, prompt, valid_range)
+            .await?;
+        self.replay_entries.push(ReplayEntry::Number(number));
+
+        Ok(number)
+    }
+
+    pub async fn confirm_risk_life(&mut self, fighter: FighterIndex) -> anyhow::Result<bool> {
+        let io = self.io.clone();
+        let confirmation = io.lock().await.confirm_risk_life(self, fighter).await?;
+        self.replay_entries
+            .push(ReplayEntry::RiskLife(confirmation));
+
+        Ok(confirmation)
+    }
+
+    /// The intruder only joins when the next turn starts, so a replay can let it in at the same point.
+    pub fn join_intruder(&mut self, fighter: FighterData) {
+        self.pending_intruders.push(fighter);
+    }
+
+    /// Builds the replay of everything that happened in the battle so far.
+    pub fn replay(&self) -> BattleReplay {
+        BattleReplay {
+            seed: self.battle.seed,
+            region: self.battle.region,
+            settings: self.battle.settings.clone(),
+            fighters: self
+                .battle
+                .initial_fighters
+                .iter()
+                .map(ReplayFighter::from)
+                .collect(),
+            entries: self.replay_entries.clone(),
+        }
+    }
+
+    pub fn emit_event(&mut self, event: BattleEvent) {
+        self.current_turn_history.events.push(event);
+    }
+
+    /// Triggers the combos the fighter completed by using `skill` after the skills of its last turns.
+    async fn trigger_skill_combos(&mut self, fighter_index: FighterIndex, skill: &SkillKind) {
+        let longest_combo = SKILL_COMBOS
+            .iter()
+            .map(|c| c.sequence.len())
+            .max()
+            .unwrap_or_default();
+        let previous_skills = self
+            .battle
+            .last_skills_used_by(fighter_index, longest_combo.saturating_sub(1));
+
+        for combo in SKILL_COMBOS
+            .iter()
+            .filter(|c| c.is_completed_by(&previous_skills, skill))
+        {
+            let fighter = self.battle.get_fighter(fighter_index).clone();
+            let target = self.battle.get_fighter(fighter.target).clone();
+            if target.is_defeated || target.team == fighter.team {
+                continue;
+            }
+
+            if let Some(effect) = combo.required_target_effect {
+                if !target.has_effect(effect) {
+                    continue;
+                }
+
+                self.battle
+                    .get_fighter_mut(target.index)
+                    .delete_effect(effect);
+            }
+
+            let mut api = BattleApi::new(self);
+            let damage = match combo.damage {
+                Some(damage) => {
+                    let amount =
+                        damage.base + (target.health().max as f32 * damage.max_health_ratio) as i32;
+                    api.apply_damage(
+                        target.index,
+                        DamageSpecifier {
+                            culprit: fighter_index,
+                            kind: damage.kind,
+                            amount,
+                            balance_effectiveness: damage.balance_effectiveness,
+                            accuracy: 100,
+                            effect: None,
+                        },
+                    )
+                    .await
+                    .to_string()
+                }
+                None => String::new(),
+            };
+
+            if let Some((kind, amount)) = combo.effect {
+                api.apply_effect(target.index, Effect::new(kind, amount, fighter_index))
+                    .await;
+            }
+
+            api.emit_message(format_combo_message(
+                combo,
+                &fighter.name,
+                &target.name,
+                &damage,
+            ));
+
+            let fighter = self.battle.get_fighter_mut(fighter_index);
+            if !fighter.triggered_combos.contains(&combo.identifier) {
+                fighter.triggered_combos.push(combo.identifier);
+            }
+        }
+    }
+
+    pub fn emit_turn_message(&mut self, message: impl Into<String>) {
+        self.emit_event(BattleEvent::Message(message.into()));
+    }
+
+    pub fn defer_message(&mut self, message: impl Into<String>) {
+        self.battle.deferred_turn_messages.push(message.into());
+    }
+
+    pub fn emit_random_turn_message(&mut self, messages: &[impl ToString]) {
+        let messages = messages.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+        let message = messages
+            .choose(&mut self.battle.rng)
+            .cloned()
+            .unwrap_or_default();
+        self.emit_turn_message(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use etheris_data::world::regions::WorldRegion;
+    use etheris_discord::twilight_model::user::User;
+
+    use super::*;
+    use crate::{
+        brain::{BrainDifficulty, BrainKind},
+        data::enemies::weaklings,
+    };
+
+    #[tokio::test]
+    async fn headless_battle_runs_to_the_end() {
+        let fighters = vec![
+            FighterData::new_from_enemy(0, Default::default(), weaklings::giant_rat()),
+            FighterData::new_from_enemy(1, Default::default(), weaklings::greenagis_mutant()),
+        ];
+
+        let battle =
+            Battle::new(WorldRegion::Greenagis, BattleSettings::default(), fighters).unwrap();
+        let mut engine = BattleEngine::new_headless(battle);
+        let result = engine.run().await.unwrap();
+
+        assert!(!result.winners.is_empty());
+        assert!(matches!(engine.battle.state, BattleState::Ended { .. }));
+        assert!(!engine.battle.history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn tactical_brains_fight_to_the_end() {
+        let mut fighters = vec![
+            FighterData::new_from_enemy(0, Default::default(), weaklings::giant_rat()),
+            FighterData::new_from_enemy(1, Default::default(), weaklings::greenagis_mutant()),
+        ];
+        for fighter in fighters.iter_mut() {
+            fighter.brain = Some(BrainKind::Tactical(BrainDifficulty::Hard));
+        }
+
+        let settings = BattleSettings {
+            seed: Some(11),
+            ..Default::default()
+        };
+        let battle = Battle::new(WorldRegion::Greenagis, settings, fighters).unwrap();
+        let mut engine = BattleEngine::new_headless(battle);
+        let result = engine.run().await.unwrap();
+
+        assert!(!result.winners.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fleeing_fighters_leave_part_of_their_drop() {
+        let mut engine = BattleEngine::new_headless(seeded_battle(5));
+        engine.battle.current_fighter = FighterIndex(1);
+
+        engine.battle.get_current_fighter_mut().composure = Composure::OnGround;
+        engine.process_input(BattleInput::Flee).await.unwrap();
+        assert!(!engine.battle.get_current_fighter().is_defeated);
+
+        let fighter = engine.battle.get_current_fighter_mut();
+        fighter.composure = Composure::Standing;
+        fighter.flags.insert(FighterFlags::FLED);
+        fighter.is_defeated = true;
+        let drop = fighter.drop.clone();
+
+        engine.next_turn().await.unwrap();
+        let result = engine.run().await.unwrap();
+
+        assert_eq!(result.reward, drop.scaled(FLED_FIGHTER_REWARD_RATIO));
+        assert!(engine
+            .battle
+            .events()
+            .any(|e| matches!(e, BattleEvent::FighterFled { .. })));
+    }
+
+    #[tokio::test]
+    async fn battle_events_are_recorded() {
+        let mut engine = BattleEngine::new_headless(seeded_battle(3));
+        engine.run().await.unwrap();
+
+        let events = engine.battle.events().collect::<Vec<_>>();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, BattleEvent::DamageDealt { .. })));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, BattleEvent::TeamWon { .. })));
+
+        let history = engine.battle.history.last().unwrap();
+        assert!(!history.messages(&engine.battle).is_empty());
+
+        for event in events {
+            let bson = etheris_database::bson::to_bson(event).unwrap();
+            assert_eq!(
+                &etheris_database::bson::from_bson::<BattleEvent>(bson).unwrap(),
+                event
+            );
+        }
+    }
+
+    fn seeded_battle(seed: u64) -> Battle {
+        let user: User = serde_json::from_str(
+            r#"{"id": "1", "username": "tester", "discriminator": "0000", "avatar": null}"#,
+        )
+        .unwrap();
+
+        let mut player = FighterData::new_from_enemy(0, Default::default(), weaklings::giant_rat());
+        player.user = Some(user);
+        player.brain = None;
+
+        let fighters = vec![
+            player,
+            FighterData::new_from_enemy(1, Default::default(), weaklings::greenagis_mutant()),
+        ];
+
+        let settings = BattleSettings {
+            seed: Some(seed),
+            ..Default::default()
+        };
+
+        Battle::new(WorldRegion::Greenagis, settings, fighters).unwrap()
+    }
+
+    #[tokio::test]
+    async fn changing_target_does_not_tick_cooldowns() {
+        let io = ReplayIo::new(vec![ReplayEntry::Input {
+            fighter: 0,
+            input: ReplayInput::ChangeTarget(1),
+        }]);
+        let mut engine = BattleEngine::new(seeded_battle(9), Arc::new(Mutex::new(io)));
+        engine.battle.current_fighter = FighterIndex(0);
+
+        let mut skill = FighterSkill::from(SkillKind::ImbuedPunch);
+        skill.cooldown = 2;
+        engine.battle.get_current_fighter_mut().skills.push(skill);
+
+        engine.turn().await.unwrap();
+
+        let fighter = engine.battle.get_current_fighter();
+        assert_eq!(fighter.index, FighterIndex(0));
+        assert_eq!(fighter.skills.last().unwrap().cooldown, 2);
+    }
+
+    /// A `HeadlessIo` that lets an AI intruder in at the end of the first turn.
+    struct IntruderIo(HeadlessIo);
+
+    #[async_trait::async_trait]
+    impl BattleIo for IntruderIo {
+        async fn select_input(&mut self, engine: &mut BattleEngine) -> anyhow::Result<BattleInput> {
+            self.0.select_input(engine).await
+        }
+
+        async fn select_option(
+            &mut self,
+            engine: &mut BattleEngine,
+            selection: IoSelection,
+        ) -> anyhow::Result<Option<String>> {
+            self.0.select_option(engine, selection).await
+        }
+
+        async fn input_number(
+            &mut self,
+            engine: &mut BattleEngine,
+            fighter: FighterIndex,
+            prompt: String,
+            valid_range: (f64, f64),
+        ) -> anyhow::Result<f64> {
+            self.0
+                .input_number(engine, fighter, prompt, valid_range)
+                .await
+        }
+
+        async fn confirm_risk_life(
+            &mut self,
+            engine: &mut BattleEngine,
+            fighter: FighterIndex,
+        ) -> anyhow::Result<bool> {
+            self.0.confirm_risk_life(engine, fighter).await
+        }
+
+        async fn update_turn(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+            self.0.update_turn(engine).await
+        }
+
+        async fn on_turn_end(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+            if engine.battle.turn_counter == 1 {
+                engine.join_intruder(FighterData::new_from_enemy(
+                    2,
+                    Default::default(),
+                    weaklings::giant_rat(),
+                ));
+            }
+
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_let_intruders_in_before_ai_turns() {
+        let fighters = vec![
+            FighterData::new_from_enemy(0, Default::default(), weaklings::giant_rat()),
+            FighterData::new_from_enemy(1, Default::default(), weaklings::greenagis_mutant()),
+        ];
+        let settings = BattleSettings {
+            seed: Some(13),
+            ..Default::default()
+        };
+        let battle = Battle::new(WorldRegion::Greenagis, settings, fighters).unwrap();
+
+        let io = IntruderIo(HeadlessIo::new(13));
+        let mut engine = BattleEngine::new(battle, Arc::new(Mutex::new(io)));
+        engine.run().await.unwrap();
+        assert_eq!(engine.battle.intruder_count, 1);
+
+        let replay = BattleReplay::from_json(&engine.replay().to_json().unwrap()).unwrap();
+        let mut replayed = replay.create_engine().unwrap();
+        replayed.run().await.unwrap();
+
+        assert_eq!(replayed.battle.intruder_count, 1);
+        assert_eq!(engine.battle.history, replayed.battle.history);
+        assert_eq!(engine.replay_entries, replayed.replay_entries);
+    }
+
+    #[tokio::test]
+    async fn seeded_battles_are_reproducible() {
+        let mut first = BattleEngine::new_headless(seeded_battle(42));
+        first.run().await.unwrap();
+
+        let mut second = BattleEngine::new_headless(seeded_battle(42));
+        second.run().await.unwrap();
+
+        assert_eq!(first.battle.history, second.battle.history);
+    }
+
+    #[tokio::test]
+    async fn replays_reproduce_the_battle() {
+        let mut engine = BattleEngine::new_headless(seeded_battle(7));
+        engine.run().await.unwrap();
+
+        let replay = BattleReplay::from_json(&engine.replay().to_json().unwrap()).unwrap();
+        assert_eq!(replay, engine.replay());
+
+        let mut replayed = replay.create_engine().unwrap();
+        replayed.run().await.unwrap();
+
+        assert_eq!(engine.battle.history, replayed.battle.history);
+        assert_eq!(engine.replay_entries, replayed.replay_entries);
+    }
+}
@@ -0,0 +1,87 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{brain::defaults, *};
+
+/// A `BattleIo` with no frontend at all. Human fighters are played by the default AI,
+/// so a whole battle can run in tests, scripts and simulations.
+///
+/// The choices made for human fighters use the io's own RNG, not the battle's one,
+/// so they don't change the battle rolls (just like a real player's choices wouldn't).
+#[derive(Debug, Clone)]
+pub struct HeadlessIo {
+    rng: StdRng,
+}
+
+impl Default for HeadlessIo {
+    fn default() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl HeadlessIo {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn swap_rng(&mut self, engine: &mut BattleEngine) {
+        std::mem::swap(&mut self.rng, &mut engine.battle.rng);
+    }
+}
+
+#[async_trait::async_trait]
+impl BattleIo for HeadlessIo {
+    async fn select_input(&mut self, engine: &mut BattleEngine) -> anyhow::Result<BattleInput> {
+        self.swap_rng(engine);
+        let input = defaults::select_a_input(BattleApi::new(engine)).await;
+        self.swap_rng(engine);
+
+        Ok(input)
+    }
+
+    async fn select_option(
+        &mut self,
+        _engine: &mut BattleEngine,
+        selection: IoSelection,
+    ) -> anyhow::Result<Option<String>> {
+        let options = selection
+            .options
+            .into_iter()
+            .filter(|o| o.active)
+            .collect::<Vec<_>>();
+
+        Ok(options.choose(&mut self.rng).map(|o| o.id.clone()))
+    }
+
+    async fn input_number(
+        &mut self,
+        _engine: &mut BattleEngine,
+        _fighter: FighterIndex,
+        _prompt: String,
+        valid_range: (f64, f64),
+    ) -> anyhow::Result<f64> {
+        Ok(valid_range.1)
+    }
+
+    async fn confirm_risk_life(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+    ) -> anyhow::Result<bool> {
+        self.swap_rng(engine);
+        let mut api = BattleApi::new(engine);
+        api.fighter_index = fighter;
+        api.target_index = api.battle().get_fighter(fighter).target;
+        let confirmation = defaults::should_risk_life(api).await;
+        self.swap_rng(engine);
+
+        Ok(confirmation)
+    }
+
+    async fn update_turn(&mut self, _engine: &mut BattleEngine) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::{sync::Arc, time::Duration};
+
+use etheris_common::Color;
+use tokio::sync::Mutex;
+
+use crate::{BattleEngine, BattleInput, FighterIndex};
+
+pub type BattleIoHandle = Arc<Mutex<dyn BattleIo + Send + 'static>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoOption {
+    pub id: String,
+    pub label: String,
+    /// An unicode emoji or a custom emoji mention, like `<:name:id>`.
+    pub emoji: Option<String>,
+    pub active: bool,
+}
+
+/// What is shown to a fighter while they pick an option.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IoPrompt {
+    pub title: String,
+    pub icon_url: Option<String>,
+    pub color: Option<Color>,
+    /// Name and value of each field.
+    pub fields: Vec<(String, String)>,
+}
+
+impl IoPrompt {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_icon_url(mut self, icon_url: Option<String>) -> Self {
+        self.icon_url = icon_url;
+        self
+    }
+
+    pub fn set_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn add_field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IoSelection {
+    pub fighter: FighterIndex,
+    pub prompt: IoPrompt,
+    pub options: Vec<IoOption>,
+}
+
+/// Everything the battle engine needs from the outside world.
+/// Only human-controlled fighters are routed through it: AI fighters are driven by their brains.
+#[async_trait::async_trait]
+pub trait BattleIo {
+    /// Asks the current fighter for its next input.
+    async fn select_input(&mut self, engine: &mut BattleEngine) -> anyhow::Result<BattleInput>;
+
+    /// Asks a fighter to pick one of the options. Returns the id of the selected option.
+    async fn select_option(
+        &mut self,
+        engine: &mut BattleEngine,
+        selection: IoSelection,
+    ) -> anyhow::Result<Option<String>>;
+
+    /// Asks a fighter to write a number. The prompt is plain text addressed to the fighter.
+    async fn input_number(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+        prompt: String,
+        valid_range: (f64, f64),
+    ) -> anyhow::Result<f64>;
+
+    async fn confirm_risk_life(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+    ) -> anyhow::Result<bool>;
+
+    /// Called every time the current turn history should be shown.
+    async fn update_turn(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()>;
+
+    async fn on_turn_end(&mut self, _engine: &mut BattleEngine) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Pacing between battle steps. Frontends that don't need it can ignore it.
+    async fn wait(&mut self, _duration: Duration) {}
+}
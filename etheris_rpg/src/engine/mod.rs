@@ -0,0 +1,9 @@
+mod battle_engine;
+mod headless;
+mod io;
+mod replay;
+
+pub use battle_engine::*;
+pub use headless::*;
+pub use io::*;
+pub use replay::*;
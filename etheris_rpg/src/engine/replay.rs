@@ -0,0 +1,429 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use etheris_common::Attribute;
+use etheris_data::{
+    items, personality::Personality, weapon::WeaponKind, world::regions::WorldRegion, BossKind,
+    BrainKind, ItemValues, SkillKind, SkillMastery,
+};
+use etheris_database::character_model::BattleAction;
+use etheris_discord::twilight_model::user::User;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::{finishers::Finisher, Reward, RewardItem},
+    list::prelude::BattleItem,
+    *,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayItem {
+    pub identifier: String,
+    pub quantity: usize,
+    pub values: ItemValues,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayRewardItem {
+    pub identifier: String,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayReward {
+    pub orbs: i32,
+    pub xp: i32,
+    pub items: Vec<ReplayRewardItem>,
+}
+
+/// A `FighterData` that can be serialized: items are stored by their identifiers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFighter {
+    pub team: u8,
+    pub name: String,
+    pub user: Option<User>,
+    pub boss: Option<BossKind>,
+
+    pub brain: Option<BrainKind>,
+    pub actions: Vec<BattleAction>,
+
+    pub inventory: Vec<ReplayItem>,
+    pub personalities: Vec<Personality>,
+    pub skills: Vec<SkillKind>,
+    #[serde(default)]
+    pub skill_mastery: Vec<SkillMastery>,
+
+    pub strength_level: u32,
+    pub intelligence_level: u32,
+    pub potential: f64,
+    pub immunities: BodyImmunities,
+
+    pub weapon: Option<WeaponKind>,
+    #[serde(default)]
+    pub weapon_values: ItemValues,
+    /// Identifiers of the equipped items.
+    #[serde(default)]
+    pub equipment: Vec<String>,
+
+    pub resistance: Attribute,
+    pub vitality: Attribute,
+    pub ether: Attribute,
+
+    pub drop: ReplayReward,
+}
+
+impl From<&FighterData> for ReplayFighter {
+    fn from(data: &FighterData) -> Self {
+        Self {
+            team: data.team,
+            name: data.name.clone(),
+            user: data.user.clone(),
+            boss: data.boss,
+            brain: data.brain,
+            actions: data.actions.clone(),
+            inventory: data
+                .inventory
+                .iter()
+                .map(|i| ReplayItem {
+                    identifier: i.item.identifier.to_string(),
+                    quantity: i.quantity,
+                    values: i.values.clone(),
+                })
+                .collect(),
+            personalities: data.personalities.clone(),
+            skills: data.skills.clone(),
+            skill_mastery: data.skill_mastery.clone(),
+            strength_level: data.strength_level,
+            intelligence_level: data.intelligence_level,
+            potential: data.potential,
+            immunities: data.immunities.clone(),
+            weapon: data.weapon,
+            weapon_values: data.weapon_values.clone(),
+            equipment: data
+                .equipment
+                .iter()
+                .map(|i| i.identifier.to_string())
+                .collect(),
+            resistance: data.resistance,
+            vitality: data.vitality,
+            ether: data.ether,
+            drop: ReplayReward {
+                orbs: data.drop.orbs,
+                xp: data.drop.xp,
+                items: data
+                    .drop
+                    .items
+                    .iter()
+                    .map(|i| ReplayRewardItem {
+                        identifier: i.item.identifier.to_string(),
+                        amount: i.amount,
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl ReplayFighter {
+    pub fn to_fighter_data(&self) -> anyhow::Result<FighterData> {
+        let get_item = |identifier: &str| {
+            items::get_item(identifier)
+                .with_context(|| format!("unknown item in replay: {identifier}"))
+        };
+
+        Ok(FighterData {
+            team: self.team,
+            name: self.name.clone(),
+            user: self.user.clone(),
+            boss: self.boss,
+            brain: self.brain,
+            actions: self.actions.clone(),
+            inventory: self
+                .inventory
+                .iter()
+                .map(|i| {
+                    Ok(BattleItem {
+                        item: get_item(&i.identifier)?,
+                        quantity: i.quantity,
+                        values: i.values.clone(),
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            personalities: self.personalities.clone(),
+            skills: self.skills.clone(),
+            skill_mastery: self.skill_mastery.clone(),
+            strength_level: self.strength_level,
+            intelligence_level: self.intelligence_level,
+            potential: self.potential,
+            immunities: self.immunities.clone(),
+            weapon: self.weapon,
+            weapon_values: self.weapon_values.clone(),
+            equipment: self
+                .equipment
+                .iter()
+                .map(|identifier| get_item(identifier))
+                .collect::<anyhow::Result<_>>()?,
+            resistance: self.resistance,
+            vitality: self.vitality,
+            ether: self.ether,
+            drop: Reward {
+                orbs: self.drop.orbs,
+                xp: self.drop.xp,
+                items: self
+                    .drop
+                    .items
+                    .iter()
+                    .map(|i| {
+                        Ok(RewardItem {
+                            item: get_item(&i.identifier)?,
+                            amount: i.amount,
+                        })
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            },
+        })
+    }
+}
+
+/// A `BattleInput` that can be serialized. Skills and items are stored by their kinds and identifiers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReplayInput {
+    Nothing,
+    Reinput,
+    ChangeTarget(usize),
+    ChangeTeam(u8),
+    Attack,
+    Defend,
+    UseSkill(SkillKind),
+    GetUp,
+    Upkick,
+    UseItem(String),
+    UseAction(BattleAction),
+    Finish(Finisher),
+    Flee,
+    Taunt,
+}
+
+impl From<&BattleInput> for ReplayInput {
+    fn from(input: &BattleInput) -> Self {
+        match input {
+            BattleInput::Nothing => Self::Nothing,
+            BattleInput::Reinput => Self::Reinput,
+            BattleInput::ChangeTarget(index) => Self::ChangeTarget(index.0),
+            BattleInput::ChangeTeam(team) => Self::ChangeTeam(*team),
+            BattleInput::Attack => Self::Attack,
+            BattleInput::Defend => Self::Defend,
+            BattleInput::UseSkill(skill) => Self::UseSkill(skill.base_kind.clone()),
+            BattleInput::GetUp => Self::GetUp,
+            BattleInput::Upkick => Self::Upkick,
+            BattleInput::UseItem(item) => Self::UseItem(item.identifier.to_string()),
+            BattleInput::UseAction(action) => Self::UseAction(*action),
+            BattleInput::Finish(finisher) => Self::Finish(*finisher),
+            BattleInput::Flee => Self::Flee,
+            BattleInput::Taunt => Self::Taunt,
+        }
+    }
+}
+
+impl ReplayInput {
+    /// Converts it back to a `BattleInput`. The fighter is needed to find the skill instance that was used.
+    pub fn to_battle_input(&self, fighter: &Fighter) -> anyhow::Result<BattleInput> {
+        Ok(match self {
+            Self::Nothing => BattleInput::Nothing,
+            Self::Reinput => BattleInput::Reinput,
+            Self::ChangeTarget(index) => BattleInput::ChangeTarget(FighterIndex(*index)),
+            Self::ChangeTeam(team) => BattleInput::ChangeTeam(*team),
+            Self::Attack => BattleInput::Attack,
+            Self::Defend => BattleInput::Defend,
+            Self::UseSkill(kind) => BattleInput::UseSkill(
+                fighter
+                    .skills
+                    .iter()
+                    .find(|s| s.base_kind == *kind)
+                    .cloned()
+                    .with_context(|| format!("{} doesn't have the skill {kind:?}", fighter.name))?,
+            ),
+            Self::GetUp => BattleInput::GetUp,
+            Self::Upkick => BattleInput::Upkick,
+            Self::UseItem(identifier) => BattleInput::UseItem(
+                items::get_item(identifier)
+                    .with_context(|| format!("unknown item in replay: {identifier}"))?,
+            ),
+            Self::UseAction(action) => BattleInput::UseAction(*action),
+            Self::Finish(finisher) => BattleInput::Finish(*finisher),
+            Self::Flee => BattleInput::Flee,
+            Self::Taunt => BattleInput::Taunt,
+        })
+    }
+}
+
+/// Everything that came from outside the battle, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ReplayEntry {
+    Input { fighter: usize, input: ReplayInput },
+    Option(Option<String>),
+    Number(f64),
+    RiskLife(bool),
+    Intruder(Box<ReplayFighter>),
+}
+
+pub const REPLAYS_FOLDER: &str = "replays";
+
+/// Where replays are saved: the `REPLAYS_PATH` environment variable, or `REPLAYS_FOLDER`.
+pub fn replays_folder() -> PathBuf {
+    std::env::var("REPLAYS_PATH")
+        .unwrap_or_else(|_| REPLAYS_FOLDER.to_owned())
+        .into()
+}
+
+/// A battle that can be reproduced exactly: the seed of the battle RNG, the fighters it started with
+/// and every input that was given to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BattleReplay {
+    pub seed: u64,
+    pub region: WorldRegion,
+    pub settings: BattleSettings,
+    pub fighters: Vec<ReplayFighter>,
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl BattleReplay {
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Writes the replay to the path, creating its folder if needed.
+    pub async fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        if let Some(folder) = path.parent() {
+            tokio::fs::create_dir_all(folder).await?;
+        }
+
+        tokio::fs::write(path, self.to_json()?).await?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn create_battle(&self) -> anyhow::Result<Battle> {
+        let fighters = self
+            .fighters
+            .iter()
+            .map(|f| f.to_fighter_data())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let settings = BattleSettings {
+            seed: Some(self.seed),
+            ..self.settings.clone()
+        };
+
+        Ok(Battle::new(self.region, settings, fighters)?)
+    }
+
+    /// Creates an engine that plays the battle again, feeding it the recorded inputs.
+    pub fn create_engine(&self) -> anyhow::Result<BattleEngine> {
+        let battle = self.create_battle()?;
+        Ok(BattleEngine::new(
+            battle,
+            std::sync::Arc::new(tokio::sync::Mutex::new(ReplayIo::new(self.entries.clone()))),
+        ))
+    }
+}
+
+/// A `BattleIo` that answers with the entries of a `BattleReplay`.
+/// AI fighters still think by themselves: given the same seed, they pick the same inputs again.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayIo {
+    entries: Vec<ReplayEntry>,
+}
+
+impl ReplayIo {
+    pub fn new(entries: Vec<ReplayEntry>) -> Self {
+        Self { entries }
+    }
+
+    /// The engine records the same entries again while replaying, so they tell where the replay is.
+    fn position(engine: &BattleEngine) -> usize {
+        engine.replay_entries.len() + engine.pending_intruders.len()
+    }
+
+    /// Queues every intruder that comes next, so it joins when the next turn starts.
+    fn queue_intruders(&self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+        while let Some(ReplayEntry::Intruder(fighter)) = self.entries.get(Self::position(engine)) {
+            engine.join_intruder(fighter.to_fighter_data()?);
+        }
+
+        Ok(())
+    }
+
+    fn next_entry(&self, engine: &mut BattleEngine) -> anyhow::Result<ReplayEntry> {
+        self.queue_intruders(engine)?;
+        self.entries
+            .get(Self::position(engine))
+            .cloned()
+            .context("the replay has no more entries")
+    }
+}
+
+#[async_trait::async_trait]
+impl BattleIo for ReplayIo {
+    async fn select_input(&mut self, engine: &mut BattleEngine) -> anyhow::Result<BattleInput> {
+        match self.next_entry(engine)? {
+            ReplayEntry::Input { fighter, input } => {
+                input.to_battle_input(engine.battle.get_fighter(FighterIndex(fighter)))
+            }
+            entry => bail!("expected an input in the replay, found {entry:?}"),
+        }
+    }
+
+    async fn select_option(
+        &mut self,
+        engine: &mut BattleEngine,
+        _selection: IoSelection,
+    ) -> anyhow::Result<Option<String>> {
+        match self.next_entry(engine)? {
+            ReplayEntry::Option(option) => Ok(option),
+            entry => bail!("expected an option in the replay, found {entry:?}"),
+        }
+    }
+
+    async fn input_number(
+        &mut self,
+        engine: &mut BattleEngine,
+        _fighter: FighterIndex,
+        _prompt: String,
+        _valid_range: (f64, f64),
+    ) -> anyhow::Result<f64> {
+        match self.next_entry(engine)? {
+            ReplayEntry::Number(number) => Ok(number),
+            entry => bail!("expected a number in the replay, found {entry:?}"),
+        }
+    }
+
+    async fn confirm_risk_life(
+        &mut self,
+        engine: &mut BattleEngine,
+        _fighter: FighterIndex,
+    ) -> anyhow::Result<bool> {
+        match self.next_entry(engine)? {
+            ReplayEntry::RiskLife(confirmation) => Ok(confirmation),
+            entry => bail!("expected a risk life answer in the replay, found {entry:?}"),
+        }
+    }
+
+    async fn update_turn(&mut self, _engine: &mut BattleEngine) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn on_turn_end(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+        // Intruders are recorded when their turn starts, so they must be queued before it even if
+        // only AI fighters act next
+        self.queue_intruders(engine)
+    }
+}
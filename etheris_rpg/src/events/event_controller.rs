@@ -10,7 +10,7 @@ use etheris_data::{
     items::{get_item, Item, ItemTag},
     ItemValue,
 };
-use etheris_database::character_model::CharacterModel;
+use etheris_database::character_model::{CharacterModel, XpGain};
 use etheris_discord::{
     twilight_model::{channel::message::component::ButtonStyle, user::User},
     *,
@@ -19,7 +19,7 @@ use etheris_framework::{util::make_multiple_rows, watcher::WatcherOptions, *};
 use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
 use crate::{
-    data::enemies::Enemy, encounter, shop::Shop, Battle, BattleController, BattleSettings,
+    data::enemies::Enemy, encounter, party, shop::Shop, Battle, BattleController, BattleSettings,
     FighterData,
 };
 
@@ -366,7 +366,7 @@ impl EventController {
         &mut self,
         mut consequence: Consequence,
     ) -> anyhow::Result<()> {
-        let Some(mut character) = self
+        let Some(character) = self
             .ctx
             .db()
             .characters()
@@ -428,7 +428,7 @@ impl EventController {
 
                 let mut rng = StdRng::from_entropy();
                 let enemies = battle.enemies;
-                let enemies_fighter_data = enemies
+                let mut enemies_fighter_data = enemies
                     .iter()
                     .map(|e| {
                         FighterData::new_from_enemy(
@@ -470,12 +470,20 @@ impl EventController {
                     )
                     .await?
                 } else {
+                    let party = party::prepare_party_battle(
+                        &self.ctx,
+                        &character,
+                        &mut enemies_fighter_data,
+                    )
+                    .await?;
+
                     let mut fighters = vec![FighterData::new_from_character(
                         0,
                         &character,
                         self.user.clone(),
                         Default::default(),
                     )];
+                    fighters.extend_from_slice(&party);
                     fighters.extend_from_slice(&enemies_fighter_data);
                     fighters.extend_from_slice(&allies_fighter_data);
 
@@ -486,6 +494,8 @@ impl EventController {
                             has_consequences: true,
                             is_risking_life_allowed: true,
                             max_intruders: 2,
+                            seed: None,
+                            ranked: false,
                         },
                         fighters,
                     )?;
@@ -681,18 +691,26 @@ impl EventController {
                     );
                 }
 
-                character.add_orbs(orbs);
-                character.strength_xp += strength_xp as u32;
-                character.health_xp += health_xp as u32;
-                character.intelligence_xp += intelligence_xp as u32;
-                character.knowledge_xp += knowledge_xp as u32;
+                let characters = self.ctx.db().characters();
+                characters.add_orbs(character.id, orbs).await?;
+                characters
+                    .add_xp(
+                        character.id,
+                        XpGain {
+                            strength: strength_xp as u32,
+                            health: health_xp as u32,
+                            intelligence: intelligence_xp as u32,
+                            knowledge: knowledge_xp as u32,
+                        },
+                    )
+                    .await?;
 
                 for (item, amount) in items {
-                    character.add_item(*item, amount as usize, None);
+                    characters
+                        .add_item(character.id, *item, amount as usize, None)
+                        .await?;
                 }
 
-                self.ctx.db().characters().save(character).await?;
-
                 self.ctx
                     .send(Response::new_user_reply(&self.user, message).add_embed(embed))
                     .await?;
@@ -783,13 +801,17 @@ impl EventController {
                 let mut stealed_orbs = rng.gen_range(fixed_orbs.0..=fixed_orbs.1);
                 stealed_orbs += (character.orbs as f64 * orbs_percentage) as i64;
 
-                character.remove_orbs(stealed_orbs.clamp(0, character.orbs));
-                character.take_damage(damage);
-                for (item, amount) in &stealed_items {
-                    character.remove_item(*item, *amount);
-                }
-
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.remove_orbs(stealed_orbs.clamp(0, character.orbs));
+                        character.take_damage(damage);
+                        for (item, amount) in &stealed_items {
+                            character.remove_item(*item, *amount);
+                        }
+                    })
+                    .await?;
 
                 let mut embed = EmbedBuilder::new_common()
                     .set_color(Color::LIGHT_RED)
@@ -846,23 +868,33 @@ impl EventController {
                 shop.prompt(self.user.clone(), &mut self.ctx).await?;
             }
             ConsequenceKind::RemoveItemDurability(item, amount) => {
-                let Some(inventory_item) = character.get_inventory_item_mut(&item) else {
-                    return Ok(());
-                };
-
                 let mut broke = false;
-                for value in inventory_item.values.values.iter_mut() {
-                    if let ItemValue::Durability(durability) = value {
-                        *durability = durability.saturating_sub(amount);
-                        if *durability == 0 {
-                            broke = true;
-                            break;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        broke = false;
+                        let Some(inventory_item) = character.get_inventory_item_mut(&item) else {
+                            return;
+                        };
+
+                        for value in inventory_item.values.values.iter_mut() {
+                            if let ItemValue::Durability(durability) = value {
+                                *durability = durability.saturating_sub(amount);
+                                if *durability == 0 {
+                                    broke = true;
+                                    break;
+                                }
+                            }
                         }
-                    }
-                }
+
+                        if broke {
+                            character.remove_item(item, amount as usize);
+                        }
+                    })
+                    .await?;
 
                 if broke {
-                    character.remove_item(item, amount as usize);
                     self.ctx
                         .send_in_channel(
                             Response::new_user_reply(
@@ -873,42 +905,81 @@ impl EventController {
                         )
                         .await?;
                 }
-
-                self.ctx.db().characters().save(character).await?;
             }
             ConsequenceKind::RemoveItem(item, amount) => {
-                character.remove_item(item, amount);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.remove_item(item, amount);
+                    })
+                    .await?;
             }
             ConsequenceKind::AddActionPoint(amount) => {
-                character.action_points =
-                    (character.action_points + amount).min(character.max_action_points);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.action_points =
+                            (character.action_points + amount).min(character.max_action_points);
+                    })
+                    .await?;
             }
             ConsequenceKind::AddEther(ether) => {
-                character.stats.ether.value =
-                    (character.stats.ether.value + ether).min(character.stats.ether.max);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.stats.ether.value =
+                            (character.stats.ether.value + ether).min(character.stats.ether.max);
+                    })
+                    .await?;
             }
             ConsequenceKind::RemoveEther(ether) => {
-                character.stats.ether.value = character.stats.ether.value.saturating_sub(ether);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.stats.ether.value =
+                            character.stats.ether.value.saturating_sub(ether);
+                    })
+                    .await?;
             }
             ConsequenceKind::AddTag(tag) => {
-                character.insert_tag(tag);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.insert_tag(tag.clone());
+                    })
+                    .await?;
             }
             ConsequenceKind::RemoveTag(tag) => {
-                character.remove_tag(&tag);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.remove_tag(&tag);
+                    })
+                    .await?;
             }
             ConsequenceKind::AddKarma(amount) => {
-                character.add_karma(amount);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.add_karma(amount);
+                    })
+                    .await?;
             }
             ConsequenceKind::RemoveKarma(amount) => {
-                character.remove_karma(amount);
-                self.ctx.db().characters().save(character).await?;
+                self.ctx
+                    .db()
+                    .characters()
+                    .update(character.id, |character| {
+                        character.remove_karma(amount);
+                    })
+                    .await?;
             }
         }
 
@@ -968,6 +1039,7 @@ impl EventController {
             };
 
             let mut fighters = enemies;
+            let party = party::prepare_party_battle(&self.ctx, &character, &mut fighters).await?;
             fighters.insert(
                 0,
                 FighterData::new_from_character(
@@ -977,6 +1049,9 @@ impl EventController {
                     Default::default(),
                 ),
             );
+            for (i, member) in party.into_iter().enumerate() {
+                fighters.insert(i + 1, member);
+            }
 
             let battle = Battle::new(
                 character.region,
@@ -985,6 +1060,8 @@ impl EventController {
                     has_consequences: true,
                     is_risking_life_allowed: true,
                     max_intruders: 2,
+                    seed: None,
+                    ranked: false,
                 },
                 fighters,
             )?;
@@ -992,7 +1069,7 @@ impl EventController {
             let mut controller = BattleController::new(battle, self.ctx.clone());
             controller.run().await?;
 
-            self.ctx = controller.ctx;
+            self.ctx = controller.ctx().await;
         }
 
         Ok(())
@@ -3,11 +3,13 @@ pub mod combat;
 pub mod common;
 pub mod controller;
 pub mod data;
+pub mod engine;
 pub mod events;
 pub mod skills;
 mod util;
 
 pub use combat::*;
 pub use controller::*;
+pub use engine::*;
 pub use skills::*;
 pub use util::*;
@@ -0,0 +1,129 @@
+use etheris_data::SkillKind;
+
+use crate::{common::DamageKind, EffectKind};
+
+/// Extra damage dealt to the target when a combo is triggered: `base` plus a ratio of its max health.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboDamage {
+    pub kind: DamageKind,
+    pub base: i32,
+    pub max_health_ratio: f32,
+    pub balance_effectiveness: u8,
+}
+
+/// A sequence of skills that, used by the same fighter in consecutive turns, triggers a bonus.
+/// Messages can use `{name}`, `{target}` and `{dmg}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillCombo {
+    pub identifier: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub sequence: &'static [SkillKind],
+    /// The target must have this effect for the combo to happen. The combo consumes it.
+    pub required_target_effect: Option<EffectKind>,
+    pub damage: Option<ComboDamage>,
+    pub effect: Option<(EffectKind, i32)>,
+    pub message: &'static str,
+}
+
+impl SkillCombo {
+    pub fn contains_skill(&self, skill: &SkillKind) -> bool {
+        self.sequence.contains(skill)
+    }
+
+    /// Checks if the combo ends with `skill`, with `previous_skills` being the skills used by the
+    /// fighter in its last turns, from the oldest to the newest.
+    pub fn is_completed_by(&self, previous_skills: &[SkillKind], skill: &SkillKind) -> bool {
+        let Some((last, rest)) = self.sequence.split_last() else {
+            return false;
+        };
+
+        if last != skill || previous_skills.len() < rest.len() {
+            return false;
+        }
+
+        previous_skills[previous_skills.len() - rest.len()..]
+            .iter()
+            .zip(rest)
+            .all(|(used, expected)| used == expected)
+    }
+}
+
+pub const SKILL_COMBOS: &[SkillCombo] = &[
+    SkillCombo {
+        identifier: "shatter",
+        name: "Estilhaçar",
+        description: "Um terremoto logo após um sopro gelado estilhaça o gelo de um alvo congelado.",
+        sequence: &[SkillKind::IcyBreath, SkillKind::Earthquake],
+        required_target_effect: Some(EffectKind::Frozen),
+        damage: Some(ComboDamage {
+            kind: DamageKind::Physical,
+            base: 20,
+            max_health_ratio: 0.12,
+            balance_effectiveness: 40,
+        }),
+        effect: Some((EffectKind::Bleeding, 30)),
+        message: "**COMBO!** O gelo de **{target}** se estilhaçou com o tremor de **{name}** e causou **{dmg}**!",
+    },
+    SkillCombo {
+        identifier: "chain_shock",
+        name: "Choque em Cadeia",
+        description: "Um tapa elétrico logo após um jato de água conduz a eletricidade por todo o corpo do alvo.",
+        sequence: &[SkillKind::WaterJet, SkillKind::ElectricSlap],
+        required_target_effect: None,
+        damage: Some(ComboDamage {
+            kind: DamageKind::Special,
+            base: 8,
+            max_health_ratio: 0.05,
+            balance_effectiveness: 15,
+        }),
+        effect: Some((EffectKind::Shocked, 60)),
+        message: "**COMBO!** A eletricidade de **{name}** correu pela água no corpo de **{target}** e causou **{dmg}**!",
+    },
+    SkillCombo {
+        identifier: "steam_explosion",
+        name: "Explosão de Vapor",
+        description: "Uma bola flamejante logo após um disparo gélido transforma o gelo do alvo em vapor explosivo.",
+        sequence: &[SkillKind::IcyShot, SkillKind::FlamingBall],
+        required_target_effect: Some(EffectKind::Ice),
+        damage: Some(ComboDamage {
+            kind: DamageKind::Fire,
+            base: 12,
+            max_health_ratio: 0.06,
+            balance_effectiveness: 25,
+        }),
+        effect: Some((EffectKind::Wet, 40)),
+        message: "**COMBO!** O gelo em **{target}** virou vapor com o fogo de **{name}** e explodiu, causando **{dmg}**!",
+    },
+];
+
+pub fn format_combo_message(combo: &SkillCombo, name: &str, target: &str, damage: &str) -> String {
+    combo
+        .message
+        .replace("{name}", name)
+        .replace("{target}", target)
+        .replace("{dmg}", damage)
+}
+
+pub fn get_skill_combo(identifier: &str) -> Option<&'static SkillCombo> {
+    SKILL_COMBOS.iter().find(|c| c.identifier == identifier)
+}
+
+pub fn skill_combos_with(skill: &SkillKind) -> impl Iterator<Item = &'static SkillCombo> + '_ {
+    SKILL_COMBOS.iter().filter(move |c| c.contains_skill(skill))
+}
+
+#[test]
+fn combos_are_completed_by_their_last_skill() {
+    let combo = get_skill_combo("chain_shock").unwrap();
+
+    assert!(combo.is_completed_by(
+        &[SkillKind::ImbuedPunch, SkillKind::WaterJet],
+        &SkillKind::ElectricSlap
+    ));
+    assert!(!combo.is_completed_by(
+        &[SkillKind::WaterJet, SkillKind::ImbuedPunch],
+        &SkillKind::ElectricSlap
+    ));
+    assert!(!combo.is_completed_by(&[], &SkillKind::ElectricSlap));
+}
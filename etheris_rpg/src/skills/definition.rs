@@ -0,0 +1,232 @@
+use std::{path::Path, sync::OnceLock};
+
+use anyhow::{bail, Context};
+use etheris_data::{register_custom_skills, CustomSkillInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::{common::DamageKind, EffectKind, ModKind};
+
+use super::{SkillComplexity, SkillLimits};
+
+/// Damage dealt by a skill: a roll of `base` plus a roll of `scaling` multiplied by the fighter's
+/// strength and intelligence, weighted by `strength_weight` and `intelligence_weight`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DamageFormula {
+    pub kind: DamageKind,
+    pub base: (i32, i32),
+    pub scaling: (i32, i32),
+    pub strength_weight: f32,
+    pub intelligence_weight: f32,
+    #[serde(default = "default_accuracy")]
+    pub accuracy: u8,
+    #[serde(default)]
+    pub balance_effectiveness: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SkillTarget {
+    #[default]
+    Target,
+    User,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectDefinition {
+    pub kind: EffectKind,
+    pub amount: i32,
+    #[serde(default)]
+    pub target: SkillTarget,
+    /// The probability of the effect being applied.
+    #[serde(default = "default_chance")]
+    pub chance: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModifierDefinition {
+    pub kind: ModKind,
+    #[serde(default)]
+    pub target: SkillTarget,
+    pub turns: Option<u32>,
+}
+
+/// Message templates. They can use `{name}`, `{target}` and `{dmg}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillMessages {
+    pub hit: Vec<String>,
+    #[serde(default)]
+    pub miss: Vec<String>,
+}
+
+/// A simple skill described in a file instead of code.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillDefinition {
+    #[serde(flatten)]
+    pub info: CustomSkillInfo,
+    pub name: String,
+    pub description: String,
+    pub explanation: String,
+    pub complexity: SkillComplexity,
+    #[serde(default)]
+    pub limits: SkillLimits,
+    pub ether_cost: i32,
+    /// The chance of the AI picking the skill when it can use it.
+    #[serde(default = "default_ai_chance")]
+    pub ai_chance: u8,
+    pub damage: Option<DamageFormula>,
+    #[serde(default)]
+    pub effects: Vec<EffectDefinition>,
+    #[serde(default)]
+    pub modifiers: Vec<ModifierDefinition>,
+    pub messages: SkillMessages,
+}
+
+const fn default_accuracy() -> u8 {
+    80
+}
+
+const fn default_chance() -> u8 {
+    100
+}
+
+const fn default_ai_chance() -> u8 {
+    50
+}
+
+impl SkillDefinition {
+    pub fn identifier(&self) -> &str {
+        &self.info.identifier
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.info.identifier.is_empty() {
+            bail!("skill without identifier");
+        }
+
+        if self.messages.hit.is_empty() {
+            bail!("skill {} has no messages", self.identifier());
+        }
+
+        if let Some(damage) = &self.damage {
+            if damage.base.0 > damage.base.1 || damage.scaling.0 > damage.scaling.1 {
+                bail!("skill {} has an invalid damage range", self.identifier());
+            }
+
+            if damage.strength_weight + damage.intelligence_weight <= 0.0 {
+                bail!("skill {} has no damage weights", self.identifier());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+static SKILL_DEFINITIONS: OnceLock<Vec<SkillDefinition>> = OnceLock::new();
+
+fn read_skill_definition(path: &Path) -> anyhow::Result<SkillDefinition> {
+    let content = std::fs::read_to_string(path)?;
+    let definition: SkillDefinition = serde_json::from_str(&content)
+        .with_context(|| format!("invalid skill definition in {}", path.display()))?;
+    definition.validate()?;
+
+    Ok(definition)
+}
+
+/// Reads every `.json` file of the directory as a skill definition. A missing directory has no skills.
+/// Files that can't be read or aren't valid definitions are logged and skipped; only failing to read
+/// the directory itself is an error.
+pub fn read_skill_definitions(directory: impl AsRef<Path>) -> anyhow::Result<Vec<SkillDefinition>> {
+    let directory = directory.as_ref();
+    if !directory.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = std::fs::read_dir(directory)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|e| e == "json"));
+    paths.sort();
+
+    let mut definitions: Vec<SkillDefinition> = vec![];
+    for path in paths {
+        // A broken file only costs its own skill, never the others
+        let definition = match read_skill_definition(&path) {
+            Ok(definition) => definition,
+            Err(e) => {
+                eprintln!("skipping skill definition {}: {e:?}", path.display());
+                continue;
+            }
+        };
+
+        if definitions
+            .iter()
+            .any(|d| d.identifier() == definition.identifier())
+        {
+            eprintln!(
+                "skipping skill definition {}: duplicated skill identifier {}",
+                path.display(),
+                definition.identifier()
+            );
+            continue;
+        }
+
+        definitions.push(definition);
+    }
+
+    Ok(definitions)
+}
+
+/// Loads the skill definitions of the directory, making them available to the whole game.
+/// Must be called once, before any battle. Returns how many skills were loaded.
+pub fn load_skill_definitions(directory: impl AsRef<Path>) -> anyhow::Result<usize> {
+    let definitions = read_skill_definitions(directory)?;
+    let count = definitions.len();
+
+    register_custom_skills(definitions.iter().map(|d| d.info.clone()).collect());
+    if SKILL_DEFINITIONS.set(definitions).is_err() {
+        bail!("skill definitions were already loaded");
+    }
+
+    Ok(count)
+}
+
+pub fn skill_definitions() -> &'static [SkillDefinition] {
+    SKILL_DEFINITIONS
+        .get()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+pub fn get_skill_definition(identifier: &str) -> Option<&'static SkillDefinition> {
+    skill_definitions()
+        .iter()
+        .find(|d| d.identifier() == identifier)
+}
+
+#[test]
+fn bundled_skill_definitions_are_valid() {
+    let definitions =
+        read_skill_definitions(concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/skills")).unwrap();
+    assert!(!definitions.is_empty());
+}
+
+#[test]
+fn invalid_skill_definitions_are_skipped() {
+    let directory = std::env::temp_dir().join(format!("etheris_skills_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::copy(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/skills/ether_palm.json"
+        ),
+        directory.join("ether_palm.json"),
+    )
+    .unwrap();
+    std::fs::write(directory.join("broken.json"), "{ \"info\": ").unwrap();
+
+    let definitions = read_skill_definitions(&directory);
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    let definitions = definitions.unwrap();
+    assert_eq!(definitions.len(), 1);
+    assert_eq!(definitions[0].identifier(), "ether_palm");
+}
@@ -21,6 +21,7 @@ impl Skill for AtomicHollow {
             description: "Custa metade do ether máximo, e lança uma bola de ether condensado que aplica diversos efeitos e causa dano no alvo.",
             explanation: "Materializar uma bola de ether condensado com diversos efeitos requer massiva energia e controle do ether, além de ser perigoso de manusear, então requer um cuidado extra.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::cooldown(2),
             use_cost: SkillCost { ether: cost },
         }
     }
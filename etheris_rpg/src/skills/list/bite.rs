@@ -16,6 +16,7 @@ impl Skill for Bite {
             description: "Uma simples mordida com força no corpo do inimigo, mas que suga um pouco de ether.",
             explanation: "Dependendo da profundidade da mordida, é possível surrupiar um pouco do fluxo de ether do alvo, mas ether de terceiros é perigoso então apenas pequenas quantidades podem ser absorvidas.",
             complexity: SkillComplexity::VerySimple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 5 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for BloodDonation {
             description: "Doa seu sangue para um aliado, para poder curar um pouco seus ferimentos.",
             explanation: "Por mais simples que pareça, requer extremo controle do ether para manter a vitalidade no seu sangue doado e impedir que seu ether danifique o aliado. De resto basta beber vitalidade líquida.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 25 },
         }
     }
@@ -36,7 +37,8 @@ impl Skill for BloodDonation {
 
         let hp = ((fighter.health().value as f32) * 0.3) as i32;
 
-        api.fighter_mut().take_damage(
+        api.battle_mut().damage_fighter(
+            fighter.index,
             fighter.index, 
             DamageSpecifier { kind: DamageKind::Special, amount: hp, balance_effectiveness: 0, accuracy: 100, effect: None, culprit: fighter.index }
         );
@@ -16,6 +16,7 @@ impl Skill for BloodSpear {
             description: "Materializa uma lança feita do seu sangue e atira no seu inimigo, transferindo parte do seu sangramento para ele.",
             explanation: "A materialização através do ether é extremamente complexa, no entanto, a Lança de Sangue utiliza o próprio sangue como matéria para criar o objeto, sendo assim não muito difícil.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 30 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for BloodTheft {
             description: "Encosta sua mão no inimigo e rouba sua força vital para restaurar vida. Se o alvo estiver sangrando, você se cura mais.",
             explanation: "Habilidades de cura que surgem a partir do nada são extremamente complexas, por isso essa habilidade utiliza do sangue para obter energia vital. Quanto mais sangue, mais seu ether consegue roubar energia vital para si mesmo.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 25 },
         }
     }
@@ -18,6 +18,7 @@ impl Skill for Charge {
             description: "Concentra e carrega uma poderosa investida para lançar com uma grande potência.",
             explanation: "Utilizar ether para fortalecer alguns músculos e estabilizar o equilíbrio do corpo é a preparação perfeita para uma investida de alta potência.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: if self.charged { 5 } else { 10 } },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for CursedBlood {
             description: "Usando seu sangue ou do seu alvo, aplica uma maldição no seu alvo que o faz tomar o dano que causa aos outros. Requer que você ou o alvo estejam sangrando.",
             explanation: "Não se sabe quem criou a Maldição do Karma: *Soridu'Karmi*, mas ela é uma poderosa maldição que faz com que todo o dano que você causa para alguém volte em parte para você. A habilidade do Sangue Amaldiçoado usa ether para aplicar a maldição no alvo, sendo assim uma habilidade complexa e poderosa.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 45 },
         }
     }
@@ -0,0 +1,175 @@
+use etheris_data::CustomSkillInfo;
+use once_cell::sync::Lazy;
+use rand::seq::SliceRandom;
+
+use super::prelude::*;
+
+/// Used when a character has a skill whose definition file doesn't exist anymore.
+static UNKNOWN_SKILL: Lazy<SkillDefinition> = Lazy::new(|| SkillDefinition {
+    info: CustomSkillInfo {
+        identifier: "unknown".to_string(),
+        intelligence_requirement: u32::MAX,
+        knowledge_cost: 1,
+        personalities: vec![],
+    },
+    name: "Habilidade Desconhecida".to_string(),
+    description: "Uma habilidade que foi esquecida.".to_string(),
+    explanation: "Ninguém mais sabe como usar esta habilidade.".to_string(),
+    complexity: SkillComplexity::VerySimple,
+    limits: SkillLimits::NONE,
+    ether_cost: 0,
+    ai_chance: 0,
+    damage: None,
+    effects: vec![],
+    modifiers: vec![],
+    messages: SkillMessages {
+        hit: vec![],
+        miss: vec![],
+    },
+});
+
+/// A skill loaded from a `SkillDefinition`.
+#[derive(Debug, Clone)]
+pub struct CustomSkill {
+    identifier: String,
+    definition: &'static SkillDefinition,
+}
+
+impl CustomSkill {
+    pub fn new(identifier: String) -> Self {
+        Self {
+            definition: get_skill_definition(&identifier).unwrap_or(&UNKNOWN_SKILL),
+            identifier,
+        }
+    }
+
+    fn format_message(message: &str, fighter: &Fighter, target: &Fighter, damage: &str) -> String {
+        message
+            .replace("{name}", &fighter.name)
+            .replace("{target}", &target.name)
+            .replace("{dmg}", damage)
+    }
+}
+
+#[async_trait::async_trait]
+impl Skill for CustomSkill {
+    fn kind(&self) -> SkillKind {
+        SkillKind::Custom(self.identifier.clone())
+    }
+
+    fn data(&self, _fighter: &Fighter) -> SkillData {
+        SkillData {
+            identifier: self.definition.identifier(),
+            name: &self.definition.name,
+            description: &self.definition.description,
+            explanation: &self.definition.explanation,
+            complexity: self.definition.complexity,
+            limits: self.definition.limits,
+            use_cost: SkillCost {
+                ether: self.definition.ether_cost,
+            },
+        }
+    }
+
+    fn can_use(&self, api: BattleApi<'_>) -> bool {
+        !self.definition.messages.hit.is_empty() && self.default_can_use(api)
+    }
+
+    fn ai_chance_to_pick(&self, api: BattleApi<'_>) -> Probability {
+        if self.can_use(api) {
+            Probability::new(self.definition.ai_chance)
+        } else {
+            Probability::NEVER
+        }
+    }
+
+    async fn on_use(&mut self, mut api: BattleApi<'_>) -> SkillResult<()> {
+        let fighter = api.fighter().clone();
+        let target = api.target().clone();
+        let definition = self.definition;
+
+        let mut damage_text = String::new();
+        if let Some(formula) = &definition.damage {
+            let base_damage = api.rng().gen_range(formula.base.0..=formula.base.1);
+            let damage = api.rng().gen_range(formula.scaling.0..=formula.scaling.1);
+
+            let multiplier =
+                fighter.mixed_multiplier(formula.strength_weight, formula.intelligence_weight);
+            let damage = base_damage + ((damage as f32) * multiplier) as i32;
+
+            let damage = api
+                .apply_damage(
+                    target.index,
+                    DamageSpecifier {
+                        culprit: fighter.index,
+                        kind: formula.kind,
+                        amount: damage,
+                        balance_effectiveness: formula.balance_effectiveness,
+                        accuracy: formula.accuracy,
+                        effect: None,
+                    },
+                )
+                .await;
+
+            if damage.missed {
+                let message = definition
+                    .messages
+                    .miss
+                    .choose(api.rng())
+                    .map(|m| Self::format_message(m, &fighter, &target, &damage.to_string()))
+                    .unwrap_or_else(|| {
+                        format!(
+                            "**{}** tentou usar **{}** em **{}** mas errou!",
+                            fighter.name, definition.name, target.name
+                        )
+                    });
+                api.emit_message(message);
+                return Ok(());
+            }
+
+            damage_text = damage.to_string();
+        }
+
+        for effect in definition.effects.iter() {
+            if !Probability::new(effect.chance).generate_random_bool_with(api.rng()) {
+                continue;
+            }
+
+            let index = match effect.target {
+                SkillTarget::Target => target.index,
+                SkillTarget::User => fighter.index,
+            };
+            api.apply_effect(
+                index,
+                Effect::new(effect.kind, effect.amount, fighter.index),
+            )
+            .await;
+        }
+
+        for modifier in definition.modifiers.iter() {
+            let fighter = match modifier.target {
+                SkillTarget::Target => api.target_mut(),
+                SkillTarget::User => api.fighter_mut(),
+            };
+            fighter.modifiers.add(
+                Modifier::new(modifier.kind.clone(), modifier.turns)
+                    .with_tag(definition.identifier()),
+            );
+        }
+
+        let message = definition
+            .messages
+            .hit
+            .choose(api.rng())
+            .cloned()
+            .unwrap_or_default();
+        api.emit_message(Self::format_message(
+            &message,
+            &fighter,
+            &target,
+            &damage_text,
+        ));
+
+        Ok(())
+    }
+}
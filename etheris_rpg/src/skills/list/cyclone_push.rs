@@ -16,6 +16,7 @@ impl Skill for CyclonePush {
             description: "Lança um forte vento para empurrar o seu inimigo.",
             explanation: "Espalha seu ether pelo vento ao seu redor e então basta controlar o ether espalhado para mover o ar com velocidade e força até seu inimigo.",
             complexity: SkillComplexity::VerySimple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 6 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for Debug {
             description: "Debug.",
             explanation: "Debug skill",
             complexity: SkillComplexity::SuperMaster,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 1 },
         }
     }
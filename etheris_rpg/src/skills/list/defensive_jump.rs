@@ -16,6 +16,7 @@ impl Skill for DefensiveJump {
             description: "Efetua um alto pulo de 3-4m enquanto defende seu corpo. Ficar no ar aumenta levemente seu dano e precisão!",
             explanation: "É um simples pulo defensivo. Não é uma habilidade especial, mas é um movimento estratégico inteligente.",
             complexity: SkillComplexity::VerySimple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 5 },
         }
     }
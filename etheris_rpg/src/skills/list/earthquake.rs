@@ -16,6 +16,7 @@ impl Skill for Earthquake {
             description: "Emite ether pelos seus pés até o chão, tremendo toda a arena e dando dano em todos os inimigos. O dano em aliados é menor.",
             explanation: "Requer uma quantia alta de ether destrutivo sendo emitido pelo seu pé até o fundo do chão da arena. O controle preciso do ether é a chave aqui.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::cooldown(2),
             use_cost: SkillCost { ether: 30 },
         }
     }
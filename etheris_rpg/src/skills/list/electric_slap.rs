@@ -16,6 +16,7 @@ impl Skill for ElectricSlap {
             description: "Acumula eletricidade na mão e dá um tapa com eletricidade concentrada.",
             explanation: "Eletricidade estática é a forma mais simples de conseguir eletricidade, depois basta aumentar com ether e você tem eletricidade de sobra para dar um poderoso tapa no seu inimigo.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 15 },
         }
     }
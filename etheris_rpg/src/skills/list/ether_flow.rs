@@ -19,6 +19,7 @@ impl Skill for EtherFlow {
             description: "Aumenta temporariamente a regeneração de ether, mas reduz ligeiramente o dano causado.",
             explanation: "Ao alterar o fluxo de ether no corpo, é possível acelerar sua regeneração às custas de potência ofensiva.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 20 },
         }
     }
@@ -5,9 +5,7 @@ use self::brain::BrainKind;
 use super::prelude::*;
 
 #[derive(Debug, Clone, Default)]
-pub struct EtherShadow {
-    used: bool
-}
+pub struct EtherShadow;
 
 #[async_trait::async_trait]
 impl Skill for EtherShadow {
@@ -22,18 +20,13 @@ impl Skill for EtherShadow {
             description: "Invoca uma sombra sua feita de ether para ajudar na batlha. Um uso por batalha.",
             explanation: "Habilidade de materialização simples, mas requer entendimento profundo do seu próprio ether para invocar uma sombra sua.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::ONCE_PER_BATTLE,
             use_cost: SkillCost { ether: 60 },
         }
     }
 
-    
-    fn can_use(&self, api: BattleApi<'_>) -> bool {
-        !self.used && self.default_can_use(api)
-    }
 
     async fn on_use(&mut self, mut api: BattleApi<'_>) -> SkillResult<()> {
-        self.used = true;
-
         let fighter = api.fighter().clone();
 
         let mut skills = vec![];
@@ -61,9 +54,12 @@ impl Skill for EtherShadow {
             personalities: fighter.personalities.clone(), 
             potential: fighter.power,
             skills, 
+            skill_mastery: fighter.skill_mastery.clone(),
             strength_level: 1 + (fighter.strength_level as f32 * 0.3) as u32, 
             intelligence_level: 1 + (fighter.intelligence_level as f32 * 0.3) as u32, 
             weapon: fighter.weapon.map(|w| w.kind), 
+            weapon_values: fighter.weapon.map(|w| w.values()).unwrap_or_default(),
+            equipment: vec![],
             resistance: Attribute::from(resistance), 
             vitality: Attribute::from(vitality),
             ether: Attribute::from(ether), 
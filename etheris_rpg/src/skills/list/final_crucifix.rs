@@ -16,6 +16,7 @@ impl Skill for FinalCrucifix {
             description: "Um ataque desesperado e final: sacrifica parte da sua vida para liberar uma explosão GIGANTE em forma de crucifixo. Dano em área. Depois de usar, seu ether não irá mais regenerar.",
             explanation: "Apesar de não ser muito complicado, pois é apenas uma explosão gigante em forma de crucifixo, utilizar ether em MASSIVAS quantidades não é para iniciantes. Essa habilidade ainda sacrifica sua regeneração natural de ether por horas, que pode ser fatal se você não descansar logo após invocar o Crucifixo Final.",
             complexity: SkillComplexity::VeryHard,
+            limits: SkillLimits::ONCE_PER_BATTLE,
             use_cost: SkillCost { ether: 100 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for FirePunch {
             description: "Imbui sua mão em fogo e desfere um poderoso soco em seu alvo.",
             explanation: "Requer materializar chamas na sua mão, o que fere a si mesmo mas aumenta grandemente o poder de ataque. ",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 25 },
         }
     }
@@ -26,7 +27,12 @@ impl Skill for FirePunch {
 
         let base_damage = api.rng().gen_range(10..=25);
         let damage = api.rng().gen_range(20..=30);
-        let self_damage = api.rng().gen_range(3..=5);
+        // Fighters that mastered the skill learned to protect their hands from the fire
+        let self_damage = if fighter.skill_mastery_level(&self.kind()) >= 3 {
+            api.rng().gen_range(1..=3)
+        } else {
+            api.rng().gen_range(3..=5)
+        };
 
         let multiplier = fighter.mixed_multiplier(0.7, 0.4);
         let damage = base_damage + ((damage as f32) * multiplier) as i32;
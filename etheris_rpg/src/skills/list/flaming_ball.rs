@@ -16,6 +16,7 @@ impl Skill for FlamingBall {
             description: "Lança uma bola flamejante com alta velocidade no seu inimigo.",
             explanation: "Emitir ether em uma esfera semi-perfeita ao redor de sua mão e aplicar um feitiço de alteração para queimar o ether cria uma bola de fogo em suas mãos. Em seguida basta lançar uma rápida rajada de vento pela mão pra empurrar a bola flamejante até o alvo.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 50 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for Hakikotenchou {
             description: "Se congela e protege por 3 turnos para tirar a sobrecarga de ether do seu corpo. Durante os 3 turnos você perde a capacidade de agir.",
             explanation: "<HABILIDADE LENDÁRIA. EXPLICAÇÃO FALTANDO>",
             complexity: SkillComplexity::BeginnerMaster,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 80 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for IcyBreath {
             description: "Sopra uma quantia alta de vento congelante no seu inimigo, tirando o equilíbrio e congelando.",
             explanation: "Essa habilidade se baseia em armazenar muito ar no pulmão e liberar tudo enquanto usa ether para aumentar a densidade do ar e reduzir a temperatura em níveis insanos.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 12 },
         }
     }
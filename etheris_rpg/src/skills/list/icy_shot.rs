@@ -16,6 +16,7 @@ impl Skill for IcyShot {
             description: "Dispara uma bala de gelo afiada no seu alvo.",
             explanation: "Precisão com ether é algo complexo, por isso materializar uma bala afiada de ether e moldar o ether em gelo requer grande concentração e energia, principalmente na hora de lançar a bala com alta velocidade e precisão.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 30 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for ImbuedPunch {
             description: "Ataca com um soco imbuído de ether, com impacto e força maior.",
             explanation: "Concentrar ether nos punhos para fortalecer o golpe é um dos ataques mais simples, porém eficaz.",
             complexity: SkillComplexity::VerySimple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 10 },
         }
     }
@@ -24,7 +25,9 @@ impl Skill for ImbuedPunch {
         let fighter = api.fighter().clone();
         let target = api.target().clone();
 
-        let critical = Probability::new(5).generate_random_bool();
+        // Mastering the skill makes critical punches more common
+        let mastery = fighter.skill_mastery_level(&self.kind());
+        let critical = Probability::new(5 + 2 * (mastery as u8 - 1)).generate_random_bool_with(api.rng());
 
         let base_damage = api.rng().gen_range(3..=7); 
         let damage = api.rng().gen_range(if critical { 25..=33 } else { 15..=22 });
@@ -13,57 +13,47 @@ impl Skill for InstinctiveReaction {
         SkillData {
             identifier: "instinctive_reaction",
             name: "Reação Instintiva",
-            description: "Habilidade passiva. Quando alguém errar um ataque em você, tem 80% de chance de instintivamente dar um ataque de volta.",
+            description: "Prepara o corpo para reagir até seu próximo turno. Quando alguém te atacar fisicamente, tem 80% de chance de instintivamente dar um ataque de volta.",
             explanation: "Essa habilidade requer extrema concentração e controle do ether. Se baseia em ter ether fluindo no corpo esperando a oportunidade de atacar antes mesmo do cérebro mandar o ataque.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 10 },
         }
     }
 
-    fn can_use(&self, _api: BattleApi<'_>) -> bool {
-        false
+    fn reaction(&self, fighter: &Fighter) -> Option<Reaction> {
+        let damage = 7 + (14.0 * fighter.mixed_multiplier(0.9, 0.1)) as i32;
+
+        Some(
+            Reaction::new(
+                self.data(fighter).name,
+                ReactionKind::Counter {
+                    kind: DamageKind::Physical,
+                    amount: damage,
+                },
+                Reaction::PHYSICAL_TRIGGERS,
+            )
+            .with_chance(80),
+        )
     }
 
-    async fn passive_on_damage_miss(&mut self, mut api: BattleApi<'_>, _damage: DamageSpecifier) -> SkillResult<()> {
-        if api.fighter_index == api.target_index {
-            return Ok(());
+    fn ai_chance_to_pick(&self, api: BattleApi<'_>) -> Probability {
+        if self.can_use(api) {
+            Probability::new(30)
+        } else {
+            Probability::NEVER
         }
+    }
 
+    async fn on_use(&mut self, mut api: BattleApi<'_>) -> SkillResult<()> {
         let fighter = api.fighter().clone();
-        if fighter.ether.value < self.data(&fighter).use_cost.ether {
-            return Ok(());
-        }
-
-        if Probability::new(20).generate_random_bool() {
+        let Some(reaction) = self.reaction(&fighter) else {
             return Ok(());
-        }
-
-        api.fighter_mut().ether.value -= self.data(&fighter).use_cost.ether;
-
-        let base_damage = api.rng().gen_range(4..=10);
-        let damage = api.rng().gen_range(12..=16);
-        let damage = base_damage + (damage as f32 * api.fighter().mixed_multiplier(0.9, 0.1)) as i32;
-
-        let damage = api
-            .apply_damage(
-                api.target_index,
-                DamageSpecifier {
-                    culprit: fighter.index,
-                    amount: damage,
-                    kind: DamageKind::Physical,
-                    balance_effectiveness: 15,
-                    accuracy: 100,
-                    effect: None,
-                },
-            )
-            .await;
+        };
 
-        api.emit_message(format!("**{}** instintivamente atacou e deu **{damage}** em **{}**!", api.fighter().name, api.target().name));
-    
-        Ok(())
-    }
+        api.fighter_mut().prepare_reaction(reaction);
+        api.emit_message(format!("**{}** deixou o ether fluir pelo corpo e está pronto para reagir a qualquer ataque!", fighter.name));
 
-    async fn on_use(&mut self, mut _api: BattleApi<'_>) -> SkillResult<()> {
         Ok(())
     }
 }
@@ -16,6 +16,7 @@ impl Skill for Intimidation {
             description: "Emite uma aura ameaçadora, que faz todos da batalha focarem em você. Defende automaticamente após o uso.",
             explanation: "Habilidade simples, apenas emite ether descontroladamente para atrair atenção e se protege com ether depois.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 8 },
         }
     }
@@ -1,11 +1,7 @@
-use std::ops::Add;
-
 use super::prelude::*;
 
 #[derive(Debug, Clone, Default)]
-pub struct MirrorDamage {
-    pub accumulated_damage: i32
-}
+pub struct MirrorDamage;
 
 #[async_trait::async_trait]
 impl Skill for MirrorDamage {
@@ -14,93 +10,41 @@ impl Skill for MirrorDamage {
     }
 
     fn data(&self, _fighter: &Fighter) -> SkillData {
-        let ether_cost = match self.accumulated_damage {
-            0 => 0,
-            1..=10 => 5,
-            11..=30 => 10,
-            31..=50 => 20,
-            51..=80 => 35,
-            _ => 50
-        };
-
         SkillData {
             identifier: "mirror_damage",
             name: "Espelhar Dano",
-            description: "Acumula 60% de todo o dano físico recebido e então retorna de uma vez só. O dano acumulado não passa de 1000.",
+            description: "Prepara o ether para espelhar o próximo ataque físico recebido até seu próximo turno, retornando 60% do dano para quem atacou.",
             explanation: "A energia ether é tão versátil que com treinamento suficiente é possível armazenar impacto físico de forma segura no fluxo de ether do corpo, e então basta liberar esse ether com impacto acumulado no alvo e o dano se transfere.",
             complexity: SkillComplexity::Normal,
-            use_cost: SkillCost { ether: ether_cost },
+            limits: SkillLimits::NONE,
+            use_cost: SkillCost { ether: 15 },
         }
     }
 
-    fn can_use(&self, api: BattleApi<'_>) -> bool {
-        self.accumulated_damage > 0 && self.default_can_use(api)
+    fn reaction(&self, fighter: &Fighter) -> Option<Reaction> {
+        Some(Reaction::new(
+            self.data(fighter).name,
+            ReactionKind::Reflect { ratio: 0.6 },
+            Reaction::PHYSICAL_TRIGGERS,
+        ))
     }
 
-    fn display(&self, fighter: &Fighter) -> SkillDisplay {
-        let mut display = self.default_display(fighter);
-
-        display.sub_header.push_str(&format!("\n**Acumulado**: {} dano", self.accumulated_damage));
-
-        display
-    }
-
-    fn ai_chance_to_pick(&self, _api: BattleApi<'_>) -> Probability {
-        if self.accumulated_damage < 30 {
+    fn ai_chance_to_pick(&self, api: BattleApi<'_>) -> Probability {
+        if !self.can_use(api) {
             Probability::NEVER
-        } else if self.accumulated_damage < 500 {
-            Probability::new(30)
-        } else {
-            Probability::new(50)
-        }
-    }
-
-    async fn passive_fighter_tick(&mut self, mut api: BattleApi<'_>) -> SkillResult<()> {
-        if self.accumulated_damage > 0 && api.fighter().ether.value <= 0 {
-            api.emit_message(format!("***{}** ficou sem ether e perdeu o dano acumulado na habilidade **{}***", api.fighter().name, self.data(api.fighter()).name));
-            self.accumulated_damage = 0;
-        }
-
-        Ok(())
-    }
-
-    async fn passive_on_damage(&mut self, mut api: BattleApi<'_>, damage: DamageSpecifier) -> SkillResult<()> {
-        let damage = match damage.kind {
-            DamageKind::Physical | DamageKind::PhysicalCut => (damage.amount as f32 * 0.6) as i32,
-            DamageKind::SpecialPhysical => (damage.amount as f32 * 0.5) as i32,
-            _ => 0
-        };
-
-        if api.fighter().ether.value < 1 || damage < 1 || api.fighter().is_defeated {
-            return Ok(());
-        }
-
-        self.accumulated_damage = self.accumulated_damage.add(damage).min(1000);
-
-        if self.accumulated_damage == 1000 {
-            api.emit_message(format!("***{}** acumulou o dano máximo na habilidade **{}***", api.fighter().name, self.data(api.fighter()).name));
         } else {
-            api.emit_message(format!("***{}** acumulou **{damage} dano** na habilidade **{}***", api.fighter().name, self.data(api.fighter()).name));
+            Probability::new(25)
         }
-        
-        Ok(())
     }
 
     async fn on_use(&mut self, mut api: BattleApi<'_>) -> SkillResult<()> {
-        let damage = DamageSpecifier {
-            kind: DamageKind::Physical,
-            amount: self.accumulated_damage,
-            balance_effectiveness: if self.accumulated_damage < 50 { 5 } else { 10 },
-            accuracy: 95,
-            ..Default::default()
+        let fighter = api.fighter().clone();
+        let Some(reaction) = self.reaction(&fighter) else {
+            return Ok(());
         };
 
-        let damage = api.apply_damage(api.target_index, damage).await;
-        self.accumulated_damage = 0;
-
-        api.emit_message(
-            format!("**{}** refletiu **{damage}** em **{}**", api.fighter().name, api.target().name)
-        );
+        api.fighter_mut().prepare_reaction(reaction);
+        api.emit_message(format!("**{}** concentrou o ether no corpo para espelhar o próximo ataque físico!", fighter.name));
 
         Ok(())
     }
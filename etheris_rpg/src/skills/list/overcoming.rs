@@ -26,6 +26,7 @@ impl Skill for Overcoming {
             description: "Começa a batalha com seu poder pela metade, mas com o passar do tempo vai ficando mais e mais forte, até ficar mais forte que o normal.",
             explanation: "Usa a metaconsciência do ether: se impôr uma limitação te concede mais ether e poder com o tempo.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 0 },
         }
     }
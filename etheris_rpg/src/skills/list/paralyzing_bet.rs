@@ -18,6 +18,7 @@ impl Skill for ParalyzingBet {
             description: "55% de chance de paralisar o inimigo e 45% de chance de paralisar a si mesmo. A paralisia é de dois turnos.",
             explanation: "Baseada no conceito de metaconsciência do ether, essa habilidade expande o limite do que o ether do seu corpo pode fazer adicionando uma restrição com consequências ao uso dessa habilidade, assim permitindo o efeito de paralisar os músculos ser efetuado ao emitir uma forma de ether caótica e liderada pela aleatoriedade no corpo do afetado para prender os músculos por um tempo.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 30 },
         }
     }
@@ -34,7 +35,7 @@ impl Skill for ParalyzingBet {
         let fighter = api.fighter().clone();
         let target = api.target().clone();
 
-        let unlucky_index = if Probability::new(55).generate_random_bool() {
+        let unlucky_index = if Probability::new(55).generate_random_bool_with(api.rng()) {
             target.index
         } else {
             fighter.index
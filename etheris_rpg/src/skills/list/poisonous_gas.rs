@@ -16,6 +16,7 @@ impl Skill for PoisonousGas {
             description: "Emite um gás venenoso pelos poros do seu corpo que envenena seu alvo.",
             explanation: "Técnica de uma tribo isolada de assassinos da savana, o gás venenoso causa dano ao longo do tempo e pode acumular.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 25 },
         }
     }
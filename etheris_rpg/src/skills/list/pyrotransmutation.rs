@@ -19,6 +19,7 @@ impl Skill for Pyrotransmutation {
             description: "Transforma seu corpo em fogo, se tornando imune a fogo e aumentando seu dano em 50% por 4 rodadas.",
             explanation: "Uma habilidade poderosa e complexa. Moldando a propriedade física do seu corpo temporariamente, o corpo evolui por alguns minutos. Extremamente perigoso, mas poderoso.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 30 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for Refresh {
             description: "Reduz muito todos os efeitos negativos de um aliado e melhora seu equilíbrio.",
             explanation: "Habilidade de renegeração: a categoria mais difícil do controle de ether. Alguns efeitos como fogo só precisam de um ether que apague, mas outros efeitos como sangramento precisam de um ether que regenera o ferimento. Requer muito ether.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 15 },
         }
     }
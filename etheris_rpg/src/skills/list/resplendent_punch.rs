@@ -16,6 +16,7 @@ impl Skill for ResplendentPunch {
             description: "Ataca com um soco imbuído de muito ether, mas com 40% de chance de acertar. Se acertar, o inimigo não poderá regenerar ether por 4 turnos e você aumenta o seu próprio dano.",
             explanation: "Utiliza o mesmo princípio do Soco Imbuído, mas é intensificiado pela metaconsciência do ether por ter uma condição de uso (baixa chance de acerto), tornando o ether mais poderoso.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 40 },
         }
     }
@@ -24,7 +25,7 @@ impl Skill for ResplendentPunch {
         let fighter = api.fighter().clone();
         let target = api.target().clone();
 
-        let critical = Probability::new(10).generate_random_bool();
+        let critical = Probability::new(10).generate_random_bool_with(api.rng());
 
         let base_damage = api.rng().gen_range(5..=15); 
         let damage = base_damage + api.rng().gen_range(if critical { 30..=40 } else { 25..=30 });
@@ -16,6 +16,7 @@ impl Skill for SimpleCut {
             description: "Dispara uma rajada de ether afiada e pequena para efetuar cortes superficiais",
             explanation: "Existem duas formas de fazer cortes utilizando ether: Um corte real, onde o ether é usado para romper o objeto-alvo e causar um corte profundo, e um corte simples, que apenas lança ether extremamente fino para cortar. Essa habilidade faz um corte simples afiando e lançando uma rajada de ar imbuída com ether.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 8 },
         }
     }
@@ -1,7 +1,7 @@
-use std::{fmt::Debug, mem::discriminant};
+use std::fmt::Debug;
 
 use etheris_common::{Color, Identifiable};
-use etheris_discord::{EmbedAuthor, EmbedBuilder, Emoji, UserExtension};
+use etheris_discord::{Emoji, UserExtension};
 
 use self::api_input::ApiInput;
 
@@ -47,6 +47,7 @@ impl Skill for SkillMirror {
             description: "Copia uma habilidade de um inimigo e a usa contra ele. Custa muita sobrecarga.",
             explanation: "Habilidade de metaconsciência avançada: requer foco e controle do ether para analisar o ether do inimigo e então replicar a habilidade.",
             complexity: SkillComplexity::UltraHard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { 
                 ether: if let Some(skill) = &self.stored_skill { 
                     let skill = get_boxed_skill_from_kind(skill.base_kind.clone());
@@ -193,7 +194,7 @@ async fn copy_fighter_skills(
     let mut enemy_skills = vec![];
     for skill in target.skills.iter() {
         let skill_kind = skill.dynamic_skill.lock().await.kind();
-        if fighter.skills.iter().any(|s| discriminant(&s.base_kind) == discriminant(&skill_kind)) {
+        if fighter.skills.iter().any(|s| s.base_kind == skill.base_kind) {
             continue;
         }
 
@@ -206,12 +207,9 @@ async fn copy_fighter_skills(
     }
 
     let mut inputs = vec![];
-    let mut embed = EmbedBuilder::new_common()
+    let mut prompt = IoPrompt::new("Escolha uma habilidade para copiar!")
         .set_color(Color::DARK_YELLOW)
-        .set_author(EmbedAuthor {
-            name: "Escolha uma habilidade para copiar!".to_string(),
-            icon_url: fighter.user.as_ref().map(|u| u.avatar_url()),
-        });
+        .set_icon_url(fighter.user.as_ref().map(|u| u.avatar_url()));
 
     for skill in enemy_skills.iter() {
         let can_copy = (skill.intelligence_requirement() as f32) <= ((fighter.intelligence_level + 5) as f32 * 1.25);
@@ -236,7 +234,7 @@ async fn copy_fighter_skills(
             }
         });
 
-        embed = embed.add_inlined_field(
+        prompt = prompt.add_field(
             skill.data(target).name.to_string(),
             if !can_copy {
                 "**Habilidade complexa demais para você copiar**".to_string()
@@ -246,7 +244,7 @@ async fn copy_fighter_skills(
         );
     }
 
-    let Some(selected_skill) = api_input::select_input(api, Some(embed), inputs).await? else {
+    let Some(selected_skill) = api_input::select_input(api, Some(prompt), inputs).await? else {
         return Ok(());
     };
 
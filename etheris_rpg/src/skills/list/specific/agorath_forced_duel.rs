@@ -19,6 +19,7 @@ impl Skill for AgorathForcedDuel {
             description: "Força a batalha a ser um duelo de verdade.",
             explanation: "Habilidade única de um poderoso duelista.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost {
                 ether: if self.duel_target.is_none() { 0 } else { 25 },
             },
@@ -16,6 +16,7 @@ impl Skill for EthriaAdaptation {
             description: "Se adapta a qualquer forma de dano conforme o recebe.",
             explanation: "Habilidade complexa de uma arrogante evolucionária.",
             complexity: SkillComplexity::Master,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 0 },
         }
     }
@@ -28,6 +28,7 @@ impl Skill for GarhyanRatSummon {
             description: "Cria ratos gigantes materializados a partir de ratos comuns.",
             explanation: "Habilidade repulsiva mas fascinante de um nobre caído.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 25 },
         }
     }
@@ -60,11 +61,14 @@ impl Skill for GarhyanRatSummon {
             inventory: vec![],
             personalities: vec![Personality::Cowardice, Personality::Aggressiveness],
             skills: vec![SkillKind::Bite, SkillKind::Charge, SkillKind::DefensiveJump],
+            skill_mastery: vec![],
             strength_level: 16,
             intelligence_level: 6,
             potential: EnemyPotential::Low.to_f64(),
             immunities: BodyImmunities::new().with_little_resistance(ImmunityKind::Poison),
             weapon: None,
+            weapon_values: Default::default(),
+            equipment: vec![],
             resistance: Attribute::from(280),
             vitality: Attribute::from(75),
             ether: Attribute::from(25),
@@ -16,6 +16,7 @@ impl Skill for Suplex {
             description: "Agarra seu inimigo quando ele ter equilíbrio baixo e com um movimento ágil o joga no chão de cabeça.",
             explanation: "Requer pouco ether físico, mas gasta uma quantia extrema de energia para impulsionar seu corpo com velocidade para efetuar o movimento.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 25 },
         }
     }
@@ -32,7 +33,7 @@ impl Skill for Suplex {
         let damage = api.rng().gen_range(12..=23);
         let damage = base_damage + (damage as f32 * fighter.strength_multiplier()) as i32;
 
-        let paralyze = Probability::new(20).generate_random_bool();
+        let paralyze = Probability::new(20).generate_random_bool_with(api.rng());
 
         let damage = api.apply_damage(
             target.index, 
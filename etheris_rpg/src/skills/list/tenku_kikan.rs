@@ -28,6 +28,7 @@ impl Skill for TenkuKikan {
             description: "Armazena a alma do último que morreu pelas suas mãos e cria uma cópia para lutar ao seu lado. A alma só pode ser usada uma vez.",
             explanation: "Alma é ether: guardar ether de alguém que já morreu é extremamente complicado. O ether se desfaz na morte, então é necessário agir rápido para armazenar ether em uma área neutra do seu corpo, efetivamente armazenando a alma do seu inimigo junto. Como ether é vida, na morte o ether da alma enfraquece. Depois é só materializar um corpo e colocar o resquício da alma que você armazenou.",
             complexity: SkillComplexity::VeryHard,
+            limits: SkillLimits::ONCE_PER_BATTLE,
             use_cost: SkillCost { ether: 80 },
         }
     }
@@ -119,7 +120,10 @@ impl Skill for TenkuKikan {
             intelligence_level: soul.intelligence,
 
             weapon: None,
+            weapon_values: Default::default(),
+            equipment: vec![],
             skills: soul.skills,
+            skill_mastery: vec![],
             immunities: BodyImmunities::new()
         });
 
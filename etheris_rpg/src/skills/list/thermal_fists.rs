@@ -16,6 +16,7 @@ impl Skill for ThermalFists {
             description: "Dois socos imbuídos, um em fogo e outro em gelo.",
             explanation: "Concentra ether nas mãos, criando um punho de fogo e outro de gelo. Requer controle preciso para manter as temperaturas extremas sem se ferir.",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 35 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for TornadoKick {
             description: "Pula no ar e se lança ao inimigo para acertar com um poderoso chute de uma perna.",
             explanation: "Necessita um pulo alto seguido de ether para se lançar em direção ao inimigo com impulso do vento, para acertar um chute com extrema potência e velocidade. Requer muita precisão.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 20 },
         }
     }
@@ -16,6 +16,7 @@ impl Skill for WaterBlessing {
             description: "Despeja água abençoada com ether em um aliado, fortalecendo seu corpo por alguns turnos.",
             explanation: "Requer materialização de uma boa quantia de água, e então enviar um fluxo de ether com propriedades fortalecedoras pelo líquido. ",
             complexity: SkillComplexity::Normal,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 18 },
         }
     }
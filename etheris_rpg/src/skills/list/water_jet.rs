@@ -16,6 +16,7 @@ impl Skill for WaterJet {
             description: "Lança um poderoso jato de água de extrema velocidade e pressão pela palma da mão. Útil para tirar equilíbrio, apagar fogo e dar dano em eletricidade.",
             explanation: "Requer materialização de muita água e ether suficiente pra lançar com muita potência pelas mãos, tornando-se assim uma habilidade complexa.",
             complexity: SkillComplexity::Simple,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 15 },
         }
     }
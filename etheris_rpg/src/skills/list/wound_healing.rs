@@ -18,6 +18,7 @@ impl Skill for WoundHealing {
             description: "Cura uma quantia pequena de vida de um aliado cicatrizando ferimentos",
             explanation: "Ether curativo é extremamente complexo, por isso mesmo curas simples como cicatrizações são complicadas de serem feitas.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 20 },
         }
     }
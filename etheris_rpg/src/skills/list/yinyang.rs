@@ -42,6 +42,7 @@ impl Skill for YinYang {
             description: "Controla o estado da alma e alterna entre o modo Yin (ataque) ou o modo Yang (defesa).",
             explanation: "Alma é a base do ether, ether é a base da alma. Quando seu controle sobre o ether do seu corpo é preciso ao ponto de poder alterar sua alma, você pode alternar entre o estado de agressividade e proteção primordiais dos seres vivos. Requer extrema concentração e paz interior para alterar o estado da sua alma através do ether.",
             complexity: SkillComplexity::Hard,
+            limits: SkillLimits::NONE,
             use_cost: SkillCost { ether: 20 },
         }
     }
@@ -1,11 +1,17 @@
+mod combos;
+mod definition;
 pub mod list;
 
+pub use combos::*;
+pub use definition::*;
+
 use std::fmt::Display;
 
 use etheris_common::Probability;
-use etheris_data::{emojis, SkillKind};
+use etheris_data::{emojis, skill_mastery_ether_discount, SkillKind};
+use serde::{Deserialize, Serialize};
 
-use crate::{common::DamageSpecifier, BattleApi, Fighter, FighterIndex};
+use crate::{common::DamageSpecifier, BattleApi, Fighter, FighterIndex, Reaction};
 
 pub type SkillResult<T> = anyhow::Result<T>;
 
@@ -14,7 +20,42 @@ pub struct SkillCost {
     pub ether: i32,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// How often a skill can be used in a battle, besides its ether cost.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub struct SkillLimits {
+    /// How many of the fighter's turns must pass before the skill can be used again.
+    #[serde(default)]
+    pub cooldown: u32,
+    /// How many times the skill can be used in a battle.
+    #[serde(default)]
+    pub charges: Option<u32>,
+}
+
+impl SkillLimits {
+    pub const NONE: Self = Self {
+        cooldown: 0,
+        charges: None,
+    };
+    pub const ONCE_PER_BATTLE: Self = Self::charges(1);
+
+    pub const fn cooldown(turns: u32) -> Self {
+        Self {
+            cooldown: turns,
+            charges: None,
+        }
+    }
+
+    pub const fn charges(charges: u32) -> Self {
+        Self {
+            cooldown: 0,
+            charges: Some(charges),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum SkillComplexity {
     VerySimple,
     Simple,
@@ -66,6 +107,7 @@ pub struct SkillData {
     pub description: &'static str,
     pub explanation: &'static str,
     pub complexity: SkillComplexity,
+    pub limits: SkillLimits,
     pub use_cost: SkillCost,
 }
 
@@ -85,15 +127,53 @@ pub trait Skill {
 
     fn data(&self, fighter: &Fighter) -> SkillData;
 
+    /// The ether the fighter spends to use the skill, discounted by its mastery.
+    fn ether_cost(&self, fighter: &Fighter) -> i32 {
+        let discount = skill_mastery_ether_discount(fighter.skill_mastery_level(&self.kind()));
+        (self.data(fighter).use_cost.ether as f32 * (1.0 - discount)).round() as i32
+    }
+
+    /// Whether the cooldown and the charges of the skill allow the fighter to use it.
+    fn is_ready(&self, fighter: &Fighter) -> bool {
+        let data = self.data(fighter);
+        fighter
+            .get_skill(data.identifier)
+            .is_none_or(|skill| skill.is_ready(data.limits))
+    }
+
+    /// The reaction the skill prepares when used, resolved when the fighter is attacked before its next turn.
+    fn reaction(&self, _fighter: &Fighter) -> Option<Reaction> {
+        None
+    }
+
     fn default_display(&self, fighter: &Fighter) -> SkillDisplay {
+        let data = self.data(fighter);
+        let mut sub_header = format!("**{} {}**", emojis::ETHER, self.ether_cost(fighter));
+
+        let skill = fighter.get_skill(data.identifier);
+        match skill.map(|s| s.cooldown).unwrap_or_default() {
+            0 if data.limits.cooldown > 0 => {
+                sub_header.push_str(&format!(" | ⏳ Recarga de {} turnos", data.limits.cooldown))
+            }
+            0 => {}
+            cooldown => sub_header.push_str(&format!(" | ⏳ Recarregando: {cooldown} turnos")),
+        }
+
+        if self.reaction(fighter).is_some() {
+            sub_header.push_str(" | 🔁 Reação");
+        }
+
+        if let Some(charges) = data.limits.charges {
+            let remaining = skill
+                .and_then(|s| s.remaining_charges(data.limits))
+                .unwrap_or(charges);
+            sub_header.push_str(&format!(" | 🔋 {remaining}/{charges} usos"));
+        }
+
         SkillDisplay {
-            header: self.data(fighter).name.to_owned(),
-            sub_header: format!(
-                "**{} {}**",
-                emojis::ETHER,
-                self.data(fighter).use_cost.ether
-            ),
-            body: self.data(fighter).description.to_owned(),
+            header: data.name.to_owned(),
+            sub_header,
+            body: data.description.to_owned(),
         }
     }
 
@@ -102,7 +182,7 @@ pub trait Skill {
     }
 
     fn default_can_use(&self, api: BattleApi<'_>) -> bool {
-        api.fighter().ether.value >= self.data(api.fighter()).use_cost.ether
+        api.fighter().ether.value >= self.ether_cost(api.fighter()) && self.is_ready(api.fighter())
     }
 
     fn can_use(&self, api: BattleApi<'_>) -> bool {
@@ -157,3 +237,27 @@ pub trait Skill {
         Ok(())
     }
 }
+
+#[test]
+fn skill_cooldowns_block_the_next_turns() {
+    use crate::FighterSkill;
+
+    let limits = SkillLimits::cooldown(2);
+    let mut fighter = Fighter::default();
+    fighter
+        .skills
+        .push(FighterSkill::from(SkillKind::Earthquake));
+
+    fighter.register_skill_use("earthquake", limits);
+    for _ in 0..2 {
+        fighter.tick_skill_cooldowns();
+        assert!(!fighter.get_skill("earthquake").unwrap().is_ready(limits));
+    }
+
+    fighter.tick_skill_cooldowns();
+    assert!(fighter.get_skill("earthquake").unwrap().is_ready(limits));
+    assert!(!fighter
+        .get_skill("earthquake")
+        .unwrap()
+        .is_ready(SkillLimits::ONCE_PER_BATTLE));
+}
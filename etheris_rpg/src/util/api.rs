@@ -2,7 +2,6 @@ use std::fmt::Display;
 
 use etheris_common::Probability;
 use etheris_data::emojis;
-use etheris_framework::CommandContext;
 use rand::{seq::SliceRandom, Rng};
 
 use crate::*;
@@ -12,7 +11,7 @@ use self::common::{DamageKind, DamageSpecifier};
 pub struct BattleApi<'a> {
     pub fighter_index: FighterIndex,
     pub target_index: FighterIndex,
-    pub controller: &'a mut BattleController,
+    pub engine: &'a mut BattleEngine,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -45,27 +44,22 @@ impl Display for EffectiveDamage {
 }
 
 impl<'a> BattleApi<'a> {
-    pub fn new(controller: &'a mut BattleController) -> Self {
+    pub fn new(engine: &'a mut BattleEngine) -> Self {
         Self {
-            fighter_index: controller.battle.get_current_fighter().index,
-            target_index: controller.battle.get_target_fighter().index,
-            controller,
+            fighter_index: engine.battle.get_current_fighter().index,
+            target_index: engine.battle.get_target_fighter().index,
+            engine,
         }
     }
 
-    #[inline(always)]
-    pub fn ctx(&mut self) -> &mut CommandContext {
-        &mut self.controller.ctx
-    }
-
     #[inline(always)]
     pub fn battle(&self) -> &Battle {
-        &self.controller.battle
+        &self.engine.battle
     }
 
     #[inline(always)]
     pub fn battle_mut(&mut self) -> &mut Battle {
-        &mut self.controller.battle
+        &mut self.engine.battle
     }
 
     pub fn fighter(&self) -> &Fighter {
@@ -87,15 +81,19 @@ impl<'a> BattleApi<'a> {
     }
 
     pub fn rng(&mut self) -> &mut impl Rng {
-        &mut self.controller.battle.rng
+        &mut self.engine.battle.rng
     }
 
     pub fn emit_message(&mut self, message: impl ToString) {
-        self.controller.emit_turn_message(message.to_string());
+        self.engine.emit_turn_message(message.to_string());
+    }
+
+    pub fn emit_event(&mut self, event: BattleEvent) {
+        self.engine.emit_event(event);
     }
 
     pub fn defer_message(&mut self, message: impl ToString) {
-        self.controller
+        self.engine
             .battle
             .deferred_turn_messages
             .push(message.to_string());
@@ -126,7 +124,7 @@ impl<'a> BattleApi<'a> {
     }
 
     pub fn emit_random_message(&mut self, messages: &[impl ToString]) {
-        self.controller.emit_random_turn_message(messages)
+        self.engine.emit_random_turn_message(messages)
     }
 
     pub fn report_error(&mut self, message: impl ToString) {
@@ -158,6 +156,12 @@ impl<'a> BattleApi<'a> {
         fighter.overload += amount;
 
         let fighter = fighter.clone();
+        self.emit_event(BattleEvent::OverloadAdded {
+            fighter: target_index,
+            amount,
+            total: fighter.overload,
+        });
+
         if base_overload <= 5.0 && fighter.overload >= 5.0 {
             self.emit_message(format!(
                 "O corpo de **{}** está sobrecarregando pelo uso de ether!",
@@ -267,7 +271,9 @@ impl<'a> BattleApi<'a> {
         let mut dodged = false;
         let mut defended = false;
 
-        let target = self.battle_mut().get_fighter_mut(target_index);
+        let battle = self.battle_mut();
+        let rng = &mut battle.rng;
+        let target = &mut battle.fighters[target_index.0];
 
         if target.defense > 0 {
             defended = true;
@@ -315,20 +321,38 @@ impl<'a> BattleApi<'a> {
                 105u8.saturating_sub(damage.accuracy + if target.balance > 80 { 15 } else { 0 }),
             );
 
-            let good_accuracy =
-                Probability::new(if target.balance > 90 { 5 } else { 15 }).generate_random_bool();
+            let good_accuracy = Probability::new(if target.balance > 90 { 5 } else { 15 })
+                .generate_random_bool_with(rng);
 
             if !good_accuracy
-                && (bad_accuracy_prob.generate_random_bool()
-                    || unlucky_miss_prob.generate_random_bool())
+                && (bad_accuracy_prob.generate_random_bool_with(rng)
+                    || unlucky_miss_prob.generate_random_bool_with(rng))
             {
                 missed = true;
-            } else if dodge_prob.generate_random_bool() {
+            } else if dodge_prob.generate_random_bool_with(rng) {
                 missed = true;
                 dodged = true;
             }
         }
 
+        // Only a landed hit spends the reaction prepared by the target
+        let reaction = target
+            .take_triggered_reaction(culprit_index, damage.kind, !missed)
+            .filter(|r| Probability::new(r.chance).generate_random_bool_with(rng));
+
+        match reaction.as_ref().map(|r| r.kind) {
+            Some(ReactionKind::Evade) => {
+                missed = true;
+                dodged = true;
+            }
+            Some(ReactionKind::Parry { damage_multiplier }) => {
+                defended = true;
+                damage.amount = ((damage.amount as f32) * damage_multiplier) as i32;
+                damage.balance_effectiveness /= 2;
+            }
+            _ => (),
+        }
+
         if missed {
             damage.amount = 0;
             damage.balance_effectiveness = 0;
@@ -360,7 +384,7 @@ impl<'a> BattleApi<'a> {
             .round()
             .clamp(0.0, 100.0) as u8;
 
-        target.take_damage(culprit_index, damage);
+        target.take_damage(rng, culprit_index, damage);
         target.balance = target.balance.saturating_sub(damage.balance_effectiveness);
 
         let mut falling_prob = match target.balance {
@@ -440,7 +464,7 @@ impl<'a> BattleApi<'a> {
         // This is pretty rare BUT is a possibility.
         if missed
             && target.composure == Composure::OnGround
-            && Probability::new(40).generate_random_bool()
+            && Probability::new(40).generate_random_bool_with(self.rng())
             && target.balance > 60
         {
             self.battle_mut().get_fighter_mut(target_index).composure = Composure::OnGround;
@@ -489,19 +513,27 @@ impl<'a> BattleApi<'a> {
         {
             if !missed && damage.amount > 10 {
                 let dmg = (damage.amount as f32 * if curse.amount > 80 { 0.4 } else { 0.3 }) as i32;
-                self.battle_mut()
-                    .get_fighter_mut(culprit_index)
-                    .take_damage(
-                        curse.culprit,
-                        DamageSpecifier {
-                            kind: DamageKind::Special,
-                            amount: dmg,
-                            balance_effectiveness: 0,
-                            accuracy: 100,
-                            effect: None,
-                            culprit: curse.culprit,
-                        },
-                    );
+                self.battle_mut().damage_fighter(
+                    culprit_index,
+                    curse.culprit,
+                    DamageSpecifier {
+                        kind: DamageKind::Special,
+                        amount: dmg,
+                        balance_effectiveness: 0,
+                        accuracy: 100,
+                        effect: None,
+                        culprit: curse.culprit,
+                    },
+                );
+                self.emit_event(BattleEvent::DamageDealt {
+                    culprit: curse.culprit,
+                    target: culprit_index,
+                    kind: DamageKind::Special,
+                    amount: dmg,
+                    missed: false,
+                    dodged: false,
+                    defended: false,
+                });
 
                 self.battle_mut().deferred_turn_messages.push(format!(
                     "***{}** recebeu **{dmg} dano** do seu próprio ataque graças à maldição!*",
@@ -510,6 +542,20 @@ impl<'a> BattleApi<'a> {
             }
         }
 
+        self.emit_event(BattleEvent::DamageDealt {
+            culprit: culprit_index,
+            target: target_index,
+            kind: damage.kind,
+            amount: damage.amount,
+            missed,
+            dodged,
+            defended,
+        });
+
+        if let Some(reaction) = reaction {
+            self.resolve_reaction(reaction, target_index, culprit_index, damage.amount);
+        }
+
         EffectiveDamage {
             damage_specifier: damage,
             amount: damage.amount,
@@ -520,7 +566,80 @@ impl<'a> BattleApi<'a> {
         }
     }
 
-    pub async fn apply_effect(&mut self, target_index: FighterIndex, effect: Effect) {
+    /// Answers an attack of `culprit` with the reaction prepared by `target`.
+    /// Parries and evasions already changed the attack, so only their messages are left.
+    fn resolve_reaction(
+        &mut self,
+        reaction: Reaction,
+        target_index: FighterIndex,
+        culprit_index: FighterIndex,
+        damage_received: i32,
+    ) {
+        let target = self.battle().get_fighter(target_index).clone();
+        let culprit = self.battle().get_fighter(culprit_index).clone();
+
+        let (kind, amount) = match reaction.kind {
+            ReactionKind::Parry { .. } => {
+                self.defer_message(format!(
+                    "**{}** aparou o ataque de **{}** com **{}**!",
+                    target.name, culprit.name, reaction.name
+                ));
+                return;
+            }
+            ReactionKind::Evade => {
+                self.defer_message(format!(
+                    "**{}** se esquivou do ataque de **{}** com **{}**!",
+                    target.name, culprit.name, reaction.name
+                ));
+                return;
+            }
+            ReactionKind::Reflect { ratio } => (
+                DamageKind::Special,
+                ((damage_received as f32) * ratio) as i32,
+            ),
+            ReactionKind::Counter { kind, amount } => (kind, amount),
+        };
+
+        if amount < 1 || target.is_defeated || culprit.is_defeated {
+            return;
+        }
+
+        // The answer doesn't go through apply_damage, so a reaction can't trigger another one
+        self.battle_mut().damage_fighter(
+            culprit_index,
+            target_index,
+            DamageSpecifier {
+                culprit: target_index,
+                kind,
+                amount,
+                balance_effectiveness: 5,
+                accuracy: 100,
+                effect: None,
+            },
+        );
+        self.emit_event(BattleEvent::DamageDealt {
+            culprit: target_index,
+            target: culprit_index,
+            kind,
+            amount,
+            missed: false,
+            dodged: false,
+            defended: false,
+        });
+
+        self.defer_message(match reaction.kind {
+            ReactionKind::Reflect { .. } => format!(
+                "**{}** refletiu **{amount} dano** em **{}** com **{}**!",
+                target.name, culprit.name, reaction.name
+            ),
+            _ => format!(
+                "**{}** reagiu com **{}** e deu **{amount} dano** em **{}**!",
+                target.name, reaction.name, culprit.name
+            ),
+        });
+    }
+
+    pub async fn apply_effect(&mut self, target_index: FighterIndex, mut effect: Effect) {
         let target = self.battle_mut().get_fighter_mut(target_index);
         if target
             .modifiers
@@ -531,84 +650,92 @@ impl<'a> BattleApi<'a> {
             return;
         }
 
+        // Resistant bodies get less of the effect, weak ones get more.
+        // Ticks and bursts don't apply the effect's immunity again
+        if let Some(immunity) = effect.kind.affected_immunity() {
+            let multiplier = target
+                .body_immunities
+                .dmg_multiplier_from_immunity(immunity);
+            effect.amount = ((effect.amount as f64) * multiplier).round() as i32;
+            if effect.amount <= 0 {
+                return;
+            }
+        }
+
         let target_name = target.name.clone();
         let should_emit_message = target.apply_effect(effect);
-
-        let message = match effect.kind {
-            EffectKind::Flaming => format!("**{}** está queimando!", target_name),
-            EffectKind::Burning => format!("**{}** entrou em combustão!", target_name),
-            EffectKind::Shocked => format!("**{}** está em choque!", target_name),
-            EffectKind::Paralyzed => format!("**{}** paralisou!", target_name),
-            EffectKind::Ice => format!("**{}** está congelando!", target_name),
-            EffectKind::Wet => format!("**{}** está molhado!", target_name),
-            EffectKind::Frozen => format!("**{}** congelou!", target_name),
-            EffectKind::Bleeding => format!("**{}** começou a sangrar!", target_name),
-            EffectKind::Poisoned => format!("**{}** está envenenado!", target_name),
-            EffectKind::Curse => format!("**{}** está com uma maldição!", target_name),
-            EffectKind::Exhausted => format!(
-                "**{}** está exausto e não pode mais regenerar ether!",
-                target_name
-            ),
-            EffectKind::LowProtection => format!(
-                "**{}** está com uma proteção extra a danos leves!",
-                target_name
-            ),
-        };
+        self.emit_event(BattleEvent::EffectApplied {
+            culprit: effect.culprit,
+            target: target_index,
+            kind: effect.kind,
+            amount: effect.amount,
+        });
 
         if should_emit_message {
-            self.battle_mut().deferred_turn_messages.push(message)
-        };
-
-        let target = self.battle_mut().get_fighter_mut(target_index);
-
-        if let Some(flaming) = target.get_effect(EffectKind::Flaming) {
-            if flaming.amount >= 100 {
-                target.remove_effect(flaming);
-                target.apply_effect(Effect {
-                    amount: 20,
-                    kind: EffectKind::Burning,
-                    culprit: flaming.culprit,
-                });
-
-                self.defer_message(format!(
-                    "**{}** queimou tanto que entrou em combustão!",
-                    target_name
-                ));
-            }
+            self.defer_message(format_effect_message(
+                effect.kind.rules().applied_message,
+                &target_name,
+            ));
         }
 
-        let target = self.battle_mut().get_fighter_mut(target_index);
-
-        if let Some(ice) = target.get_effect(EffectKind::Ice) {
-            if ice.amount >= 100 {
-                target.remove_effect(ice);
-                target.apply_effect(Effect {
-                    amount: 1,
-                    kind: EffectKind::Frozen,
-                    culprit: ice.culprit,
-                });
+        self.resolve_effect_threshold(target_index, effect.kind);
+    }
 
-                self.battle_mut()
-                    .deferred_turn_messages
-                    .push(format!("**{}** congelou completamente!", target_name));
-            }
-        }
+    /// Applies the threshold of the effect if the fighter reached it. Returns true if the effect was consumed.
+    pub fn resolve_effect_threshold(
+        &mut self,
+        target_index: FighterIndex,
+        kind: EffectKind,
+    ) -> bool {
+        let Some(threshold) = kind.rules().threshold else {
+            return false;
+        };
 
         let target = self.battle_mut().get_fighter_mut(target_index);
+        let Some(effect) = target
+            .get_effect(kind)
+            .filter(|e| e.amount >= threshold.amount())
+        else {
+            return false;
+        };
 
-        if let Some(shock) = target.get_effect(EffectKind::Shocked) {
-            if shock.amount >= 100 {
-                target.remove_effect(shock);
-                target.apply_effect(Effect {
-                    amount: 1,
-                    kind: EffectKind::Paralyzed,
-                    culprit: shock.culprit,
-                });
-
-                self.battle_mut()
-                    .deferred_turn_messages
-                    .push(format!("**{}** paralisou devido ao choque!", target_name));
+        let target_name = target.name.clone();
+        target.delete_effect(kind);
+
+        match threshold {
+            EffectThreshold::Transform {
+                into,
+                into_amount,
+                message,
+                ..
+            } => {
+                target.apply_effect(Effect::new(into, into_amount, effect.culprit));
+                self.defer_message(format_effect_message(message, &target_name));
+            }
+            EffectThreshold::Burst {
+                base,
+                max_health_ratio,
+                message,
+                ..
+            } => {
+                let dmg = base + (target.health().max as f32 * max_health_ratio) as i32;
+
+                self.battle_mut().damage_fighter(
+                    target_index,
+                    effect.culprit,
+                    DamageSpecifier {
+                        culprit: effect.culprit,
+                        kind: DamageKind::Special,
+                        amount: dmg,
+                        balance_effectiveness: 0,
+                        accuracy: 100,
+                        effect: None,
+                    },
+                );
+                self.defer_message(format_effect_damage_message(message, &target_name, dmg));
             }
         }
+
+        true
     }
 }
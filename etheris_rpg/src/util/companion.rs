@@ -0,0 +1,205 @@
+use etheris_common::Probability;
+use etheris_data::emojis;
+use etheris_database::{
+    character_model::CharacterModel,
+    companion::{Companion, MAX_COMPANIONS},
+};
+use etheris_discord::twilight_model::user::User;
+use etheris_framework::*;
+
+use crate::{data::enemies, Battle, FighterData, FighterIndex};
+
+/// The identifiers of the enemies that can be tamed after being defeated.
+pub const TAMEABLE_ENEMIES: &[&str] = &[
+    "giant_rat",
+    "conscious_beast",
+    "dangerous_bear",
+    "small_scorpion",
+    "frost_wolf",
+    "mountain_goat",
+    "conscious_bear",
+    "serpentoid_weak",
+    "thornbeast",
+];
+
+const TAME_PROBABILITY: Probability = Probability::new(30);
+const COMPANION_XP_PER_WIN: u32 = 20;
+
+pub fn is_tameable(enemy: &str) -> bool {
+    TAMEABLE_ENEMIES.contains(&enemy)
+}
+
+/// Adds the active companion of the character to its team. Returns the index of the companion in the battle.
+pub fn join_companion(
+    battle: &mut Battle,
+    character: &CharacterModel,
+    team: u8,
+) -> Option<FighterIndex> {
+    let companion = character.active_companion()?;
+    let data = FighterData::new_from_companion(team, companion)?;
+
+    let index = FighterIndex(battle.fighters.len());
+    // Kept with the initial fighters so the replays of the battle have the companion too
+    battle.initial_fighters.push(data.clone());
+    battle.join_fighter(data);
+
+    Some(index)
+}
+
+/// Gives XP to the companion that fought, buries it if it died in a battle with consequences,
+/// and lets the player tame one of the animals it defeated.
+pub async fn finish_companion_battle(
+    ctx: &mut CommandContext,
+    user: &User,
+    battle: &Battle,
+    companion_index: Option<FighterIndex>,
+) -> anyhow::Result<()> {
+    let Some(character) = ctx
+        .db()
+        .characters()
+        .get_by_user(&user.id.to_string())
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let Some(player) = battle
+        .fighters
+        .iter()
+        .find(|f| f.user.as_ref().is_some_and(|u| u.id == user.id))
+    else {
+        return Ok(());
+    };
+
+    let won = battle.winner_team() == Some(player.team);
+    let mut messages = vec![];
+
+    // The battle took a while, so the companion is changed on the character as it is now
+    let character = if let Some(fighter) = companion_index.map(|index| battle.get_fighter(index)) {
+        let died = fighter.killed_by.is_some() || fighter.vitality.value <= 0;
+
+        ctx.db()
+            .characters()
+            .update(character.id, |character| {
+                messages.clear();
+                if died && battle.settings.has_consequences {
+                    character.companions.retain(|c| !c.active);
+                    messages.push((
+                        "💀",
+                        format!(
+                            "seu companheiro **{}** morreu na batalha e não vai mais te acompanhar.",
+                            fighter.name
+                        ),
+                    ));
+                } else if won {
+                    if let Some(companion) = character.active_companion_mut() {
+                        if companion.add_xp(COMPANION_XP_PER_WIN) > 0 {
+                            messages.push((
+                                "🐾",
+                                format!(
+                                    "seu companheiro **{}** subiu para o nível **{}**!",
+                                    companion.name, companion.level
+                                ),
+                            ));
+                        }
+                    }
+                }
+            })
+            .await?
+            .unwrap_or(character)
+    } else {
+        character
+    };
+
+    for (emoji, message) in messages {
+        ctx.send(Response::new_user_reply(user, message).add_emoji_prefix(emoji))
+            .await?;
+    }
+
+    if won {
+        try_to_tame(ctx, user, battle, player.team, character).await?;
+    }
+
+    Ok(())
+}
+
+/// Gives the player the chance to tame one of the animals of the other team that fainted in the battle.
+async fn try_to_tame(
+    ctx: &mut CommandContext,
+    user: &User,
+    battle: &Battle,
+    team: u8,
+    character: CharacterModel,
+) -> anyhow::Result<()> {
+    if character.companions.len() >= MAX_COMPANIONS {
+        return Ok(());
+    }
+
+    let Some(enemy) = battle
+        .fighters
+        .iter()
+        .filter(|f| f.team != team && f.user.is_none() && f.boss.is_none() && f.killed_by.is_none())
+        .find_map(|f| {
+            enemies::ALL_ENEMIES
+                .iter()
+                .find(|e| e.name == f.name && is_tameable(e.identifier))
+        })
+    else {
+        return Ok(());
+    };
+
+    if !TAME_PROBABILITY.generate_random_bool() {
+        return Ok(());
+    }
+
+    let confirmation = ctx
+        .helper()
+        .create_confirmation(
+            user.id,
+            false,
+            Response::new_user_reply(
+                user,
+                format!(
+                    "**{}** ficou impressionado com a sua força e quer te seguir. Você aceita ele como companheiro?",
+                    enemy.name
+                ),
+            )
+            .add_emoji_prefix("🐾"),
+        )
+        .await?;
+
+    if !confirmation {
+        return Ok(());
+    }
+
+    let mut tamed = false;
+    ctx.db()
+        .characters()
+        .update(character.id, |character| {
+            tamed = character.companions.len() < MAX_COMPANIONS;
+            if tamed {
+                let mut companion =
+                    Companion::new(enemy.identifier, enemy.name, enemy.skills.clone());
+                companion.active = character.active_companion().is_none();
+                character.companions.push(companion);
+            }
+        })
+        .await?;
+    if !tamed {
+        return Ok(());
+    }
+
+    ctx.send(
+        Response::new_user_reply(
+            user,
+            format!(
+                "**{}** agora é seu companheiro! Use **/companheiro ver** para ver seus companheiros.",
+                enemy.name
+            ),
+        )
+        .add_emoji_prefix(emojis::SUCCESS),
+    )
+    .await?;
+
+    Ok(())
+}
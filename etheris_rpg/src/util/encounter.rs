@@ -3,6 +3,7 @@ use etheris_data::{emojis, items::get_item_by_weapon};
 use etheris_discord::{bold, twilight_model::user::User, EmbedBuilder};
 use etheris_framework::*;
 
+use super::{companion, party};
 use crate::{
     events::list::prelude::Reward, list::get_boxed_skill_from_kind, Battle, BattleController,
     BattleResult, BattleSettings, Fighter, FighterData,
@@ -11,7 +12,7 @@ use crate::{
 pub async fn prompt_encounter(
     ctx: &mut CommandContext,
     user: User,
-    enemies: Vec<FighterData>,
+    mut enemies: Vec<FighterData>,
     allies: Vec<FighterData>,
 ) -> anyhow::Result<Option<BattleResult>> {
     let Some(character) = ctx
@@ -23,6 +24,10 @@ pub async fn prompt_encounter(
         return Ok(Default::default());
     };
 
+    // The party is only asked after the character agrees to fight, and the enemies are scaled
+    // to whoever joins, so the character is compared to them alone
+    let party_candidates = party::get_party_candidates(ctx, &character).await?;
+
     let reward = enemies
         .iter()
         .fold(Reward::default(), |acc, x| acc.add(x.drop.clone()));
@@ -61,6 +66,25 @@ pub async fn prompt_encounter(
         embed = embed.add_description_text(warning);
     }
 
+    if let Some(companion) = character.active_companion() {
+        embed = embed.add_description_text(format!(
+            "🐾 **Seu companheiro luta junto**: {} (nível {})\n",
+            bold(&companion.name),
+            companion.level
+        ));
+    }
+
+    if !party_candidates.is_empty() {
+        embed = embed.add_description_text(format!(
+            "👥 **Seu grupo será chamado para lutar junto**: {}\nQuem entrar deixa os inimigos mais fortes e divide as recompensas.\n",
+            party_candidates
+                .iter()
+                .map(|(m, _)| bold(&m.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
     for enemy in enemies.iter() {
         let strength_message =
             match (character.stats.strength_level as i64 - enemy.strength_level as i64) / 2 {
@@ -170,24 +194,33 @@ pub async fn prompt_encounter(
         return Ok(None);
     }
 
+    let party = party::join_party_battle(ctx, &character, party_candidates, &mut enemies).await?;
+
     let mut fighters = enemies;
     fighters.insert(
         0,
-        FighterData::new_from_character(0, &character, user, Default::default()),
+        FighterData::new_from_character(0, &character, user.clone(), Default::default()),
     );
+    fighters.extend_from_slice(&party);
     fighters.extend_from_slice(&allies);
 
-    let battle = Battle::new(
+    let mut battle = Battle::new(
         character.region,
         BattleSettings {
             casual: false,
             has_consequences: true,
             is_risking_life_allowed: true,
             max_intruders: 2,
+            seed: None,
+            ranked: false,
         },
         fighters,
     )?;
+    let companion = companion::join_companion(&mut battle, &character, 0);
 
     let mut controller = BattleController::new(battle, ctx.clone());
-    Ok(Some(controller.run().await?))
+    let result = controller.run().await?;
+    companion::finish_companion_battle(ctx, &user, &controller.engine.battle, companion).await?;
+
+    Ok(Some(result))
 }
@@ -1,6 +1,8 @@
 mod api;
 pub mod api_input;
+pub mod companion;
 pub mod encounter;
+pub mod party;
 pub mod shop;
 
 pub use api::*;
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use etheris_database::character_model::CharacterModel;
+use etheris_discord::twilight_model::{id::Id, user::User};
+use etheris_framework::{CommandContext, Response};
+use tokio::task::JoinSet;
+
+use crate::FighterData;
+
+/// How long the party members have to agree to join a battle.
+const PARTY_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The other members of the character's party that could fight alongside it:
+/// alive, in the same region and not in another battle.
+pub async fn get_party_candidates(
+    ctx: &CommandContext,
+    character: &CharacterModel,
+) -> anyhow::Result<Vec<(CharacterModel, User)>> {
+    let Some(party) = ctx.db().parties().get_by_member(character.id).await? else {
+        return Ok(vec![]);
+    };
+
+    let mut candidates = vec![];
+    for member_id in party.members {
+        if member_id == character.id {
+            continue;
+        }
+
+        let Some(member) = ctx.db().characters().get_by_id(member_id).await? else {
+            continue;
+        };
+
+        if !member.alive || member.region != character.region {
+            continue;
+        }
+
+        let user_id = Id::new(member.user_id.parse::<u64>()?);
+        if ctx.client.is_user_fighting(user_id).await {
+            continue;
+        }
+
+        let user = ctx.client.get_user(user_id).await?;
+        candidates.push((member, user));
+    }
+
+    Ok(candidates)
+}
+
+/// Asks every candidate at the same time if they want to join the battle, and returns the ones
+/// that agreed. Whoever doesn't answer in time stays out of it.
+pub async fn ask_party_members(
+    ctx: &CommandContext,
+    character: &CharacterModel,
+    candidates: Vec<(CharacterModel, User)>,
+    team: u8,
+) -> anyhow::Result<Vec<FighterData>> {
+    let mut confirmations = JoinSet::new();
+    for (member, user) in candidates {
+        let mut ctx = ctx.clone();
+        // The confirmations are sent at the same time, so each one is a new message in the channel
+        ctx.already_replied = true;
+        let message = format!(
+            "**{}** vai entrar em uma batalha. Você quer lutar junto com o seu grupo?",
+            character.name
+        );
+
+        // The battle has consequences for the members too, so they have to agree to join it
+        confirmations.spawn(async move {
+            let mut helper = ctx.helper();
+            let confirmation = helper.create_confirmation(
+                user.id,
+                true,
+                Response::new_user_reply(&user, message).add_emoji_prefix("👥"),
+            );
+
+            let accepted = matches!(
+                tokio::time::timeout(PARTY_CONFIRMATION_TIMEOUT, confirmation).await,
+                Ok(Ok(true))
+            );
+            accepted
+                .then(|| FighterData::new_from_character(team, &member, user, Default::default()))
+        });
+    }
+
+    let mut fighters = vec![];
+    while let Some(fighter) = confirmations.join_next().await {
+        fighters.extend(fighter?);
+    }
+
+    Ok(fighters)
+}
+
+/// Makes enemies tougher and more rewarding so a party fights them as hard as `leader_pl` would alone.
+/// The enemies keep their skills, only their health, ether and drops grow.
+pub fn scale_enemies_to_party(enemies: &mut [FighterData], leader_pl: i64, party_pl: i64) {
+    let factor = party_pl as f64 / leader_pl.max(1) as f64;
+    if factor <= 1.0 {
+        return;
+    }
+
+    let scale = |value: i32| (value as f64 * factor) as i32;
+    for enemy in enemies.iter_mut() {
+        enemy.resistance.max = scale(enemy.resistance.max);
+        enemy.resistance.value = scale(enemy.resistance.value);
+        enemy.vitality.max = scale(enemy.vitality.max);
+        enemy.vitality.value = scale(enemy.vitality.value);
+        enemy.ether.max = scale(enemy.ether.max);
+        enemy.ether.value = scale(enemy.ether.value);
+
+        enemy.drop.orbs = scale(enemy.drop.orbs);
+        enemy.drop.xp = scale(enemy.drop.xp);
+    }
+}
+
+/// Asks the party of the character to join its team and scales the enemies to the summed PL
+/// of the members that joined.
+pub async fn join_party_battle(
+    ctx: &CommandContext,
+    character: &CharacterModel,
+    candidates: Vec<(CharacterModel, User)>,
+    enemies: &mut [FighterData],
+) -> anyhow::Result<Vec<FighterData>> {
+    if candidates.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let members = ask_party_members(ctx, character, candidates, 0).await?;
+    if members.is_empty() {
+        return Ok(members);
+    }
+
+    let party_pl = character.pl + members.iter().map(|m| m.power_level()).sum::<i64>();
+    scale_enemies_to_party(enemies, character.pl, party_pl);
+
+    Ok(members)
+}
+
+/// Adds the party of the character to its team and scales the enemies, for battles the character
+/// doesn't have to confirm.
+pub async fn prepare_party_battle(
+    ctx: &CommandContext,
+    character: &CharacterModel,
+    enemies: &mut [FighterData],
+) -> anyhow::Result<Vec<FighterData>> {
+    let candidates = get_party_candidates(ctx, character).await?;
+    join_party_battle(ctx, character, candidates, enemies).await
+}
@@ -1,8 +1,13 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
 use etheris_common::Color;
-use etheris_data::{emojis, items, ShopItem};
-use etheris_database::{bson::oid::ObjectId, character_model::CharacterModel};
+use etheris_data::{emojis, items, world::regions::WorldRegion, ShopItem};
+use etheris_database::{
+    bson::oid::ObjectId,
+    character_model::CharacterModel,
+    city_price_model::{shop_item_with_demand, PriceTrend},
+    common::InventoryItem,
+};
 use etheris_discord::{
     twilight_http::request::channel::reaction::RequestReactionType,
     twilight_model::{
@@ -22,20 +27,82 @@ use tokio_stream::StreamExt;
 pub struct Shop {
     pub shop_name: String,
     pub profit_goes_to: Option<ObjectId>,
+    /// The player building that holds the stock of the shop, if any.
+    pub building: Option<ObjectId>,
+    /// The city whose prices follow what players buy and sell there, if this is a city shop.
+    pub city: Option<WorldRegion>,
+    pub trends: BTreeMap<String, PriceTrend>,
     pub items: Vec<ShopItem>,
 }
 
 const ITEMS_PER_PAGE: usize = 6;
 
+/// The copy of the item the character can sell to a shop.
+/// Shops pay the base price, so weapons that were upgraded or enchanted are never sold to them.
+fn sellable_copy<'a>(
+    character: &'a CharacterModel,
+    item: &items::Item,
+) -> Option<&'a InventoryItem> {
+    character
+        .get_inventory_item(item)
+        .filter(|i| i.values.upgrade_level() == 0 && i.values.enchantment().is_none())
+}
+
 impl Shop {
     pub fn new(shop_name: String, profit_owner: Option<ObjectId>, items: Vec<ShopItem>) -> Shop {
         Shop {
             shop_name,
             profit_goes_to: profit_owner,
+            building: None,
+            city: None,
+            trends: BTreeMap::new(),
             items,
         }
     }
 
+    pub fn with_building(mut self, building: ObjectId) -> Self {
+        self.building = Some(building);
+        self
+    }
+
+    pub fn with_city(mut self, region: WorldRegion) -> Self {
+        self.city = Some(region);
+        self
+    }
+
+    /// Moves the prices of a city shop to the current demand of each item in the city.
+    pub async fn update_city_prices(&mut self, ctx: &CommandContext) -> anyhow::Result<()> {
+        let Some(region) = self.city else {
+            return Ok(());
+        };
+        let Some(city) = region.city() else {
+            return Ok(());
+        };
+
+        let demands = ctx.db().city_prices().get_demands(region).await?;
+        for shop_item in self.items.iter_mut() {
+            let Some(base_item) = city
+                .shop_items
+                .iter()
+                .find(|i| i.identifier == shop_item.identifier)
+            else {
+                continue;
+            };
+
+            let demand = demands.get(&shop_item.identifier).copied().unwrap_or(1.0);
+            *shop_item = ShopItem {
+                quantity: shop_item.quantity,
+                ..shop_item_with_demand(base_item, demand)
+            };
+            self.trends.insert(
+                shop_item.identifier.clone(),
+                PriceTrend::from_demand(demand),
+            );
+        }
+
+        Ok(())
+    }
+
     pub async fn prompt(mut self, user: User, ctx: &mut CommandContext) -> anyhow::Result<()> {
         let Some(channel_id) = ctx.interaction.channel.as_ref().map(|c| c.id) else {
             ctx.send(
@@ -65,6 +132,7 @@ impl Shop {
             return Ok(());
         }
 
+        self.update_city_prices(ctx).await?;
         let mut pages = self.generate_pages(&user);
 
         let Some(character) = ctx
@@ -224,7 +292,7 @@ impl Shop {
                 ctx = CommandContext::from_with_interaction(&ctx, Box::new(interaction.clone()));
             }
 
-            if !buying && !character.has_item(&item, 1) {
+            if !buying && sellable_copy(&character, &item).is_none() {
                 let error_message = EmbedBuilder::new_common()
                     .set_color(Color::RED)
                     .set_author_to_user(&user)
@@ -375,7 +443,10 @@ impl Shop {
                 continue;
             }
 
-            if !buying && !character.has_item(&item, amount as usize) {
+            let sold_values = sellable_copy(&character, &item)
+                .filter(|i| i.quantity >= amount as usize)
+                .map(|i| i.values.clone());
+            if !buying && sold_values.is_none() {
                 ctx.send_in_channel(
                     Response::new_user_reply(&user, "você não tem essa quantia de itens!")
                         .add_emoji_prefix(emojis::ERROR),
@@ -386,17 +457,104 @@ impl Shop {
                 continue;
             }
 
+            // The orbs, items and stock are changed in place so concurrent buyers, restocks
+            // and other commands can't overwrite each other
             if buying {
-                character.remove_orbs(price);
-                character.add_item(item, amount as usize, None);
-                self.items[index].quantity -= amount as i32;
+                if !ctx.db().characters().take_orbs(character.id, price).await? {
+                    ctx.send_in_channel(
+                        Response::new_user_reply(&user, "você não tem orbs suficientes!")
+                            .add_emoji_prefix(emojis::ERROR),
+                    )
+                    .await?;
+                    self.update_specific_page(&character, &message, &mut ctx, page, &pages)
+                        .await?;
+                    continue;
+                }
+
+                if let Some(building_id) = self.building {
+                    let sold = ctx
+                        .db()
+                        .buildings()
+                        .sell_shop_stock(building_id, &shop_item.identifier, amount, price)
+                        .await?;
+                    if !sold {
+                        ctx.db().characters().add_orbs(character.id, price).await?;
+                        ctx.send_in_channel(
+                            Response::new_user_reply(
+                                &user,
+                                "a loja não tem essa quantia do item em estoque!",
+                            )
+                            .add_emoji_prefix(emojis::ERROR),
+                        )
+                        .await?;
+                        self.reload_building_stock(&ctx, building_id).await?;
+                        pages = self.generate_pages(&user);
+                        self.update_specific_page(&character, &message, &mut ctx, page, &pages)
+                            .await?;
+                        continue;
+                    }
+                }
+
+                if let Err(e) = ctx
+                    .db()
+                    .characters()
+                    .add_item(character.id, item, amount as usize, None)
+                    .await
+                {
+                    if let Some(building_id) = self.building {
+                        ctx.db()
+                            .buildings()
+                            .refund_shop_stock(
+                                building_id,
+                                &shop_item.identifier,
+                                amount,
+                                shop_item.price,
+                            )
+                            .await?;
+                    }
+                    ctx.db().characters().add_orbs(character.id, price).await?;
+                    return Err(e);
+                }
+
+                match self.building {
+                    Some(building_id) => self.reload_building_stock(&ctx, building_id).await?,
+                    None => self.items[index].quantity -= amount as i32,
+                }
+
+                if let Some(owner_id) = self.profit_goes_to {
+                    ctx.db().characters().add_orbs(owner_id, price).await?;
+                }
             } else {
-                character.add_orbs(price);
-                character.remove_item(item, amount as usize);
+                let values = sold_values.unwrap_or_default();
+                let mut sold = false;
+                ctx.db()
+                    .characters()
+                    .update(character.id, |character| {
+                        sold = character.has_item_with_values(&item, &values, amount as usize);
+                        if sold {
+                            character.remove_item_with_values(item, &values, amount as usize);
+                            character.add_orbs(price);
+                        }
+                    })
+                    .await?;
+
+                if !sold {
+                    ctx.send_in_channel(
+                        Response::new_user_reply(&user, "você não tem essa quantia de itens!")
+                            .add_emoji_prefix(emojis::ERROR),
+                    )
+                    .await?;
+                    self.update_specific_page(&character, &message, &mut ctx, page, &pages)
+                        .await?;
+                    continue;
+                }
+
                 self.items[index].quantity += amount as i32;
             }
 
-            ctx.db().characters().save(character.clone()).await?;
+            if let Some(updated) = ctx.db().characters().get_by_id(character.id).await? {
+                character = updated;
+            }
 
             ctx.send(
                 Response::new_user_reply(
@@ -412,6 +570,18 @@ impl Shop {
             )
             .await?;
 
+            if let Some(region) = self.city {
+                ctx.db()
+                    .city_prices()
+                    .record_trade(
+                        region,
+                        item.identifier,
+                        if buying { amount } else { -amount },
+                    )
+                    .await?;
+                self.update_city_prices(&ctx).await?;
+            }
+
             pages = self.generate_pages(&user);
             self.update_specific_page(&character, &message, &mut ctx, page, &pages)
                 .await?;
@@ -420,6 +590,25 @@ impl Shop {
         Ok(())
     }
 
+    /// Shows the stock as it is now in the building, since other players may have bought from it.
+    async fn reload_building_stock(
+        &mut self,
+        ctx: &CommandContext,
+        building_id: ObjectId,
+    ) -> anyhow::Result<()> {
+        if let Some(shop_data) = ctx
+            .db()
+            .buildings()
+            .get_by_id(building_id)
+            .await?
+            .and_then(|b| b.get_shop_data())
+        {
+            self.items = shop_data.items;
+        }
+
+        Ok(())
+    }
+
     pub fn generate_pages(&self, user: &User) -> Vec<(EmbedBuilder, Vec<usize>)> {
         let mut pages = vec![];
         let total_pages = (self.items.len() as f64 / ITEMS_PER_PAGE as f64).ceil() as usize;
@@ -458,7 +647,7 @@ impl Shop {
                         item.emoji, item.display_name, shop_item.quantity
                     ),
                     format!(
-                        "{}{}",
+                        "{}{}{}",
                         if let Some(sell_price) = shop_item.sellable_price {
                             format!(
                                 "**Comprar**: {} ◎\n**Vender**: {} ◎",
@@ -467,6 +656,11 @@ impl Shop {
                         } else {
                             format!("{} ◎", shop_item.price)
                         },
+                        if let Some(trend) = self.trends.get(&shop_item.identifier) {
+                            format!("\n**Tendência**: {trend}")
+                        } else {
+                            String::new()
+                        },
                         if let Some(description) = &shop_item.description {
                             format!("\n\"*{}*\"", description)
                         } else {
@@ -562,7 +756,7 @@ impl Shop {
             let can_buy = shop_item.price <= character.orbs;
             let can_sell = shop_item
                 .sellable_price
-                .is_some_and(|_| character.has_item(&item, 1));
+                .is_some_and(|_| sellable_copy(character, &item).is_some());
 
             buttons.push(
                 ButtonBuilder::new()
@@ -0,0 +1,173 @@
+//! Pits fighters against each other thousands of times to see how balanced they are.
+//!
+//! Usage: `cargo run -p etheris_simulator --release -- <team> <team> [--battles N] [--seed S] [--region R]`
+//!
+//! A team is a list of fighters joined by `+`. A fighter is either the identifier of an enemy
+//! (like `giant_rat`) or the path to an exported character JSON file.
+//! A fighter can end with `@<brain>` to pick its brain instead of the default one, like
+//! `frost_shaman@tactical-hard`. The brains are `simple`, `insane`, `boss` and `tactical-<easy|normal|hard>`.
+//! Skills are loaded from `SKILLS_PATH`, like the bot does.
+
+mod simulation;
+
+use anyhow::{bail, Context};
+use etheris_data::world::regions::WorldRegion;
+use etheris_database::character_model::CharacterModel;
+use etheris_rpg::{
+    brain::{BrainDifficulty, BrainKind},
+    data::enemies,
+    FighterData,
+};
+
+use simulation::Simulation;
+
+const DEFAULT_BATTLES: usize = 1000;
+
+fn parse_brain(brain: &str) -> anyhow::Result<BrainKind> {
+    Ok(match brain.to_lowercase().as_str() {
+        "simple" => BrainKind::Simple,
+        "insane" => BrainKind::Insane,
+        "boss" => BrainKind::Boss,
+        "tactical-easy" => BrainKind::Tactical(BrainDifficulty::Easy),
+        "tactical" | "tactical-normal" => BrainKind::Tactical(BrainDifficulty::Normal),
+        "tactical-hard" => BrainKind::Tactical(BrainDifficulty::Hard),
+        _ => bail!("brain not found: {brain}"),
+    })
+}
+
+fn parse_fighter(team: u8, fighter: &str) -> anyhow::Result<FighterData> {
+    let (fighter, brain) = match fighter.split_once('@') {
+        Some((fighter, brain)) => (fighter, Some(parse_brain(brain)?)),
+        None => (fighter, None),
+    };
+
+    let mut data = parse_fighter_data(team, fighter)?;
+    if let Some(brain) = brain {
+        data.brain = Some(brain);
+    }
+
+    Ok(data)
+}
+
+fn parse_fighter_data(team: u8, fighter: &str) -> anyhow::Result<FighterData> {
+    if fighter.ends_with(".json") {
+        let json = std::fs::read_to_string(fighter)
+            .with_context(|| format!("failed to read the character file {fighter}"))?;
+        let character: CharacterModel = serde_json::from_str(&json)
+            .with_context(|| format!("{fighter} is not a valid exported character"))?;
+
+        return Ok(FighterData::new_from_character_with_brain(
+            team,
+            &character,
+            BrainKind::Simple,
+            Default::default(),
+        ));
+    }
+
+    let Some(enemy) = enemies::get_enemy_by_id(fighter) else {
+        bail!("enemy not found: {fighter}");
+    };
+
+    Ok(FighterData::new_from_enemy(team, Default::default(), enemy))
+}
+
+fn parse_region(region: &str) -> anyhow::Result<WorldRegion> {
+    WorldRegion::LIST
+        .iter()
+        .find(|r| format!("{r:?}").eq_ignore_ascii_case(region))
+        .copied()
+        .with_context(|| format!("region not found: {region}"))
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    let skills_path = std::env::var("SKILLS_PATH").unwrap_or_else(|_| "assets/skills".to_owned());
+    etheris_rpg::load_skill_definitions(&skills_path)
+        .with_context(|| format!("failed to load the skill definitions of {skills_path}"))?;
+
+    let mut args = std::env::args().skip(1);
+
+    let mut teams = vec![];
+    let mut battles = DEFAULT_BATTLES;
+    let mut seed = 0;
+    let mut region = WorldRegion::Greenagis;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--battles" => battles = args.next().context("missing value of --battles")?.parse()?,
+            "--seed" => seed = args.next().context("missing value of --seed")?.parse()?,
+            "--region" => {
+                region = parse_region(&args.next().context("missing value of --region")?)?
+            }
+            team => teams.push(team.to_owned()),
+        }
+    }
+
+    if teams.len() < 2 {
+        bail!("usage: etheris_simulator <team> <team> [--battles N] [--seed S] [--region R]");
+    }
+
+    let mut fighters = vec![];
+    for (team, fighters_of_team) in teams.iter().enumerate() {
+        for fighter in fighters_of_team.split('+') {
+            fighters.push(parse_fighter(team as u8, fighter)?);
+        }
+    }
+
+    let simulation = Simulation {
+        region,
+        fighters: fighters.clone(),
+        battles,
+        seed,
+    };
+
+    let stats = simulation.run().await?;
+
+    println!(
+        "{} battles ({} failed)\n",
+        stats.battles, stats.failed_battles
+    );
+
+    if let Some(error) = &stats.first_error {
+        eprintln!("First failed battle: {error}\n");
+    }
+
+    println!("Win rate:");
+    for (team, _) in teams.iter().enumerate() {
+        let names = fighters
+            .iter()
+            .filter(|f| f.team == team as u8)
+            .map(|f| format!("{} ({} PL)", f.name, f.power_level()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "  [{team}] {names}: {:.1}%",
+            stats.win_rate(team as u8) * 100.0
+        );
+    }
+
+    println!("\nAverage turns: {:.1}", stats.average_turns());
+
+    println!("\nDamage:");
+    let attacks = [("Attack".to_string(), stats.attacks)];
+    let skills = stats.skills.iter().map(|(k, s)| (format!("{k:?}"), *s));
+    for (name, skill) in attacks.into_iter().chain(skills) {
+        let average = if skill.uses == 0 {
+            0.0
+        } else {
+            skill.damage as f64 / skill.uses as f64
+        };
+
+        println!(
+            "  {name}: {} total, {} uses, {average:.1} per use",
+            skill.damage, skill.uses
+        );
+    }
+
+    println!("\nFinishers:");
+    for (finisher, uses) in stats.finishers.iter() {
+        println!("  {}: {uses}", finisher.name());
+    }
+
+    Ok(())
+}
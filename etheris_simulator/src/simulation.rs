@@ -0,0 +1,253 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use etheris_data::{world::regions::WorldRegion, SkillKind};
+use etheris_rpg::{data::finishers::Finisher, *};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SkillStats {
+    pub uses: usize,
+    pub damage: i64,
+}
+
+/// Numbers gathered from a batch of simulated battles.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationStats {
+    pub battles: usize,
+    pub failed_battles: usize,
+    /// The error of the first battle that failed, so a broken fighter or skill can be tracked down.
+    pub first_error: Option<String>,
+    pub wins: BTreeMap<u8, usize>,
+    pub total_turns: usize,
+    pub attacks: SkillStats,
+    pub skills: BTreeMap<SkillKind, SkillStats>,
+    pub finishers: BTreeMap<Finisher, usize>,
+}
+
+impl SimulationStats {
+    pub fn win_rate(&self, team: u8) -> f64 {
+        let finished = self.battles - self.failed_battles;
+        if finished == 0 {
+            return 0.0;
+        }
+
+        *self.wins.get(&team).unwrap_or(&0) as f64 / finished as f64
+    }
+
+    pub fn average_turns(&self) -> f64 {
+        let finished = self.battles - self.failed_battles;
+        if finished == 0 {
+            return 0.0;
+        }
+
+        self.total_turns as f64 / finished as f64
+    }
+}
+
+/// Runs battles between the same fighters many times, each one with a different seed.
+pub struct Simulation {
+    pub region: WorldRegion,
+    pub fighters: Vec<FighterData>,
+    pub battles: usize,
+    pub seed: u64,
+}
+
+impl Simulation {
+    pub async fn run(&self) -> anyhow::Result<SimulationStats> {
+        let mut stats = SimulationStats::default();
+
+        for i in 0..self.battles {
+            let settings = BattleSettings {
+                is_risking_life_allowed: true,
+                has_consequences: false,
+                casual: true,
+                max_intruders: 0,
+                seed: Some(self.seed.wrapping_add(i as u64)),
+                ranked: false,
+            };
+
+            let battle = Battle::new(self.region, settings, self.fighters.clone())?;
+            let io = Arc::new(Mutex::new(SimulatorIo::new(&battle)));
+            let mut engine = BattleEngine::new(battle, io.clone());
+
+            stats.battles += 1;
+            let result = match engine.run().await {
+                Ok(result) => result,
+                Err(e) => {
+                    stats.failed_battles += 1;
+                    if stats.first_error.is_none() {
+                        stats.first_error = Some(format!("battle {i}: {e:?}"));
+                    }
+                    continue;
+                }
+            };
+
+            *stats.wins.entry(result.winners[0].team).or_default() += 1;
+            stats.total_turns += engine.battle.turn_counter;
+
+            let io = io.lock().await;
+            stats.attacks.uses += io.attacks.uses;
+            stats.attacks.damage += io.attacks.damage;
+            for (kind, skill) in io.skills.iter() {
+                let entry = stats.skills.entry(kind.clone()).or_default();
+                entry.uses += skill.uses;
+                entry.damage += skill.damage;
+            }
+            for (finisher, uses) in io.finishers.iter() {
+                *stats.finishers.entry(*finisher).or_default() += uses;
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+/// A headless io that watches every turn to count what each input did.
+/// The damage of a turn is read from the `DamageDealt` events the fighter caused to its enemies.
+struct SimulatorIo {
+    headless: HeadlessIo,
+    seen_turns: usize,
+    seen_entries: usize,
+
+    attacks: SkillStats,
+    skills: BTreeMap<SkillKind, SkillStats>,
+    finishers: BTreeMap<Finisher, usize>,
+}
+
+impl SimulatorIo {
+    fn new(battle: &Battle) -> Self {
+        Self {
+            headless: HeadlessIo::new(battle.seed),
+            seen_turns: battle.history.len(),
+            seen_entries: 0,
+            attacks: SkillStats::default(),
+            skills: BTreeMap::new(),
+            finishers: BTreeMap::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BattleIo for SimulatorIo {
+    async fn select_input(&mut self, engine: &mut BattleEngine) -> anyhow::Result<BattleInput> {
+        self.headless.select_input(engine).await
+    }
+
+    async fn select_option(
+        &mut self,
+        engine: &mut BattleEngine,
+        selection: IoSelection,
+    ) -> anyhow::Result<Option<String>> {
+        self.headless.select_option(engine, selection).await
+    }
+
+    async fn input_number(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+        prompt: String,
+        valid_range: (f64, f64),
+    ) -> anyhow::Result<f64> {
+        self.headless
+            .input_number(engine, fighter, prompt, valid_range)
+            .await
+    }
+
+    async fn confirm_risk_life(
+        &mut self,
+        engine: &mut BattleEngine,
+        fighter: FighterIndex,
+    ) -> anyhow::Result<bool> {
+        self.headless.confirm_risk_life(engine, fighter).await
+    }
+
+    async fn update_turn(&mut self, _engine: &mut BattleEngine) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn on_turn_end(&mut self, engine: &mut BattleEngine) -> anyhow::Result<()> {
+        // The turn was given back to the same fighter, it isn't over yet
+        if engine.battle.history.len() == self.seen_turns {
+            return Ok(());
+        }
+
+        let input = engine.replay_entries[self.seen_entries..]
+            .iter()
+            .rev()
+            .find_map(|entry| match entry {
+                ReplayEntry::Input { fighter, input } => Some((*fighter, input.clone())),
+                _ => None,
+            });
+        self.seen_entries = engine.replay_entries.len();
+
+        let turns = &engine.battle.history[self.seen_turns..];
+        self.seen_turns = engine.battle.history.len();
+
+        let Some((fighter, input)) = input else {
+            return Ok(());
+        };
+
+        let fighter = FighterIndex(fighter);
+        let team = engine.battle.get_fighter(fighter).team;
+        let damage = turns
+            .iter()
+            .flat_map(|turn| turn.events.iter())
+            .filter_map(|event| match event {
+                BattleEvent::DamageDealt {
+                    culprit,
+                    target,
+                    amount,
+                    missed: false,
+                    ..
+                } if *culprit == fighter && engine.battle.get_fighter(*target).team != team => {
+                    Some(*amount as i64)
+                }
+                _ => None,
+            })
+            .sum::<i64>();
+
+        match input {
+            ReplayInput::Attack => {
+                self.attacks.uses += 1;
+                self.attacks.damage += damage;
+            }
+            ReplayInput::UseSkill(kind) => {
+                let skill = self.skills.entry(kind).or_default();
+                skill.uses += 1;
+                skill.damage += damage;
+            }
+            ReplayInput::Finish(finisher) => {
+                *self.finishers.entry(finisher).or_default() += 1;
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use etheris_rpg::data::enemies::weaklings;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn every_battle_is_counted() {
+        let simulation = Simulation {
+            region: WorldRegion::Greenagis,
+            fighters: vec![
+                FighterData::new_from_enemy(0, Default::default(), weaklings::giant_rat()),
+                FighterData::new_from_enemy(1, Default::default(), weaklings::greenagis_mutant()),
+            ],
+            battles: 20,
+            seed: 0,
+        };
+
+        let stats = simulation.run().await.unwrap();
+        let wins = stats.wins.values().sum::<usize>();
+
+        assert_eq!(wins + stats.failed_battles, 20);
+        assert!(stats.average_turns() > 0.0);
+    }
+}
@@ -0,0 +1,189 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use crate::bitmap_font::{draw_text, text_width, GLYPH_HEIGHT};
+
+const TEXT_SCALE: u32 = 2;
+const MAX_NAME_LENGTH: usize = 12;
+const MARGIN: u32 = 20;
+const TITLE_HEIGHT: u32 = 30;
+const BOX_WIDTH: u32 = 170;
+const LINE_HEIGHT: u32 = GLYPH_HEIGHT * TEXT_SCALE + 10;
+const BOX_HEIGHT: u32 = LINE_HEIGHT * 2;
+const SLOT_HEIGHT: u32 = BOX_HEIGHT + 16;
+const COLUMN_GAP: u32 = 40;
+
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([28, 28, 38, 255]);
+const BOX_COLOR: Rgba<u8> = Rgba([54, 54, 74, 255]);
+const SEPARATOR_COLOR: Rgba<u8> = Rgba([80, 80, 104, 255]);
+const LINE_COLOR: Rgba<u8> = Rgba([120, 120, 150, 255]);
+const TITLE_COLOR: Rgba<u8> = Rgba([230, 230, 240, 255]);
+const NAME_COLOR: Rgba<u8> = Rgba([210, 210, 220, 255]);
+const WINNER_COLOR: Rgba<u8> = Rgba([250, 200, 60, 255]);
+const LOSER_COLOR: Rgba<u8> = Rgba([120, 120, 130, 255]);
+
+#[derive(Debug, Clone, Default)]
+pub struct BracketMatchBox {
+    /// `None` is a player that isn't known yet or a bye.
+    pub players: [Option<String>; 2],
+    /// The index of the player that won, if the match is over.
+    pub winner: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BracketColumn {
+    pub matches: Vec<BracketMatchBox>,
+}
+
+/// A part of the bracket drawn as columns of rounds, like the winners or the losers bracket.
+#[derive(Debug, Clone, Default)]
+pub struct BracketSection {
+    pub title: String,
+    pub columns: Vec<BracketColumn>,
+}
+
+impl BracketSection {
+    fn height(&self) -> u32 {
+        let max_matches = self
+            .columns
+            .iter()
+            .map(|c| c.matches.len())
+            .max()
+            .unwrap_or(0)
+            .max(1) as u32;
+        TITLE_HEIGHT + max_matches * SLOT_HEIGHT
+    }
+
+    /// The vertical center of every match of the column, relative to the top of the section.
+    fn match_centers(&self, column: &BracketColumn) -> Vec<u32> {
+        let area = self.height() - TITLE_HEIGHT;
+        let count = column.matches.len().max(1) as u32;
+        (0..count)
+            .map(|i| TITLE_HEIGHT + area * (2 * i + 1) / (2 * count))
+            .collect()
+    }
+}
+
+fn fill_rect(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn draw_connector(image: &mut RgbaImage, from: (u32, u32), to: (u32, u32)) {
+    let middle_x = from.0 + (to.0 - from.0) / 2;
+    fill_rect(image, from.0, from.1, middle_x - from.0, 2, LINE_COLOR);
+    fill_rect(
+        image,
+        middle_x,
+        from.1.min(to.1),
+        2,
+        from.1.abs_diff(to.1) + 2,
+        LINE_COLOR,
+    );
+    fill_rect(image, middle_x, to.1, to.0 - middle_x, 2, LINE_COLOR);
+}
+
+fn draw_match(image: &mut RgbaImage, x: u32, y: u32, bracket_match: &BracketMatchBox) {
+    fill_rect(image, x, y, BOX_WIDTH, BOX_HEIGHT, BOX_COLOR);
+    fill_rect(image, x, y + LINE_HEIGHT - 1, BOX_WIDTH, 2, SEPARATOR_COLOR);
+
+    for (index, player) in bracket_match.players.iter().enumerate() {
+        let name = match player {
+            Some(name) => name.chars().take(MAX_NAME_LENGTH).collect::<String>(),
+            None => "-".to_string(),
+        };
+
+        let color = match bracket_match.winner {
+            Some(winner) if winner == index => WINNER_COLOR,
+            Some(_) => LOSER_COLOR,
+            None => NAME_COLOR,
+        };
+
+        draw_text(
+            image,
+            &name,
+            x + 8,
+            y + index as u32 * LINE_HEIGHT + 5,
+            TEXT_SCALE,
+            color,
+        );
+    }
+}
+
+/// Draws the sections of a tournament bracket, one below the other, and returns the PNG bytes.
+pub fn generate_bracket_image(sections: &[BracketSection]) -> Vec<u8> {
+    let max_columns = sections
+        .iter()
+        .map(|s| s.columns.len())
+        .max()
+        .unwrap_or(0)
+        .max(1) as u32;
+    let title_width = sections
+        .iter()
+        .map(|s| text_width(&s.title, TEXT_SCALE))
+        .max()
+        .unwrap_or(0);
+
+    let width = (MARGIN * 2 + max_columns * (BOX_WIDTH + COLUMN_GAP) - COLUMN_GAP)
+        .max(MARGIN * 2 + title_width);
+    let height = MARGIN + sections.iter().map(|s| s.height() + MARGIN).sum::<u32>();
+
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND_COLOR);
+
+    let mut section_y = MARGIN;
+    for section in sections {
+        draw_text(
+            &mut image,
+            &section.title,
+            MARGIN,
+            section_y,
+            TEXT_SCALE,
+            TITLE_COLOR,
+        );
+
+        for (column_index, column) in section.columns.iter().enumerate() {
+            let x = MARGIN + column_index as u32 * (BOX_WIDTH + COLUMN_GAP);
+            let centers = section.match_centers(column);
+
+            if let Some(next_column) = section.columns.get(column_index + 1) {
+                let next_centers = section.match_centers(next_column);
+                let halves = next_column.matches.len() * 2 == column.matches.len();
+                let same = next_column.matches.len() == column.matches.len();
+
+                for (match_index, center) in centers.iter().enumerate() {
+                    let target = match (halves, same) {
+                        (true, _) => match_index / 2,
+                        (_, true) => match_index,
+                        _ => continue,
+                    };
+
+                    draw_connector(
+                        &mut image,
+                        (x + BOX_WIDTH, section_y + center),
+                        (x + BOX_WIDTH + COLUMN_GAP, section_y + next_centers[target]),
+                    );
+                }
+            }
+
+            for (bracket_match, center) in column.matches.iter().zip(centers) {
+                draw_match(
+                    &mut image,
+                    x,
+                    section_y + center - BOX_HEIGHT / 2,
+                    bracket_match,
+                );
+            }
+        }
+
+        section_y += section.height() + MARGIN;
+    }
+
+    let mut data = Cursor::new(Vec::new());
+    image.write_to(&mut data, ImageFormat::Png).unwrap();
+
+    data.into_inner()
+}
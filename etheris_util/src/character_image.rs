@@ -2,8 +2,9 @@ use std::io::Cursor;
 
 use etheris_common::Color;
 use etheris_data::appearance::*;
+use etheris_data::equipment::{EquipmentProperties, EquipmentSlot};
 use image::imageops::{overlay, resize, FilterType};
-use image::ImageFormat;
+use image::{ImageFormat, Rgba, RgbaImage};
 
 use crate::image_util::*;
 use cached::proc_macro::cached;
@@ -12,15 +13,47 @@ use cached::SizedCache;
 const WIDTH: u32 = 512;
 const HEIGHT: u32 = 512;
 
+// Size of the body part assets, which are stretched to WIDTH x HEIGHT
+const ASSET_WIDTH: u32 = 106;
+const ASSET_HEIGHT: u32 = 92;
+
+/// An equipment drawn over the character.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EquipmentLayer {
+    pub slot: EquipmentSlot,
+    pub color: Color,
+}
+
+impl From<EquipmentProperties> for EquipmentLayer {
+    fn from(value: EquipmentProperties) -> Self {
+        Self {
+            slot: value.slot,
+            color: value.color,
+        }
+    }
+}
+
 pub fn generate_character_image_buffer(appearance: &CharacterAppearance) -> Vec<u8> {
-    internal_generate_character_image_buffer(appearance).expect("Error generating character image buffer, probably generated at one of the image::load_from_memory's.")
+    generate_equipped_character_image_buffer(appearance, &[])
+}
+
+/// Generates the character image with the visible equipment drawn over it. Legs and rings are out of the picture.
+pub fn generate_equipped_character_image_buffer(
+    appearance: &CharacterAppearance,
+    equipment: &[EquipmentLayer],
+) -> Vec<u8> {
+    internal_generate_character_image_buffer(appearance, equipment).expect("Error generating character image buffer, probably generated at one of the image::load_from_memory's.")
 }
 
 #[cached(
-    type = "SizedCache<CharacterAppearance, Option<Vec<u8>>>",
-    create = "{ SizedCache::with_size(256) }"
+    type = "SizedCache<(CharacterAppearance, Vec<EquipmentLayer>), Option<Vec<u8>>>",
+    create = "{ SizedCache::with_size(256) }",
+    convert = r#"{ (appearance.clone(), equipment.to_vec()) }"#
 )]
-fn internal_generate_character_image_buffer(appearance: &CharacterAppearance) -> Option<Vec<u8>> {
+fn internal_generate_character_image_buffer(
+    appearance: &CharacterAppearance,
+    equipment: &[EquipmentLayer],
+) -> Option<Vec<u8>> {
     // Colors
     let skin_color = appearance.skin_color;
     let eyes_color = appearance.eyes_color;
@@ -134,6 +167,15 @@ fn internal_generate_character_image_buffer(appearance: &CharacterAppearance) ->
         },
     );
 
+    // Body equipment is worn over the neck, so it goes right after the skin
+    for layer in equipment.iter() {
+        match layer.slot {
+            EquipmentSlot::Body => draw_body_armor(&mut neck, layer.color),
+            EquipmentSlot::Amulet => draw_amulet(&mut neck, layer.color),
+            EquipmentSlot::Legs | EquipmentSlot::Ring => {}
+        }
+    }
+
     // Eyes and pupils
     let mut eyes = resize(&eyes, WIDTH, HEIGHT, FilterType::Nearest);
     let mut pupils = resize(&pupils, WIDTH, HEIGHT, FilterType::Nearest);
@@ -215,3 +257,49 @@ fn internal_generate_character_image_buffer(appearance: &CharacterAppearance) ->
     data.get_mut().clear(); // Clear the Vec to free its memory
     result
 }
+
+fn shade(color: Color, factor: f32) -> Rgba<u8> {
+    let rgb = color.to_rgb_u8();
+    let channel = |c: u8| (c as f32 * factor).clamp(0.0, 255.0) as u8;
+    Rgba([channel(rgb[0]), channel(rgb[1]), channel(rgb[2]), 255])
+}
+
+/// Fills a rectangle given in asset pixels, stretching it like the assets are stretched.
+fn fill_asset_rect(
+    image: &mut RgbaImage,
+    (x0, y0): (u32, u32),
+    (x1, y1): (u32, u32),
+    pixel: Rgba<u8>,
+) {
+    let to_x = |x: u32| x * WIDTH / ASSET_WIDTH;
+    let to_y = |y: u32| y * HEIGHT / ASSET_HEIGHT;
+
+    for y in to_y(y0)..to_y(y1 + 1).min(image.height()) {
+        for x in to_x(x0)..to_x(x1 + 1).min(image.width()) {
+            image.put_pixel(x, y, pixel);
+        }
+    }
+}
+
+fn draw_body_armor(image: &mut RgbaImage, color: Color) {
+    // The collar gets wider towards the bottom of the image, like shoulders
+    for (row, y) in (84..ASSET_HEIGHT).enumerate() {
+        let spread = row as u32 / 2;
+        fill_asset_rect(image, (41 - spread, y), (64 + spread, y), shade(color, 1.0));
+    }
+
+    fill_asset_rect(image, (44, 84), (61, 84), shade(color, 0.6));
+    fill_asset_rect(image, (52, 85), (53, 91), shade(color, 0.8));
+}
+
+fn draw_amulet(image: &mut RgbaImage, color: Color) {
+    let cord = shade(Color::DARK_BROWN, 1.0);
+    for step in 0..5 {
+        fill_asset_rect(image, (45 + step, 81 + step), (45 + step, 81 + step), cord);
+        fill_asset_rect(image, (60 - step, 81 + step), (60 - step, 81 + step), cord);
+    }
+
+    fill_asset_rect(image, (50, 86), (55, 86), cord);
+    fill_asset_rect(image, (51, 87), (54, 89), shade(color, 0.6));
+    fill_asset_rect(image, (52, 87), (53, 88), shade(color, 1.0));
+}
@@ -1,3 +1,5 @@
+mod bitmap_font;
+pub mod bracket_image;
 pub mod character_image;
 mod image_util;
 pub mod math;
@@ -72,7 +72,12 @@ pub async fn execute_command(
         let mut character = parse_user_character!(ctx, author);
 
         if let Some(death_info) = character.check_for_death() {
-            ctx.db().characters().save(character.clone()).await?;
+            ctx.db()
+                .characters()
+                .update(character.id, |character| {
+                    character.check_for_death();
+                })
+                .await?;
             let cause = match death_info.cause {
                 DeathCause::Vitality => "Sem vitalidade".to_string(),
                 DeathCause::KilledBy(killer_name) => format!("Morto(a) por {killer_name}"),
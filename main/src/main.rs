@@ -5,6 +5,7 @@ use std::{sync::Arc, time::Duration};
 
 pub use event_handler::EventHandler;
 
+use etheris_commands::{market, tournament};
 use etheris_common::config;
 use etheris_database::{common::DatabaseDateTime, DatabaseState, EtherisDatabase};
 use etheris_discord::{
@@ -22,6 +23,10 @@ use tokio_stream::StreamExt;
 async fn main() {
     dotenv::dotenv().unwrap();
 
+    let skills_path = std::env::var("SKILLS_PATH").unwrap_or_else(|_| "assets/skills".to_owned());
+    etheris_rpg::load_skill_definitions(&skills_path)
+        .expect("expected a readable skills directory");
+
     let discord_token = std::env::var(if config::DEBUG {
         "DEBUG_DISCORD_TOKEN"
     } else {
@@ -53,25 +58,47 @@ async fn main() {
             tokio::time::sleep(Duration::from_secs(15)).await;
             let characters = db.characters().get_all_refilled_characters().await.unwrap();
 
-            for mut character in characters {
+            for character in characters {
                 let user_id = Id::new(character.user_id.parse::<u64>().unwrap_or(12345678));
 
-                character.last_refill = DatabaseDateTime::now();
-                if character.stats.vitality.value < 1 {
-                    db.characters().save(character.clone()).await.ok();
-                    continue;
-                }
-
-                character.stats.resistance.value = character.stats.resistance.max;
-                character.stats.vitality.value = character.stats.vitality.max;
-                character.stats.ether.value = character.stats.ether.max;
-                if character.settings.is_notifications_enabled
-                    && character.action_points < character.max_action_points
-                {
-                    character.action_points = character.max_action_points;
+                let mut notify = false;
+                let refilled = db
+                    .characters()
+                    .update(character.id, |character| {
+                        character.last_refill = DatabaseDateTime::now();
+                        notify = false;
+                        if character.stats.vitality.value < 1 {
+                            return;
+                        }
+
+                        character.stats.resistance.value = character.stats.resistance.max;
+                        character.stats.vitality.value = character.stats.vitality.max;
+                        character.stats.ether.value = character.stats.ether.max;
+                        if character.settings.is_notifications_enabled
+                            && character.action_points < character.max_action_points
+                        {
+                            character.action_points = character.max_action_points;
+                            notify = true;
+                        }
+                    })
+                    .await;
+
+                if notify && matches!(refilled, Ok(Some(_))) {
                     send_notification(&client_clone, user_id).await.ok();
                 }
-                db.characters().save(character.clone()).await.ok();
+            }
+
+            if let Err(e) = tournament::start_due_tournaments(&db, &client_clone).await {
+                eprintln!("Failed to start due tournaments: {e:?}");
+            }
+            if let Err(e) = tournament::settle_overdue_matches(&db, &client_clone).await {
+                eprintln!("Failed to settle overdue tournament matches: {e:?}");
+            }
+            if let Err(e) = tournament::pay_unpaid_prizes(&db).await {
+                eprintln!("Failed to pay unpaid tournament prizes: {e:?}");
+            }
+            if let Err(e) = market::settle_expired_listings(&db, &client_clone).await {
+                eprintln!("Failed to settle expired listings: {e:?}");
             }
         }
     });